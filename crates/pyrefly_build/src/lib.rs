@@ -31,6 +31,7 @@ use std::sync::Arc;
 use std::sync::LazyLock;
 
 use dupe::Dupe as _;
+use pyrefly_python::sys_info::SysInfo;
 use pyrefly_util::arc_id::ArcId;
 use pyrefly_util::arc_id::WeakArcId;
 use pyrefly_util::lock::Mutex;
@@ -50,6 +51,10 @@ use crate::query::buck::BxlArgs;
 use crate::query::buck::BxlQuerier;
 use crate::query::custom::CustomQuerier;
 use crate::query::custom::CustomQueryArgs;
+use crate::query::pyproject::PyprojectArgs;
+use crate::query::pyproject::PyprojectQuerier;
+use crate::source_db::build_file::BuildFileArgs;
+use crate::source_db::build_file::BuildFileQuerier;
 use crate::source_db::query_source_db::QuerySourceDatabase;
 
 /// A cache of previously loaded build systems, keyed on their project root
@@ -57,17 +62,30 @@ use crate::source_db::query_source_db::QuerySourceDatabase;
 static BUILD_SYSTEM_CACHE: LazyLock<
     Mutex<
         SmallMap<
-            (PathBuf, BuildSystemArgs),
+            (PathBuf, BuildSystemArgs, ConfiguredEnvironment),
             WeakArcId<Box<dyn source_db::SourceDatabase + 'static>>,
         >,
     >,
 > = LazyLock::new(|| Mutex::new(SmallMap::new()));
 
+/// The Python environment from the config, for build systems that don't record one
+/// themselves.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ConfiguredEnvironment {
+    /// The `SysInfo` the config checks with.
+    pub sys_info: SysInfo,
+    /// Whether `python-version` was set explicitly, in which case it wins over any version
+    /// the project itself declares.
+    pub python_version_configured: bool,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
 #[serde(rename_all = "kebab-case", tag = "type")]
 pub enum BuildSystemArgs {
     Buck(BxlArgs),
     Custom(CustomQueryArgs),
+    Pyproject(PyprojectArgs),
+    BuildFile(BuildFileArgs),
 }
 
 impl BuildSystemArgs {
@@ -77,6 +95,8 @@ impl BuildSystemArgs {
             let cmd = match self {
                 Self::Buck(_) => "buck2",
                 Self::Custom(args) => args.command.first(),
+                // Read straight from `pyproject.toml` or `BUILD` files, so there is nothing to run.
+                Self::Pyproject(_) | Self::BuildFile(_) => return true,
             };
             which(cmd).is_ok()
         }
@@ -88,6 +108,8 @@ impl BuildSystemArgs {
         match self {
             Self::Buck(args) => args.get_repo_root(cwd),
             Self::Custom(args) => args.get_repo_root(cwd),
+            Self::Pyproject(args) => args.get_repo_root(cwd),
+            Self::BuildFile(args) => args.get_repo_root(cwd),
         }
     }
 }
@@ -123,6 +145,7 @@ impl BuildSystem {
     pub fn get_source_db(
         &mut self,
         config_root: PathBuf,
+        env: ConfiguredEnvironment,
     ) -> Option<anyhow::Result<ArcId<Box<dyn source_db::SourceDatabase + 'static>>>> {
         let build_system_available = self.args.is_build_system_available();
         if !build_system_available {
@@ -140,7 +163,7 @@ impl BuildSystem {
             Ok(path) => path,
         };
         let mut cache = BUILD_SYSTEM_CACHE.lock();
-        let key = (repo_root.clone(), self.args.clone());
+        let key = (repo_root.clone(), self.args.clone(), env.clone());
         if let Some(maybe_result) = cache.get(&key)
             && let Some(result) = maybe_result.upgrade()
         {
@@ -154,6 +177,8 @@ impl BuildSystem {
         let querier: Arc<dyn SourceDbQuerier> = match &self.args {
             BuildSystemArgs::Buck(args) => Arc::new(BxlQuerier::new(args.clone())),
             BuildSystemArgs::Custom(args) => Arc::new(CustomQuerier::new(args.clone())),
+            BuildSystemArgs::Pyproject(_) => Arc::new(PyprojectQuerier::new(env)),
+            BuildSystemArgs::BuildFile(_) => Arc::new(BuildFileQuerier::new(env)),
         };
         let source_db = ArcId::new(Box::new(QuerySourceDatabase::new(
             repo_root.to_path_buf(),
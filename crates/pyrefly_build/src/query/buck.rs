@@ -13,8 +13,12 @@ use std::process::Command;
 use anyhow::Context as _;
 use serde::Deserialize;
 use serde::Serialize;
+use starlark_map::small_set::SmallSet;
 
+use crate::query::Include;
 use crate::query::SourceDbQuerier;
+use crate::query::TargetManifestDatabase;
+use crate::query::query_source_db_with_command;
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Default, Hash)]
 #[serde(rename_all = "kebab-case")]
@@ -60,9 +64,7 @@ impl BxlQuerier {
     pub fn new(args: BxlArgs) -> Self {
         Self(args)
     }
-}
 
-impl SourceDbQuerier for BxlQuerier {
     fn construct_command(&self) -> Command {
         let mut cmd = Command::new("buck2");
         if let Some(isolation_dir) = &self.0.isolation_dir {
@@ -78,3 +80,13 @@ impl SourceDbQuerier for BxlQuerier {
         cmd
     }
 }
+
+impl SourceDbQuerier for BxlQuerier {
+    fn query_source_db(
+        &self,
+        files: &SmallSet<Include>,
+        cwd: &Path,
+    ) -> anyhow::Result<TargetManifestDatabase> {
+        query_source_db_with_command(self.construct_command(), files, cwd)
+    }
+}
@@ -6,14 +6,19 @@
  */
 
 use std::fmt::Debug;
+use std::path::Path;
 use std::path::PathBuf;
 use std::process::Command;
 
 use serde::Deserialize;
 use serde::Serialize;
+use starlark_map::small_set::SmallSet;
 use vec1::Vec1;
 
+use crate::query::Include;
 use crate::query::SourceDbQuerier;
+use crate::query::TargetManifestDatabase;
+use crate::query::query_source_db_with_command;
 
 /// Args and settings for querying a custom source DB.
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Default, Hash)]
@@ -52,12 +57,20 @@ impl CustomQuerier {
     pub fn new(args: CustomQueryArgs) -> Self {
         Self(args)
     }
-}
 
-impl SourceDbQuerier for CustomQuerier {
     fn construct_command(&self) -> Command {
         let mut cmd = Command::new(self.0.command.first());
         cmd.args(self.0.command.iter().skip(1));
         cmd
     }
 }
+
+impl SourceDbQuerier for CustomQuerier {
+    fn query_source_db(
+        &self,
+        files: &SmallSet<Include>,
+        cwd: &Path,
+    ) -> anyhow::Result<TargetManifestDatabase> {
+        query_source_db_with_command(self.construct_command(), files, cwd)
+    }
+}
@@ -31,6 +31,7 @@ use crate::source_db::Target;
 
 pub mod buck;
 pub mod custom;
+pub mod pyproject;
 
 /// An enum representing something that has been included by the build system, and
 /// which the build system should query for when building the sourcedb.
@@ -59,82 +60,95 @@ pub trait SourceDbQuerier: Send + Sync + fmt::Debug {
         &self,
         files: &SmallSet<Include>,
         cwd: &Path,
-    ) -> anyhow::Result<TargetManifestDatabase> {
-        if files.is_empty() {
-            return Ok(TargetManifestDatabase {
-                db: SmallMap::new(),
-                root: cwd.to_path_buf(),
-            });
-        }
+    ) -> anyhow::Result<TargetManifestDatabase>;
 
-        let mut argfile =
-            NamedTempFile::with_prefix("pyrefly_build_query_").with_context(|| {
-                "Failed to create temporary argfile for querying source DB".to_owned()
-            })?;
-        let mut argfile_args = OsString::from("--");
-        files.iter().flat_map(Include::to_cli_arg).for_each(|arg| {
-            argfile_args.push("\n");
-            argfile_args.push(arg);
-        });
+    /// Whether importing a module owned by a target outside of the importing target's
+    /// dependencies is an error, rather than something to resolve from the search path.
+    fn enforces_declared_dependencies(&self) -> bool {
+        false
+    }
 
-        argfile
-            .as_file_mut()
-            .write_all(argfile_args.as_encoded_bytes())
-            .with_context(|| "Could not write to argfile when querying source DB".to_owned())?;
+    /// If `path`, found in site-packages, is installed by a distribution that `target` does
+    /// not declare as a dependency, get the distribution's name.
+    fn undeclared_distribution(&self, _target: Target, _path: &Path) -> Option<String> {
+        None
+    }
+}
 
-        let mut cmd = self.construct_command();
-        cmd.arg(format!("@{}", argfile.path().display()));
-        cmd.current_dir(cwd);
+/// Query a source DB by shelling out to `cmd`, passing the includes in an argfile.
+fn query_source_db_with_command(
+    mut cmd: Command,
+    files: &SmallSet<Include>,
+    cwd: &Path,
+) -> anyhow::Result<TargetManifestDatabase> {
+    if files.is_empty() {
+        return Ok(TargetManifestDatabase {
+            db: SmallMap::new(),
+            root: cwd.to_path_buf(),
+        });
+    }
 
-        let result = cmd.output()?;
-        if !result.status.success() {
-            let stdout = String::from_utf8(result.stdout)
-                .unwrap_or_else(|_| "<Failed to parse stdout from source DB query>".to_owned());
-            let stderr = String::from_utf8(result.stderr).unwrap_or_else(|_| {
-                "<Failed to parse stderr from Buck source DB query>".to_owned()
-            });
+    let mut argfile = NamedTempFile::with_prefix("pyrefly_build_query_")
+        .with_context(|| "Failed to create temporary argfile for querying source DB".to_owned())?;
+    let mut argfile_args = OsString::from("--");
+    files.iter().flat_map(Include::to_cli_arg).for_each(|arg| {
+        argfile_args.push("\n");
+        argfile_args.push(arg);
+    });
 
-            return Err(anyhow::anyhow!(
-                "Source DB query failed...\nSTDOUT: {stdout}\nSTDERR: {stderr}"
-            ));
-        }
+    argfile
+        .as_file_mut()
+        .write_all(argfile_args.as_encoded_bytes())
+        .with_context(|| "Could not write to argfile when querying source DB".to_owned())?;
 
-        match serde_json::from_slice(&result.stdout)
-                .with_context(|| {
-                    format!(
-                        "Failed to construct valid `TargetManifestDatabase` from querier result. Command run: {} {}",
-                        cmd.get_program().display(),
-                        cmd.get_args().map(|a| a.to_string_lossy()).join(" "),
-                    )
-                }) {
-            Err(e) => {
-                let Some(downcast) = e.downcast_ref::<serde_json::error::Error>() else {
-                    return Err(e);
-                };
-                let Ok(content) = String::from_utf8(result.stdout) else {
-                    return Err(e);
-                };
-                let lines = content.lines().collect::<Vec<_>>();
-                let error_line = downcast.line();
-                let start = std::cmp::max(0, error_line - 30);
-                let end = std::cmp::min(lines.len() - 1, error_line + 20);
-                let cont = std::cmp::min(error_line + 1, end);
+    cmd.arg(format!("@{}", argfile.path().display()));
+    cmd.current_dir(cwd);
 
-                let e = e.context(
-                    format!(
-                        "Context: ```\n{} # THIS LINE HAS A PROBLEM\n{}\n```",
-                        lines[start..=error_line].iter().join("\n"),
-                        lines[cont..=end].iter().join("\n"),
-                    )
-                );
+    let result = cmd.output()?;
+    if !result.status.success() {
+        let stdout = String::from_utf8(result.stdout)
+            .unwrap_or_else(|_| "<Failed to parse stdout from source DB query>".to_owned());
+        let stderr = String::from_utf8(result.stderr)
+            .unwrap_or_else(|_| "<Failed to parse stderr from Buck source DB query>".to_owned());
 
-                Err(e)
-            },
-            ok => ok,
-        }
+        return Err(anyhow::anyhow!(
+            "Source DB query failed...\nSTDOUT: {stdout}\nSTDERR: {stderr}"
+        ));
     }
 
-    fn construct_command(&self) -> Command;
+    match serde_json::from_slice(&result.stdout)
+            .with_context(|| {
+                format!(
+                    "Failed to construct valid `TargetManifestDatabase` from querier result. Command run: {} {}",
+                    cmd.get_program().display(),
+                    cmd.get_args().map(|a| a.to_string_lossy()).join(" "),
+                )
+            }) {
+        Err(e) => {
+            let Some(downcast) = e.downcast_ref::<serde_json::error::Error>() else {
+                return Err(e);
+            };
+            let Ok(content) = String::from_utf8(result.stdout) else {
+                return Err(e);
+            };
+            let lines = content.lines().collect::<Vec<_>>();
+            let error_line = downcast.line();
+            let start = std::cmp::max(0, error_line - 30);
+            let end = std::cmp::min(lines.len() - 1, error_line + 20);
+            let cont = std::cmp::min(error_line + 1, end);
+
+            let e = e.context(
+                format!(
+                    "Context: ```\n{} # THIS LINE HAS A PROBLEM\n{}\n```",
+                    lines[start..=error_line].iter().join("\n"),
+                    lines[cont..=end].iter().join("\n"),
+                )
+            );
+
+            Err(e)
+        },
+        ok => ok,
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Deserialize, Clone)]
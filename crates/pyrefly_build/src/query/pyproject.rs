@@ -0,0 +1,881 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! A source DB built directly from the `pyproject.toml` files of a uv, Poetry or PDM
+//! workspace, without shelling out to a build system.
+//!
+//! Every package in the workspace becomes a target, whose sources are the modules under
+//! its import root, and whose dependencies are the other workspace packages it declares
+//! (through PEP 621 `dependencies`, Poetry dependencies or path/editable references).
+//! Imports resolved from site-packages are checked against the declared dependencies too,
+//! using the installed distributions' `RECORD` files to find which one provides a module.
+
+use std::collections::BTreeMap;
+use std::collections::VecDeque;
+use std::fmt::Debug;
+use std::path::Path;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use anyhow::Context as _;
+use pyrefly_python::module_name::ModuleName;
+use pyrefly_python::module_path::ModulePathBuf;
+use pyrefly_python::sys_info::PythonVersion;
+use pyrefly_python::sys_info::SysInfo;
+use pyrefly_util::fs_anyhow;
+use pyrefly_util::lock::Mutex;
+use serde::Deserialize;
+use serde::Serialize;
+use starlark_map::small_map::SmallMap;
+use starlark_map::small_set::SmallSet;
+use tracing::warn;
+use vec1::Vec1;
+
+use crate::ConfiguredEnvironment;
+use crate::query::Include;
+use crate::query::PythonLibraryManifest;
+use crate::query::SourceDbQuerier;
+use crate::query::TargetManifest;
+use crate::query::TargetManifestDatabase;
+use crate::source_db::Target;
+
+/// Args and settings for discovering a uv, Poetry or PDM workspace from its `pyproject.toml` files.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Default, Hash)]
+#[serde(rename_all = "kebab-case")]
+pub struct PyprojectArgs {
+    /// The directory containing the workspace's root `pyproject.toml`, relative to the config.
+    /// Defaults to the directory containing the config.
+    #[serde(default)]
+    workspace_root: Option<PathBuf>,
+}
+
+impl PyprojectArgs {
+    pub fn get_repo_root(&self, cwd: &Path) -> anyhow::Result<PathBuf> {
+        Ok(match &self.workspace_root {
+            Some(root) => cwd.join(root),
+            None => cwd.to_path_buf(),
+        })
+    }
+}
+
+/// A querier that reads the workspace layout from `pyproject.toml` files.
+#[derive(Debug)]
+pub struct PyprojectQuerier {
+    env: ConfiguredEnvironment,
+    /// The normalized names of the dependencies each target declares, including its own,
+    /// as of the last query.
+    declared: Mutex<SmallMap<Target, SmallSet<String>>>,
+    /// For each directory we've looked for installed distributions in, the distribution
+    /// providing each top-level module. `None` if the directory has no `.dist-info`.
+    distributions: Mutex<SmallMap<PathBuf, Option<Arc<SmallMap<String, String>>>>>,
+}
+
+impl PyprojectQuerier {
+    pub fn new(env: ConfiguredEnvironment) -> Self {
+        Self {
+            env,
+            declared: Mutex::new(SmallMap::new()),
+            distributions: Mutex::new(SmallMap::new()),
+        }
+    }
+
+    fn distributions_in(&self, dir: &Path) -> Option<Arc<SmallMap<String, String>>> {
+        if let Some(cached) = self.distributions.lock().get(dir) {
+            return cached.clone();
+        }
+        let res = read_distributions(dir).map(Arc::new);
+        self.distributions
+            .lock()
+            .insert(dir.to_path_buf(), res.clone());
+        res
+    }
+
+    /// The distribution installing `path`, from the nearest enclosing directory containing
+    /// `.dist-info` directories.
+    fn distribution_of(&self, path: &Path) -> Option<String> {
+        path.ancestors().skip(1).find_map(|dir| {
+            let distributions = self.distributions_in(dir)?;
+            let top_level = path.strip_prefix(dir).ok()?.components().next()?;
+            let top_level = top_level.as_os_str().to_str()?.split('.').next()?;
+            distributions.get(top_level).cloned()
+        })
+    }
+}
+
+impl SourceDbQuerier for PyprojectQuerier {
+    fn query_source_db(
+        &self,
+        _: &SmallSet<Include>,
+        cwd: &Path,
+    ) -> anyhow::Result<TargetManifestDatabase> {
+        // The workspace is small enough to describe in full, so we don't narrow it down
+        // to the requested files.
+        let packages = WorkspacePackage::discover(cwd)?;
+        *self.declared.lock() = packages
+            .iter()
+            .map(|p| {
+                let mut declared = p.dependencies.clone();
+                declared.insert(p.name.clone());
+                (Target::from_string(p.name.clone()), declared)
+            })
+            .collect();
+        Ok(WorkspacePackage::to_manifest_database(
+            &packages, cwd, &self.env,
+        ))
+    }
+
+    fn enforces_declared_dependencies(&self) -> bool {
+        true
+    }
+
+    fn undeclared_distribution(&self, target: Target, path: &Path) -> Option<String> {
+        let distribution = self.distribution_of(path)?;
+        let declared = self.declared.lock();
+        let declared = declared.get(&target)?;
+        // Stub-only distributions count as the package they provide types for.
+        let stubs_for = distribution
+            .strip_prefix("types-")
+            .or_else(|| distribution.strip_suffix("-stubs"));
+        if declared.contains(&distribution) || stubs_for.is_some_and(|d| declared.contains(d)) {
+            None
+        } else {
+            Some(distribution)
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct PyProject {
+    #[serde(default)]
+    project: Option<Project>,
+    #[serde(default)]
+    dependency_groups: BTreeMap<String, Vec<toml::Value>>,
+    #[serde(default)]
+    tool: Tool,
+}
+
+/// The PEP 621 `[project]` table.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct Project {
+    name: Option<String>,
+    #[serde(default)]
+    dependencies: Vec<String>,
+    #[serde(default)]
+    optional_dependencies: BTreeMap<String, Vec<String>>,
+    requires_python: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct Tool {
+    #[serde(default)]
+    uv: Option<Uv>,
+    #[serde(default)]
+    poetry: Option<Poetry>,
+    #[serde(default)]
+    pdm: Option<Pdm>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct Uv {
+    #[serde(default)]
+    workspace: Option<UvWorkspace>,
+    #[serde(default)]
+    sources: BTreeMap<String, toml::Value>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct UvWorkspace {
+    #[serde(default)]
+    members: Vec<String>,
+    #[serde(default)]
+    exclude: Vec<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct Poetry {
+    name: Option<String>,
+    #[serde(default)]
+    dependencies: BTreeMap<String, toml::Value>,
+    #[serde(default)]
+    group: BTreeMap<String, PoetryGroup>,
+    #[serde(default)]
+    packages: Vec<PoetryPackage>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct PoetryGroup {
+    #[serde(default)]
+    dependencies: BTreeMap<String, toml::Value>,
+}
+
+/// An entry in Poetry's `packages`, e.g. `{ include = "my_package", from = "src" }`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct PoetryPackage {
+    from: Option<PathBuf>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct Pdm {
+    #[serde(default)]
+    dev_dependencies: BTreeMap<String, Vec<String>>,
+}
+
+/// A package in the workspace, as described by its `pyproject.toml`.
+#[derive(Debug, PartialEq, Eq)]
+struct WorkspacePackage {
+    /// The normalized distribution name.
+    name: String,
+    /// The directory containing the `pyproject.toml`.
+    dir: PathBuf,
+    /// The directories modules are imported relative to.
+    import_roots: Vec<PathBuf>,
+    /// The normalized names of every declared dependency, whether in the workspace or not.
+    dependencies: SmallSet<String>,
+    /// Directories of packages depended on by path.
+    path_dependencies: Vec<PathBuf>,
+    /// The lowest Python version allowed by `requires-python`, used unless the config sets
+    /// `python-version` itself.
+    python_version: Option<PythonVersion>,
+}
+
+impl WorkspacePackage {
+    /// Find every package reachable from the `pyproject.toml` in `root`, through workspace
+    /// members and path dependencies.
+    fn discover(root: &Path) -> anyhow::Result<Vec<Self>> {
+        if !root.join("pyproject.toml").exists() {
+            return Err(anyhow::anyhow!(
+                "No `pyproject.toml` found at workspace root `{}`",
+                root.display()
+            ));
+        }
+        let mut packages = Vec::new();
+        let mut seen = SmallSet::new();
+        let mut queue = VecDeque::from([root.to_path_buf()]);
+        while let Some(dir) = queue.pop_front() {
+            let dir = dir.canonicalize().unwrap_or(dir);
+            if !seen.insert(dir.clone()) {
+                continue;
+            }
+            let path = dir.join("pyproject.toml");
+            if !path.exists() {
+                warn!(
+                    "Skipping `{}`, which has no `pyproject.toml`",
+                    dir.display()
+                );
+                continue;
+            }
+            let pyproject: PyProject = toml::from_str(&fs_anyhow::read_to_string(&path)?)
+                .with_context(|| format!("Failed to parse `{}`", path.display()))?;
+            if let Some(workspace) = pyproject
+                .tool
+                .uv
+                .as_ref()
+                .and_then(|u| u.workspace.as_ref())
+            {
+                queue.extend(workspace_members(&dir, workspace)?);
+            }
+            if let Some(package) = Self::parse(dir, &pyproject) {
+                queue.extend(package.path_dependencies.iter().cloned());
+                packages.push(package);
+            }
+        }
+        Ok(packages)
+    }
+
+    /// Describe the package in `dir`, or `None` if it is only a virtual workspace root.
+    fn parse(dir: PathBuf, pyproject: &PyProject) -> Option<Self> {
+        let poetry = pyproject.tool.poetry.as_ref();
+        let name = pyproject
+            .project
+            .as_ref()
+            .and_then(|p| p.name.as_deref())
+            .or_else(|| poetry.and_then(|p| p.name.as_deref()))?;
+
+        let mut dependencies = SmallSet::new();
+        let mut path_dependencies = Vec::new();
+        let mut add_requirement = |requirement: &str| {
+            if let Some((name, path)) = parse_requirement(requirement, &dir) {
+                dependencies.insert(name);
+                path_dependencies.extend(path);
+            }
+        };
+        if let Some(project) = &pyproject.project {
+            project
+                .dependencies
+                .iter()
+                .chain(project.optional_dependencies.values().flatten())
+                .for_each(|r| add_requirement(r.as_str()));
+        }
+        pyproject
+            .dependency_groups
+            .values()
+            .flatten()
+            .filter_map(|r| r.as_str())
+            .for_each(&mut add_requirement);
+        if let Some(pdm) = &pyproject.tool.pdm {
+            pdm.dev_dependencies
+                .values()
+                .flatten()
+                .for_each(|r| add_requirement(r.as_str()));
+        }
+        if let Some(poetry) = poetry {
+            for (name, spec) in poetry
+                .dependencies
+                .iter()
+                .chain(poetry.group.values().flat_map(|g| g.dependencies.iter()))
+            {
+                if name == "python" {
+                    continue;
+                }
+                dependencies.insert(normalize_name(name));
+                if let Some(path) = spec.get("path").and_then(|p| p.as_str()) {
+                    path_dependencies.push(dir.join(path));
+                }
+            }
+        }
+        if let Some(uv) = &pyproject.tool.uv {
+            for (name, source) in &uv.sources {
+                if let Some(path) = source.get("path").and_then(|p| p.as_str()) {
+                    dependencies.insert(normalize_name(name));
+                    path_dependencies.push(dir.join(path));
+                }
+            }
+        }
+
+        let mut import_roots: Vec<PathBuf> = poetry
+            .iter()
+            .flat_map(|p| &p.packages)
+            .filter_map(|p| p.from.as_ref().map(|from| dir.join(from)))
+            .collect();
+        import_roots.dedup();
+        if import_roots.is_empty() {
+            let src = dir.join("src");
+            import_roots.push(if src.is_dir() { src } else { dir.clone() });
+        }
+
+        let python_version = pyproject
+            .project
+            .as_ref()
+            .and_then(|p| p.requires_python.as_deref())
+            .and_then(minimum_python_version);
+
+        Some(Self {
+            name: normalize_name(name),
+            dir,
+            import_roots,
+            dependencies,
+            path_dependencies,
+            python_version,
+        })
+    }
+
+    fn to_manifest_database(
+        packages: &[Self],
+        root: &Path,
+        env: &ConfiguredEnvironment,
+    ) -> TargetManifestDatabase {
+        let by_name: SmallMap<&str, &Self> =
+            packages.iter().map(|p| (p.name.as_str(), p)).collect();
+        let by_dir: SmallMap<&Path, &Self> =
+            packages.iter().map(|p| (p.dir.as_path(), p)).collect();
+        // Packages nested inside another package's directory belong to themselves.
+        let package_dirs: SmallSet<&Path> = by_dir.keys().copied().collect();
+
+        let mut db = SmallMap::new();
+        for package in packages {
+            let deps: SmallSet<Target> = package
+                .dependencies
+                .iter()
+                .filter_map(|name| by_name.get(name.as_str()))
+                .chain(package.path_dependencies.iter().filter_map(|dir| {
+                    by_dir.get(dir.canonicalize().unwrap_or_else(|_| dir.clone()).as_path())
+                }))
+                .filter(|dep| dep.name != package.name)
+                .map(|dep| Target::from_string(dep.name.clone()))
+                .collect();
+
+            let mut srcs: SmallMap<ModuleName, Vec1<ModulePathBuf>> = SmallMap::new();
+            for import_root in &package.import_roots {
+                collect_sources(
+                    import_root,
+                    import_root,
+                    &package_dirs,
+                    &package.dir,
+                    &mut srcs,
+                );
+            }
+
+            let sys_info = match package.python_version {
+                Some(version) if !env.python_version_configured => {
+                    SysInfo::new(version, env.sys_info.platform().clone())
+                }
+                _ => env.sys_info.clone(),
+            };
+            db.insert(
+                Target::from_string(package.name.clone()),
+                TargetManifest::Library(PythonLibraryManifest {
+                    deps,
+                    srcs,
+                    relative_to: None,
+                    sys_info,
+                    buildfile_path: package.dir.join("pyproject.toml"),
+                    packages: SmallMap::new(),
+                }),
+            );
+        }
+        TargetManifestDatabase {
+            db,
+            root: root.to_path_buf(),
+        }
+    }
+}
+
+/// Expand the `members` globs of a `[tool.uv.workspace]`, dropping anything `exclude`d.
+fn workspace_members(root: &Path, workspace: &UvWorkspace) -> anyhow::Result<Vec<PathBuf>> {
+    let exclude = workspace
+        .exclude
+        .iter()
+        .map(|pattern| glob::Pattern::new(&root.join(pattern).to_string_lossy()))
+        .collect::<Result<Vec<_>, _>>()
+        .context("Invalid `exclude` pattern in `[tool.uv.workspace]`")?;
+    let mut members = Vec::new();
+    for pattern in &workspace.members {
+        let pattern = root.join(pattern);
+        for path in glob::glob(&pattern.to_string_lossy())
+            .context("Invalid `members` pattern in `[tool.uv.workspace]`")?
+        {
+            let path = path?;
+            if path.is_dir() && !exclude.iter().any(|e| e.matches_path(&path)) {
+                members.push(path);
+            }
+        }
+    }
+    Ok(members)
+}
+
+/// Normalize a distribution name as described in PEP 503.
+fn normalize_name(name: &str) -> String {
+    let mut res = String::with_capacity(name.len());
+    for c in name.trim().chars() {
+        if matches!(c, '-' | '_' | '.') {
+            if !res.ends_with('-') {
+                res.push('-');
+            }
+        } else {
+            res.push(c.to_ascii_lowercase());
+        }
+    }
+    res
+}
+
+/// Get the normalized name of a PEP 508 requirement, and the local path it points to, if any.
+/// Handles PDM's `name @ file:///${PROJECT_ROOT}/../path` and `-e` editable forms.
+fn parse_requirement(requirement: &str, dir: &Path) -> Option<(String, Option<PathBuf>)> {
+    let requirement = requirement.trim();
+    let requirement = requirement
+        .strip_prefix("-e ")
+        .unwrap_or(requirement)
+        .trim_start();
+    let end = requirement
+        .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')))
+        .unwrap_or(requirement.len());
+    if end == 0 {
+        return None;
+    }
+    let name = normalize_name(&requirement[..end]);
+    let path = requirement.split_once('@').and_then(|(_, url)| {
+        let url = url.trim().split(';').next()?.trim();
+        let path = url.strip_prefix("file://")?;
+        let project_root = dir.to_string_lossy();
+        Some(PathBuf::from(
+            path.replace("${PROJECT_ROOT}", &project_root)
+                .trim_start_matches("//"),
+        ))
+        .map(|p| if p.is_relative() { dir.join(p) } else { p })
+    });
+    Some((name, path))
+}
+
+/// The lowest version permitted by a `requires-python` specifier such as `>=3.10,<4`.
+fn minimum_python_version(specifier: &str) -> Option<PythonVersion> {
+    specifier.split(',').find_map(|clause| {
+        let clause = clause.trim();
+        let version = clause
+            .strip_prefix(">=")
+            .or_else(|| clause.strip_prefix("~="))
+            .or_else(|| clause.strip_prefix("=="))?;
+        PythonVersion::from_str(version.trim().trim_end_matches(".*")).ok()
+    })
+}
+
+/// Map each top-level module installed in the site-packages directory `dir` to the normalized
+/// name of the distribution installing it, using the `RECORD` of every `.dist-info` directory.
+/// Returns `None` if `dir` contains no distributions.
+fn read_distributions(dir: &Path) -> Option<SmallMap<String, String>> {
+    let mut res = SmallMap::new();
+    let mut found = false;
+    for entry in fs_anyhow::read_dir(dir).ok()?.filter_map(|e| e.ok()) {
+        let file_name = entry.file_name();
+        let Some(dist_info) = file_name
+            .to_str()
+            .and_then(|n| n.strip_suffix(".dist-info"))
+        else {
+            continue;
+        };
+        found = true;
+        // The directory is `{name}-{version}.dist-info`, with any `-` in the name escaped.
+        let name = normalize_name(dist_info.split('-').next().unwrap_or(dist_info));
+        let Ok(record) = fs_anyhow::read_to_string(&entry.path().join("RECORD")) else {
+            continue;
+        };
+        for line in record.lines() {
+            let path = line.split(',').next().unwrap_or_default();
+            let first = path.split('/').next().unwrap_or_default();
+            if first.starts_with("..")
+                || first == "__pycache__"
+                || first.ends_with(".dist-info")
+                || first.ends_with(".data")
+                || first.ends_with(".pth")
+            {
+                continue;
+            }
+            if let Some(module) = first.split('.').next()
+                && !module.is_empty()
+            {
+                res.entry(module.to_owned()).or_insert_with(|| name.clone());
+            }
+        }
+    }
+    found.then_some(res)
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_alphabetic() || c == '_')
+        && chars.all(|c| c.is_alphanumeric() || c == '_')
+}
+
+/// Add every Python module under `dir` to `srcs`, named relative to `import_root`. Directories
+/// that can't be imported, or that belong to another package, are skipped.
+fn collect_sources(
+    import_root: &Path,
+    dir: &Path,
+    package_dirs: &SmallSet<&Path>,
+    own_dir: &Path,
+    srcs: &mut SmallMap<ModuleName, Vec1<ModulePathBuf>>,
+) {
+    let Ok(entries) = fs_anyhow::read_dir(dir) else {
+        return;
+    };
+    let mut paths: Vec<PathBuf> = entries.filter_map(|e| e.ok()).map(|e| e.path()).collect();
+    paths.sort();
+    for path in paths {
+        let Some(file_name) = path.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+        if path.is_dir() {
+            if is_identifier(file_name)
+                && file_name != "__pycache__"
+                && !(path != own_dir && package_dirs.contains(path.as_path()))
+            {
+                collect_sources(import_root, &path, package_dirs, own_dir, srcs);
+            }
+            continue;
+        }
+        let Some(stem) = file_name
+            .strip_suffix(".pyi")
+            .or_else(|| file_name.strip_suffix(".py"))
+        else {
+            continue;
+        };
+        if !is_identifier(stem) {
+            continue;
+        }
+        let Ok(relative) = path.strip_prefix(import_root) else {
+            continue;
+        };
+        let Ok(module) = ModuleName::from_relative_path(relative) else {
+            continue;
+        };
+        if module.as_str().is_empty() {
+            continue;
+        }
+        let path = ModulePathBuf::new(path.clone());
+        match srcs.get_mut(&module) {
+            Some(paths) => paths.push(path),
+            None => {
+                srcs.insert(module, Vec1::new(path));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use pyrefly_python::sys_info::PythonPlatform;
+
+    use super::*;
+
+    fn env(python_version_configured: bool) -> ConfiguredEnvironment {
+        ConfiguredEnvironment {
+            sys_info: SysInfo::new(PythonVersion::new(3, 9, 0), PythonPlatform::linux()),
+            python_version_configured,
+        }
+    }
+
+    fn write(root: &Path, path: &str, contents: &str) {
+        let path = root.join(path);
+        fs_anyhow::create_dir_all(path.parent().unwrap()).unwrap();
+        fs_anyhow::write(&path, contents).unwrap();
+    }
+
+    #[test]
+    fn test_normalize_name() {
+        assert_eq!(normalize_name("Foo_Bar.baz"), "foo-bar-baz");
+        assert_eq!(normalize_name("foo--bar"), "foo-bar");
+    }
+
+    #[test]
+    fn test_parse_requirement() {
+        let dir = Path::new("/repo/packages/a");
+        assert_eq!(
+            parse_requirement("requests>=2.0; python_version > '3.8'", dir),
+            Some(("requests".to_owned(), None))
+        );
+        assert_eq!(
+            parse_requirement("My_Lib[extra]==1.0", dir),
+            Some(("my-lib".to_owned(), None))
+        );
+        assert_eq!(
+            parse_requirement("b @ file:///${PROJECT_ROOT}/../b", dir),
+            Some(("b".to_owned(), Some(PathBuf::from("/repo/packages/a/../b"))))
+        );
+        assert_eq!(parse_requirement("", dir), None);
+    }
+
+    #[test]
+    fn test_minimum_python_version() {
+        assert_eq!(
+            minimum_python_version(">=3.10,<4"),
+            Some(PythonVersion::new(3, 10, 0))
+        );
+        assert_eq!(
+            minimum_python_version("<4, ~=3.9"),
+            Some(PythonVersion::new(3, 9, 0))
+        );
+        assert_eq!(minimum_python_version("<4"), None);
+    }
+
+    #[test]
+    fn test_uv_workspace() {
+        let tdir = tempfile::tempdir().unwrap();
+        let root = tdir.path().canonicalize().unwrap();
+        write(
+            &root,
+            "pyproject.toml",
+            r#"
+[project]
+name = "app"
+requires-python = ">=3.11"
+dependencies = ["core", "requests"]
+
+[tool.uv.workspace]
+members = ["packages/*"]
+exclude = ["packages/ignored"]
+
+[tool.uv.sources]
+core = { workspace = true }
+"#,
+        );
+        write(&root, "app/__init__.py", "");
+        write(&root, "app/main.py", "");
+        write(
+            &root,
+            "packages/core/pyproject.toml",
+            r#"
+[project]
+name = "core"
+dependencies = ["utils"]
+"#,
+        );
+        write(&root, "packages/core/src/core/__init__.py", "");
+        write(
+            &root,
+            "packages/utils/pyproject.toml",
+            r#"
+[tool.poetry]
+name = "utils"
+"#,
+        );
+        write(&root, "packages/utils/utils.py", "");
+        write(
+            &root,
+            "packages/ignored/pyproject.toml",
+            "[project]\nname = \"ignored\"\n",
+        );
+
+        let packages = WorkspacePackage::discover(&root).unwrap();
+        let names: Vec<&str> = packages.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["app", "core", "utils"]);
+
+        let db = WorkspacePackage::to_manifest_database(&packages, &root, &env(false));
+        let manifest = |name: &str| match db.db.get(&Target::from_string(name.to_owned())) {
+            Some(TargetManifest::Library(lib)) => lib.clone(),
+            _ => panic!("Missing target {name}"),
+        };
+        let app = manifest("app");
+        assert_eq!(
+            app.deps.iter().map(|t| t.to_string()).collect::<Vec<_>>(),
+            vec!["core"]
+        );
+        assert_eq!(
+            app.srcs.keys().map(|m| m.as_str()).collect::<Vec<_>>(),
+            vec!["app", "app.main"]
+        );
+        assert_eq!(app.sys_info.version(), PythonVersion::new(3, 11, 0));
+        assert_eq!(app.sys_info.platform(), &PythonPlatform::linux());
+        let core = manifest("core");
+        assert_eq!(
+            core.deps.iter().map(|t| t.to_string()).collect::<Vec<_>>(),
+            vec!["utils"]
+        );
+        assert_eq!(
+            core.srcs.keys().map(|m| m.as_str()).collect::<Vec<_>>(),
+            vec!["core"]
+        );
+        let utils = manifest("utils");
+        assert!(utils.deps.is_empty());
+        assert_eq!(
+            utils.srcs.keys().map(|m| m.as_str()).collect::<Vec<_>>(),
+            vec!["utils"]
+        );
+    }
+
+    #[test]
+    fn test_poetry_path_dependencies() {
+        let tdir = tempfile::tempdir().unwrap();
+        let root = tdir.path().canonicalize().unwrap();
+        write(
+            &root,
+            "service/pyproject.toml",
+            r#"
+[tool.poetry]
+name = "service"
+packages = [{ include = "service", from = "src" }]
+
+[tool.poetry.dependencies]
+python = "^3.10"
+shared = { path = "../shared", develop = true }
+"#,
+        );
+        write(&root, "service/src/service/__init__.py", "");
+        write(
+            &root,
+            "shared/pyproject.toml",
+            "[tool.poetry]\nname = \"shared\"\n",
+        );
+        write(&root, "shared/shared/__init__.pyi", "");
+
+        let packages = WorkspacePackage::discover(&root.join("service")).unwrap();
+        let db = WorkspacePackage::to_manifest_database(&packages, &root, &env(false));
+        let Some(TargetManifest::Library(service)) =
+            db.db.get(&Target::from_string("service".to_owned()))
+        else {
+            panic!("Missing target service");
+        };
+        assert_eq!(
+            service.deps,
+            SmallSet::from_iter([Target::from_string("shared".to_owned())])
+        );
+        assert!(
+            db.db
+                .contains_key(&Target::from_string("shared".to_owned()))
+        );
+    }
+
+    #[test]
+    fn test_configured_python_version() {
+        let tdir = tempfile::tempdir().unwrap();
+        let root = tdir.path().canonicalize().unwrap();
+        write(
+            &root,
+            "pyproject.toml",
+            "[project]\nname = \"app\"\nrequires-python = \">=3.11\"\n",
+        );
+        let packages = WorkspacePackage::discover(&root).unwrap();
+        let db = WorkspacePackage::to_manifest_database(&packages, &root, &env(true));
+        let Some(TargetManifest::Library(app)) = db.db.get(&Target::from_string("app".to_owned()))
+        else {
+            panic!("Missing target app");
+        };
+        assert_eq!(app.sys_info, env(true).sys_info);
+    }
+
+    #[test]
+    fn test_undeclared_distribution() {
+        let tdir = tempfile::tempdir().unwrap();
+        let root = tdir.path().canonicalize().unwrap();
+        write(
+            &root,
+            "pyproject.toml",
+            r#"
+[project]
+name = "app"
+dependencies = ["requests"]
+"#,
+        );
+        write(&root, "app/__init__.py", "");
+        let site_packages = root.join("venv/site-packages");
+        write(
+            &site_packages,
+            "requests-2.31.0.dist-info/RECORD",
+            "requests/__init__.py,sha256=abc,10\nrequests-2.31.0.dist-info/RECORD,,\n",
+        );
+        write(
+            &site_packages,
+            "types_six-1.16.0.dist-info/RECORD",
+            "six-stubs/__init__.pyi,,\n",
+        );
+        write(
+            &site_packages,
+            "Typing_Extensions-4.0.dist-info/RECORD",
+            "typing_extensions.py,,\n__pycache__/typing_extensions.cpython-311.pyc,,\n",
+        );
+
+        let querier = PyprojectQuerier::new(env(false));
+        querier.query_source_db(&SmallSet::new(), &root).unwrap();
+        let app = Target::from_string("app".to_owned());
+        assert_eq!(
+            querier.undeclared_distribution(app, &site_packages.join("requests/api.py")),
+            None
+        );
+        assert_eq!(
+            querier.undeclared_distribution(app, &site_packages.join("typing_extensions.py")),
+            Some("typing-extensions".to_owned())
+        );
+        assert_eq!(
+            querier.undeclared_distribution(app, &site_packages.join("six-stubs/__init__.pyi")),
+            Some("types-six".to_owned())
+        );
+        // Modules no distribution claims are left alone.
+        assert_eq!(
+            querier.undeclared_distribution(app, &site_packages.join("other.py")),
+            None
+        );
+    }
+}
@@ -0,0 +1,1006 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! A source DB read statically from Bazel or Pants `BUILD` files, without running the
+//! build system.
+//!
+//! We don't evaluate Starlark: `BUILD` files are parsed as a sequence of calls, and any
+//! call to a known Python rule (`py_library`, `py_binary`, `py_test`, or Pants'
+//! `python_sources` family) becomes a target. Lists, string concatenation, `glob()` and
+//! top-level variables are understood; anything else (macros, comprehensions, `select()`)
+//! is skipped. Parsed files are cached on their modification time, so a requery only
+//! re-reads the `BUILD` files that changed.
+
+use std::collections::VecDeque;
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use dupe::Dupe as _;
+use pyrefly_python::module_name::ModuleName;
+use pyrefly_python::module_path::ModulePathBuf;
+use pyrefly_python::sys_info::SysInfo;
+use pyrefly_util::fs_anyhow;
+use pyrefly_util::lock::Mutex;
+use serde::Deserialize;
+use serde::Serialize;
+use starlark_map::small_map::SmallMap;
+use starlark_map::small_set::SmallSet;
+use tracing::debug;
+use tracing::warn;
+use vec1::Vec1;
+
+use crate::ConfiguredEnvironment;
+use crate::query::Include;
+use crate::query::PythonLibraryManifest;
+use crate::query::SourceDbQuerier;
+use crate::query::TargetManifest;
+use crate::query::TargetManifestDatabase;
+use crate::source_db::Target;
+
+/// The names a `BUILD` file may have, in order of preference.
+const BUILD_FILE_NAMES: &[&str] = &["BUILD.bazel", "BUILD"];
+
+/// Files marking the root of a Bazel or Pants repository.
+const REPO_ROOT_MARKERS: &[&str] = &["MODULE.bazel", "WORKSPACE.bazel", "WORKSPACE", "pants.toml"];
+
+/// Args and settings for reading targets from Bazel or Pants `BUILD` files.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Default, Hash)]
+#[serde(rename_all = "kebab-case")]
+pub struct BuildFileArgs {
+    /// The root of the repository, relative to the config, which `//` labels are resolved
+    /// against. Defaults to the nearest ancestor containing a `MODULE.bazel`, `WORKSPACE`
+    /// or `pants.toml` file.
+    #[serde(default)]
+    repo_root: Option<PathBuf>,
+}
+
+impl BuildFileArgs {
+    pub fn get_repo_root(&self, cwd: &Path) -> anyhow::Result<PathBuf> {
+        if let Some(root) = &self.repo_root {
+            return Ok(cwd.join(root));
+        }
+        Ok(cwd
+            .ancestors()
+            .find(|dir| REPO_ROOT_MARKERS.iter().any(|m| dir.join(m).exists()))
+            .unwrap_or(cwd)
+            .to_path_buf())
+    }
+}
+
+/// A `BUILD` file we've already parsed, along with the modification time it had then.
+#[derive(Debug)]
+struct CachedBuildFile {
+    modified: Option<SystemTime>,
+    targets: Arc<Vec<BuildTarget>>,
+}
+
+/// A querier that statically reads `BUILD` files.
+#[derive(Debug)]
+pub struct BuildFileQuerier {
+    /// `BUILD` files don't record a Python version or platform, so every target is checked
+    /// with the configured ones.
+    sys_info: SysInfo,
+    cache: Mutex<SmallMap<PathBuf, CachedBuildFile>>,
+}
+
+impl BuildFileQuerier {
+    pub fn new(env: ConfiguredEnvironment) -> Self {
+        Self {
+            sys_info: env.sys_info,
+            cache: Mutex::new(SmallMap::new()),
+        }
+    }
+
+    /// Get the targets declared in the `BUILD` file of `package`, re-parsing it only if it
+    /// has changed since we last read it.
+    fn load_package(&self, root: &Path, package: &str) -> Option<(PathBuf, Arc<Vec<BuildTarget>>)> {
+        let build_file = find_build_file(&root.join(package))?;
+        let modified = build_file.metadata().and_then(|m| m.modified()).ok();
+        let mut cache = self.cache.lock();
+        if let Some(cached) = cache.get(&build_file)
+            && modified.is_some()
+            && cached.modified == modified
+        {
+            return Some((build_file, cached.targets.dupe()));
+        }
+        debug!("Parsing build file `{}`", build_file.display());
+        let targets = match fs_anyhow::read_to_string(&build_file) {
+            Ok(contents) => Arc::new(parse_build_file(&contents, package)),
+            Err(e) => {
+                warn!("{e:#}");
+                Arc::new(Vec::new())
+            }
+        };
+        cache.insert(
+            build_file.clone(),
+            CachedBuildFile {
+                modified,
+                targets: targets.dupe(),
+            },
+        );
+        Some((build_file, targets))
+    }
+}
+
+impl SourceDbQuerier for BuildFileQuerier {
+    fn query_source_db(
+        &self,
+        files: &SmallSet<Include>,
+        cwd: &Path,
+    ) -> anyhow::Result<TargetManifestDatabase> {
+        let mut queue: VecDeque<String> = files
+            .iter()
+            .filter_map(|include| match include {
+                Include::Path(path) => owning_package(cwd, path),
+                Include::Target(target) => {
+                    parse_label(&target.to_string(), "").map(|label| label.package)
+                }
+            })
+            .collect();
+
+        // Walk the dependency closure of the packages owning the requested files, the
+        // same way a build system query would.
+        let mut visited: SmallSet<String> = SmallSet::new();
+        let mut db = SmallMap::new();
+        while let Some(package) = queue.pop_front() {
+            if !visited.insert(package.clone()) {
+                continue;
+            }
+            let Some((build_file, targets)) = self.load_package(cwd, &package) else {
+                continue;
+            };
+            let package_dir = cwd.join(&package);
+            for target in targets.iter() {
+                let mut deps = SmallSet::new();
+                for dep in &target.deps {
+                    queue.push_back(dep.package.clone());
+                    deps.insert(dep.to_target());
+                }
+                let srcs = target.expand_sources(&package_dir, cwd);
+                let label = Label {
+                    package: package.clone(),
+                    name: target.name.clone(),
+                };
+                db.insert(
+                    label.to_target(),
+                    TargetManifest::Library(PythonLibraryManifest {
+                        deps,
+                        srcs,
+                        relative_to: None,
+                        sys_info: self.sys_info.clone(),
+                        buildfile_path: build_file.clone(),
+                        packages: SmallMap::new(),
+                    }),
+                );
+            }
+        }
+        Ok(TargetManifestDatabase {
+            db,
+            root: cwd.to_path_buf(),
+        })
+    }
+}
+
+fn find_build_file(dir: &Path) -> Option<PathBuf> {
+    BUILD_FILE_NAMES
+        .iter()
+        .map(|name| dir.join(name))
+        .find(|path| path.is_file())
+}
+
+/// The package (directory relative to `root`, `/`-separated) whose `BUILD` file is
+/// closest to `path`.
+fn owning_package(root: &Path, path: &Path) -> Option<String> {
+    path.parent()?
+        .ancestors()
+        .take_while(|dir| dir.starts_with(root))
+        .find(|dir| find_build_file(dir).is_some())
+        .and_then(|dir| package_name(dir.strip_prefix(root).ok()?))
+}
+
+fn package_name(relative: &Path) -> Option<String> {
+    let parts = relative
+        .components()
+        .map(|c| match c {
+            Component::Normal(part) => part.to_str(),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+    Some(parts.join("/"))
+}
+
+/// Resolve `.` and `..` components without touching the filesystem.
+fn normalize_path(path: &Path) -> PathBuf {
+    let mut res = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                res.pop();
+            }
+            c => res.push(c),
+        }
+    }
+    res
+}
+
+/// A reference to a target within this repository.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Label {
+    package: String,
+    name: String,
+}
+
+impl Label {
+    fn to_target(&self) -> Target {
+        Target::from_string(format!("//{}:{}", self.package, self.name))
+    }
+}
+
+/// Resolve a label written in the `BUILD` file of `package`. Accepts Bazel's `//pkg:name`,
+/// `:name` and `name` forms, and Pants' root-relative `pkg:name` and `pkg` forms. Labels in
+/// other repositories (`@repo//...`) are third-party code and give `None`.
+fn parse_label(label: &str, package: &str) -> Option<Label> {
+    if label.starts_with('@') || label.is_empty() {
+        return None;
+    }
+    let (absolute, rest) = match label.strip_prefix("//") {
+        Some(rest) => (true, rest),
+        None => (false, label),
+    };
+    let label = match rest.split_once(':') {
+        Some(("", name)) if !absolute => Label {
+            package: package.to_owned(),
+            name: name.to_owned(),
+        },
+        Some((pkg, name)) => Label {
+            package: pkg.to_owned(),
+            name: name.to_owned(),
+        },
+        None if absolute || rest.contains('/') => Label {
+            package: rest.to_owned(),
+            name: rest.rsplit('/').next()?.to_owned(),
+        },
+        None => Label {
+            package: package.to_owned(),
+            name: rest.to_owned(),
+        },
+    };
+    if label.name.is_empty() {
+        return None;
+    }
+    Some(label)
+}
+
+/// Glob patterns selecting a target's sources, relative to its package.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+struct Sources {
+    include: Vec<String>,
+    exclude: Vec<String>,
+}
+
+impl Sources {
+    fn new(include: &[&str], exclude: &[&str]) -> Self {
+        Self {
+            include: include.iter().map(|s| (*s).to_owned()).collect(),
+            exclude: exclude.iter().map(|s| (*s).to_owned()).collect(),
+        }
+    }
+
+    fn add(&mut self, value: &Value) {
+        match value {
+            // Labels of generated sources can't be resolved statically.
+            Value::Str(s) if s.starts_with(':') || s.starts_with("//") || s.starts_with('@') => {}
+            Value::Str(s) => match s.strip_prefix('!') {
+                Some(exclude) => self.exclude.push(exclude.to_owned()),
+                None => self.include.push(s.clone()),
+            },
+            Value::List(xs) => xs.iter().for_each(|x| self.add(x)),
+            Value::Call { name, args, kwargs } if name == "glob" => {
+                if let Some(include) = args.first().or_else(|| kwarg(kwargs, "include")) {
+                    self.add(include);
+                }
+                if let Some(Value::List(xs)) = kwarg(kwargs, "exclude") {
+                    self.exclude
+                        .extend(xs.iter().filter_map(Value::as_str).map(str::to_owned));
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// A Python target declared in a `BUILD` file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct BuildTarget {
+    name: String,
+    srcs: Sources,
+    deps: Vec<Label>,
+    /// Directories, relative to the package, that sources are imported relative to.
+    imports: Vec<String>,
+}
+
+impl BuildTarget {
+    fn from_call(name: &str, kwargs: &[(String, Value)], package: &str) -> Option<Self> {
+        let (srcs_attr, deps_attr, default_sources) = match name {
+            "py_library" | "py_binary" | "py_test" => ("srcs", "deps", Sources::default()),
+            "python_sources" => (
+                "sources",
+                "dependencies",
+                Sources::new(
+                    &["*.py", "*.pyi"],
+                    &[
+                        "test_*.py",
+                        "*_test.py",
+                        "tests.py",
+                        "conftest.py",
+                        "test_*.pyi",
+                        "*_test.pyi",
+                        "tests.pyi",
+                    ],
+                ),
+            ),
+            "python_tests" => (
+                "sources",
+                "dependencies",
+                Sources::new(&["test_*.py", "*_test.py", "tests.py"], &[]),
+            ),
+            "python_test_utils" => (
+                "sources",
+                "dependencies",
+                Sources::new(
+                    &["conftest.py", "test_*.pyi", "*_test.pyi", "tests.pyi"],
+                    &[],
+                ),
+            ),
+            "python_source" | "python_test" => ("source", "dependencies", Sources::default()),
+            _ => return None,
+        };
+        // Pants targets default to being named after their directory.
+        let target_name = match kwarg(kwargs, "name").and_then(Value::as_str) {
+            Some(target_name) => target_name.to_owned(),
+            None if srcs_attr != "srcs" => package.rsplit('/').next()?.to_owned(),
+            None => return None,
+        };
+        let srcs = match kwarg(kwargs, srcs_attr) {
+            Some(value) => {
+                let mut srcs = Sources::default();
+                srcs.add(value);
+                srcs
+            }
+            None => default_sources,
+        };
+        let deps = match kwarg(kwargs, deps_attr) {
+            Some(Value::List(xs)) => xs
+                .iter()
+                .filter_map(Value::as_str)
+                .filter_map(|label| parse_label(label, package))
+                .collect(),
+            _ => Vec::new(),
+        };
+        let imports = match kwarg(kwargs, "imports") {
+            Some(Value::List(xs)) => xs
+                .iter()
+                .filter_map(Value::as_str)
+                .map(str::to_owned)
+                .collect(),
+            _ => Vec::new(),
+        };
+        Some(Self {
+            name: target_name,
+            srcs,
+            deps,
+            imports,
+        })
+    }
+
+    /// Expand the source globs against the filesystem. Globs are re-evaluated on every
+    /// query (rather than cached with the parsed `BUILD` file), so new files are picked up.
+    fn expand_sources(
+        &self,
+        package_dir: &Path,
+        root: &Path,
+    ) -> SmallMap<ModuleName, Vec1<ModulePathBuf>> {
+        let exclude: Vec<glob::Pattern> = self
+            .srcs
+            .exclude
+            .iter()
+            .filter_map(|pattern| glob::Pattern::new(pattern).ok())
+            .collect();
+        let import_roots: Vec<PathBuf> = self
+            .imports
+            .iter()
+            .map(|import| normalize_path(&package_dir.join(import)))
+            .collect();
+        let mut srcs: SmallMap<ModuleName, Vec1<ModulePathBuf>> = SmallMap::new();
+        for pattern in &self.srcs.include {
+            let Ok(paths) = glob::glob(&package_dir.join(pattern).to_string_lossy()) else {
+                continue;
+            };
+            for path in paths.filter_map(Result::ok) {
+                let Ok(relative) = path.strip_prefix(package_dir) else {
+                    continue;
+                };
+                if !matches!(
+                    path.extension().and_then(|e| e.to_str()),
+                    Some("py" | "pyi")
+                ) || exclude.iter().any(|e| e.matches_path(relative))
+                    || !path.is_file()
+                    || in_subpackage(package_dir, &path)
+                {
+                    continue;
+                }
+                let import_root = import_roots
+                    .iter()
+                    .find(|import_root| path.starts_with(import_root))
+                    .map_or(root, |import_root| import_root.as_path());
+                let Ok(module) = path
+                    .strip_prefix(import_root)
+                    .map_err(anyhow::Error::from)
+                    .and_then(ModuleName::from_relative_path)
+                else {
+                    continue;
+                };
+                let path = ModulePathBuf::new(path);
+                match srcs.get_mut(&module) {
+                    Some(paths) if !paths.contains(&path) => paths.push(path),
+                    Some(_) => {}
+                    None => {
+                        srcs.insert(module, Vec1::new(path));
+                    }
+                }
+            }
+        }
+        srcs
+    }
+}
+
+/// Whether `path` is owned by a package nested inside `package_dir`, which globs don't
+/// cross into.
+fn in_subpackage(package_dir: &Path, path: &Path) -> bool {
+    path.parent().is_some_and(|parent| {
+        parent
+            .ancestors()
+            .take_while(|dir| *dir != package_dir && dir.starts_with(package_dir))
+            .any(|dir| find_build_file(dir).is_some())
+    })
+}
+
+fn kwarg<'a>(kwargs: &'a [(String, Value)], name: &str) -> Option<&'a Value> {
+    kwargs.iter().find(|(k, _)| k == name).map(|(_, v)| v)
+}
+
+/// Parse the Python targets out of a `BUILD` file in `package`.
+fn parse_build_file(contents: &str, package: &str) -> Vec<BuildTarget> {
+    let mut parser = Parser {
+        tokens: tokenize(contents),
+        pos: 0,
+        variables: SmallMap::new(),
+    };
+    let mut targets = Vec::new();
+    while parser.peek().is_some() {
+        let start = parser.pos;
+        match parser.statement() {
+            Some(Value::Call { name, kwargs, .. }) => {
+                targets.extend(BuildTarget::from_call(&name, &kwargs, package))
+            }
+            Some(_) => {}
+            None => parser.recover(start),
+        }
+    }
+    targets
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Ident(String),
+    Str(String),
+    Punct(char),
+    /// Numbers and operators we don't interpret.
+    Other,
+}
+
+/// A token, and whether it's the first on its line outside any brackets, i.e. whether it
+/// can start a new top-level statement.
+#[derive(Debug, Clone)]
+struct Spanned {
+    token: Token,
+    starts_statement: bool,
+}
+
+fn tokenize(contents: &str) -> Vec<Spanned> {
+    let chars: Vec<char> = contents.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    let mut depth = 0usize;
+    let mut line_start = true;
+    while i < chars.len() {
+        let c = chars[i];
+        if c == '\n' {
+            line_start = true;
+            i += 1;
+            continue;
+        }
+        if c.is_whitespace() || c == '\\' {
+            i += 1;
+            continue;
+        }
+        if c == '#' {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+            continue;
+        }
+        let starts_statement = line_start && depth == 0;
+        line_start = false;
+        let token = if c.is_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            let ident: String = chars[start..i].iter().collect();
+            // String prefixes, e.g. `r"..."`.
+            if i < chars.len()
+                && matches!(chars[i], '"' | '\'')
+                && matches!(ident.to_ascii_lowercase().as_str(), "r" | "b" | "rb" | "br")
+            {
+                continue;
+            }
+            Token::Ident(ident)
+        } else if c == '"' || c == '\'' {
+            let triple = chars.get(i + 1) == Some(&c) && chars.get(i + 2) == Some(&c);
+            i += if triple { 3 } else { 1 };
+            let mut s = String::new();
+            while i < chars.len() {
+                if chars[i] == '\\' && i + 1 < chars.len() {
+                    s.push(chars[i + 1]);
+                    i += 2;
+                } else if chars[i] == c
+                    && (!triple || (chars.get(i + 1) == Some(&c) && chars.get(i + 2) == Some(&c)))
+                {
+                    i += if triple { 3 } else { 1 };
+                    break;
+                } else {
+                    s.push(chars[i]);
+                    i += 1;
+                }
+            }
+            Token::Str(s)
+        } else if c.is_ascii_digit() {
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '.') {
+                i += 1;
+            }
+            Token::Other
+        } else {
+            i += 1;
+            match c {
+                '(' | '[' | '{' => {
+                    depth += 1;
+                    Token::Punct(c)
+                }
+                ')' | ']' | '}' => {
+                    depth = depth.saturating_sub(1);
+                    Token::Punct(c)
+                }
+                ',' | '=' | ':' | '+' | '.' => Token::Punct(c),
+                _ => Token::Other,
+            }
+        };
+        tokens.push(Spanned {
+            token,
+            starts_statement,
+        });
+    }
+    tokens
+}
+
+/// The subset of Starlark values we understand.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Value {
+    Str(String),
+    List(Vec<Value>),
+    Call {
+        name: String,
+        args: Vec<Value>,
+        kwargs: Vec<(String, Value)>,
+    },
+    Other,
+}
+
+impl Value {
+    fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::Str(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    fn into_items(self) -> Vec<Value> {
+        match self {
+            Value::List(xs) => xs,
+            x => vec![x],
+        }
+    }
+
+    /// Evaluate `self + other`. List concatenation with a `glob()` keeps both sides, so
+    /// `srcs = ["a.py"] + glob(["b/*.py"])` still finds every source.
+    fn concat(self, other: Value) -> Value {
+        match (self, other) {
+            (Value::Str(a), Value::Str(b)) => Value::Str(a + &b),
+            (Value::Other, _) | (_, Value::Other) => Value::Other,
+            (a, b) => {
+                let mut items = a.into_items();
+                items.extend(b.into_items());
+                Value::List(items)
+            }
+        }
+    }
+}
+
+struct Parser {
+    tokens: Vec<Spanned>,
+    pos: usize,
+    /// Top-level assignments, e.g. `SRCS = [...]`, so later references can be resolved.
+    variables: SmallMap<String, Value>,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|t| &t.token)
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<&Token> {
+        self.tokens.get(self.pos + offset).map(|t| &t.token)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos)?.token.clone();
+        self.pos += 1;
+        Some(token)
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(&Token::Punct(c)) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Option<()> {
+        self.eat(c).then_some(())
+    }
+
+    /// Skip to the next token that can start a statement after a parse error.
+    fn recover(&mut self, start: usize) {
+        self.pos = start + 1;
+        while self
+            .tokens
+            .get(self.pos)
+            .is_some_and(|t| !t.starts_statement)
+        {
+            self.pos += 1;
+        }
+    }
+
+    fn statement(&mut self) -> Option<Value> {
+        if let Some(Token::Ident(name)) = self.peek()
+            && self.peek_at(1) == Some(&Token::Punct('='))
+        {
+            let name = name.clone();
+            self.pos += 2;
+            let value = self.expression()?;
+            self.variables.insert(name, value);
+            return Some(Value::Other);
+        }
+        self.expression()
+    }
+
+    fn expression(&mut self) -> Option<Value> {
+        let mut value = self.primary()?;
+        while self.eat('+') {
+            value = value.concat(self.primary()?);
+        }
+        Some(value)
+    }
+
+    fn primary(&mut self) -> Option<Value> {
+        let mut value = match self.next()? {
+            Token::Str(mut s) => {
+                // Adjacent string literals are implicitly concatenated.
+                while let Some(Token::Str(next)) = self.peek() {
+                    s.push_str(next);
+                    self.pos += 1;
+                }
+                Value::Str(s)
+            }
+            Token::Ident(name) => {
+                if self.peek() == Some(&Token::Punct('(')) {
+                    self.pos += 1;
+                    self.call(name)?
+                } else {
+                    self.variables.get(&name).cloned().unwrap_or(Value::Other)
+                }
+            }
+            Token::Punct('[') => self.sequence(']')?,
+            Token::Punct('(') => match self.sequence(')')? {
+                Value::List(mut xs) if xs.len() == 1 => xs.pop()?,
+                value => value,
+            },
+            Token::Punct('{') => {
+                self.skip_to_close('{', '}')?;
+                Value::Other
+            }
+            Token::Other => Value::Other,
+            Token::Punct(_) => return None,
+        };
+        // Method calls, attribute access and indexing, none of which we evaluate.
+        loop {
+            if self.eat('.') {
+                let Some(Token::Ident(_)) = self.next() else {
+                    return None;
+                };
+                value = Value::Other;
+            } else if self.eat('(') {
+                self.skip_to_close('(', ')')?;
+                value = Value::Other;
+            } else if self.eat('[') {
+                self.skip_to_close('[', ']')?;
+                value = Value::Other;
+            } else {
+                return Some(value);
+            }
+        }
+    }
+
+    /// Parse comma-separated items up to `close`, treating comprehensions as opaque.
+    fn sequence(&mut self, close: char) -> Option<Value> {
+        let open = if close == ']' { '[' } else { '(' };
+        let mut items = Vec::new();
+        loop {
+            if self.eat(close) {
+                return Some(Value::List(items));
+            }
+            items.push(self.expression()?);
+            if matches!(self.peek(), Some(Token::Ident(x)) if x == "for") {
+                self.skip_to_close(open, close)?;
+                return Some(Value::Other);
+            }
+            if !self.eat(',') {
+                self.expect(close)?;
+                return Some(Value::List(items));
+            }
+        }
+    }
+
+    /// Skip past the bracket matching one we've already consumed.
+    fn skip_to_close(&mut self, open: char, close: char) -> Option<()> {
+        let mut depth = 1;
+        while depth > 0 {
+            match self.next()? {
+                Token::Punct(c) if c == open => depth += 1,
+                Token::Punct(c) if c == close => depth -= 1,
+                _ => {}
+            }
+        }
+        Some(())
+    }
+
+    fn call(&mut self, name: String) -> Option<Value> {
+        let mut args = Vec::new();
+        let mut kwargs = Vec::new();
+        loop {
+            if self.eat(')') {
+                break;
+            }
+            if let Some(Token::Ident(key)) = self.peek()
+                && self.peek_at(1) == Some(&Token::Punct('='))
+            {
+                let key = key.clone();
+                self.pos += 2;
+                kwargs.push((key, self.expression()?));
+            } else {
+                // `*args` and `**kwargs` are tokenized as `Other`, so just parse past them.
+                while self.peek() == Some(&Token::Other) {
+                    self.pos += 1;
+                }
+                args.push(self.expression()?);
+            }
+            if !self.eat(',') {
+                self.expect(')')?;
+                break;
+            }
+        }
+        Some(Value::Call { name, args, kwargs })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use pyrefly_python::sys_info::PythonPlatform;
+    use pyrefly_python::sys_info::PythonVersion;
+
+    use super::*;
+
+    fn write(root: &Path, path: &str, contents: &str) {
+        let path = root.join(path);
+        fs_anyhow::create_dir_all(path.parent().unwrap()).unwrap();
+        fs_anyhow::write(&path, contents).unwrap();
+    }
+
+    fn label(package: &str, name: &str) -> Label {
+        Label {
+            package: package.to_owned(),
+            name: name.to_owned(),
+        }
+    }
+
+    #[test]
+    fn test_parse_label() {
+        assert_eq!(parse_label("//a/b:c", "x"), Some(label("a/b", "c")));
+        assert_eq!(parse_label("//a/b", "x"), Some(label("a/b", "b")));
+        assert_eq!(parse_label(":c", "x/y"), Some(label("x/y", "c")));
+        assert_eq!(parse_label("c", "x/y"), Some(label("x/y", "c")));
+        assert_eq!(
+            parse_label("src/lib:util", "x"),
+            Some(label("src/lib", "util"))
+        );
+        assert_eq!(parse_label("src/lib", "x"), Some(label("src/lib", "lib")));
+        assert_eq!(parse_label("@pip//requests", "x"), None);
+    }
+
+    #[test]
+    fn test_parse_build_file() {
+        let targets = parse_build_file(
+            r#"
+load("@rules_python//python:defs.bzl", "py_binary", "py_library")
+
+COMMON_DEPS = ["//lib:util"]
+
+py_library(
+    name = "core",
+    srcs = ["core.py"] + glob(["impl/**/*.py"], exclude = ["impl/gen_*.py"]),
+    deps = COMMON_DEPS + [":base", "@pip//requests"],
+    imports = ["."],
+)
+
+some_macro(name = [x for x in range(3)], srcs = {"a": "b"})
+
+py_binary(
+    name = "main",
+    srcs = ["main.py"],
+    deps = [":core"],
+    visibility = ["//visibility:public"],
+)
+
+genrule(name = "gen", outs = ["gen.py"], cmd = "touch $@")
+"#,
+            "app",
+        );
+        assert_eq!(
+            targets,
+            vec![
+                BuildTarget {
+                    name: "core".to_owned(),
+                    srcs: Sources::new(&["core.py", "impl/**/*.py"], &["impl/gen_*.py"]),
+                    deps: vec![label("lib", "util"), label("app", "base")],
+                    imports: vec![".".to_owned()],
+                },
+                BuildTarget {
+                    name: "main".to_owned(),
+                    srcs: Sources::new(&["main.py"], &[]),
+                    deps: vec![label("app", "core")],
+                    imports: Vec::new(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_recovers_from_errors() {
+        let targets = parse_build_file(
+            r#"
+py_library(name = "broken", srcs = ["a.py" "b.py" +])
+py_test(name = "ok", srcs = ['''test_a.py'''])
+"#,
+            "",
+        );
+        assert_eq!(
+            targets.iter().map(|t| t.name.as_str()).collect::<Vec<_>>(),
+            vec!["ok"]
+        );
+    }
+
+    #[test]
+    fn test_pants_defaults() {
+        let targets = parse_build_file(
+            "python_sources()\npython_tests(name = \"tests\", dependencies = [\"src/util\"])\n",
+            "src/app",
+        );
+        assert_eq!(targets[0].name, "app");
+        assert!(targets[0].srcs.exclude.contains(&"test_*.py".to_owned()));
+        assert_eq!(targets[1].deps, vec![label("src/util", "util")]);
+    }
+
+    #[test]
+    fn test_query_source_db() {
+        let tdir = tempfile::tempdir().unwrap();
+        let root = tdir.path().canonicalize().unwrap();
+        write(&root, "MODULE.bazel", "");
+        write(
+            &root,
+            "app/BUILD.bazel",
+            r#"py_binary(name = "main", srcs = glob(["**/*.py"]), deps = ["//lib:util"])"#,
+        );
+        write(&root, "app/main.py", "");
+        write(&root, "app/sub/helper.py", "");
+        write(&root, "app/nested/BUILD", "py_library(name = \"nested\")");
+        write(&root, "app/nested/ignored.py", "");
+        write(
+            &root,
+            "lib/BUILD",
+            r#"py_library(name = "util", srcs = ["src/util/__init__.py"], imports = ["src"])"#,
+        );
+        write(&root, "lib/src/util/__init__.py", "");
+
+        let args = BuildFileArgs::default();
+        assert_eq!(args.get_repo_root(&root.join("app")).unwrap(), root);
+        let sys_info = SysInfo::new(PythonVersion::new(3, 11, 0), PythonPlatform::linux());
+        let querier = BuildFileQuerier::new(ConfiguredEnvironment {
+            sys_info: sys_info.clone(),
+            python_version_configured: true,
+        });
+        let includes =
+            SmallSet::from_iter([Include::path(ModulePathBuf::new(root.join("app/main.py")))]);
+        let db = querier.query_source_db(&includes, &root).unwrap();
+        let manifest = |name: &str| match db.db.get(&Target::from_string(name.to_owned())) {
+            Some(TargetManifest::Library(lib)) => lib.clone(),
+            _ => panic!("Missing target {name}"),
+        };
+        let main = manifest("//app:main");
+        assert_eq!(main.sys_info, sys_info);
+        assert_eq!(
+            main.srcs.keys().map(|m| m.as_str()).collect::<Vec<_>>(),
+            vec!["app.main", "app.sub.helper"]
+        );
+        assert_eq!(
+            main.deps,
+            SmallSet::from_iter([Target::from_string("//lib:util".to_owned())])
+        );
+        let util = manifest("//lib:util");
+        assert_eq!(
+            util.srcs.keys().map(|m| m.as_str()).collect::<Vec<_>>(),
+            vec!["util"]
+        );
+        assert!(
+            !db.db
+                .contains_key(&Target::from_string("//app/nested:nested".to_owned()))
+        );
+
+        // Unchanged files are served from the cache, and edited ones are re-parsed.
+        let cached = querier.load_package(&root, "lib").unwrap().1;
+        assert!(Arc::ptr_eq(
+            &cached,
+            &querier.load_package(&root, "lib").unwrap().1
+        ));
+        let build_file = root.join("lib/BUILD");
+        write(&root, "lib/BUILD", "py_library(name = \"renamed\")");
+        std::fs::File::options()
+            .write(true)
+            .open(&build_file)
+            .unwrap()
+            .set_modified(SystemTime::now() + std::time::Duration::from_secs(10))
+            .unwrap();
+        let reparsed = querier.load_package(&root, "lib").unwrap().1;
+        assert_eq!(reparsed[0].name, "renamed");
+    }
+}
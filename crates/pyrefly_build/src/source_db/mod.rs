@@ -29,6 +29,7 @@ use static_interner::Interner;
 use crate::handle::Handle;
 
 pub mod buck_check;
+pub mod build_file;
 pub mod map_db;
 pub(crate) mod query_source_db;
 
@@ -132,6 +133,17 @@ pub trait SourceDatabase: Send + Sync + fmt::Debug {
     fn get_paths_to_watch(&self) -> SmallSet<WatchPattern<'_>>;
     /// Get the target for the given [`ModulePath`], if one exists.
     fn get_target(&self, origin: Option<&Path>) -> Option<Target>;
+    /// If `module` is owned by a target that `origin`'s target does not depend on, and this
+    /// sourcedb treats that as an error, get the owning target.
+    fn undeclared_dependency(&self, _module: ModuleName, _origin: Option<&Path>) -> Option<Target> {
+        None
+    }
+    /// If `path`, found in site-packages, is installed by a distribution that `origin`'s target
+    /// does not declare as a dependency, and this sourcedb treats that as an error, get the
+    /// distribution.
+    fn undeclared_distribution(&self, _path: &Path, _origin: Option<&Path>) -> Option<Target> {
+        None
+    }
     /// Get any generated files for which we might have to override the config finder.
     fn get_generated_files(&self) -> SmallSet<ModulePathBuf>;
 }
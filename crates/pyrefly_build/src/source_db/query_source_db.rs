@@ -396,6 +396,38 @@ impl SourceDatabase for QuerySourceDatabase {
         read.path_lookup.get(&origin).copied()
     }
 
+    fn undeclared_dependency(&self, module: ModuleName, origin: Option<&Path>) -> Option<Target> {
+        if !self.querier.enforces_declared_dependencies() {
+            return None;
+        }
+        let origin = ModulePathBuf::from_path(origin?);
+        let read = self.inner.read();
+        if !read.known_modules.contains(&module) {
+            return None;
+        }
+        let start_target = *read.path_lookup.get(&origin)?;
+        if self
+            .lookup_from_target(&read, module, start_target, None)
+            .is_some()
+        {
+            return None;
+        }
+        read.db
+            .iter()
+            .find(|(_, manifest)| {
+                manifest.srcs.contains_key(&module) || manifest.packages.contains_key(&module)
+            })
+            .map(|(target, _)| target.dupe())
+    }
+
+    fn undeclared_distribution(&self, path: &Path, origin: Option<&Path>) -> Option<Target> {
+        let origin = ModulePathBuf::from_path(origin?);
+        let target = *self.inner.read().path_lookup.get(&origin)?;
+        self.querier
+            .undeclared_distribution(target, path)
+            .map(Target::from_string)
+    }
+
     fn get_generated_files(&self) -> SmallSet<ModulePathBuf> {
         let read = self.inner.read();
         read.db
@@ -444,9 +476,22 @@ mod tests {
         ) -> anyhow::Result<TargetManifestDatabase> {
             Ok(TargetManifestDatabase::get_test_database())
         }
+    }
+
+    #[derive(Debug)]
+    struct StrictDummyQuerier {}
 
-        fn construct_command(&self) -> std::process::Command {
-            panic!("We shouldn't be calling this...");
+    impl SourceDbQuerier for StrictDummyQuerier {
+        fn query_source_db(
+            &self,
+            _: &SmallSet<Include>,
+            _: &Path,
+        ) -> anyhow::Result<TargetManifestDatabase> {
+            Ok(TargetManifestDatabase::get_test_database())
+        }
+
+        fn enforces_declared_dependencies(&self) -> bool {
+            true
         }
     }
 
@@ -799,6 +844,30 @@ mod tests {
         assert_handle("colorama/__init__.pyi", "colorama");
     }
 
+    #[test]
+    fn test_undeclared_dependency() {
+        let (mut db, root) = get_db();
+        let click = root.join("click/__init__.py");
+        let log = ModuleName::from_str("pyre.client.log");
+        // Buck falls back to the search path, so nothing is reported unless the querier asks.
+        assert_eq!(db.undeclared_dependency(log, Some(&click)), None);
+
+        db.querier = Arc::new(StrictDummyQuerier {});
+        assert_eq!(
+            db.undeclared_dependency(log, Some(&click)),
+            Some(Target::from_string("//pyre/client/log:log".to_owned())),
+        );
+        // `colorama` is a dependency of `click`, and unknown modules are left to the search path.
+        assert_eq!(
+            db.undeclared_dependency(ModuleName::from_str("colorama"), Some(&click)),
+            None,
+        );
+        assert_eq!(
+            db.undeclared_dependency(ModuleName::from_str("does_not_exist"), Some(&click)),
+            None,
+        );
+    }
+
     #[test]
     fn test_update_with_target_manifest() {
         let (db, root) = get_db();
@@ -18,8 +18,10 @@ use pyrefly_util::arc_id::ArcId;
 use pyrefly_util::display;
 use tracing::warn;
 
+use crate::base::Preset;
 use crate::base::UntypedDefBehavior;
 use crate::config::ConfigFile;
+use crate::config::PythonTarget;
 use crate::config::validate_path;
 use crate::error::ErrorDisplayConfig;
 use crate::error_kind::ErrorKind;
@@ -74,6 +76,13 @@ pub struct ConfigOverrideArgs {
     #[arg(long)]
     python_platform: Option<PythonPlatform>,
 
+    /// Also check against each of these Python versions and platforms in the same run, reporting
+    /// which of them each error occurs in. Entries are `VERSION`, `VERSION:PLATFORM` or
+    /// `:PLATFORM`, where a missing part uses the configured value. Can be passed multiple
+    /// times or as a comma-separated list.
+    #[arg(long, value_name = "VERSION:PLATFORM", value_delimiter = ',')]
+    python_matrix: Option<Vec<PythonTarget>>,
+
     /// Directories containing third-party package imports, searched
     /// after first checking `search_path` and `typeshed`.
     #[arg(long)]
@@ -152,6 +161,10 @@ pub struct ConfigOverrideArgs {
     /// Controls how Pyrefly analyzes function definitions that lack type annotations on parameters and return values.
     #[arg(long)]
     untyped_def_behavior: Option<UntypedDefBehavior>,
+    /// A preset of error severities and untyped-def-behavior to start from: `basic`, `standard` or `strict`.
+    /// Error severities and untyped-def-behavior set explicitly take precedence.
+    #[arg(long)]
+    preset: Option<Preset>,
     /// Whether Pyrefly will respect ignore statements for other tools, e.g. `# pyright: ignore`.
     /// Equivalent to passing the names of all tools to `--enabled-ignores`.
     #[arg(
@@ -261,6 +274,9 @@ impl ConfigOverrideArgs {
         if let Some(x) = &self.python_version {
             config.python_environment.python_version = Some(*x);
         }
+        if let Some(x) = &self.python_matrix {
+            config.python_matrix = x.clone();
+        }
         if let Some(x) = &self.search_path {
             config.search_path_from_args = x.clone();
         }
@@ -309,6 +325,9 @@ impl ConfigOverrideArgs {
         if let Some(x) = &self.untyped_def_behavior {
             config.root.untyped_def_behavior = Some(*x);
         }
+        if let Some(x) = &self.preset {
+            config.root.preset = Some(*x);
+        }
         match (self.permissive_ignores, &self.enabled_ignores) {
             // Special case: if the underlying config sets enabled-ignores and --permissive-ignores
             // is passed on the command-line, we overwrite enabled-ignores.
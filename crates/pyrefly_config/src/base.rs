@@ -13,6 +13,8 @@ use starlark_map::small_set::SmallSet;
 use toml::Table;
 
 use crate::error::ErrorDisplayConfig;
+use crate::error_kind::ErrorKind;
+use crate::error_kind::Severity;
 use crate::module_wildcard::ModuleWildcard;
 
 #[derive(Debug, PartialEq, Eq, Deserialize, Serialize, Clone, Copy, Default)]
@@ -25,9 +27,47 @@ pub enum UntypedDefBehavior {
     SkipAndInferReturnAny,
 }
 
+/// A named bundle of error severities and an `untyped-def-behavior`, which the settings
+/// given explicitly next to it override.
+#[derive(Debug, PartialEq, Eq, Deserialize, Serialize, Clone, Copy, Default)]
+#[derive(ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum Preset {
+    /// Turn off the error kinds that are warnings by default, and infer `Any` as the
+    /// return type of unannotated functions.
+    Basic,
+    /// The default severities and `untyped-def-behavior`.
+    #[default]
+    Standard,
+    /// Report missing annotations, implicit `Any`, untyped imports and unused code as
+    /// errors, along with the error kinds that are warnings by default.
+    Strict,
+}
+
+impl Preset {
+    pub fn severity(self, kind: ErrorKind) -> Severity {
+        match self {
+            Self::Basic if kind.default_severity() == Severity::Warn => Severity::Ignore,
+            Self::Basic | Self::Standard => kind.default_severity(),
+            Self::Strict => kind.strict_severity(),
+        }
+    }
+
+    pub fn untyped_def_behavior(self) -> UntypedDefBehavior {
+        match self {
+            Self::Basic => UntypedDefBehavior::CheckAndInferReturnAny,
+            Self::Standard | Self::Strict => UntypedDefBehavior::CheckAndInferReturnType,
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Deserialize, Serialize, Clone, Default)]
 #[serde(rename_all = "kebab-case")]
 pub struct ConfigBase {
+    /// A preset to expand into `errors` and `untyped-def-behavior`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preset: Option<Preset>,
+
     /// Errors to silence (or not) when printing errors.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub errors: Option<ErrorDisplayConfig>,
@@ -112,6 +152,21 @@ impl ConfigBase {
         }
     }
 
+    /// Expand `preset` into the error severities and `untyped-def-behavior` it stands for,
+    /// starting from the severities in `base`. Settings given explicitly win over the preset.
+    pub(crate) fn expand_preset(&mut self, base: &ErrorDisplayConfig) {
+        let Some(preset) = self.preset else {
+            return;
+        };
+        let mut errors = base.with_preset(preset);
+        if let Some(explicit) = &self.errors {
+            errors.extend(explicit);
+        }
+        self.errors = Some(errors);
+        self.untyped_def_behavior
+            .get_or_insert(preset.untyped_def_behavior());
+    }
+
     pub fn get_errors(base: &Self) -> Option<&ErrorDisplayConfig> {
         base.errors.as_ref()
     }
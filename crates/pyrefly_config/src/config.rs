@@ -12,6 +12,7 @@ use std::fmt::Display;
 use std::mem;
 use std::path::Path;
 use std::path::PathBuf;
+use std::str::FromStr;
 use std::sync::Arc;
 use std::sync::LazyLock;
 
@@ -22,6 +23,7 @@ use derivative::Derivative;
 use dupe::Dupe as _;
 use itertools::Itertools;
 use pyrefly_build::BuildSystem;
+use pyrefly_build::ConfiguredEnvironment;
 use pyrefly_build::handle::Handle;
 use pyrefly_build::source_db::SourceDatabase;
 use pyrefly_build::source_db::Target;
@@ -57,7 +59,8 @@ use crate::environment::interpreters::Interpreters;
 use crate::error::ErrorConfig;
 use crate::error::ErrorDisplayConfig;
 use crate::finder::ConfigError;
-use crate::module_wildcard::Match;
+use crate::module_wildcard::ModuleWildcard;
+use crate::module_wildcard::matches_any;
 use crate::pyproject::PyProject;
 
 pub static GENERATED_FILE_CONFIG_OVERRIDE: LazyLock<
@@ -71,6 +74,68 @@ pub struct SubConfig {
     pub settings: ConfigBase,
 }
 
+/// A Python version and platform to check against, as an entry of `python-matrix`.
+/// A missing version or platform defaults to the configured one.
+#[derive(Debug, PartialEq, Eq, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "kebab-case")]
+pub struct PythonTarget {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub python_version: Option<PythonVersion>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub python_platform: Option<PythonPlatform>,
+}
+
+/// A restriction on which modules may import which others, as an entry of `import-rules`.
+/// Importing a module matching `forbidden` from a module matching `modules` (or from any
+/// module, if `modules` is empty) is an error, unless the importer matches `exempt`.
+#[derive(Debug, PartialEq, Eq, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "kebab-case")]
+pub struct ImportRule {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) modules: Vec<ModuleWildcard>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) exempt: Vec<ModuleWildcard>,
+    pub(crate) forbidden: Vec<ModuleWildcard>,
+    /// An explanation of the rule, added to the errors it reports.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+impl ImportRule {
+    /// Does this rule forbid `importer` from importing `imported`?
+    pub fn forbids(&self, importer: ModuleName, imported: ModuleName) -> bool {
+        (self.modules.is_empty() || matches_any(&self.modules, importer))
+            && !matches_any(&self.exempt, importer)
+            && matches_any(&self.forbidden, imported)
+    }
+}
+
+impl FromStr for PythonTarget {
+    type Err = anyhow::Error;
+
+    /// Parse `VERSION`, `VERSION:PLATFORM` or `:PLATFORM`, e.g. `3.9:win32`.
+    fn from_str(s: &str) -> Result<Self> {
+        let (version, platform) = s.split_once(':').unwrap_or((s, ""));
+        if version.is_empty() && platform.is_empty() {
+            return Err(anyhow!(
+                "Expected `VERSION`, `VERSION:PLATFORM` or `:PLATFORM`, got `{s}`"
+            ));
+        }
+        Ok(Self {
+            python_version: if version.is_empty() {
+                None
+            } else {
+                Some(version.parse()?)
+            },
+            python_platform: if platform.is_empty() {
+                None
+            } else {
+                Some(PythonPlatform::new(platform))
+            },
+        })
+    }
+}
+
 impl SubConfig {
     fn rewrite_with_path_to_config(&mut self, config_root: &Path) {
         self.matches = self.matches.clone().from_root(config_root);
@@ -470,6 +535,16 @@ pub struct ConfigFile {
     #[serde(flatten)]
     pub python_environment: PythonEnvironment,
 
+    /// Additional Python versions and platforms to check every module against, in a single run,
+    /// as well as the `python-version` and `python-platform` above. Each module is checked once
+    /// per distinct version and platform.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub python_matrix: Vec<PythonTarget>,
+
+    /// Rules restricting which modules may import which others, e.g. to enforce layering.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub import_rules: Vec<ImportRule>,
+
     /// The `ConfigBase` values for the whole project.
     #[serde(default, flatten)]
     pub root: ConfigBase,
@@ -549,6 +624,8 @@ impl Default for ConfigFile {
             ignore_missing_source: true,
             typeshed_path: None,
             skip_lsp_config_indexing: false,
+            python_matrix: Vec::new(),
+            import_rules: Vec::new(),
         }
     }
 }
@@ -729,6 +806,25 @@ impl ConfigFile {
         SysInfo::new(self.python_version(), self.python_platform().clone())
     }
 
+    /// The distinct `SysInfo`s to check each module against: [`Self::get_sys_info`] first, then
+    /// one per entry of `python-matrix`.
+    pub fn get_sys_infos(&self) -> Vec<SysInfo> {
+        let mut res = SmallSet::new();
+        res.insert(self.get_sys_info());
+        for target in &self.python_matrix {
+            res.insert(SysInfo::new(
+                target
+                    .python_version
+                    .unwrap_or_else(|| self.python_version()),
+                target
+                    .python_platform
+                    .clone()
+                    .unwrap_or_else(|| self.python_platform().clone()),
+            ));
+        }
+        res.into_iter().collect()
+    }
+
     pub fn errors(&self, path: &Path) -> &ErrorDisplayConfig {
         self.get_from_sub_configs(ConfigBase::get_errors, path)
             .unwrap_or_else(||
@@ -746,17 +842,7 @@ impl ConfigFile {
              // we can use unwrap here, because the value in the root config must
              // be set in `ConfigFile::configure()`.
              self.root.replace_imports_with_any.as_deref().unwrap());
-        // Need to filter out any files that would be a not case.
-        let found_match = wildcards.iter().find_map(|w| {
-            if w.matches(module) == Match::Negative {
-                Some(false)
-            } else if w.matches(module) == Match::Positive {
-                Some(true)
-            } else {
-                None
-            }
-        });
-        found_match == Some(true)
+        matches_any(wildcards, module)
     }
 
     pub fn ignore_missing_imports(&self, path: Option<&Path>, module: ModuleName) -> bool {
@@ -768,16 +854,7 @@ impl ConfigFile {
              // we can use unwrap here, because the value in the root config must
              // be set in `ConfigFile::configure()`.
              self.root.ignore_missing_imports.as_deref().unwrap());
-        let found_match = wildcards.iter().find_map(|w| {
-            if w.matches(module) == Match::Negative {
-                Some(false)
-            } else if w.matches(module) == Match::Positive {
-                Some(true)
-            } else {
-                None
-            }
-        });
-        found_match == Some(true)
+        matches_any(wildcards, module)
     }
 
     pub fn untyped_def_behavior(&self, path: &Path) -> UntypedDefBehavior {
@@ -954,6 +1031,8 @@ impl ConfigFile {
     /// which should probably be everything except for `PathBuf` or `Globs` types.
     pub fn configure(&mut self) -> Vec<ConfigError> {
         let mut configure_errors = Vec::new();
+        // Checked before the interpreter fills in any gaps.
+        let python_version_configured = self.python_environment.python_version.is_some();
 
         if self.interpreters.skip_interpreter_query {
             self.python_environment.set_empty_to_default();
@@ -988,6 +1067,16 @@ impl ConfigFile {
             self.project_excludes = self.get_full_project_excludes(project_excludes);
         }
 
+        if self.root.preset.is_none() {
+            self.root.preset = Some(Default::default());
+        }
+        self.root.expand_preset(&ErrorDisplayConfig::default());
+        let root_errors = self.root.errors.clone().unwrap_or_default();
+        // A sub-config preset applies on top of the root severities.
+        for sub_config in &mut self.sub_configs {
+            sub_config.settings.expand_preset(&root_errors);
+        }
+
         if self.root.errors.is_none() {
             self.root.errors = Some(Default::default());
         }
@@ -1031,6 +1120,10 @@ impl ConfigFile {
         };
         self.root.enabled_ignores = Some(enabled_ignores);
 
+        let configured_environment = ConfiguredEnvironment {
+            sys_info: self.get_sys_info(),
+            python_version_configured,
+        };
         let mut configure_source_db = |build_system: &mut BuildSystem| {
             let root = match &self.source {
                 ConfigSource::File(path) => {
@@ -1045,7 +1138,7 @@ impl ConfigFile {
                 }
             };
 
-            match build_system.get_source_db(root.to_path_buf())? {
+            match build_system.get_source_db(root.to_path_buf(), configured_environment.clone())? {
                 Ok(source_db) => {
                     self.source_db = Some(source_db);
                     self.fallback_search_path = FallbackSearchPath::DirectoryRelative(
@@ -1160,6 +1253,61 @@ impl ConfigFile {
             .for_each(|c| c.rewrite_with_path_to_config(config_root));
     }
 
+    /// A copy of this config for a copy of the project at `to`, e.g. a git worktree of another
+    /// revision of the project at `from`, with every path under `from` moved to `to`. The Python
+    /// environment isn't part of the project, so is left alone, and the source database
+    /// describes the project at `from`, so is dropped.
+    pub fn rebase(&self, from: &Path, to: &Path) -> ConfigFile {
+        let rebase = |path: &Path| match path.strip_prefix(from) {
+            Ok(rest) => to.join(rest),
+            Err(_) => path.to_path_buf(),
+        };
+        let rebase_glob = |glob: &Glob| match glob.as_path().strip_prefix(from) {
+            Ok(rest) => Glob::new_with_root(to, rest.to_string_lossy().into_owned())
+                .unwrap_or_else(|_| glob.clone()),
+            Err(_) => glob.clone(),
+        };
+        let rebase_globs = |globs: &Globs| {
+            let mut res = Globs::empty();
+            res.append(&globs.globs().iter().map(rebase_glob).collect::<Vec<_>>());
+            res
+        };
+        let mut res = self.clone();
+        res.source = match &self.source {
+            ConfigSource::File(path) => ConfigSource::File(rebase(path)),
+            ConfigSource::Marker(path) => ConfigSource::Marker(rebase(path)),
+            ConfigSource::Synthetic => ConfigSource::Synthetic,
+        };
+        res.project_includes = rebase_globs(&self.project_includes);
+        res.project_excludes = rebase_globs(&self.project_excludes);
+        res.search_path_from_args = self
+            .search_path_from_args
+            .iter()
+            .map(|x| rebase(x))
+            .collect();
+        res.search_path_from_file = self
+            .search_path_from_file
+            .iter()
+            .map(|x| rebase(x))
+            .collect();
+        res.import_root = self.import_root.as_deref().map(rebase);
+        res.fallback_search_path = match &self.fallback_search_path {
+            FallbackSearchPath::Explicit(paths) => {
+                FallbackSearchPath::Explicit(Arc::new(paths.iter().map(|x| rebase(x)).collect()))
+            }
+            FallbackSearchPath::DirectoryRelative(cache) => FallbackSearchPath::DirectoryRelative(
+                DirectoryRelativeFallbackSearchPathCache::new(cache.up_to.as_deref().map(rebase)),
+            ),
+            FallbackSearchPath::Empty => FallbackSearchPath::Empty,
+        };
+        res.typeshed_path = self.typeshed_path.as_deref().map(rebase);
+        for sub_config in &mut res.sub_configs {
+            sub_config.matches = rebase_glob(&sub_config.matches);
+        }
+        res.source_db = None;
+        res
+    }
+
     pub fn from_file(config_path: &Path) -> (ConfigFile, Vec<ConfigError>) {
         fn read_path(config_path: &Path) -> anyhow::Result<Option<ConfigFile>> {
             let config_str = fs_anyhow::read_to_string(config_path)?;
@@ -1384,6 +1532,7 @@ mod tests {
                     disable_type_errors_in_ide: None,
                     ignore_errors_in_generated_code: Some(true),
                     infer_with_first_use: None,
+                    preset: None,
                     replace_imports_with_any: Some(vec![ModuleWildcard::new("fibonacci").unwrap()]),
                     ignore_missing_imports: Some(vec![ModuleWildcard::new("sprout").unwrap()]),
                     untyped_def_behavior: Some(UntypedDefBehavior::CheckAndInferReturnType),
@@ -1402,6 +1551,7 @@ mod tests {
                         disable_type_errors_in_ide: None,
                         ignore_errors_in_generated_code: Some(false),
                         infer_with_first_use: Some(false),
+                        preset: None,
                         replace_imports_with_any: Some(Vec::new()),
                         ignore_missing_imports: Some(Vec::new()),
                         untyped_def_behavior: Some(UntypedDefBehavior::CheckAndInferReturnAny),
@@ -1412,6 +1562,8 @@ mod tests {
                 ignore_missing_source: true,
                 typeshed_path: None,
                 skip_lsp_config_indexing: false,
+                python_matrix: Vec::new(),
+                import_rules: Vec::new(),
             }
         );
     }
@@ -1646,6 +1798,8 @@ mod tests {
             ignore_missing_source: false,
             typeshed_path: Some(PathBuf::from(typeshed)),
             skip_lsp_config_indexing: false,
+            python_matrix: Vec::new(),
+            import_rules: Vec::new(),
         };
 
         let current_dir = std::env::current_dir().unwrap();
@@ -1705,10 +1859,43 @@ mod tests {
             ignore_missing_source: false,
             typeshed_path: Some(expected_typeshed),
             skip_lsp_config_indexing: false,
+            python_matrix: Vec::new(),
+            import_rules: Vec::new(),
         };
         assert_eq!(config, expected_config);
     }
 
+    #[test]
+    fn test_rebase() {
+        let current_dir = std::env::current_dir().unwrap();
+        let from = current_dir.join("project");
+        let to = current_dir.join("worktree");
+        let outside = current_dir.join("elsewhere");
+        let config = ConfigFile {
+            source: ConfigSource::File(from.join("pyrefly.toml")),
+            project_includes: Globs::new_with_root(&from, vec!["src/**".to_owned()]).unwrap(),
+            search_path_from_file: vec![from.join("src"), outside.clone()],
+            import_root: Some(from.clone()),
+            sub_configs: vec![SubConfig {
+                matches: Glob::new_with_root(&from, "tests/**".to_owned()).unwrap(),
+                settings: Default::default(),
+            }],
+            ..Default::default()
+        };
+        let rebased = config.rebase(&from, &to);
+        assert_eq!(rebased.source, ConfigSource::File(to.join("pyrefly.toml")));
+        assert_eq!(
+            rebased.project_includes,
+            Globs::new_with_root(&to, vec!["src/**".to_owned()]).unwrap()
+        );
+        assert_eq!(rebased.search_path_from_file, vec![to.join("src"), outside]);
+        assert_eq!(rebased.import_root, Some(to.clone()));
+        assert_eq!(
+            rebased.sub_configs[0].matches,
+            Glob::new_with_root(&to, "tests/**".to_owned()).unwrap()
+        );
+    }
+
     #[test]
     fn test_deserializing_unknown_error_errors() {
         let config_str = "
@@ -1721,6 +1908,132 @@ mod tests {
         assert!(err.to_string().contains("unknown variant"));
     }
 
+    #[test]
+    fn test_python_matrix() {
+        let config_str = r#"
+             python-version = "3.12"
+             python-platform = "linux"
+             python-matrix = [
+                 { python-version = "3.9" },
+                 { python-version = "3.9", python-platform = "win32" },
+                 { python-platform = "linux" },
+                 { python-version = "3.12.0" },
+             ]
+                 "#;
+        let config = ConfigFile::parse_config(config_str).unwrap();
+        assert_eq!(
+            config.python_matrix[1],
+            PythonTarget {
+                python_version: Some(PythonVersion::new(3, 9, 0)),
+                python_platform: Some(PythonPlatform::windows()),
+            }
+        );
+        // The configured version and platform come first, and the last two entries are the same
+        // as them.
+        assert_eq!(
+            config.get_sys_infos(),
+            vec![
+                SysInfo::new(PythonVersion::new(3, 12, 0), PythonPlatform::linux()),
+                SysInfo::new(PythonVersion::new(3, 9, 0), PythonPlatform::linux()),
+                SysInfo::new(PythonVersion::new(3, 9, 0), PythonPlatform::windows()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_python_target_from_str() {
+        assert_eq!(
+            PythonTarget::from_str("3.10:darwin").unwrap(),
+            PythonTarget {
+                python_version: Some(PythonVersion::new(3, 10, 0)),
+                python_platform: Some(PythonPlatform::mac()),
+            }
+        );
+        assert_eq!(
+            PythonTarget::from_str("3.11").unwrap(),
+            PythonTarget {
+                python_version: Some(PythonVersion::new(3, 11, 0)),
+                python_platform: None,
+            }
+        );
+        assert_eq!(
+            PythonTarget::from_str(":win32").unwrap(),
+            PythonTarget {
+                python_version: None,
+                python_platform: Some(PythonPlatform::windows()),
+            }
+        );
+        assert!(PythonTarget::from_str(":").is_err());
+    }
+
+    #[test]
+    fn test_import_rules() {
+        let config_str = r#"
+             [[import-rules]]
+             modules = ["app.domain.*"]
+             forbidden = ["app.web.*"]
+
+             [[import-rules]]
+             exempt = ["infra.*"]
+             forbidden = ["boto3.*"]
+             message = "Use the wrappers in `infra` instead"
+                 "#;
+        let config = ConfigFile::parse_config(config_str).unwrap();
+        let [layering, only_infra] = &config.import_rules[..] else {
+            panic!("Expected two import rules, got {:?}", config.import_rules);
+        };
+        let m = ModuleName::from_str;
+        assert!(layering.forbids(m("app.domain.models"), m("app.web")));
+        assert!(!layering.forbids(m("app.domain.models"), m("app.domain.views")));
+        assert!(!layering.forbids(m("app.web"), m("app.web.views")));
+        assert!(only_infra.forbids(m("app.domain"), m("boto3.session")));
+        assert!(!only_infra.forbids(m("infra.aws"), m("boto3")));
+        assert_eq!(
+            only_infra.message.as_deref(),
+            Some("Use the wrappers in `infra` instead")
+        );
+        assert!(ConfigFile::parse_config("[[import-rules]]\nmodules = [\"a\"]\n").is_err());
+    }
+
+    #[test]
+    fn test_presets() {
+        let config_str = r#"
+             preset = "basic"
+             [errors]
+             deprecated = "error"
+
+             [[sub-config]]
+             matches = "strict/**"
+             preset = "strict"
+             [sub-config.errors]
+             unused-import = "warn"
+                 "#;
+        let mut config = ConfigFile::parse_config(config_str).unwrap();
+        config.interpreters.skip_interpreter_query = true;
+        config.configure();
+
+        let root = config.errors(Path::new("main.py"));
+        assert_eq!(root.severity(ErrorKind::Deprecated), Severity::Error);
+        assert_eq!(root.severity(ErrorKind::RedundantCast), Severity::Ignore);
+        assert_eq!(root.severity(ErrorKind::ImplicitAny), Severity::Ignore);
+        assert_eq!(
+            config.untyped_def_behavior(Path::new("main.py")),
+            UntypedDefBehavior::CheckAndInferReturnAny
+        );
+
+        // The strict sub-config applies on top of the root severities.
+        let strict = config.errors(Path::new("strict/main.py"));
+        assert_eq!(strict.severity(ErrorKind::Deprecated), Severity::Error);
+        assert_eq!(strict.severity(ErrorKind::RedundantCast), Severity::Error);
+        assert_eq!(strict.severity(ErrorKind::ImplicitAny), Severity::Error);
+        assert_eq!(strict.severity(ErrorKind::UnusedImport), Severity::Warn);
+        assert_eq!(strict.severity(ErrorKind::BadReturn), Severity::Error);
+        assert_eq!(
+            config.untyped_def_behavior(Path::new("strict/main.py")),
+            UntypedDefBehavior::CheckAndInferReturnType
+        );
+    }
+
     #[test]
     fn test_deserializing_sub_config_missing_matches() {
         let config_str = r#"
@@ -1778,6 +2091,7 @@ mod tests {
                 disable_type_errors_in_ide: Some(true),
                 ignore_errors_in_generated_code: Some(false),
                 infer_with_first_use: Some(true),
+                preset: None,
                 extras: Default::default(),
                 permissive_ignores: Some(false),
                 enabled_ignores: None,
@@ -2085,6 +2399,7 @@ mod tests {
                 disable_type_errors_in_ide: Some(true),
                 ignore_errors_in_generated_code: Some(false),
                 infer_with_first_use: Some(true),
+                preset: None,
                 extras: Default::default(),
                 permissive_ignores: Some(false),
                 enabled_ignores: None,
@@ -2117,6 +2432,7 @@ mod tests {
                 disable_type_errors_in_ide: Some(true),
                 ignore_errors_in_generated_code: Some(false),
                 infer_with_first_use: Some(true),
+                preset: None,
                 extras: Default::default(),
                 permissive_ignores: Some(false),
                 enabled_ignores: None,
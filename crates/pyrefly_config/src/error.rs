@@ -6,7 +6,9 @@
  */
 
 use std::collections::HashMap;
+use std::str::FromStr;
 
+use pyrefly_python::ignore::SeverityDirective;
 use pyrefly_python::ignore::Tool;
 use serde::Deserialize;
 use serde::Deserializer;
@@ -15,6 +17,7 @@ use serde::de::MapAccess;
 use serde::de::Visitor;
 use starlark_map::small_set::SmallSet;
 
+use crate::base::Preset;
 use crate::error_kind::ErrorKind;
 use crate::error_kind::Severity;
 
@@ -41,6 +44,66 @@ impl ErrorDisplayConfig {
     pub fn set_error_severity(&mut self, kind: ErrorKind, severity: Severity) {
         self.0.insert(kind, severity);
     }
+
+    /// Set the severities in `other` on top of this config.
+    pub fn extend(&mut self, other: &Self) {
+        self.0.extend(other.0.iter().map(|(k, v)| (*k, *v)));
+    }
+
+    /// Apply a preset on top of this config, setting every error kind whose severity in the
+    /// preset differs from its default.
+    pub fn with_preset(&self, preset: Preset) -> Self {
+        let mut res = self.clone();
+        for kind in enum_iterator::all::<ErrorKind>() {
+            let severity = preset.severity(kind);
+            if severity != kind.default_severity() {
+                res.set_error_severity(kind, severity);
+            }
+        }
+        res
+    }
+
+    /// Apply the file-level severity directives of a module on top of this config, in order.
+    /// `# pyrefly: strict` raises the error kinds that strict mode enables to their strict
+    /// severity, without lowering any, and `# pyrefly: <error-kind>=<severity>` sets the
+    /// severity of one kind. Directives that don't name a valid error kind and severity are
+    /// skipped here, and reported as errors when the module is checked.
+    pub fn with_directives(&self, directives: &[SeverityDirective]) -> Self {
+        let mut res = self.clone();
+        for directive in directives {
+            match directive {
+                SeverityDirective::Strict => {
+                    for kind in enum_iterator::all::<ErrorKind>() {
+                        let strict = kind.strict_severity();
+                        if strict > kind.default_severity() && strict > res.severity(kind) {
+                            res.set_error_severity(kind, strict);
+                        }
+                    }
+                }
+                SeverityDirective::Kind { kind, severity, .. } => {
+                    if let Ok((kind, severity)) = parse_severity_directive(kind, severity) {
+                        res.set_error_severity(kind, severity);
+                    }
+                }
+            }
+        }
+        res
+    }
+}
+
+/// Resolve the names in a `# pyrefly: <error-kind>=<severity>` directive.
+pub fn parse_severity_directive(
+    kind: &str,
+    severity: &str,
+) -> Result<(ErrorKind, Severity), String> {
+    let kind = ErrorKind::from_str(kind).map_err(|()| format!("Unknown error kind `{kind}`"))?;
+    let severity = Severity::from_str(severity).map_err(|e| {
+        format!(
+            "Invalid severity `{severity}` for `{kind}`: {e}",
+            kind = kind.to_name()
+        )
+    })?;
+    Ok((kind, severity))
 }
 
 impl<'de> Deserialize<'de> for ErrorDisplayConfig {
@@ -75,8 +138,7 @@ impl<'de> Deserialize<'de> for ErrorDisplayConfig {
                             }
                         }
                         serde_json::Value::String(s) => {
-                            serde_json::from_str::<Severity>(&format!("\"{s}\""))
-                                .map_err(serde::de::Error::custom)?
+                            Severity::from_str(&s).map_err(serde::de::Error::custom)?
                         }
                         other => {
                             return Err(serde::de::Error::custom(format!(
@@ -15,6 +15,7 @@ use enum_iterator::Sequence;
 use parse_display::Display;
 use serde::Deserialize;
 use serde::Serialize;
+use serde::de::IntoDeserializer;
 use starlark_map::small_map::SmallMap;
 use yansi::Paint;
 use yansi::Painted;
@@ -30,10 +31,12 @@ use yansi::Painted;
     PartialEq,
     Eq,
     Hash,
+    Display,
     Deserialize,
     Serialize
 )]
 #[serde(rename_all = "lowercase")]
+#[display(style = "lowercase")]
 pub enum Severity {
     Ignore,
     Info,
@@ -66,6 +69,15 @@ impl Severity {
     }
 }
 
+/// Parse a severity the same way a config file does.
+impl std::str::FromStr for Severity {
+    type Err = serde::de::value::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::deserialize(s.into_deserializer())
+    }
+}
+
 /// ErrorKind categorizes an error by the part of the spec the error is related to.
 /// They are used in suppressions to identify which error should be suppressed.
 //
@@ -138,6 +150,8 @@ pub enum ErrorKind {
     BadUnpacking,
     /// Calling a function marked with `@deprecated`
     Deprecated,
+    /// Importing a module that one of the config's `import-rules` forbids.
+    ForbiddenImport,
     /// Raised when a class implicitly becomes abstract by defining abstract members without
     /// inheriting from `abc.ABC` or using `abc.ABCMeta`.
     ImplicitAbstractClass,
@@ -162,6 +176,8 @@ pub enum ErrorKind {
     /// Error caused by incorrect usage of a decorator.
     /// e.g. using @final on a top-level function
     InvalidDecorator,
+    /// A `# pyrefly: <error-kind>=<severity>` directive names an unknown error kind or severity.
+    InvalidDirective,
     /// An error caused by incorrect inheritance in a class or type definition.
     /// e.g. a metaclass that is not a subclass of `type`.
     InvalidInheritance,
@@ -209,6 +225,9 @@ pub enum ErrorKind {
     NoAccess,
     /// Attempting to call an overloaded function, but none of the signatures match.
     NoMatchingOverload,
+    /// A `match` statement over an enum, literal, bool, final class or union of those does not
+    /// cover every case.
+    NonExhaustiveMatch,
     /// Attempting to use something that isn't a type where a type is expected.
     /// This is a very general error and should be used sparingly.
     NotAType,
@@ -222,6 +241,8 @@ pub enum ErrorKind {
     OpenUnpacking,
     /// An error related to parsing or syntax.
     ParseError,
+    /// Attempting to use a name that is only initialized on some paths to this point.
+    PossiblyUnbound,
     /// A protocol attribute was first defined inside a method instead of the class body.
     ProtocolImplicitlyDefinedAttribute,
     /// The attribute exists but cannot be modified.
@@ -240,17 +261,22 @@ pub enum ErrorKind {
     UnannotatedParameter,
     /// A function is missing a return type annotation.
     UnannotatedReturn,
-    /// Attempting to use a name that may be unbound or uninitialized
+    /// Attempting to use a name that is unbound or uninitialized.
     UnboundName,
     /// An error caused by a keyword argument used in the wrong place.
     UnexpectedKeyword,
     /// An error caused by passing a positional argument for a keyword-only parameter.
     UnexpectedPositionalArgument,
+    /// A pytest test or fixture requests a fixture that cannot be found.
+    UnknownFixture,
     /// Attempting to use a name that is not defined.
     UnknownName,
     /// Identity comparison (`is` or `is not`) between types that are provably disjoint
     /// or between literals whose comparison result is statically known.
     UnnecessaryComparison,
+    /// Code that can never run because every path to it has returned, raised or called a
+    /// function that never returns.
+    UnreachableCode,
     /// Attempting to use a feature that is not yet supported.
     Unsupported,
     /// Attempting to `del` something that cannot be deleted
@@ -261,6 +287,14 @@ pub enum ErrorKind {
     UntypedImport,
     /// Result of async function call is never used or awaited
     UnusedCoroutine,
+    /// A module-level import that is never used and is not re-exported.
+    UnusedImport,
+    /// A function parameter that is never used.
+    UnusedParameter,
+    /// A private module-level function that is never used.
+    UnusedPrivateFunction,
+    /// A local variable that is assigned but never read.
+    UnusedVariable,
 }
 
 impl std::str::FromStr for ErrorKind {
@@ -311,11 +345,48 @@ impl ErrorKind {
             ErrorKind::UnannotatedAttribute => Severity::Ignore,
             ErrorKind::MissingSource => Severity::Ignore,
             ErrorKind::MissingOverrideDecorator => Severity::Ignore,
+            ErrorKind::NonExhaustiveMatch => Severity::Ignore,
             ErrorKind::OpenUnpacking => Severity::Ignore,
+            ErrorKind::UnknownFixture => Severity::Ignore,
+            ErrorKind::UnreachableCode => Severity::Ignore,
+            ErrorKind::UnusedImport => Severity::Ignore,
+            ErrorKind::UnusedParameter => Severity::Ignore,
+            ErrorKind::UnusedPrivateFunction => Severity::Ignore,
+            ErrorKind::UnusedVariable => Severity::Ignore,
             _ => Severity::Error,
         }
     }
 
+    /// The severity of this error kind in files marked `# pyrefly: strict`. Strict mode
+    /// turns on the checks for missing annotations, implicit `Any`, untyped imports and unused
+    /// code.
+    pub fn strict_severity(self) -> Severity {
+        match self {
+            ErrorKind::ImplicitlyDefinedAttribute
+            | ErrorKind::ImplicitAbstractClass
+            | ErrorKind::ImplicitAny
+            | ErrorKind::UnannotatedParameter
+            | ErrorKind::UnannotatedReturn
+            | ErrorKind::UnannotatedAttribute
+            | ErrorKind::MissingOverrideDecorator
+            | ErrorKind::RedundantCast
+            | ErrorKind::NonExhaustiveMatch
+            | ErrorKind::UnnecessaryComparison
+            | ErrorKind::UntypedImport
+            | ErrorKind::UnusedImport
+            | ErrorKind::UnusedPrivateFunction
+            | ErrorKind::UnusedVariable => Severity::Error,
+            _ => self.default_severity(),
+        }
+    }
+
+    /// A one-line description of this error kind, taken from its documentation.
+    pub fn description(self) -> String {
+        self.to_possible_value()
+            .and_then(|x| x.get_help().map(|help| help.to_string()))
+            .unwrap_or_default()
+    }
+
     /// Returns the public documentation URL for this error kind.
     /// Example: https://pyrefly.org/en/docs/error-kinds/#bad-context-manager
     pub fn docs_url(self) -> String {
@@ -341,6 +412,31 @@ mod tests {
         assert_eq!(ErrorKind::ParseError.to_name(), "parse-error");
     }
 
+    #[test]
+    fn test_severity_name() {
+        for severity in [
+            Severity::Ignore,
+            Severity::Info,
+            Severity::Warn,
+            Severity::Error,
+        ] {
+            assert_eq!(severity.to_string().parse::<Severity>().unwrap(), severity);
+        }
+        assert_eq!(Severity::Warn.to_string(), "warn");
+        assert!("loud".parse::<Severity>().is_err());
+    }
+
+    #[test]
+    fn test_error_kind_description() {
+        assert_eq!(
+            ErrorKind::BadInstantiation.description(),
+            "Can't instantiate an abstract class or protocol"
+        );
+        for kind in all::<ErrorKind>() {
+            assert!(!kind.description().is_empty(), "{kind} has no description");
+        }
+    }
+
     #[test]
     fn test_doc() {
         // Verifies that the secondary headers in error-kinds.mdx contain the same variants as the ErrorKind enum and are sorted lexicographically.
@@ -218,6 +218,7 @@ mod tests {
         );
         assert_eq!(errors.severity(ErrorKind::ImplicitAny), Severity::Warn);
         assert_eq!(errors.severity(ErrorKind::UnboundName), Severity::Error);
+        assert_eq!(errors.severity(ErrorKind::PossiblyUnbound), Severity::Warn);
     }
 
     #[test]
@@ -160,7 +160,8 @@ fn code_to_kind(errors: HashMap<String, Severity>) -> Option<ErrorDisplayConfig>
             "typeddict-unknown-key" => add(severity, ErrorKind::BadTypedDictKey),
             "typeddict-readonly-mutated" => add(severity, ErrorKind::ReadOnly),
             "name-defined" => add(severity, ErrorKind::UnknownName),
-            "used-before-def" | "possibly-undefined" => add(severity, ErrorKind::UnboundName),
+            "used-before-def" => add(severity, ErrorKind::UnboundName),
+            "possibly-undefined" => add(severity, ErrorKind::PossiblyUnbound),
             "valid-type" => add(severity, ErrorKind::InvalidAnnotation),
             "type-arg" | "no-untyped-def" => add(severity, ErrorKind::ImplicitAny),
             "metaclass" => add(severity, ErrorKind::InvalidInheritance),
@@ -180,6 +181,7 @@ fn code_to_kind(errors: HashMap<String, Severity>) -> Option<ErrorDisplayConfig>
                 add(severity, ErrorKind::RedundantCondition)
             }
             "deprecated" => add(severity, ErrorKind::Deprecated),
+            "unreachable" => add(severity, ErrorKind::UnreachableCode),
             _ => {}
         }
     }
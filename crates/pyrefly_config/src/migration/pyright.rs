@@ -250,6 +250,8 @@ pub struct RuleOverrides {
     #[serde_as(as = "Option<FromInto<DiagnosticLevelOrBool>>")]
     #[expect(unused)]
     pub report_invalid_string_escape_sequence: Option<Severity>,
+    #[serde_as(as = "Option<FromInto<DiagnosticLevelOrBool>>")]
+    pub report_match_not_exhaustive: Option<Severity>,
 
     // Unknown/implicit any rules
     #[serde_as(as = "Option<FromInto<DiagnosticLevelOrBool>>")]
@@ -298,6 +300,14 @@ pub struct RuleOverrides {
     // Coroutine rules
     #[serde_as(as = "Option<FromInto<DiagnosticLevelOrBool>>")]
     pub report_unused_coroutine: Option<Severity>,
+
+    // Unused symbol rules
+    #[serde_as(as = "Option<FromInto<DiagnosticLevelOrBool>>")]
+    pub report_unused_import: Option<Severity>,
+    #[serde_as(as = "Option<FromInto<DiagnosticLevelOrBool>>")]
+    pub report_unused_variable: Option<Severity>,
+    #[serde_as(as = "Option<FromInto<DiagnosticLevelOrBool>>")]
+    pub report_unused_function: Option<Severity>,
 }
 
 impl RuleOverrides {
@@ -355,7 +365,7 @@ impl RuleOverrides {
         );
         add(
             self.report_possibly_unbound_variable,
-            ErrorKind::UnboundName,
+            ErrorKind::PossiblyUnbound,
         );
         add(
             self.report_uninitialized_instance_variable,
@@ -374,9 +384,19 @@ impl RuleOverrides {
         add(self.report_unknown_member_type, ErrorKind::ImplicitAny);
         add(self.report_invalid_type_var_use, ErrorKind::InvalidTypeVar);
         add(self.report_unnecessary_cast, ErrorKind::RedundantCast);
+        add(
+            self.report_match_not_exhaustive,
+            ErrorKind::NonExhaustiveMatch,
+        );
         add(self.report_undefined_variable, ErrorKind::UnknownName);
         add(self.report_unbound_variable, ErrorKind::UnboundName);
         add(self.report_unused_coroutine, ErrorKind::UnusedCoroutine);
+        add(self.report_unused_import, ErrorKind::UnusedImport);
+        add(self.report_unused_variable, ErrorKind::UnusedVariable);
+        add(
+            self.report_unused_function,
+            ErrorKind::UnusedPrivateFunction,
+        );
 
         if map.is_empty() {
             None
@@ -461,6 +481,7 @@ mod tests {
                 },
                 root: ConfigBase {
                     infer_with_first_use: Some(false),
+                    preset: None,
                     ..Default::default()
                 },
                 ..Default::default()
@@ -505,6 +526,7 @@ mod tests {
                 },
                 root: ConfigBase {
                     infer_with_first_use: Some(false),
+                    preset: None,
                     ..Default::default()
                 },
                 ..Default::default()
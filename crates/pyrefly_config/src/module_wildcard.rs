@@ -114,6 +114,17 @@ impl Serialize for ModuleWildcard {
     }
 }
 
+/// Does `module` match `wildcards`? The first wildcard that matches decides, so a
+/// negated (`!`) pattern can carve an exception out of a later, broader one.
+pub(crate) fn matches_any(wildcards: &[ModuleWildcard], module: ModuleName) -> bool {
+    let found_match = wildcards.iter().find_map(|w| match w.matches(module) {
+        Match::Negative => Some(false),
+        Match::Positive => Some(true),
+        Match::NoMatch => None,
+    });
+    found_match == Some(true)
+}
+
 /// Rewrites a module glob pattern into a regex. Uses the same logic
 /// as mypy to reduce issues when converting module path globs.
 fn rewrite_pattern_as_regex(original: &str) -> anyhow::Result<Regex> {
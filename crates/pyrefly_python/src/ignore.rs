@@ -23,6 +23,9 @@
 //! as equivalents to `pyre: ignore`, and `# pyre-ignore-all-errors` as
 //! an equivalent to `type: ignore` on its own line.
 //!
+//! Files can also change the severity of their errors with `# pyrefly: strict` or
+//! `# pyrefly: bad-return=warn, unused-import=error` at the beginning of the file.
+//!
 //! We are permissive with whitespace, allowing `#type:ignore[code]` and
 //! `#  type:  ignore  [  code  ]`, but do not allow a space before the colon.
 
@@ -169,6 +172,19 @@ pub struct Suppression {
     kind: Vec<String>,
 }
 
+/// A file-level directive changing the severity of the errors in a file.
+#[derive(PartialEq, Debug, Clone, Hash, Eq)]
+pub enum SeverityDirective {
+    /// `# pyrefly: strict`, which enables the strict set of error kinds.
+    Strict,
+    /// `# pyrefly: <error-kind>=<severity>`, on the given line. The names are not validated.
+    Kind {
+        kind: String,
+        severity: String,
+        line: LineNumber,
+    },
+}
+
 /// Record the position of lines affected by `# type: ignore[valid-type]` suppressions.
 /// For now we don't record the content of the ignore, but we could.
 #[derive(Debug, Clone, Default)]
@@ -178,6 +194,8 @@ pub struct Ignore {
     ignores: SmallMap<LineNumber, Vec<Suppression>>,
     /// All the tools with an ignore-all directive, with the line number that the directive is on.
     ignore_all: SmallMap<Tool, LineNumber>,
+    /// The severity directives at the beginning of the file, in order.
+    severity_directives: Vec<SeverityDirective>,
 }
 
 impl Ignore {
@@ -185,7 +203,53 @@ impl Ignore {
         Self {
             ignores: Self::parse_ignores(code),
             ignore_all: Self::parse_ignore_all(code),
+            severity_directives: Self::parse_severity_directives(code),
+        }
+    }
+
+    /// The `# pyrefly: strict` and `# pyrefly: <error-kind>=<severity>` directives in the
+    /// comments at the beginning of the file.
+    fn parse_severity_directives(code: &str) -> Vec<SeverityDirective> {
+        let mut res = Vec::new();
+        for (line, x) in code
+            .lines()
+            .map(|x| x.trim())
+            .take_while(|x| x.is_empty() || x.starts_with('#'))
+            .enumerate()
+        {
+            let line = LineNumber::from_zero_indexed(line as u32);
+            let mut lex = Lexer(x);
+            if !lex.starts_with("#") {
+                continue;
+            }
+            lex.trim_start();
+            if lex.starts_with_tool() != Some(Tool::Pyrefly) {
+                continue;
+            }
+            lex.trim_start();
+            let rest = lex.rest().trim_end();
+            if rest == "strict" {
+                res.push(SeverityDirective::Strict);
+                continue;
+            }
+            let kinds = rest
+                .split(',')
+                .map(|x| {
+                    let (kind, severity) = x.split_once('=')?;
+                    let (kind, severity) = (kind.trim(), severity.trim());
+                    let is_name = |x: &str| {
+                        !x.is_empty() && x.chars().all(|c| c.is_ascii_alphabetic() || c == '-')
+                    };
+                    (is_name(kind) && is_name(severity)).then(|| SeverityDirective::Kind {
+                        kind: kind.to_owned(),
+                        severity: severity.to_owned(),
+                        line,
+                    })
+                })
+                .collect::<Option<Vec<_>>>();
+            res.extend(kinds.into_iter().flatten());
         }
+        res
     }
 
     /// All the errors that were ignored, and the line number that ignore happened.
@@ -376,6 +440,11 @@ impl Ignore {
     pub fn get(&self, line: &LineNumber) -> Option<&Vec<Suppression>> {
         self.ignores.get(line)
     }
+
+    /// The file-level directives changing the severity of errors, in the order they appear.
+    pub fn severity_directives(&self) -> &[SeverityDirective] {
+        &self.severity_directives
+    }
 }
 
 #[cfg(test)]
@@ -533,4 +602,44 @@ mod tests {
         f("# pyrefly: ignore-errors # because I want to\nx = 5", &[]);
         f("# pyrefly: ignore-errors \nx = 5", &[(Tool::Pyrefly, 1)]);
     }
+
+    #[test]
+    fn test_parse_severity_directives() {
+        fn f(x: &str, expect: &[(&str, &str)]) {
+            assert_eq!(
+                Ignore::parse_severity_directives(x).map(|x| match x {
+                    SeverityDirective::Strict => ("strict", ""),
+                    SeverityDirective::Kind { kind, severity, .. } =>
+                        (kind.as_str(), severity.as_str()),
+                }),
+                expect,
+                "{x:?}"
+            );
+        }
+
+        f("# pyrefly: strict\nx = 5", &[("strict", "")]);
+        f(
+            "# comment\n#pyrefly:strict\n# pyrefly: bad-return=warn, unused-import = error\n",
+            &[
+                ("strict", ""),
+                ("bad-return", "warn"),
+                ("unused-import", "error"),
+            ],
+        );
+        f("x = 5\n# pyrefly: strict", &[]);
+        f("# pyrefly: strict mode\nx = 5", &[]);
+        f("# pyrefly: strictly=warn\nx = 5", &[("strictly", "warn")]);
+        f("# pyrefly: bad-return=warn, oops\nx = 5", &[]);
+        f("# type: bad-return=warn\nx = 5", &[]);
+        f("# pyrefly: ignore-errors\nx = 5", &[]);
+
+        assert_eq!(
+            Ignore::parse_severity_directives("# comment\n# pyrefly: bad-return=warn\n"),
+            vec![SeverityDirective::Kind {
+                kind: "bad-return".to_owned(),
+                severity: "warn".to_owned(),
+                line: LineNumber::from_zero_indexed(1),
+            }]
+        );
+    }
 }
@@ -197,6 +197,18 @@ impl ModuleName {
         Self::from_str("pydantic.types")
     }
 
+    pub fn sqlalchemy_orm_base() -> Self {
+        Self::from_str("sqlalchemy.orm.base")
+    }
+
+    pub fn sqlalchemy_orm_constructors() -> Self {
+        Self::from_str("sqlalchemy.orm._orm_constructors")
+    }
+
+    pub fn sqlalchemy_sql_type_api() -> Self {
+        Self::from_str("sqlalchemy.sql.type_api")
+    }
+
     /// The "unknown" module name, which corresponds to `__unknown__`.
     /// Used for files directly opened or passed on the command line which aren't on the search path.
     pub fn unknown() -> Self {
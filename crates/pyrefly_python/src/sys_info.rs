@@ -227,6 +227,12 @@ impl SysInfo {
     }
 }
 
+impl Display for SysInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.version(), self.platform())
+    }
+}
+
 impl<'de> Deserialize<'de> for SysInfo {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
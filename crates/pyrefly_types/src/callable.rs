@@ -325,6 +325,15 @@ impl FuncMetadata {
             flags: FuncFlags::default(),
         }
     }
+
+    /// The class of the object this function stands in for, if it is a callable object rather
+    /// than a plain function.
+    pub fn instance_class(&self) -> Option<&ClassType> {
+        match &self.kind {
+            FunctionKind::CallbackProtocol(cls) => Some(cls),
+            _ => self.flags.functools_wrapper.as_ref(),
+        }
+    }
 }
 
 /// Metadata extracted from a `@deprecated` decorator.
@@ -395,6 +404,13 @@ pub struct FuncFlags {
     /// `dataclass_transform` call. See
     /// https://typing.python.org/en/latest/spec/dataclasses.html#specification.
     pub dataclass_transform_metadata: Option<DataclassTransformMetadata>,
+    /// A function decorated with `functools.cache`, `functools.lru_cache` or
+    /// `functools.singledispatch`. The function keeps its signature, but its attributes come from
+    /// the wrapper class returned by the decorator.
+    pub functools_wrapper: Option<ClassType>,
+    /// A function decorated with `@pytest.fixture`. The decorator is not applied, so the
+    /// function keeps its signature and the fixture value can be read from its return type.
+    pub is_pytest_fixture: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -491,6 +507,8 @@ pub enum FunctionKind {
     Dataclass,
     DataclassField,
     DataclassReplace,
+    /// `attr.evolve`, which is typed like `dataclasses.replace`.
+    AttrsEvolve,
     /// `typing.dataclass_transform`. Note that this is `dataclass_transform` itself, *not* the
     /// decorator created by a `dataclass_transform(...)` call. See
     /// https://typing.python.org/en/latest/spec/dataclasses.html#specification.
@@ -505,7 +523,8 @@ pub enum FunctionKind {
     RuntimeCheckable,
     Def(Box<FuncId>),
     AbstractMethod,
-    /// Instance of a protocol with a `__call__` method. The function has the `__call__` signature.
+    /// Instance of a protocol with a `__call__` method, or a `functools.partial` object. The
+    /// function has the `__call__` signature.
     CallbackProtocol(Box<ClassType>),
     TotalOrdering,
     DisjointBase,
@@ -845,6 +864,7 @@ impl FunctionKind {
             ("dataclasses", None, "dataclass") => Self::Dataclass,
             ("dataclasses", None, "field") => Self::DataclassField,
             ("dataclasses", None, "replace") => Self::DataclassReplace,
+            ("attr", None, "evolve") => Self::AttrsEvolve,
             ("typing", None, "overload") => Self::Overload,
             ("typing", None, "override") => Self::Override,
             ("typing", None, "cast") => Self::Cast,
@@ -876,6 +896,7 @@ impl FunctionKind {
             Self::Dataclass => ModuleName::dataclasses(),
             Self::DataclassField => ModuleName::dataclasses(),
             Self::DataclassReplace => ModuleName::dataclasses(),
+            Self::AttrsEvolve => ModuleName::from_str("attr"),
             Self::DataclassTransform => ModuleName::typing(),
             Self::Final => ModuleName::typing(),
             Self::Overload => ModuleName::typing(),
@@ -902,6 +923,7 @@ impl FunctionKind {
             Self::Dataclass => Cow::Owned(Name::new_static("dataclass")),
             Self::DataclassField => Cow::Owned(Name::new_static("field")),
             Self::DataclassReplace => Cow::Owned(Name::new_static("replace")),
+            Self::AttrsEvolve => Cow::Owned(Name::new_static("evolve")),
             Self::DataclassTransform => Cow::Owned(Name::new_static("dataclass_transform")),
             Self::Final => Cow::Owned(Name::new_static("final")),
             Self::Overload => Cow::Owned(Name::new_static("overload")),
@@ -928,6 +950,7 @@ impl FunctionKind {
             Self::Dataclass => None,
             Self::DataclassField => None,
             Self::DataclassReplace => None,
+            Self::AttrsEvolve => None,
             Self::DataclassTransform => None,
             Self::Final => None,
             Self::Overload => None,
@@ -7,6 +7,7 @@
 
 //! Display a type. The complexity comes from if we have two classes with the same name,
 //! we want to display disambiguating information (e.g. module name or location).
+use std::cell::RefCell;
 use std::fmt;
 use std::fmt::Display;
 
@@ -77,6 +78,16 @@ impl QNameInfo {
         }
     }
 
+    /// Is `qname` the only definition with its name in its module, so doesn't need a location?
+    fn is_unique_in_module(&self, qname: &QName) -> bool {
+        matches!(self.info.get(&qname.module_name()), Some(Some(_)))
+    }
+
+    /// Does `fmt` display `qname` with its module name?
+    fn displays_module(&self, qname: &QName) -> bool {
+        !self.is_unique_in_module(qname) || self.info.len() > 1
+    }
+
     fn fmt(&self, qname: &QName, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let module_name = qname.module_name();
         match self.info.get(&module_name) {
@@ -106,6 +117,12 @@ pub struct TypeDisplayContext<'a> {
     lsp_display_mode: LspDisplayMode,
     always_display_module_name: bool,
     always_display_expanded_unions: bool,
+    /// What to display for an `Any` we inferred, rather than `Unknown`.
+    implicit_any_name: Option<&'a str>,
+    /// The module the type is displayed in, whose names never need its module name.
+    current_module: Option<ModuleName>,
+    /// The modules whose names have been displayed qualified by the module name.
+    displayed_modules: RefCell<SmallSet<ModuleName>>,
 }
 
 impl<'a> TypeDisplayContext<'a> {
@@ -167,6 +184,24 @@ impl<'a> TypeDisplayContext<'a> {
         self.always_display_module_name = true;
     }
 
+    /// Display an `Any` we inferred, rather than one the user wrote, as `name` instead of
+    /// `Unknown`, e.g. so it can be written to a stub.
+    pub fn set_implicit_any_name(&mut self, name: &'a str) {
+        self.implicit_any_name = Some(name);
+    }
+
+    /// Display names defined in `module` without its module name, as they are referenced from
+    /// within `module`, unless they need a location to tell them apart.
+    pub fn set_current_module(&mut self, module: ModuleName) {
+        self.current_module = Some(module);
+    }
+
+    /// The modules whose names have been displayed qualified by the module name so far, so
+    /// need importing for the displayed types to be valid Python.
+    pub fn displayed_modules(&self) -> SmallSet<ModuleName> {
+        self.displayed_modules.borrow().clone()
+    }
+
     /// Set the context to display in LSP.
     pub fn set_lsp_display_mode(&mut self, display_mode: LspDisplayMode) {
         self.lsp_display_mode = display_mode;
@@ -229,10 +264,24 @@ impl<'a> TypeDisplayContext<'a> {
     }
 
     pub(crate) fn fmt_qname(&self, qname: &QName, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self.qnames.get(&qname.id()) {
-            Some(info) => info.fmt(qname, f),
-            None => QNameInfo::qualified().fmt(qname, f), // we should not get here, if we do, be safe
+        let qualified;
+        let info = match self.qnames.get(&qname.id()) {
+            Some(info) => info,
+            None => {
+                // we should not get here, if we do, be safe
+                qualified = QNameInfo::qualified();
+                &qualified
+            }
+        };
+        if self.current_module == Some(qname.module_name()) && info.is_unique_in_module(qname) {
+            return qname.fmt_name(f);
+        }
+        if info.displays_module(qname) {
+            self.displayed_modules
+                .borrow_mut()
+                .insert(qname.module_name());
         }
+        info.fmt(qname, f)
     }
 
     pub(crate) fn fmt_lit(&self, lit: &Lit, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -256,6 +305,9 @@ impl<'a> TypeDisplayContext<'a> {
         output: &mut impl TypeOutput,
     ) -> fmt::Result {
         if self.always_display_module_name {
+            self.displayed_modules
+                .borrow_mut()
+                .insert(ModuleName::from_str(module));
             output.write_str(&format!("{}.{}", module, name))
         } else {
             output.write_str(name)
@@ -777,7 +829,9 @@ impl<'a> TypeDisplayContext<'a> {
             Type::Ellipsis => output.write_str("Ellipsis"),
             Type::Any(style) => match style {
                 AnyStyle::Explicit => self.maybe_fmt_with_module("typing", "Any", output),
-                AnyStyle::Implicit | AnyStyle::Error => output.write_str("Unknown"),
+                AnyStyle::Implicit | AnyStyle::Error => {
+                    output.write_str(self.implicit_any_name.unwrap_or("Unknown"))
+                }
             },
             Type::TypeAlias(ta) => {
                 if is_toplevel {
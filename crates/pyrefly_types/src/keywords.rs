@@ -166,6 +166,8 @@ impl DataclassFieldKeywords {
     pub const ALIAS: Name = Name::new_static("alias");
     /// We extract and store only the first positional parameter to the converter callable.
     pub const CONVERTER: Name = Name::new_static("converter");
+    /// `attrs` validators, which we only check against the field type.
+    pub const VALIDATOR: Name = Name::new_static("validator");
 
     pub fn new() -> Self {
         Self {
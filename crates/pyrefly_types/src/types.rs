@@ -1154,6 +1154,14 @@ impl Type {
         self.check_toplevel_func_metadata(&|meta| meta.flags.is_cached_property)
     }
 
+    pub fn functools_wrapper(&self) -> Option<ClassType> {
+        self.check_toplevel_func_metadata(&|meta| meta.flags.functools_wrapper.clone())
+    }
+
+    pub fn is_pytest_fixture(&self) -> bool {
+        self.check_toplevel_func_metadata(&|meta| meta.flags.is_pytest_fixture)
+    }
+
     pub fn is_property_setter_decorator(&self) -> bool {
         self.property_metadata()
             .is_some_and(|meta| matches!(meta.role, PropertyRole::SetterDecorator))
@@ -26,6 +26,7 @@ pub enum TelemetryEventKind {
     InvalidateOnClose,
     PopulateProjectFiles,
     PopulateWorkspaceFiles,
+    WorkspaceDiagnostics,
     SourceDbRebuild,
     FindFromDefinition,
 }
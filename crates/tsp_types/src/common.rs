@@ -166,7 +166,7 @@ pub fn snapshot_outdated_error() -> ResponseError {
 
 /// Creates a common error response for internal errors
 #[allow(dead_code)]
-pub(crate) fn create_internal_error(message: &str) -> ResponseError {
+pub fn create_internal_error(message: &str) -> ResponseError {
     ResponseError {
         code: ErrorCode::InternalError as i32,
         message: message.to_owned(),
@@ -176,7 +176,7 @@ pub(crate) fn create_internal_error(message: &str) -> ResponseError {
 
 /// Creates a common error response for language services being disabled
 #[allow(dead_code)]
-pub(crate) fn language_services_disabled_error() -> ResponseError {
+pub fn language_services_disabled_error() -> ResponseError {
     ResponseError {
         code: ErrorCode::RequestFailed as i32,
         message: "Language services disabled".to_owned(),
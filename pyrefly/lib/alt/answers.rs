@@ -33,6 +33,7 @@ use crate::alt::answers_solver::AnswersSolver;
 use crate::alt::answers_solver::ThreadState;
 use crate::alt::attr::AttrDefinition;
 use crate::alt::attr::AttrInfo;
+use crate::alt::class::plugin::ClassSynthesisPlugins;
 use crate::alt::traits::Solve;
 use crate::binding::binding::Exported;
 use crate::binding::binding::Key;
@@ -76,6 +77,9 @@ pub struct Index {
     /// A map from (child method range) to a list of parent method definitions (ModulePath, parent method range).
     /// This is used to find reimplementations when doing find-references on parent methods.
     pub parent_methods_map: SmallMap<TextRange, Vec<(ModulePath, TextRange)>>,
+    /// A map from (class name range) to a list of its direct base classes (ModulePath, base class name range).
+    /// This is used to find subclasses for type hierarchy requests.
+    pub base_classes_map: SmallMap<TextRange, Vec<(ModulePath, TextRange)>>,
 }
 
 #[derive(Debug)]
@@ -406,6 +410,7 @@ impl Answers {
         bindings: &Bindings,
         errors: &ErrorCollector,
         stdlib: &Stdlib,
+        class_synthesis_plugins: &ClassSynthesisPlugins,
         uniques: &UniqueFactory,
         compute_everything: bool,
     ) -> Solutions {
@@ -448,6 +453,7 @@ impl Answers {
             uniques,
             recurser,
             stdlib,
+            class_synthesis_plugins,
             thread_state,
         );
         table_mut_for_each!(&mut res, |items| pre_solve(
@@ -521,6 +527,7 @@ impl Answers {
         bindings: &Bindings,
         errors: &ErrorCollector,
         stdlib: &Stdlib,
+        class_synthesis_plugins: &ClassSynthesisPlugins,
         uniques: &UniqueFactory,
         key: Hashed<&K>,
         thread_state: &ThreadState,
@@ -539,6 +546,7 @@ impl Answers {
             uniques,
             recurser,
             stdlib,
+            class_synthesis_plugins,
             thread_state,
         );
         let v = solver.get_hashed_opt(key)?;
@@ -571,6 +579,17 @@ impl Answers {
         Some(self.deep_force(lock.types.get(&range)?.as_ref().clone()))
     }
 
+    /// The types of all the expressions recorded in the trace.
+    pub fn get_all_type_traces(&self) -> Option<Vec<(TextRange, Type)>> {
+        let lock = self.trace.as_ref()?.lock();
+        Some(
+            lock.types
+                .iter()
+                .map(|(range, ty)| (*range, self.deep_force(ty.as_ref().clone())))
+                .collect(),
+        )
+    }
+
     pub fn try_get_getter_for_range(&self, range: TextRange) -> Option<Type> {
         let lock = self.trace.as_ref()?.lock();
         Some(self.deep_force(lock.invoked_properties.get(&range)?.as_ref().clone()))
@@ -623,6 +642,22 @@ impl Answers {
                 .push((parent_module, parent_range));
         }
     }
+
+    pub fn add_base_class_mapping(
+        &self,
+        class_range: TextRange,
+        base_module: ModulePath,
+        base_range: TextRange,
+    ) {
+        if let Some(index) = &self.index {
+            index
+                .lock()
+                .base_classes_map
+                .entry(class_range)
+                .or_default()
+                .push((base_module, base_range));
+        }
+    }
 }
 
 impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
@@ -37,6 +37,7 @@ use crate::alt::answers::Answers;
 use crate::alt::answers::LookupAnswer;
 use crate::alt::answers::SolutionsEntry;
 use crate::alt::answers::SolutionsTable;
+use crate::alt::class::plugin::ClassSynthesisPlugins;
 use crate::alt::traits::Solve;
 use crate::binding::binding::AnyIdx;
 use crate::binding::binding::Binding;
@@ -403,6 +404,7 @@ pub struct AnswersSolver<'a, Ans: LookupAnswer> {
     pub uniques: &'a UniqueFactory,
     pub recurser: &'a VarRecurser,
     pub stdlib: &'a Stdlib,
+    pub class_synthesis_plugins: &'a ClassSynthesisPlugins,
 }
 
 impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
@@ -415,10 +417,12 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         uniques: &'a UniqueFactory,
         recurser: &'a VarRecurser,
         stdlib: &'a Stdlib,
+        class_synthesis_plugins: &'a ClassSynthesisPlugins,
         thread_state: &'a ThreadState,
     ) -> AnswersSolver<'a, Ans> {
         AnswersSolver {
             stdlib,
+            class_synthesis_plugins,
             uniques,
             answers,
             bindings,
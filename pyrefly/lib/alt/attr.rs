@@ -46,7 +46,6 @@ use crate::export::exports::Exports;
 use crate::solver::solver::SubsetError;
 use crate::types::callable::FuncMetadata;
 use crate::types::callable::Function;
-use crate::types::callable::FunctionKind;
 use crate::types::callable::PropertyMetadata;
 use crate::types::callable::PropertyRole;
 use crate::types::class::Class;
@@ -1935,10 +1934,9 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                 signatures: _,
                 metadata: box metadata,
             }) => acc.push(AttributeBase1::ClassInstance(
-                if let FunctionKind::CallbackProtocol(cls) = metadata.kind {
-                    *cls
-                } else {
-                    self.stdlib.function_type().clone()
+                match metadata.instance_class() {
+                    Some(cls) => cls.clone(),
+                    None => self.stdlib.function_type().clone(),
                 },
             )),
             Type::BoundMethod(bound_method) => {
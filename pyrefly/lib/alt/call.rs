@@ -1297,7 +1297,9 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                         errors,
                     )
                 }
-                Some(CalleeKind::Function(FunctionKind::DataclassReplace)) => {
+                Some(CalleeKind::Function(
+                    FunctionKind::DataclassReplace | FunctionKind::AttrsEvolve,
+                )) => {
                     self.call_dataclasses_replace(
                         ty,
                         &args,
@@ -1336,6 +1338,17 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                 {
                     self.call_issubclass(&x.arguments.args[0], &x.arguments.args[1], errors)
                 }
+                _ if matches!(ty, Type::ClassDef(cls) if cls.has_toplevel_qname("functools", "partial")) => {
+                    self.call_functools_partial(
+                        ty,
+                        &x.arguments.args,
+                        &x.arguments.keywords,
+                        x.func.range(),
+                        x.arguments.range,
+                        hint,
+                        errors,
+                    )
+                }
                 _ if matches!(ty, Type::ClassDef(cls) if cls == self.stdlib.builtins_type().class_object())
                     && x.arguments.args.len() == 1 && x.arguments.keywords.is_empty() =>
                 {
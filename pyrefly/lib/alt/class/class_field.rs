@@ -847,6 +847,13 @@ impl ClassField {
         }
     }
 
+    fn is_initialized_by_field_specifier(&self) -> bool {
+        matches!(
+            self.initialization(),
+            ClassFieldInitialization::ClassBody(Some(_))
+        )
+    }
+
     fn is_dataclass_kwonly_marker(&self) -> bool {
         match &self.0 {
             ClassFieldInner::Property { .. } => false,
@@ -1544,6 +1551,7 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         if let Some(annotation) = direct_annotation.as_ref() {
             self.validate_direct_annotation(
                 annotation,
+                class,
                 &metadata,
                 &initialization,
                 name,
@@ -1622,8 +1630,19 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
             range,
             errors,
         ) {
-            // Don't use the descriptor, since we've set a custom type instead.
-            descriptor = None;
+            // Don't use the descriptor, since we've set a custom type instead, unless the custom
+            // type just specializes the descriptor class differently.
+            descriptor = match (descriptor, &special_ty) {
+                (Some(descriptor), Type::ClassType(cls))
+                    if cls.class_object() == descriptor.cls.class_object() =>
+                {
+                    Some(Descriptor {
+                        cls: cls.clone(),
+                        ..descriptor
+                    })
+                }
+                _ => None,
+            };
             special_ty
         } else {
             ty
@@ -1764,6 +1783,16 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
             self.validate_post_init(class, dm, post_init, range, errors);
         }
 
+        if let Some(dm) = metadata.dataclass_metadata()
+            && class_field.is_initialized_by_field_specifier()
+            && let ClassFieldDefinition::AssignedInBody {
+                value: ExprOrBinding::Expr(Expr::Call(call)),
+                ..
+            } = field_definition
+        {
+            self.validate_field_converter_and_validator(dm, name, &class_field.ty(), call, errors);
+        }
+
         if let Some(named_tuple_metadata) = metadata.named_tuple_metadata()
             && !functional_class_def
             && named_tuple_metadata.elements.contains(name)
@@ -1845,7 +1874,9 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                 } => Some(expr),
                 _ => None,
             };
-            self.get_django_field_type(ty, class, Some(name), initial_value_expr)
+            self.plugins_for_class(class)
+                .into_iter()
+                .find_map(|plugin| plugin.field_type(self, class, name, ty, initial_value_expr))
         })
     }
 
@@ -2110,17 +2141,19 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
     fn validate_direct_annotation(
         &self,
         annotation: &Annotation,
+        class: &Class,
         metadata: &ClassMetadata,
         initialization: &ClassFieldInitialization,
         name: &Name,
         range: TextRange,
         errors: &ErrorCollector,
     ) {
-        if metadata.is_pydantic_base_model()
-            && let ClassFieldInitialization::ClassBody(Some(kws)) = initialization
-        {
-            let field_ty = annotation.get_type();
-            self.check_pydantic_range_constraints(name, field_ty, kws, range, errors);
+        let keywords = match initialization {
+            ClassFieldInitialization::ClassBody(Some(kws)) => Some(&**kws),
+            _ => None,
+        };
+        for plugin in self.plugins_for_class(class) {
+            plugin.check_field(self, class, name, annotation, keywords, range, errors);
         }
 
         // Check for qualifiers that are used in improper contexts.
@@ -2175,6 +2208,7 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                     .get_inherited_type_and_annotation(cls, name)
                     .1
                     .is_some_and(|annot| annot.has_qualifier(&Qualifier::ClassVar)))
+            || self.is_non_field_attrs_member(&member)
         {
             DataclassMember::NotAField
         } else {
@@ -2187,6 +2221,18 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         }
     }
 
+    /// `attrs` only treats attributes assigned `attr.ib()` as fields, plus annotated attributes if
+    /// `auto_attribs` is set.
+    fn is_non_field_attrs_member(&self, member: &WithDefiningClass<Arc<ClassField>>) -> bool {
+        let field = &*member.value;
+        !field.is_initialized_by_field_specifier()
+            && self
+                .get_metadata_for_class(&member.defining_class)
+                .dataclass_metadata()
+                .and_then(|dm| dm.attrs.as_ref())
+                .is_some_and(|attrs| !attrs.auto_attribs || !field.has_explicit_annotation())
+    }
+
     fn check_and_sanitize_type_parameters(
         &self,
         class: &Class,
@@ -35,6 +35,7 @@ use crate::alt::answers::LookupAnswer;
 use crate::alt::answers_solver::AnswersSolver;
 use crate::alt::solve::TypeFormContext;
 use crate::alt::types::abstract_class::AbstractClassMembers;
+use crate::alt::types::class_metadata::AttrsMetadata;
 use crate::alt::types::class_metadata::ClassMetadata;
 use crate::alt::types::class_metadata::ClassMro;
 use crate::alt::types::class_metadata::DataclassMetadata;
@@ -72,6 +73,8 @@ use crate::types::literal::Lit;
 use crate::types::types::CalleeKind;
 use crate::types::types::Type;
 
+const AUTO_ATTRIBS: Name = Name::new_static("auto_attribs");
+
 #[derive(Debug, Clone)]
 struct ParsedBaseClass {
     class_object: Class,
@@ -285,6 +288,26 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
             })
         });
 
+        let decorator_qnames = decorators
+            .iter()
+            .filter_map(|(decorator, _)| {
+                let callee_kind = match &decorator.ty {
+                    Type::ClassDef(cls) => return Some((cls.module_name(), cls.name().clone())),
+                    Type::BoundMethod(method) => method.func.clone().as_type().callee_kind(),
+                    ty => ty.callee_kind(),
+                };
+                match callee_kind? {
+                    CalleeKind::Function(kind) => {
+                        Some((kind.module_name(), kind.function_name().into_owned()))
+                    }
+                    _ => None,
+                }
+            })
+            .collect::<Vec<_>>();
+        let class_synthesis_plugins =
+            self.class_synthesis_plugins
+                .applicable(cls, &bases_with_metadata, &decorator_qnames);
+
         // If this class inherits from a dataclass_transform-ed class, record the defaults that we
         // should use for dataclass parameters.
         let dataclass_defaults_from_base_class = bases_with_metadata
@@ -359,6 +382,7 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
             dataclass_transform_metadata,
             pydantic_model_kind,
             django_model_metadata,
+            class_synthesis_plugins,
         )
     }
 
@@ -773,7 +797,8 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
             match decorator.ty.callee_kind() {
                 // `@dataclass`
                 Some(CalleeKind::Function(FunctionKind::Dataclass)) => {
-                    let dataclass_fields = self.get_dataclass_fields(cls, bases_with_metadata);
+                    let dataclass_fields =
+                        self.get_dataclass_fields(cls, bases_with_metadata, false);
                     dataclass_metadata = Some(DataclassMetadata {
                         fields: dataclass_fields,
                         kws: DataclassKeywords::new(),
@@ -784,13 +809,15 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                         alias_keyword: alias_keyword.clone(),
                         init_defaults: init_defaults.clone(),
                         default_can_be_positional,
+                        attrs: None,
                     });
                 }
                 // `@dataclass(...)`
                 _ if let Type::KwCall(call) = &decorator.ty
                     && call.has_function_kind(FunctionKind::Dataclass) =>
                 {
-                    let dataclass_fields = self.get_dataclass_fields(cls, bases_with_metadata);
+                    let dataclass_fields =
+                        self.get_dataclass_fields(cls, bases_with_metadata, false);
                     dataclass_metadata = Some(DataclassMetadata {
                         fields: dataclass_fields,
                         kws: DataclassKeywords::from_type_map(
@@ -804,24 +831,55 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                         alias_keyword: alias_keyword.clone(),
                         init_defaults: init_defaults.clone(),
                         default_can_be_positional,
+                        attrs: None,
                     });
                 }
                 _ => {}
             }
         }
         if let Some((kws, field_specifiers)) = dataclass_from_dataclass_transform {
+            let attrs = self.attrs_metadata(decorators);
             dataclass_metadata = Some(DataclassMetadata {
-                fields: self.get_dataclass_fields(cls, bases_with_metadata),
+                fields: self.get_dataclass_fields(cls, bases_with_metadata, attrs.is_some()),
                 kws,
                 field_specifiers,
                 alias_keyword,
                 init_defaults,
                 default_can_be_positional,
+                attrs,
             });
         }
         dataclass_metadata
     }
 
+    /// If the class is decorated with one of the `attrs` class decorators, get its options.
+    fn attrs_metadata(&self, decorators: &[(Arc<Decorator>, TextRange)]) -> Option<AttrsMetadata> {
+        decorators.iter().find_map(|(decorator, _)| {
+            let (kind, keywords) = match &decorator.ty {
+                // `@attr.s(...)`
+                Type::KwCall(call) => (call.func_metadata.kind.clone(), Some(&call.keywords)),
+                // `@attr.s`
+                ty => match ty.callee_kind()? {
+                    CalleeKind::Function(kind) => (kind, None),
+                    _ => return None,
+                },
+            };
+            if !matches!(
+                kind.module_name().first_component().as_str(),
+                "attr" | "attrs"
+            ) {
+                return None;
+            }
+            // Only the classic `attr.s` (an alias of `attr.attrs`) defaults to `auto_attribs=False`.
+            let is_classic = kind.function_name().as_str() == "attrs";
+            Some(AttrsMetadata {
+                auto_attribs: keywords
+                    .and_then(|keywords| keywords.get_bool(&AUTO_ATTRIBS))
+                    .unwrap_or(!is_classic),
+            })
+        })
+    }
+
     // To avoid circular computation on targs, we have a special version of `expr_infer` that does not look into any subscript of any expr
     fn base_class_expr_infer_for_metadata(
         &self,
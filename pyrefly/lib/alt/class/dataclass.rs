@@ -11,7 +11,11 @@ use dupe::Dupe;
 use pyrefly_python::dunder;
 use pyrefly_util::prelude::SliceExt;
 use ruff_python_ast::Arguments;
+use ruff_python_ast::Expr;
 use ruff_python_ast::Expr::EllipsisLiteral;
+use ruff_python_ast::ExprCall;
+use ruff_python_ast::ExprList;
+use ruff_python_ast::ExprTuple;
 use ruff_python_ast::name::Name;
 use ruff_text_size::Ranged;
 use ruff_text_size::TextRange;
@@ -60,11 +64,14 @@ use crate::types::types::AnyStyle;
 use crate::types::types::Type;
 
 impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
-    /// Gets dataclass fields for an `@dataclass`-decorated class.
+    /// Gets dataclass fields for an `@dataclass`-decorated class. `attrs` fields don't need to be
+    /// annotated if they are assigned `attr.ib()`, so for `attrs` classes we also include
+    /// unannotated attributes and check for this in `get_dataclass_member`.
     pub fn get_dataclass_fields(
         &self,
         cls: &Class,
         bases_with_metadata: &[(Class, Arc<ClassMetadata>)],
+        is_attrs: bool,
     ) -> SmallSet<Name> {
         let mut all_fields = SmallSet::new();
         for (_, metadata) in bases_with_metadata.iter().rev() {
@@ -73,7 +80,8 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
             }
         }
         for name in cls.fields() {
-            if cls.is_field_annotated(name) {
+            if cls.is_field_annotated(name) || (is_attrs && cls.is_field_initialized_on_class(name))
+            {
                 all_fields.insert(name.clone());
             }
         }
@@ -382,6 +390,66 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         });
     }
 
+    /// Checks that the values produced by the `converter` and, for `attrs` classes, accepted by the
+    /// `validator`s of a field specifier call agree with the type of the field.
+    pub fn validate_field_converter_and_validator(
+        &self,
+        dataclass_metadata: &DataclassMetadata,
+        name: &Name,
+        field_ty: &Type,
+        call: &ExprCall,
+        errors: &ErrorCollector,
+    ) {
+        // We already type-checked the arguments as part of computing the type of the field.
+        let ignore_errors = self.error_swallower();
+        for kw in call.arguments.keywords.iter() {
+            let Some(arg) = &kw.arg else {
+                continue;
+            };
+            if arg.id == DataclassFieldKeywords::CONVERTER {
+                let converter = self.expr_infer(&kw.value, &ignore_errors);
+                let converter = self
+                    .constructor_to_callable_distributed(&converter)
+                    .unwrap_or(converter);
+                // We don't solve the type parameters of generic converters.
+                if let Some(ret) = converter.callable_return_type()
+                    && !ret.any(|t| matches!(t, Type::Quantified(_)))
+                {
+                    self.check_type(&ret, field_ty, kw.value.range(), errors, &|| {
+                        TypeCheckContext::of_kind(TypeCheckKind::FieldConverter(name.clone()))
+                    });
+                }
+            } else if arg.id == DataclassFieldKeywords::VALIDATOR
+                && dataclass_metadata.attrs.is_some()
+            {
+                // A validator is called with the instance, the `attrs` attribute, and the value.
+                let want = Type::Callable(Box::new(Callable::list(
+                    ParamList::new(vec![
+                        Param::PosOnly(None, Type::any_implicit(), Required::Required),
+                        Param::PosOnly(None, Type::any_implicit(), Required::Required),
+                        Param::PosOnly(None, field_ty.clone(), Required::Required),
+                    ]),
+                    self.stdlib.object().clone().to_type(),
+                )));
+                let validators = match &kw.value {
+                    Expr::List(ExprList { elts, .. }) | Expr::Tuple(ExprTuple { elts, .. }) => {
+                        elts.iter().collect()
+                    }
+                    validator => vec![validator],
+                };
+                for validator in validators {
+                    let got = self.expr_infer(validator, &ignore_errors);
+                    self.check_type(&got, &want, validator.range(), errors, &|| {
+                        TypeCheckContext::of_kind(TypeCheckKind::FieldValidator(
+                            name.clone(),
+                            field_ty.clone(),
+                        ))
+                    });
+                }
+            }
+        }
+    }
+
     pub fn dataclass_field_keywords(
         &self,
         func: &Type,
@@ -635,6 +703,16 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                                 },
                         );
                     };
+                    // `attrs` strips leading underscores from the names of private attributes to
+                    // get their `__init__` parameter names.
+                    if dataclass.attrs.is_some()
+                        && keywords.init_by_alias.is_none()
+                        && name.as_str().starts_with('_')
+                    {
+                        keywords.init_by_name = false;
+                        keywords.init_by_alias =
+                            Some(Name::new(name.as_str().trim_start_matches('_')));
+                    }
                     if keywords.is_kw_only() {
                         kwonly_fields.push((name.clone(), (*field.value).clone(), keywords))
                     } else {
@@ -13,8 +13,10 @@ pub mod django;
 pub mod enums;
 pub mod named_tuple;
 pub mod new_type;
+pub mod plugin;
 pub mod pydantic;
 pub mod pydantic_lax;
+pub mod sqlalchemy;
 pub mod targs;
 pub mod total_ordering;
 pub mod tparams;
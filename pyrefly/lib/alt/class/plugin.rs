@@ -0,0 +1,404 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Class synthesis plugins, the extension point for frameworks whose classes behave in ways that
+//! aren't written out in the class body, like ORM models whose fields are descriptors and whose
+//! `__init__` is generated by a base class or metaclass.
+//!
+//! A plugin declares the base classes and decorators it handles, and is consulted for every class
+//! that inherits from one of those bases or is decorated with one of those decorators. It can:
+//! * synthesize fields and methods on the class,
+//! * synthesize an `__init__` signature,
+//! * replace the type of a field declared in the class body, e.g. to turn a descriptor into the
+//!   type it produces, and
+//! * report extra errors for fields declared in the class body.
+//!
+//! Plugins see the solver only through [`ClassSynthesisContext`], so they don't depend on its
+//! internals. Each `State` has its own [`ClassSynthesisPlugins`], which start with the built-in
+//! plugins for Django, Pydantic and SQLAlchemy; other plugins can be added with
+//! `State::register_class_synthesis_plugin` before any checking starts. The plugins that handle
+//! a class are worked out once, along with the rest of its [`ClassMetadata`].
+//!
+//! # Limitations
+//!
+//! These hooks are not enough to support a framework like Django or Pydantic with a plugin alone.
+//! There are no hooks yet to:
+//! * compute framework-specific class metadata, like a model's configuration,
+//! * make a class frozen, or replace an `__init__` declared in a class body,
+//! * detect fields while binding, before types are known, or
+//! * give special meaning to names a framework exports.
+//!
+//! So the built-in Django and Pydantic plugins only cover their field types, synthesized fields
+//! and field checks. The rest is still hard-wired, and a framework that needs any of the above
+//! still needs changes to Pyrefly itself:
+//! * the model metadata (`pydantic_model_kind`, `django_model_metadata`) in `class_metadata.rs`,
+//!   and what depends on it: the `__init__` of Pydantic models in `dataclass.rs`, frozen Pydantic
+//!   models and Django foreign keys, `choices` and `Meta` classes in `class_field.rs`,
+//! * the `__init__` of `RootModel`, which replaces the one declared in its body,
+//! * Django's `primary_key=True` detection in `binding/django.rs`, and
+//! * the Pydantic names recognized through `SpecialExport`.
+
+use std::sync::Arc;
+
+use pyrefly_config::error_kind::ErrorKind;
+use pyrefly_python::dunder;
+use pyrefly_python::module_name::ModuleName;
+use pyrefly_types::annotation::Annotation;
+use pyrefly_types::callable::Param;
+use pyrefly_types::class::Class;
+use pyrefly_types::class::ClassType;
+use pyrefly_types::keywords::DataclassFieldKeywords;
+use pyrefly_types::literal::Lit;
+use pyrefly_types::types::TParams;
+use pyrefly_types::types::Type;
+use ruff_python_ast::Expr;
+use ruff_python_ast::name::Name;
+use ruff_text_size::TextRange;
+use starlark_map::small_map::SmallMap;
+use starlark_map::small_set::SmallSet;
+
+use crate::alt::answers::LookupAnswer;
+use crate::alt::answers_solver::AnswersSolver;
+use crate::alt::class::class_field::ClassField;
+use crate::alt::class::django::DjangoPlugin;
+use crate::alt::class::enums::VALUE_PROP;
+use crate::alt::class::pydantic::PydanticPlugin;
+use crate::alt::class::sqlalchemy::SqlAlchemyPlugin;
+use crate::alt::types::class_metadata::ClassMetadata;
+use crate::alt::types::class_metadata::ClassMro;
+use crate::alt::types::class_metadata::ClassSynthesizedField;
+use crate::alt::types::class_metadata::ClassSynthesizedFields;
+use crate::binding::binding::KeyExport;
+use crate::error::collector::ErrorCollector;
+use crate::error::context::ErrorInfo;
+use crate::module::module_info::ModuleInfo;
+use crate::types::stdlib::Stdlib;
+
+/// The queries a [`ClassSynthesisPlugin`] can make while type checking a class. Errors from
+/// inferring types are discarded; plugins report their own errors with [`Self::add_error`].
+pub trait ClassSynthesisContext {
+    fn stdlib(&self) -> &Stdlib;
+
+    /// The module being checked.
+    fn module(&self) -> &ModuleInfo;
+
+    fn metadata(&self, cls: &Class) -> Arc<ClassMetadata>;
+
+    fn mro(&self, cls: &Class) -> Arc<ClassMro>;
+
+    fn tparams(&self, cls: &Class) -> Arc<TParams>;
+
+    /// The type of `name` in `module`, if the module exists and exports that name.
+    fn export(&self, module: ModuleName, name: &Name) -> Option<Type>;
+
+    /// The type of an expression in the module being checked.
+    fn infer_expr(&self, x: &Expr) -> Type;
+
+    /// The type of an instance of `cls`, with its own type parameters as arguments.
+    fn instantiate(&self, cls: &Class) -> Type;
+
+    fn as_class_type(&self, cls: &Class) -> ClassType;
+
+    /// The type of an instance of `cls` with the given type arguments.
+    fn specialize(&self, cls: &Class, targs: Vec<Type>) -> Type;
+
+    /// Look up a field on `cls` or its ancestors.
+    fn class_member(&self, cls: &Class, name: &Name) -> Option<Arc<ClassField>>;
+
+    /// Look up a field defined or synthesized on `cls` itself.
+    fn field_from_current_class_only(&self, cls: &Class, name: &Name) -> Option<Arc<ClassField>>;
+
+    /// The type of accessing `name` on a value of type `base`.
+    fn attr_type(&self, base: &Type, name: &Name) -> Type;
+
+    fn enum_members(&self, cls: &Class) -> SmallSet<Lit>;
+
+    /// The type of `value` on a member of the enum `cls`.
+    fn enum_value_type(&self, cls: &Class) -> Option<Type>;
+
+    /// The `self` parameter of a method synthesized on `cls`.
+    fn self_param(&self, cls: &Class) -> Param;
+
+    fn unions(&self, xs: Vec<Type>) -> Type;
+
+    fn union(&self, x: Type, y: Type) -> Type {
+        self.unions(vec![x, y])
+    }
+
+    fn is_subset_eq(&self, got: &Type, want: &Type) -> bool;
+
+    /// Prepare a type for use in an error message.
+    fn for_display(&self, t: Type) -> Type;
+
+    fn add_error(&self, errors: &ErrorCollector, range: TextRange, kind: ErrorKind, msg: String);
+}
+
+/// Framework-specific behavior for the classes that inherit from a set of base classes or are
+/// decorated with a set of decorators. All hooks are optional. See the module docs for what
+/// plugins can't do yet.
+pub trait ClassSynthesisPlugin: Send + Sync {
+    /// The name of the framework, for debugging.
+    fn name(&self) -> &'static str;
+
+    /// `(module, name)` of the classes whose subclasses this plugin handles. The classes
+    /// themselves are handled too.
+    fn base_classes(&self) -> &[(&'static str, &'static str)] {
+        &[]
+    }
+
+    /// `(module, name)` of the functions, methods or classes that, used as a class decorator, make
+    /// this plugin handle the decorated class.
+    fn decorators(&self) -> &[(&'static str, &'static str)] {
+        &[]
+    }
+
+    /// Whether this plugin handles `cls`, given the module and name of each of its decorators.
+    /// Subclasses of a class that a plugin handles are always handled too. By default, matches
+    /// `cls` against [`Self::base_classes`] and its decorators against [`Self::decorators`].
+    fn applies_to(&self, cls: &Class, decorators: &[(ModuleName, Name)]) -> bool {
+        self.base_classes()
+            .iter()
+            .any(|(module, name)| cls.has_toplevel_qname(module, name))
+            || decorators.iter().any(|(module, name)| {
+                self.decorators()
+                    .iter()
+                    .any(|(m, n)| module.as_str() == *m && name.as_str() == *n)
+            })
+    }
+
+    /// Fields and methods to add to `cls`. Fields written in the class body take precedence over
+    /// these.
+    fn synthesized_fields(
+        &self,
+        _ctx: &dyn ClassSynthesisContext,
+        _cls: &Class,
+    ) -> Option<ClassSynthesizedFields> {
+        None
+    }
+
+    /// The type of the `__init__` method to synthesize on `cls`, e.g. one whose parameters are
+    /// the fields collected by a metaclass.
+    fn init_method(&self, _ctx: &dyn ClassSynthesisContext, _cls: &Class) -> Option<Type> {
+        None
+    }
+
+    /// The type to use for the field `name` declared in the body of `cls` with type `ty`, and
+    /// assigned `initial_value` if it is assigned in the class body.
+    fn field_type(
+        &self,
+        _ctx: &dyn ClassSynthesisContext,
+        _cls: &Class,
+        _name: &Name,
+        _ty: &Type,
+        _initial_value: Option<&Expr>,
+    ) -> Option<Type> {
+        None
+    }
+
+    /// Report errors for the field `name` annotated in the body of `cls`. `keywords` are the
+    /// arguments of the field specifier it was initialized with, for dataclass-like classes.
+    fn check_field(
+        &self,
+        _ctx: &dyn ClassSynthesisContext,
+        _cls: &Class,
+        _name: &Name,
+        _annotation: &Annotation,
+        _keywords: Option<&DataclassFieldKeywords>,
+        _range: TextRange,
+        _errors: &ErrorCollector,
+    ) {
+    }
+}
+
+/// The plugins of a `State`, in the order they are consulted.
+pub struct ClassSynthesisPlugins(Vec<Arc<dyn ClassSynthesisPlugin>>);
+
+impl Default for ClassSynthesisPlugins {
+    /// The built-in plugins.
+    fn default() -> Self {
+        Self(vec![
+            Arc::new(PydanticPlugin),
+            Arc::new(DjangoPlugin),
+            Arc::new(SqlAlchemyPlugin),
+        ])
+    }
+}
+
+impl ClassSynthesisPlugins {
+    /// Add a plugin, which is consulted after the ones already registered.
+    pub fn register(&mut self, plugin: Arc<dyn ClassSynthesisPlugin>) {
+        self.0.push(plugin);
+    }
+
+    pub fn get(&self, index: usize) -> &dyn ClassSynthesisPlugin {
+        &*self.0[index]
+    }
+
+    /// The indices of the plugins that handle `cls`: those that handle any of its bases, and
+    /// those that accept `cls` itself.
+    pub fn applicable(
+        &self,
+        cls: &Class,
+        bases_with_metadata: &[(Class, Arc<ClassMetadata>)],
+        decorators: &[(ModuleName, Name)],
+    ) -> Vec<usize> {
+        let inherited = bases_with_metadata
+            .iter()
+            .flat_map(|(_, metadata)| metadata.class_synthesis_plugins().iter().copied())
+            .collect::<SmallSet<_>>();
+        (0..self.0.len())
+            .filter(|i| inherited.contains(i) || self.0[*i].applies_to(cls, decorators))
+            .collect()
+    }
+}
+
+impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
+    /// The plugins that handle `cls`, in registration order.
+    pub fn plugins_for_class(&self, cls: &Class) -> Vec<&'a dyn ClassSynthesisPlugin> {
+        let plugins: &'a ClassSynthesisPlugins = self.class_synthesis_plugins;
+        self.get_metadata_for_class(cls)
+            .class_synthesis_plugins()
+            .iter()
+            .map(|i| plugins.get(*i))
+            .collect()
+    }
+
+    pub fn get_plugin_synthesized_fields(&self, cls: &Class) -> Option<ClassSynthesizedFields> {
+        let plugins = self.plugins_for_class(cls);
+        if plugins.is_empty() {
+            return None;
+        }
+        let mut fields = ClassSynthesizedFields::default();
+        for plugin in plugins {
+            if let Some(new_fields) = plugin.synthesized_fields(self, cls) {
+                fields = fields.combine(new_fields);
+            }
+            if let Some(init) = plugin.init_method(self, cls) {
+                fields = fields.combine(ClassSynthesizedFields::new(SmallMap::from_iter([(
+                    dunder::INIT,
+                    ClassSynthesizedField::new(init),
+                )])));
+            }
+        }
+        Some(fields)
+    }
+}
+
+impl<'a, Ans: LookupAnswer> ClassSynthesisContext for AnswersSolver<'a, Ans> {
+    fn stdlib(&self) -> &Stdlib {
+        self.stdlib
+    }
+
+    fn module(&self) -> &ModuleInfo {
+        self.module()
+    }
+
+    fn metadata(&self, cls: &Class) -> Arc<ClassMetadata> {
+        self.get_metadata_for_class(cls)
+    }
+
+    fn mro(&self, cls: &Class) -> Arc<ClassMro> {
+        self.get_mro_for_class(cls)
+    }
+
+    fn tparams(&self, cls: &Class) -> Arc<TParams> {
+        self.get_class_tparams(cls)
+    }
+
+    fn export(&self, module: ModuleName, name: &Name) -> Option<Type> {
+        if self
+            .exports
+            .get(module)
+            .finding()?
+            .exports(self.exports)
+            .contains_key(name)
+        {
+            Some(Arc::unwrap_or_clone(self.get_from_export(
+                module,
+                None,
+                &KeyExport(name.clone()),
+            )))
+        } else {
+            None
+        }
+    }
+
+    fn infer_expr(&self, x: &Expr) -> Type {
+        self.expr_infer(x, &self.error_swallower())
+    }
+
+    fn instantiate(&self, cls: &Class) -> Type {
+        self.instantiate(cls)
+    }
+
+    fn as_class_type(&self, cls: &Class) -> ClassType {
+        self.as_class_type_unchecked(cls)
+    }
+
+    fn specialize(&self, cls: &Class, targs: Vec<Type>) -> Type {
+        self.specialize(cls, targs, TextRange::default(), &self.error_swallower())
+    }
+
+    fn class_member(&self, cls: &Class, name: &Name) -> Option<Arc<ClassField>> {
+        self.get_class_member(cls, name)
+    }
+
+    fn field_from_current_class_only(&self, cls: &Class, name: &Name) -> Option<Arc<ClassField>> {
+        self.get_field_from_current_class_only(cls, name)
+    }
+
+    fn attr_type(&self, base: &Type, name: &Name) -> Type {
+        self.attr_infer_for_type(
+            base,
+            name,
+            TextRange::default(),
+            &self.error_swallower(),
+            None,
+        )
+    }
+
+    fn enum_members(&self, cls: &Class) -> SmallSet<Lit> {
+        self.get_enum_members(cls)
+    }
+
+    fn enum_value_type(&self, cls: &Class) -> Option<Type> {
+        let metadata = self.get_metadata_for_class(cls);
+        let attr = self.get_enum_or_instance_attribute(
+            &self.as_class_type_unchecked(cls),
+            &metadata,
+            &VALUE_PROP,
+        )?;
+        self.resolve_get_class_attr(
+            &VALUE_PROP,
+            attr,
+            TextRange::default(),
+            &self.error_swallower(),
+            None,
+        )
+        .ok()
+    }
+
+    fn self_param(&self, cls: &Class) -> Param {
+        self.class_self_param(cls, false)
+    }
+
+    fn unions(&self, xs: Vec<Type>) -> Type {
+        self.unions(xs)
+    }
+
+    fn is_subset_eq(&self, got: &Type, want: &Type) -> bool {
+        self.is_subset_eq(got, want)
+    }
+
+    fn for_display(&self, t: Type) -> Type {
+        self.for_display(t)
+    }
+
+    fn add_error(&self, errors: &ErrorCollector, range: TextRange, kind: ErrorKind, msg: String) {
+        self.error(errors, range, ErrorInfo::Kind(kind), msg);
+    }
+}
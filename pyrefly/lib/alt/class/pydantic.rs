@@ -29,6 +29,8 @@ use ruff_text_size::TextRange;
 
 use crate::alt::answers::LookupAnswer;
 use crate::alt::answers_solver::AnswersSolver;
+use crate::alt::class::plugin::ClassSynthesisContext;
+use crate::alt::class::plugin::ClassSynthesisPlugin;
 use crate::alt::solve::TypeFormContext;
 use crate::alt::types::class_metadata::ClassMetadata;
 use crate::alt::types::class_metadata::ClassSynthesizedField;
@@ -86,21 +88,7 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         root_model_type: Type,
         has_strict: bool,
     ) -> ClassSynthesizedField {
-        let (root_requiredness, root_model_type) =
-            if root_model_type.is_any() || matches!(root_model_type, Type::Quantified(_)) {
-                (Required::Optional(None), root_model_type)
-            } else if has_strict {
-                (Required::Required, root_model_type)
-            } else {
-                (Required::Required, Type::any_explicit())
-            };
-        let root_param = Param::Pos(ROOT, root_model_type, root_requiredness);
-        let params = vec![self.class_self_param(cls, false), root_param];
-        let ty = Type::Function(Box::new(Function {
-            signature: Callable::list(ParamList::new(params), Type::None),
-            metadata: FuncMetadata::def(self.module().dupe(), cls.dupe(), dunder::INIT),
-        }));
-        ClassSynthesizedField::new(ty)
+        root_model_init(self, cls, root_model_type, has_strict)
     }
 
     pub fn get_pydantic_root_model_class_field_type(
@@ -108,9 +96,7 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         cls: &Class,
         attr_name: &Name,
     ) -> Option<Type> {
-        if !cls.has_toplevel_qname(ModuleName::pydantic_root_model().as_str(), "RootModel")
-            || *attr_name != dunder::INIT
-        {
+        if !is_root_model_class(cls) || *attr_name != dunder::INIT {
             return None;
         }
         let tparams = self.get_class_tparams(cls);
@@ -373,103 +359,6 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
             .and_then(|(_, ann)| ann.get_type().as_bool())
     }
 
-    pub fn check_pydantic_range_constraints(
-        &self,
-        field_name: &Name,
-        field_ty: &Type,
-        keywords: &DataclassFieldKeywords,
-        range: TextRange,
-        errors: &ErrorCollector,
-    ) {
-        // Note: the subset check here is too conservative when it comes to modeling runtime behavior
-        // we want to check if the bound_val is coercible to the annotation type at runtime.
-        // statically, this could be a challenge, which is why we go with this more conservative approach for now.
-        for (bound_val, label) in [
-            (&keywords.gt, "gt"),
-            (&keywords.lt, "lt"),
-            (&keywords.ge, "ge"),
-            (&keywords.le, "le"),
-        ] {
-            let Some(val) = bound_val else { continue };
-            if !self.is_subset_eq(val, field_ty) {
-                self.error(
-                        errors,
-                        range,
-                        ErrorInfo::Kind(ErrorKind::BadArgumentType),
-                        format!(
-                            "Pydantic `{label}` value is of type `{}` but the field is annotated with `{}`",
-                            self.for_display(val.clone()),
-                            self.for_display(field_ty.clone())
-                        ),
-                    );
-            }
-        }
-        self.check_pydantic_range_default(field_name, keywords, range, errors);
-    }
-
-    fn check_pydantic_range_default(
-        &self,
-        field_name: &Name,
-        keywords: &DataclassFieldKeywords,
-        range: TextRange,
-        errors: &ErrorCollector,
-    ) {
-        fn int_literal_from_type(ty: &Type) -> Option<&LitInt> {
-            // We only currently enforce range constraints for literal defaults, so carve out
-            // the `Literal[int]` case and ignore everything else.
-            match ty {
-                Type::Literal(Lit::Int(lit)) => Some(lit),
-                _ => None,
-            }
-        }
-        let Some(default_ty) = &keywords.default else {
-            return;
-        };
-        let Some(value_lit) = int_literal_from_type(default_ty) else {
-            return;
-        };
-        let emit_violation = |label: &str, constraint_ty: &Type| {
-            let Some(constraint_lit) = int_literal_from_type(constraint_ty) else {
-                return;
-            };
-            let comparison = value_lit.cmp(constraint_lit);
-            let violates = match label {
-                "gt" => !matches!(comparison, std::cmp::Ordering::Greater),
-                "ge" => matches!(comparison, std::cmp::Ordering::Less),
-                "lt" => !matches!(comparison, std::cmp::Ordering::Less),
-                "le" => matches!(comparison, std::cmp::Ordering::Greater),
-                _ => false,
-            };
-            if violates {
-                self.error(
-                    errors,
-                    range,
-                    ErrorInfo::Kind(ErrorKind::BadArgumentType),
-                    format!(
-                        "Default value `{}` violates Pydantic `{}` constraint `{}` for field `{}`",
-                        self.for_display(default_ty.clone()),
-                        label,
-                        self.for_display(constraint_ty.clone()),
-                        field_name
-                    ),
-                );
-            }
-        };
-
-        if let Some(gt) = &keywords.gt {
-            emit_violation("gt", gt);
-        }
-        if let Some(ge) = &keywords.ge {
-            emit_violation("ge", ge);
-        }
-        if let Some(lt) = &keywords.lt {
-            emit_violation("lt", lt);
-        }
-        if let Some(le) = &keywords.le {
-            emit_violation("le", le);
-        }
-    }
-
     /// Extract Pydantic Field metadata from an annotation binding.
     /// This handles the Pydantic-specific pattern where fields can be declared as:
     /// `field: Annotated[some_type, Field(some_keyword=<value>)]`
@@ -500,3 +389,157 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         None
     }
 }
+
+fn root_model_init(
+    ctx: &dyn ClassSynthesisContext,
+    cls: &Class,
+    root_model_type: Type,
+    has_strict: bool,
+) -> ClassSynthesizedField {
+    let (root_requiredness, root_model_type) =
+        if root_model_type.is_any() || matches!(root_model_type, Type::Quantified(_)) {
+            (Required::Optional(None), root_model_type)
+        } else if has_strict {
+            (Required::Required, root_model_type)
+        } else {
+            (Required::Required, Type::any_explicit())
+        };
+    let root_param = Param::Pos(ROOT, root_model_type, root_requiredness);
+    let params = vec![ctx.self_param(cls), root_param];
+    let ty = Type::Function(Box::new(Function {
+        signature: Callable::list(ParamList::new(params), Type::None),
+        metadata: FuncMetadata::def(ctx.module().dupe(), cls.dupe(), dunder::INIT),
+    }));
+    ClassSynthesizedField::new(ty)
+}
+
+/// Pydantic models.
+pub struct PydanticPlugin;
+
+impl ClassSynthesisPlugin for PydanticPlugin {
+    fn name(&self) -> &'static str {
+        "pydantic"
+    }
+
+    fn base_classes(&self) -> &[(&'static str, &'static str)] {
+        &[("pydantic.main", "BaseModel")]
+    }
+
+    fn check_field(
+        &self,
+        ctx: &dyn ClassSynthesisContext,
+        cls: &Class,
+        name: &Name,
+        annotation: &Annotation,
+        keywords: Option<&DataclassFieldKeywords>,
+        range: TextRange,
+        errors: &ErrorCollector,
+    ) {
+        if let Some(keywords) = keywords
+            && ctx.metadata(cls).is_pydantic_base_model()
+        {
+            check_range_constraints(ctx, name, annotation.get_type(), keywords, range, errors);
+        }
+    }
+}
+
+fn is_root_model_class(cls: &Class) -> bool {
+    cls.has_toplevel_qname(ModuleName::pydantic_root_model().as_str(), "RootModel")
+}
+
+fn check_range_constraints(
+    ctx: &dyn ClassSynthesisContext,
+    field_name: &Name,
+    field_ty: &Type,
+    keywords: &DataclassFieldKeywords,
+    range: TextRange,
+    errors: &ErrorCollector,
+) {
+    // Note: the subset check here is too conservative when it comes to modeling runtime behavior
+    // we want to check if the bound_val is coercible to the annotation type at runtime.
+    // statically, this could be a challenge, which is why we go with this more conservative approach for now.
+    for (bound_val, label) in [
+        (&keywords.gt, "gt"),
+        (&keywords.lt, "lt"),
+        (&keywords.ge, "ge"),
+        (&keywords.le, "le"),
+    ] {
+        let Some(val) = bound_val else { continue };
+        if !ctx.is_subset_eq(val, field_ty) {
+            ctx.add_error(
+                errors,
+                range,
+                ErrorKind::BadArgumentType,
+                format!(
+                    "Pydantic `{label}` value is of type `{}` but the field is annotated with `{}`",
+                    ctx.for_display(val.clone()),
+                    ctx.for_display(field_ty.clone())
+                ),
+            );
+        }
+    }
+    check_range_default(ctx, field_name, keywords, range, errors);
+}
+
+fn check_range_default(
+    ctx: &dyn ClassSynthesisContext,
+    field_name: &Name,
+    keywords: &DataclassFieldKeywords,
+    range: TextRange,
+    errors: &ErrorCollector,
+) {
+    fn int_literal_from_type(ty: &Type) -> Option<&LitInt> {
+        // We only currently enforce range constraints for literal defaults, so carve out
+        // the `Literal[int]` case and ignore everything else.
+        match ty {
+            Type::Literal(Lit::Int(lit)) => Some(lit),
+            _ => None,
+        }
+    }
+    let Some(default_ty) = &keywords.default else {
+        return;
+    };
+    let Some(value_lit) = int_literal_from_type(default_ty) else {
+        return;
+    };
+    let emit_violation = |label: &str, constraint_ty: &Type| {
+        let Some(constraint_lit) = int_literal_from_type(constraint_ty) else {
+            return;
+        };
+        let comparison = value_lit.cmp(constraint_lit);
+        let violates = match label {
+            "gt" => !matches!(comparison, std::cmp::Ordering::Greater),
+            "ge" => matches!(comparison, std::cmp::Ordering::Less),
+            "lt" => !matches!(comparison, std::cmp::Ordering::Less),
+            "le" => matches!(comparison, std::cmp::Ordering::Greater),
+            _ => false,
+        };
+        if violates {
+            ctx.add_error(
+                errors,
+                range,
+                ErrorKind::BadArgumentType,
+                format!(
+                    "Default value `{}` violates Pydantic `{}` constraint `{}` for field `{}`",
+                    ctx.for_display(default_ty.clone()),
+                    label,
+                    ctx.for_display(constraint_ty.clone()),
+                    field_name
+                ),
+            );
+        }
+    };
+
+    if let Some(gt) = &keywords.gt {
+        emit_violation("gt", gt);
+    }
+    if let Some(ge) = &keywords.ge {
+        emit_violation("ge", ge);
+    }
+    if let Some(lt) = &keywords.lt {
+        emit_violation("lt", lt);
+    }
+    if let Some(le) = &keywords.le {
+        emit_violation("le", le);
+    }
+}
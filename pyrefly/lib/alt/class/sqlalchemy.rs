@@ -0,0 +1,282 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use std::iter;
+
+use dupe::Dupe;
+use pyrefly_python::dunder;
+use pyrefly_python::module_name::ModuleName;
+use pyrefly_types::callable::Callable;
+use pyrefly_types::callable::FuncMetadata;
+use pyrefly_types::callable::Function;
+use pyrefly_types::callable::Param;
+use pyrefly_types::callable::ParamList;
+use pyrefly_types::callable::Required;
+use pyrefly_types::class::Class;
+use pyrefly_types::types::CalleeKind;
+use pyrefly_types::types::Type;
+use ruff_python_ast::Expr;
+use ruff_python_ast::ExprCall;
+use ruff_python_ast::ExprStringLiteral;
+use ruff_python_ast::name::Name;
+use starlark_map::small_map::SmallMap;
+use starlark_map::small_set::SmallSet;
+
+use crate::alt::class::plugin::ClassSynthesisContext;
+use crate::alt::class::plugin::ClassSynthesisPlugin;
+
+const SQLALCHEMY: &str = "sqlalchemy";
+const MAPPED: &str = "Mapped";
+const TYPE_ENGINE: &str = "TypeEngine";
+const MAPPED_COLUMN: &str = "mapped_column";
+const RELATIONSHIP: &str = "relationship";
+const NULLABLE: &str = "nullable";
+const PRIMARY_KEY: &str = "primary_key";
+const USELIST: &str = "uselist";
+const COLLECTION_CLASS: &str = "collection_class";
+
+/// SQLAlchemy 2.0 declarative models, i.e. subclasses of `DeclarativeBase` and classes decorated
+/// with `registry.mapped`.
+pub struct SqlAlchemyPlugin;
+
+impl ClassSynthesisPlugin for SqlAlchemyPlugin {
+    fn name(&self) -> &'static str {
+        "sqlalchemy"
+    }
+
+    fn base_classes(&self) -> &[(&'static str, &'static str)] {
+        &[
+            ("sqlalchemy.orm.decl_api", "DeclarativeBase"),
+            ("sqlalchemy.orm.decl_api", "DeclarativeBaseNoMeta"),
+        ]
+    }
+
+    fn decorators(&self) -> &[(&'static str, &'static str)] {
+        &[("sqlalchemy.orm.decl_api", "mapped")]
+    }
+
+    /// The default constructor of a model accepts any of its mapped attributes as keyword
+    /// arguments.
+    fn init_method(&self, ctx: &dyn ClassSynthesisContext, cls: &Class) -> Option<Type> {
+        // `MappedAsDataclass` models get a dataclass `__init__` instead.
+        if is_sqlalchemy_class(cls)
+            || ctx.metadata(cls).dataclass_metadata().is_some()
+            || has_custom_init(ctx, cls)
+        {
+            return None;
+        }
+        let params = iter::once(ctx.self_param(cls))
+            .chain(
+                mapped_attributes(ctx, cls)
+                    .into_iter()
+                    .map(|(name, ty)| Param::KwOnly(name, ty, Required::Optional(None))),
+            )
+            .collect();
+        Some(Type::Function(Box::new(Function {
+            signature: Callable::list(ParamList::new(params), Type::None),
+            metadata: FuncMetadata::def(ctx.module().dupe(), cls.dupe(), dunder::INIT),
+        })))
+    }
+
+    /// Refine the type held by a `mapped_column()` or `relationship()` attribute using the
+    /// arguments of the call, since they always return `MappedColumn[Any]` and
+    /// `Relationship[Any]`.
+    fn field_type(
+        &self,
+        ctx: &dyn ClassSynthesisContext,
+        cls: &Class,
+        _name: &Name,
+        ty: &Type,
+        initial_value: Option<&Expr>,
+    ) -> Option<Type> {
+        let call = initial_value?.as_call_expr()?;
+        let Type::ClassType(mapped) = ty else {
+            return None;
+        };
+        let [held_type] = mapped.targs().as_slice() else {
+            return None;
+        };
+        if !is_mapped(ctx, mapped.class_object()) {
+            return None;
+        }
+        let held_type = match orm_constructor(ctx, call)?.as_str() {
+            MAPPED_COLUMN if held_type.is_any() => {
+                let python_type = call
+                    .arguments
+                    .args
+                    .iter()
+                    .find_map(|arg| python_type_of_sql_type(ctx, &ctx.infer_expr(arg)))?;
+                // Columns are nullable by default, unless they are part of the primary key.
+                let nullable = bool_keyword(call, NULLABLE)
+                    .unwrap_or_else(|| bool_keyword(call, PRIMARY_KEY) != Some(true));
+                if nullable {
+                    ctx.union(python_type, Type::None)
+                } else {
+                    python_type
+                }
+            }
+            // An explicit `nullable=True` makes the annotated type optional.
+            MAPPED_COLUMN if bool_keyword(call, NULLABLE) == Some(true) => {
+                ctx.union(held_type.clone(), Type::None)
+            }
+            RELATIONSHIP if held_type.is_any() => {
+                let target = resolve_target(ctx, cls, call.arguments.args.first()?)?;
+                let collection_class = keyword(call, COLLECTION_CLASS).map(|e| ctx.infer_expr(e));
+                match (bool_keyword(call, USELIST), collection_class) {
+                    (Some(false), _) => ctx.union(target, Type::None),
+                    (_, Some(Type::ClassDef(c))) if c.has_toplevel_qname("builtins", "set") => {
+                        ctx.stdlib().set(target).to_type()
+                    }
+                    (Some(true), _) | (_, Some(_)) => ctx.stdlib().list(target).to_type(),
+                    // Whether this is a collection depends on the foreign keys.
+                    (None, None) => return None,
+                }
+            }
+            _ => return None,
+        };
+        Some(ctx.specialize(mapped.class_object(), vec![held_type]))
+    }
+}
+
+fn is_sqlalchemy_class(cls: &Class) -> bool {
+    cls.module_name().first_component().as_str() == SQLALCHEMY
+}
+
+/// Whether `cls` is `Mapped`, or a subclass like `MappedColumn`.
+fn is_mapped(ctx: &dyn ClassSynthesisContext, cls: &Class) -> bool {
+    let orm_base = ModuleName::sqlalchemy_orm_base();
+    cls.has_toplevel_qname(orm_base.as_str(), MAPPED)
+        || ctx
+            .mro(cls)
+            .ancestors_no_object()
+            .iter()
+            .any(|ancestor| ancestor.has_qname(orm_base.as_str(), MAPPED))
+}
+
+/// Whether one of the user-defined ancestors of `cls` defines `__init__`, which SQLAlchemy uses
+/// instead of its default constructor.
+fn has_custom_init(ctx: &dyn ClassSynthesisContext, cls: &Class) -> bool {
+    ctx.mro(cls)
+        .ancestors_no_object()
+        .iter()
+        .map(|ancestor| ancestor.class_object())
+        .any(|ancestor| !is_sqlalchemy_class(ancestor) && ancestor.contains(&dunder::INIT))
+}
+
+/// The mapped attributes of `cls` and its ancestors, with the types they hold on an instance.
+fn mapped_attributes(ctx: &dyn ClassSynthesisContext, cls: &Class) -> SmallMap<Name, Type> {
+    let mro = ctx.mro(cls);
+    let mut attributes = SmallMap::new();
+    // Start from the most distant ancestor, so that subclasses override the types of attributes.
+    let classes = mro
+        .ancestors_no_object()
+        .iter()
+        .rev()
+        .map(|ancestor| ancestor.class_object())
+        .chain(iter::once(cls));
+    for c in classes {
+        for name in c.fields() {
+            if let Some(field) = ctx.field_from_current_class_only(c, name)
+                && let Type::ClassType(mapped) = field.ty()
+                && is_mapped(ctx, mapped.class_object())
+            {
+                let held_type = mapped
+                    .targs()
+                    .as_slice()
+                    .first()
+                    .cloned()
+                    .unwrap_or_else(Type::any_implicit);
+                attributes.insert(name.clone(), held_type);
+            }
+        }
+    }
+    attributes
+}
+
+/// If `call` calls `mapped_column` or `relationship`, the name of the function.
+fn orm_constructor(ctx: &dyn ClassSynthesisContext, call: &ExprCall) -> Option<Name> {
+    match ctx.infer_expr(&call.func).callee_kind()? {
+        CalleeKind::Function(kind)
+            if kind.module_name() == ModuleName::sqlalchemy_orm_constructors() =>
+        {
+            Some(kind.function_name().into_owned())
+        }
+        _ => None,
+    }
+}
+
+/// The Python type of the values of a SQL type like `Integer` or `String(50)`.
+fn python_type_of_sql_type(ctx: &dyn ClassSynthesisContext, ty: &Type) -> Option<Type> {
+    let cls = match ty {
+        Type::ClassDef(cls) => cls,
+        Type::ClassType(cls) => cls.class_object(),
+        _ => return None,
+    };
+    let type_api = ModuleName::sqlalchemy_sql_type_api();
+    let mro = ctx.mro(cls);
+    let type_engine = mro
+        .ancestors_no_object()
+        .iter()
+        .find(|ancestor| ancestor.has_qname(type_api.as_str(), TYPE_ENGINE))?;
+    let python_type = type_engine.targs().as_slice().first()?;
+    // We don't substitute the type arguments of generic SQL types like `ARRAY`.
+    if python_type.any(|t| matches!(t, Type::Quantified(_))) {
+        None
+    } else {
+        Some(python_type.clone())
+    }
+}
+
+/// The model a `relationship()` refers to, which may be a class or the name of one, optionally
+/// qualified by its module.
+///
+/// SQLAlchemy looks a bare name up among every model in the same registry, which we can't
+/// enumerate. Instead we look in the module defining `cls`, then in the modules defining its
+/// user-defined ancestors, such as its declarative base.
+fn resolve_target(ctx: &dyn ClassSynthesisContext, cls: &Class, target: &Expr) -> Option<Type> {
+    let ty = match target {
+        Expr::StringLiteral(ExprStringLiteral { value, .. }) => match value
+            .to_str()
+            .rsplit_once('.')
+        {
+            Some((module, name)) => ctx.export(ModuleName::from_str(module), &Name::new(name))?,
+            None => {
+                let name = Name::new(value.to_str());
+                let mro = ctx.mro(cls);
+                let mut modules = SmallSet::new();
+                iter::once(cls)
+                    .chain(
+                        mro.ancestors_no_object()
+                            .iter()
+                            .map(|ancestor| ancestor.class_object()),
+                    )
+                    .filter(|c| !is_sqlalchemy_class(c) && modules.insert(c.module_name()))
+                    .find_map(|c| ctx.export(c.module_name(), &name))?
+            }
+        },
+        _ => ctx.infer_expr(target),
+    };
+    match ty {
+        Type::ClassDef(model) => Some(ctx.instantiate(&model)),
+        _ => None,
+    }
+}
+
+fn keyword<'a>(call: &'a ExprCall, name: &str) -> Option<&'a Expr> {
+    call.arguments
+        .keywords
+        .iter()
+        .find(|keyword| keyword.arg.as_ref().is_some_and(|arg| arg.as_str() == name))
+        .map(|keyword| &keyword.value)
+}
+
+fn bool_keyword(call: &ExprCall, name: &str) -> Option<bool> {
+    match keyword(call, name)? {
+        Expr::BooleanLiteral(bool_lit) => Some(bool_lit.value),
+        _ => None,
+    }
+}
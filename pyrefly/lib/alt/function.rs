@@ -41,6 +41,7 @@ use crate::alt::answers::LookupAnswer;
 use crate::alt::answers_solver::AnswersSolver;
 use crate::alt::call::CallStyle;
 use crate::alt::callable::CallArg;
+use crate::alt::pytest::PytestParamHints;
 use crate::alt::types::decorated_function::DecoratedFunction;
 use crate::alt::types::decorated_function::Decorator;
 use crate::alt::types::decorated_function::SpecialDecorator;
@@ -92,6 +93,14 @@ fn is_class_property_decorator_type(ty: &Type) -> bool {
     }
 }
 
+/// The type of a parameter that can be passed positionally.
+fn positional_param_type(param: &Param) -> Option<&Type> {
+    match param {
+        Param::PosOnly(_, ty, _) | Param::Pos(_, ty, _) => Some(ty),
+        _ => None,
+    }
+}
+
 struct ParentParamHints {
     posonly: VecDeque<Type>,
     positional: VecDeque<Type>,
@@ -370,7 +379,7 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         def: &StmtFunctionDef,
         stub_or_impl: FunctionStubOrImpl,
         class_key: Option<&Idx<KeyClass>>,
-        decorators: &[Idx<KeyDecorator>],
+        decorator_keys: &[Idx<KeyDecorator>],
         legacy_tparams: &[Idx<KeyLegacyTypeParam>],
         module_style: ModuleStyle,
         errors: &ErrorCollector,
@@ -393,7 +402,12 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
             ..Default::default()
         };
         let mut found_class_property = false;
-        let decorators = Box::from_iter(decorators.iter().filter_map(|k| {
+        let decorators = Box::from_iter(decorator_keys.iter().filter_map(|k| {
+            // `@pytest.fixture` is not applied, so the fixture keeps its signature.
+            if self.is_pytest_fixture_decorator(*k, &def.name.id) {
+                flags.is_pytest_fixture = true;
+                return None;
+            }
             let decorator = self.get_idx(*k);
             let range = self.bindings().idx_to_key(*k).range();
             let keep = if let Some(special_decorator) = self.get_special_decorator(&decorator) {
@@ -436,6 +450,9 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
             None
         };
 
+        let mut pytest_param_hints =
+            self.pytest_param_hints(def, defining_cls.as_ref(), decorator_keys, errors);
+
         if stub_or_impl == FunctionStubOrImpl::Stub {
             flags.lacks_implementation = true;
         }
@@ -457,6 +474,7 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
             &mut self_type,
             &mut decorator_param_hints,
             &mut parent_param_hints,
+            &mut pytest_param_hints,
             errors,
         );
         let mut tparams = self.scoped_type_params(def.type_params.as_deref(), errors);
@@ -466,6 +484,9 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         tparams.extend(legacy_tparams);
         let tparams = self.validated_tparams(def.range, tparams, TParamsSource::Function, errors);
 
+        self.check_singledispatch_registrations(def, decorator_keys, &params, errors);
+        self.check_pytest_parametrize(decorator_keys, &params, errors);
+
         let kind =
             FunctionKind::from_name(self.module().dupe(), defining_cls.clone(), &def.name.id);
         let metadata = FuncMetadata { kind, flags };
@@ -580,6 +601,97 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         Arc::new(ty)
     }
 
+    /// Check the implementations registered with `@f.register` or `@f.register(cls)`, where `f`
+    /// is a `functools.singledispatch` function. The implementation dispatches on the class passed
+    /// to `register`, or else on the annotation of its first parameter, and that class must be
+    /// accepted by the first parameter of `f`.
+    fn check_singledispatch_registrations(
+        &self,
+        def: &StmtFunctionDef,
+        decorators: &[Idx<KeyDecorator>],
+        params: &[Param],
+        errors: &ErrorCollector,
+    ) {
+        for k in decorators {
+            let (register, dispatch_cls) = match &self.bindings().get(*k).expr {
+                Expr::Attribute(register) => (register, None),
+                Expr::Call(call)
+                    if let Expr::Attribute(register) = &*call.func
+                        && let [cls] = &*call.arguments.args
+                        && call.arguments.keywords.is_empty() =>
+                {
+                    (register, Some(cls))
+                }
+                _ => continue,
+            };
+            if register.attr.as_str() != "register" {
+                continue;
+            }
+            let base = self.expr_infer(&register.value, &self.error_swallower());
+            let Type::Function(base_func) = &base else {
+                continue;
+            };
+            if !base
+                .functools_wrapper()
+                .is_some_and(|cls| cls.has_qname("functools", "_SingleDispatchCallable"))
+            {
+                continue;
+            }
+            let range = self.bindings().idx_to_key(*k).range();
+            let impl_param = params.first().and_then(positional_param_type);
+            let dispatch_ty = if let Some(cls) = dispatch_cls {
+                let Some(dispatch_ty) = self.untype_opt(
+                    self.expr_infer(cls, &self.error_swallower()),
+                    cls.range(),
+                    &self.error_swallower(),
+                ) else {
+                    continue;
+                };
+                if let Some(impl_param) = impl_param {
+                    self.check_type(&dispatch_ty, impl_param, range, errors, &|| {
+                        TypeCheckContext::of_kind(TypeCheckKind::SingleDispatchRegistration(
+                            def.name.id.clone(),
+                        ))
+                    });
+                }
+                dispatch_ty
+            } else {
+                let is_annotated = def
+                    .parameters
+                    .posonlyargs
+                    .iter()
+                    .chain(&def.parameters.args)
+                    .next()
+                    .is_some_and(|p| p.parameter.annotation.is_some());
+                match impl_param {
+                    Some(impl_param) if is_annotated => impl_param.clone(),
+                    _ => {
+                        self.error(
+                            errors,
+                            range,
+                            ErrorInfo::Kind(ErrorKind::InvalidArgument),
+                            format!(
+                                "`{}` is registered without a dispatch class, so its first parameter needs an annotation",
+                                def.name
+                            ),
+                        );
+                        continue;
+                    }
+                }
+            };
+            if let Params::List(base_params) = &base_func.signature.params
+                && let Some(base_param) =
+                    base_params.items().first().and_then(positional_param_type)
+            {
+                self.check_type(&dispatch_ty, base_param, range, errors, &|| {
+                    TypeCheckContext::of_kind(TypeCheckKind::SingleDispatchRegistration(
+                        base_func.metadata.kind.function_name().into_owned(),
+                    ))
+                });
+            }
+        }
+    }
+
     pub fn get_special_decorator(
         &'a self,
         decorator: &'a Decorator,
@@ -795,6 +907,7 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         self_type: &mut Option<Type>,
         decorator_param_hints: &mut Option<DecoratorParamHints>,
         parent_param_hints: &mut Option<ParentParamHints>,
+        pytest_param_hints: &mut Option<PytestParamHints>,
         errors: &ErrorCollector,
     ) -> (Vec<Param>, Option<Quantified>) {
         let mut paramspec_args = None;
@@ -811,12 +924,15 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                     .as_mut()
                     .and_then(|hint| hint.take_posonly())
             };
+            let pytest_hint = pytest_param_hints
+                .as_mut()
+                .and_then(|hint| hint.take(&x.parameter.name));
             let (ty, required) = self.get_param_type_and_requiredness(
                 &x.parameter.name,
                 x.default.as_deref(),
                 stub_or_impl,
                 self_type,
-                decorator_hint.or(parent_hint),
+                decorator_hint.or(parent_hint).or(pytest_hint),
                 errors,
             );
             Param::PosOnly(Some(x.parameter.name.id.clone()), ty, required)
@@ -838,12 +954,15 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                     .as_mut()
                     .and_then(|hint| hint.take_positional())
             };
+            let pytest_hint = pytest_param_hints
+                .as_mut()
+                .and_then(|hint| hint.take(&x.parameter.name));
             let (ty, required) = self.get_param_type_and_requiredness(
                 &x.parameter.name,
                 x.default.as_deref(),
                 stub_or_impl,
                 self_type,
-                decorator_hint.or(parent_hint),
+                decorator_hint.or(parent_hint).or(pytest_hint),
                 errors,
             );
 
@@ -903,12 +1022,15 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
             let parent_hint = parent_param_hints
                 .as_mut()
                 .and_then(|hint| hint.take_kwonly(&x.parameter.name));
+            let pytest_hint = pytest_param_hints
+                .as_mut()
+                .and_then(|hint| hint.take(&x.parameter.name));
             let (ty, required) = self.get_param_type_and_requiredness(
                 &x.parameter.name,
                 x.default.as_deref(),
                 stub_or_impl,
                 self_type,
-                parent_hint,
+                parent_hint.or(pytest_hint),
                 errors,
             );
             Param::KwOnly(x.parameter.name.id.clone(), ty, required)
@@ -1182,6 +1304,18 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                     }
                 }
                 Type::ClassType(cls) if cls.has_qname("functools", "_Wrapped") => decoratee.clone(),
+                // Caching and dispatching wrappers keep the signature of the function they wrap.
+                Type::ClassType(cls)
+                    if (cls.has_qname("functools", "_lru_cache_wrapper")
+                        || cls.has_qname("functools", "_SingleDispatchCallable"))
+                        && decoratee_arg.has_toplevel_func_metadata() =>
+                {
+                    let mut ty = decoratee_arg.clone();
+                    ty.transform_toplevel_func_metadata(|m| {
+                        m.flags.functools_wrapper = Some(cls.clone());
+                    });
+                    ty
+                }
                 returned_ty => returned_ty,
             };
 
@@ -17,6 +17,7 @@ pub mod function;
 pub mod narrow;
 pub mod operators;
 pub mod overload;
+pub mod pytest;
 pub mod solve;
 pub mod special_calls;
 pub mod specials;
@@ -5,6 +5,11 @@
  * LICENSE file in the root directory of this source tree.
  */
 
+use std::fmt;
+use std::fmt::Display;
+
+use dupe::Dupe;
+use itertools::Itertools;
 use num_traits::ToPrimitive;
 use pyrefly_python::ast::Ast;
 use pyrefly_types::class::Class;
@@ -21,6 +26,8 @@ use ruff_python_ast::name::Name;
 use ruff_text_size::Ranged;
 use ruff_text_size::TextRange;
 use ruff_text_size::TextSize;
+use starlark_map::Hashed;
+use starlark_map::small_set::SmallSet;
 use vec1::Vec1;
 
 use crate::alt::answers::LookupAnswer;
@@ -28,11 +35,15 @@ use crate::alt::answers_solver::AnswersSolver;
 use crate::alt::call::CallTargetLookup;
 use crate::alt::callable::CallArg;
 use crate::alt::callable::CallKeyword;
+use crate::binding::binding::BindingExpect;
+use crate::binding::binding::KeyExpect;
 use crate::binding::narrow::AtomicNarrowOp;
 use crate::binding::narrow::FacetOrigin;
 use crate::binding::narrow::FacetSubject;
 use crate::binding::narrow::NarrowOp;
+use crate::config::error_kind::ErrorKind;
 use crate::error::collector::ErrorCollector;
+use crate::error::context::ErrorInfo;
 use crate::error::style::ErrorStyle;
 use crate::types::callable::FunctionKind;
 use crate::types::class::ClassType;
@@ -44,6 +55,7 @@ use crate::types::tuple::Tuple;
 use crate::types::type_info::TypeInfo;
 use crate::types::types::CalleeKind;
 use crate::types::types::Type;
+use crate::types::types::Union;
 
 /// Beyond this size, don't try and narrow an enum.
 ///
@@ -52,6 +64,34 @@ use crate::types::types::Type;
 /// is very high.
 const NARROW_ENUM_LIMIT: usize = 100;
 
+/// A `case` pattern that a `match` statement doesn't handle.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum MissingCase {
+    /// A pattern that doesn't refer to a class, such as `None` or `"a"`.
+    Value(String),
+    /// A member of an enum, such as `Color.RED`.
+    EnumMember(Class, Name),
+    /// A class pattern that matches every instance of a class, such as `Foo()`.
+    Class(Class),
+}
+
+impl MissingCase {
+    /// The pattern, referring to a class by what `class_ref` gives for it.
+    pub fn pattern(&self, class_ref: impl Fn(&Class) -> String) -> String {
+        match self {
+            Self::Value(x) => x.clone(),
+            Self::EnumMember(cls, member) => format!("{}.{member}", class_ref(cls)),
+            Self::Class(cls) => format!("{}()", class_ref(cls)),
+        }
+    }
+}
+
+impl Display for MissingCase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.pattern(|cls| cls.name().to_string()))
+    }
+}
+
 impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
     // Get the union of all members of an enum, minus the specified member
     fn subtract_enum_member(&self, cls: &ClassType, name: &Name) -> Type {
@@ -1009,6 +1049,133 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         }
     }
 
+    /// Whether a `match` over a value of this type should handle every case: true for enums,
+    /// literals, bools, `None` and final classes, and for unions of those. A union with a class
+    /// that may have unexpected subclasses isn't sealed, so is never reported.
+    fn is_finite_match_subject(&self, ty: &Type) -> bool {
+        let is_finite = |ty: &Type| match ty {
+            Type::Literal(_) | Type::None => true,
+            Type::ClassType(cls) => {
+                cls.is_builtin("bool")
+                    || self.get_enum_from_class(cls.class_object()).is_some()
+                    || self.get_metadata_for_class(cls.class_object()).is_final()
+            }
+            _ => false,
+        };
+        match ty {
+            Type::Union(box Union { members, .. }) => members.iter().all(is_finite),
+            Type::ClassType(_) => is_finite(ty),
+            _ => false,
+        }
+    }
+
+    /// The `case` patterns that would match the values of `ty` that a `match` didn't handle,
+    /// or `None` if we can't write them.
+    fn missing_match_cases(&self, ty: &Type) -> Option<Vec<MissingCase>> {
+        let mut cases = SmallSet::new();
+        let mut complete = true;
+        self.map_over_union(ty, |t| match t {
+            Type::Literal(Lit::Enum(lit_enum)) => {
+                cases.insert(MissingCase::EnumMember(
+                    lit_enum.class.class_object().dupe(),
+                    lit_enum.member.clone(),
+                ));
+            }
+            Type::Literal(lit) => {
+                cases.insert(MissingCase::Value(lit.to_string()));
+            }
+            Type::None => {
+                cases.insert(MissingCase::Value("None".to_owned()));
+            }
+            Type::ClassType(cls) if cls.is_builtin("bool") => {
+                cases.insert(MissingCase::Value("True".to_owned()));
+                cases.insert(MissingCase::Value("False".to_owned()));
+            }
+            Type::ClassType(cls) => match self.get_enum_from_class(cls.class_object()) {
+                Some(e) if !e.is_flag => {
+                    if cls.class_object().fields().len() > NARROW_ENUM_LIMIT {
+                        complete = false;
+                    }
+                    for member in self.get_enum_members(cls.class_object()) {
+                        match member {
+                            Lit::Enum(lit_enum) => cases.insert(MissingCase::EnumMember(
+                                lit_enum.class.class_object().dupe(),
+                                lit_enum.member,
+                            )),
+                            lit => cases.insert(MissingCase::Value(lit.to_string())),
+                        };
+                    }
+                }
+                _ => {
+                    cases.insert(MissingCase::Class(cls.class_object().dupe()));
+                }
+            },
+            _ => complete = false,
+        });
+        if complete {
+            Some(cases.into_iter().collect())
+        } else {
+            None
+        }
+    }
+
+    /// The cases a `match` without an irrefutable case doesn't handle, where `narrowing` is the
+    /// negation of all its cases. Empty if it is exhaustive, or `None` if we can't tell.
+    fn unhandled_match_cases(
+        &self,
+        subject: &TypeInfo,
+        narrowing: &NarrowOp,
+        subject_range: TextRange,
+    ) -> Option<Vec<MissingCase>> {
+        if !self.is_finite_match_subject(subject.ty()) {
+            return None;
+        }
+        // Any errors from narrowing are reported where the narrowed subject is used.
+        let remaining = self.narrow(subject, narrowing, subject_range, &self.error_swallower());
+        self.missing_match_cases(remaining.ty())
+    }
+
+    /// Check that a `match` without an irrefutable case handles every value of its subject,
+    /// where `narrowing` is the negation of all its cases.
+    pub fn check_match_exhaustive(
+        &self,
+        subject: &TypeInfo,
+        narrowing: &NarrowOp,
+        subject_range: TextRange,
+        errors: &ErrorCollector,
+    ) {
+        if let Some(cases) = self.unhandled_match_cases(subject, narrowing, subject_range)
+            && !cases.is_empty()
+        {
+            self.error(
+                errors,
+                subject_range,
+                ErrorInfo::Kind(ErrorKind::NonExhaustiveMatch),
+                format!(
+                    "Match is not exhaustive, missing {} {}",
+                    if cases.len() == 1 { "case" } else { "cases" },
+                    cases.iter().map(|x| format!("`{x}`")).join(", ")
+                ),
+            );
+        }
+    }
+
+    /// The `case` patterns the `match` statement at `match_range` is missing, for the quick fix
+    /// that adds them.
+    pub fn missing_cases_of_match(&self, match_range: TextRange) -> Option<Vec<MissingCase>> {
+        let idx = self
+            .bindings()
+            .key_to_idx_hashed_opt(Hashed::new(&KeyExpect(match_range)))?;
+        match self.bindings().get(idx) {
+            BindingExpect::MatchExhaustive {
+                subject,
+                narrowing,
+                subject_range,
+            } => self.unhandled_match_cases(&self.get_idx(*subject), narrowing, *subject_range),
+            _ => None,
+        }
+    }
+
     /// We only narrow `x.get("key")` if `x` resolves to a `dict`
     fn supports_dict_get_subject(
         &self,
@@ -0,0 +1,401 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Types for the parameters of pytest tests and fixtures. Pytest passes each parameter the
+//! value of the fixture with the same name, so an unannotated parameter gets the type of that
+//! value instead of `Any`.
+
+use pyrefly_graph::index::Idx;
+use pyrefly_python::module_name::ModuleName;
+use pyrefly_python::module_path::ModulePath;
+use pyrefly_types::types::CalleeKind;
+use ruff_python_ast::Expr;
+use ruff_python_ast::Identifier;
+use ruff_python_ast::StmtFunctionDef;
+use ruff_python_ast::name::Name;
+use ruff_text_size::Ranged;
+use ruff_text_size::TextRange;
+use starlark_map::small_map::SmallMap;
+use starlark_map::small_set::SmallSet;
+
+use crate::alt::answers::LookupAnswer;
+use crate::alt::answers_solver::AnswersSolver;
+use crate::alt::unwrap::HintRef;
+use crate::binding::binding::KeyClassMetadata;
+use crate::binding::binding::KeyDecorator;
+use crate::binding::binding::KeyExport;
+use crate::config::error_kind::ErrorKind;
+use crate::error::collector::ErrorCollector;
+use crate::error::context::ErrorInfo;
+use crate::error::context::TypeCheckContext;
+use crate::error::context::TypeCheckKind;
+use crate::export::exports::ExportLocation;
+use crate::export::pytest::is_test_file;
+use crate::export::pytest::is_test_function;
+use crate::export::pytest::parse_fixture;
+use crate::export::pytest::parse_mark;
+use crate::export::pytest::parse_parametrize;
+use crate::export::special::SpecialExport;
+use crate::types::callable::FunctionKind;
+use crate::types::callable::Param;
+use crate::types::class::Class;
+use crate::types::types::Type;
+
+/// The fixture values passed to the parameters of a pytest test or fixture.
+pub struct PytestParamHints(SmallMap<Name, Type>);
+
+impl PytestParamHints {
+    pub fn take(&mut self, name: &Identifier) -> Option<Type> {
+        self.0.shift_remove(&name.id)
+    }
+}
+
+impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
+    fn decorator_exprs(&self, decorators: &[Idx<KeyDecorator>]) -> Vec<&Expr> {
+        decorators
+            .iter()
+            .map(|k| &self.bindings().get(*k).expr)
+            .collect()
+    }
+
+    /// If `e` is `@pytest.fixture` or `@pytest.fixture(...)`, the name the fixture is requested
+    /// by. The decorator is matched on the AST, then its type is checked to rule out other
+    /// functions called `fixture`.
+    fn pytest_fixture_name(&self, e: &Expr, func: &Name) -> Option<Name> {
+        let name = parse_fixture(e, func)?;
+        let callee = match e {
+            Expr::Call(call) => &*call.func,
+            _ => e,
+        };
+        match self
+            .expr_infer(callee, &self.error_swallower())
+            .callee_kind()
+        {
+            Some(CalleeKind::Function(FunctionKind::Def(func)))
+                if func.cls.is_none()
+                    && func.name == "fixture"
+                    && SpecialExport::PytestFixture.defined_in(func.module.name()) =>
+            {
+                Some(name)
+            }
+            _ => None,
+        }
+    }
+
+    /// Is this function decorated with `@pytest.fixture`?
+    pub fn is_pytest_fixture_decorator(&self, decorator: Idx<KeyDecorator>, func: &Name) -> bool {
+        self.pytest_fixture_name(&self.bindings().get(decorator).expr, func)
+            .is_some()
+    }
+
+    /// If `def` is a pytest test or fixture, resolve the fixtures requested by its parameters,
+    /// reporting the ones that can't be found. Parameters with defaults are not fixtures, and
+    /// neither are the ones given values by `@pytest.mark.parametrize`.
+    pub fn pytest_param_hints(
+        &self,
+        def: &StmtFunctionDef,
+        defining_cls: Option<&Class>,
+        decorators: &[Idx<KeyDecorator>],
+        errors: &ErrorCollector,
+    ) -> Option<PytestParamHints> {
+        let decorators = self.decorator_exprs(decorators);
+        let fixture_name = decorators
+            .iter()
+            .find_map(|x| self.pytest_fixture_name(x, &def.name.id));
+        if fixture_name.is_none()
+            && !(is_test_file(self.module().path())
+                && is_test_function(&def.name.id, defining_cls.map(|cls| cls.name())))
+        {
+            return None;
+        }
+        // Other decorators, like `unittest.mock.patch` or `hypothesis.given`, may supply some of
+        // the arguments themselves.
+        if decorators
+            .iter()
+            .any(|x| parse_mark(x).is_none() && self.pytest_fixture_name(x, &def.name.id).is_none())
+        {
+            return None;
+        }
+        let mut parametrized: SmallSet<Name> = decorators
+            .iter()
+            .filter_map(|x| parse_parametrize(x))
+            .flat_map(|x| x.names)
+            .collect();
+        if let Some(cls) = defining_cls {
+            let metadata = self
+                .bindings()
+                .get(self.bindings().key_to_idx(&KeyClassMetadata(cls.index())));
+            parametrized.extend(
+                self.decorator_exprs(&metadata.decorators)
+                    .into_iter()
+                    .filter_map(parse_parametrize)
+                    .flat_map(|x| x.names),
+            );
+        }
+        let params = def
+            .parameters
+            .posonlyargs
+            .iter()
+            .chain(&def.parameters.args)
+            .chain(&def.parameters.kwonlyargs)
+            // Skip `self`.
+            .skip(if defining_cls.is_some() { 1 } else { 0 });
+        let mut hints = SmallMap::new();
+        for x in params {
+            let name = &x.parameter.name;
+            if x.default.is_some() || parametrized.contains(&name.id) {
+                continue;
+            }
+            match self.resolve_fixture(&name.id, fixture_name.as_ref(), defining_cls) {
+                Some(ty) => {
+                    hints.insert(name.id.clone(), ty);
+                }
+                None => {
+                    self.error(
+                        errors,
+                        name.range,
+                        ErrorInfo::Kind(ErrorKind::UnknownFixture),
+                        format!("Could not find fixture `{}`", name.id),
+                    );
+                }
+            }
+        }
+        Some(PytestParamHints(hints))
+    }
+
+    /// Find the value of the fixture `name`, following pytest's scoping rules: fixtures in the
+    /// enclosing class, then in the module, then in the `conftest.py` of each enclosing package,
+    /// then in the plugin modules loaded with `pytest_plugins`, then pytest's builtin fixtures.
+    /// A fixture requesting a fixture with its own name gets the one it overrides.
+    fn resolve_fixture(
+        &self,
+        name: &Name,
+        requester: Option<&Name>,
+        defining_cls: Option<&Class>,
+    ) -> Option<Type> {
+        let overrides = requester == Some(name);
+        if let Some(cls) = defining_cls
+            && !overrides
+            && let Some(field) = self.get_non_synthesized_class_member(cls, name)
+            && field.ty().is_pytest_fixture()
+        {
+            return Some(self.fixture_value(&field.ty()));
+        }
+        let module = self.module().name();
+        if !(overrides && defining_cls.is_none())
+            && let Some(ty) = self.module_fixture(module, Some(self.module().path()), name)
+        {
+            return Some(ty);
+        }
+        let mut plugins = self
+            .exports
+            .get(module)
+            .finding()
+            .map(|x| x.pytest_plugins().to_vec())
+            .unwrap_or_default();
+        let mut package = module.parent();
+        loop {
+            let conftest = match package {
+                Some(package) => package.append(&Name::new_static("conftest")),
+                None => ModuleName::from_str("conftest"),
+            };
+            if conftest != module
+                && let Some(exports) = self.exports.get(conftest).finding()
+            {
+                if let Some(ty) = self.module_fixture(conftest, None, name) {
+                    return Some(ty);
+                }
+                plugins.extend_from_slice(exports.pytest_plugins());
+            }
+            match package {
+                Some(x) => package = x.parent(),
+                None => break,
+            }
+        }
+        plugins
+            .into_iter()
+            .find_map(|plugin| self.module_fixture(plugin, None, name))
+            .or_else(|| self.builtin_fixture(name))
+    }
+
+    /// Find a fixture defined at the top level of `module`, or imported into it.
+    fn module_fixture(
+        &self,
+        module: ModuleName,
+        path: Option<&ModulePath>,
+        name: &Name,
+    ) -> Option<Type> {
+        let exports = self.exports.get(module).finding()?;
+        let module_exports = exports.exports(self.exports);
+        if let Some(func) = exports.pytest_fixtures().get(name) {
+            return self.exported_fixture(module, path, func, &module_exports);
+        }
+        module_exports.iter().find_map(|(local, location)| {
+            let ExportLocation::OtherModule(from, original) = location else {
+                return None;
+            };
+            let func = original.as_ref().unwrap_or(local);
+            let from_exports = self.exports.get(*from).finding()?;
+            if from_exports.pytest_fixtures().get(name) != Some(func) {
+                return None;
+            }
+            self.exported_fixture(*from, None, func, &from_exports.exports(self.exports))
+        })
+    }
+
+    fn exported_fixture(
+        &self,
+        module: ModuleName,
+        path: Option<&ModulePath>,
+        func: &Name,
+        exports: &SmallMap<Name, ExportLocation>,
+    ) -> Option<Type> {
+        if !exports.contains_key(func) {
+            return None;
+        }
+        // The table of fixtures is built from the AST, so check the decorator really was
+        // `pytest.fixture`.
+        let ty = self.get_from_export(module, path, &KeyExport(func.clone()));
+        ty.is_pytest_fixture().then(|| self.fixture_value(&ty))
+    }
+
+    /// The value a fixture function provides: what it yields if it is a generator, otherwise
+    /// what it returns.
+    fn fixture_value(&self, ty: &Type) -> Type {
+        let Some(ret) = ty.callable_return_type() else {
+            return Type::any_implicit();
+        };
+        if let Type::ClassType(cls) = &ret {
+            let targs = cls.targs().as_slice();
+            let is_generator = [
+                "Generator",
+                "Iterator",
+                "Iterable",
+                "AsyncGenerator",
+                "AsyncIterator",
+                "AsyncIterable",
+            ]
+            .iter()
+            .any(|x| cls.has_qname("typing", x));
+            if is_generator && let Some(value) = targs.first() {
+                return value.clone();
+            }
+            if cls.has_qname("typing", "Coroutine")
+                && let Some(value) = targs.get(2)
+            {
+                return value.clone();
+            }
+        }
+        ret
+    }
+
+    /// The fixtures pytest itself provides.
+    fn builtin_fixture(&self, name: &Name) -> Option<Type> {
+        let (module, class, targs) = match name.as_str() {
+            "request" => ("pytest", "FixtureRequest", Vec::new()),
+            "pytestconfig" => ("pytest", "Config", Vec::new()),
+            "monkeypatch" => ("pytest", "MonkeyPatch", Vec::new()),
+            "tmp_path" => ("pathlib", "Path", Vec::new()),
+            "tmp_path_factory" => ("pytest", "TempPathFactory", Vec::new()),
+            "tmpdir_factory" => ("pytest", "TempdirFactory", Vec::new()),
+            "capsys" | "capfd" | "capteesys" => (
+                "pytest",
+                "CaptureFixture",
+                vec![self.stdlib.str().clone().to_type()],
+            ),
+            "capsysbinary" | "capfdbinary" => (
+                "pytest",
+                "CaptureFixture",
+                vec![self.stdlib.bytes().clone().to_type()],
+            ),
+            "caplog" => ("pytest", "LogCaptureFixture", Vec::new()),
+            "recwarn" => ("pytest", "WarningsRecorder", Vec::new()),
+            "cache" => ("pytest", "Cache", Vec::new()),
+            "pytester" => ("pytest", "Pytester", Vec::new()),
+            "testdir" => ("pytest", "Testdir", Vec::new()),
+            "doctest_namespace" => {
+                return Some(
+                    self.stdlib
+                        .dict(self.stdlib.str().clone().to_type(), Type::any_implicit())
+                        .to_type(),
+                );
+            }
+            "tmpdir"
+            | "record_property"
+            | "record_xml_attribute"
+            | "record_testsuite_property"
+            | "subtests" => return Some(Type::any_implicit()),
+            _ => return None,
+        };
+        Some(
+            self.exported_instance(ModuleName::from_str(module), class, targs)
+                .unwrap_or_else(Type::any_implicit),
+        )
+    }
+
+    /// An instance of the class `module.class`, if the module can be found.
+    fn exported_instance(&self, module: ModuleName, class: &str, targs: Vec<Type>) -> Option<Type> {
+        let class = Name::new(class);
+        let exports = self.exports.get(module).finding()?;
+        if !exports.exports(self.exports).contains_key(&class) {
+            return None;
+        }
+        match &*self.get_from_export(module, None, &KeyExport(class)) {
+            Type::ClassDef(cls) if targs.is_empty() => Some(self.promote_silently(cls)),
+            Type::ClassDef(cls) => {
+                Some(self.specialize(cls, targs, TextRange::default(), &self.error_swallower()))
+            }
+            _ => None,
+        }
+    }
+
+    /// Check the values given by `@pytest.mark.parametrize` decorators against the types of the
+    /// parameters they are passed to.
+    pub fn check_pytest_parametrize(
+        &self,
+        decorators: &[Idx<KeyDecorator>],
+        params: &[Param],
+        errors: &ErrorCollector,
+    ) {
+        for decorator in self.decorator_exprs(decorators) {
+            let Some(parametrize) = parse_parametrize(decorator) else {
+                continue;
+            };
+            let wants = parametrize
+                .names
+                .iter()
+                .map(|name| {
+                    params.iter().find_map(|param| match param {
+                        Param::PosOnly(Some(x), ty, _)
+                        | Param::Pos(x, ty, _)
+                        | Param::KwOnly(x, ty, _)
+                            if x == name =>
+                        {
+                            Some(ty)
+                        }
+                        _ => None,
+                    })
+                })
+                .collect::<Vec<_>>();
+            for row in &parametrize.rows {
+                for ((name, want), value) in parametrize.names.iter().zip(&wants).zip(row) {
+                    let Some(want) = want else {
+                        continue;
+                    };
+                    let got = self.expr_infer_with_hint(
+                        value,
+                        Some(HintRef::soft(want)),
+                        &self.error_swallower(),
+                    );
+                    self.check_type(&got, want, value.range(), errors, &|| {
+                        TypeCheckContext::of_kind(TypeCheckKind::PytestParametrize(name.clone()))
+                    });
+                }
+            }
+        }
+    }
+}
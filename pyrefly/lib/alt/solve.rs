@@ -1735,6 +1735,31 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                 self.check_is_exception(exc, exc.range(), false, errors);
                 self.check_is_exception(cause, cause.range(), true, errors);
             }
+            BindingExpect::UnreachableAfterCall {
+                call,
+                unreachable_range,
+            } => {
+                if self.get_idx(*call).ty().is_never() {
+                    self.error(
+                        errors,
+                        *unreachable_range,
+                        ErrorInfo::Kind(ErrorKind::UnreachableCode),
+                        "This code is unreachable".to_owned(),
+                    );
+                }
+            }
+            BindingExpect::MatchExhaustive {
+                subject,
+                narrowing,
+                subject_range,
+            } => {
+                self.check_match_exhaustive(
+                    &self.get_idx(*subject),
+                    narrowing,
+                    *subject_range,
+                    errors,
+                );
+            }
             BindingExpect::Redefinition {
                 new,
                 existing,
@@ -1832,6 +1857,23 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         }
     }
 
+    /// Populate base classes map for type hierarchy requests.
+    /// Only direct bases are recorded; callers walk the map to find indirect subclasses.
+    /// Bases in library code are kept, since user classes often derive from them.
+    fn populate_base_classes_map(&self, cls: &Class, class_bases: &ClassBases) {
+        if Self::should_skip_module_for_indexing(cls.module().path()) {
+            return;
+        }
+        for base in class_bases.iter() {
+            let base = base.class_object();
+            self.current().add_base_class_mapping(
+                cls.range(),
+                base.module().path().dupe(),
+                base.range(),
+            );
+        }
+    }
+
     pub fn solve_consistent_override_check(
         &self,
         binding: &BindingConsistentOverrideCheck,
@@ -1841,6 +1883,7 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
             let class_bases = self.get_base_types_for_class(cls);
 
             self.populate_parent_methods_map(cls);
+            self.populate_base_classes_map(cls, class_bases.as_ref());
 
             for (name, field) in self.get_class_field_map(cls).iter() {
                 self.check_consistent_override_for_field(
@@ -1951,10 +1994,7 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                 if let Some(new_fields) = self.get_total_ordering_synthesized_fields(errors, cls) {
                     fields = fields.combine(new_fields);
                 }
-                if let Some(new_fields) = self.get_django_enum_synthesized_fields(cls) {
-                    fields = fields.combine(new_fields);
-                }
-                if let Some(new_fields) = self.get_django_model_synthesized_fields(cls) {
+                if let Some(new_fields) = self.get_plugin_synthesized_fields(cls) {
                     fields = fields.combine(new_fields);
                 }
                 fields
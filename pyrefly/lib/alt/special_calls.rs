@@ -11,8 +11,15 @@
  * file contains the implementations of a few special calls that need to be hard-coded.
  */
 
+use std::iter;
+
+use pyrefly_types::callable::FuncFlags;
 use pyrefly_types::callable::FuncMetadata;
+use pyrefly_types::types::Overload;
+use pyrefly_types::types::OverloadType;
 use pyrefly_types::types::Union;
+use pyrefly_util::display::count;
+use pyrefly_util::prelude::SliceExt;
 use pyrefly_util::visit::Visit;
 use pyrefly_util::visit::VisitMut;
 use ruff_python_ast::Expr;
@@ -20,12 +27,14 @@ use ruff_python_ast::Keyword;
 use ruff_python_ast::name::Name;
 use ruff_text_size::Ranged;
 use ruff_text_size::TextRange;
+use vec1::Vec1;
 use vec1::vec1;
 
 use crate::alt::answers::LookupAnswer;
 use crate::alt::answers_solver::AnswersSolver;
 use crate::alt::callable::CallArg;
 use crate::alt::callable::CallKeyword;
+use crate::alt::expr::TypeOrExpr;
 use crate::alt::solve::TypeFormContext;
 use crate::alt::types::decorated_function::Decorator;
 use crate::alt::unwrap::HintRef;
@@ -34,7 +43,13 @@ use crate::error::collector::ErrorCollector;
 use crate::error::context::ErrorInfo;
 use crate::error::context::TypeCheckContext;
 use crate::error::context::TypeCheckKind;
+use crate::types::callable::Callable;
+use crate::types::callable::Function;
 use crate::types::callable::FunctionKind;
+use crate::types::callable::Param;
+use crate::types::callable::ParamList;
+use crate::types::callable::Params;
+use crate::types::callable::Required;
 use crate::types::callable::unexpected_keyword;
 use crate::types::class::Class;
 use crate::types::special_form::SpecialForm;
@@ -277,6 +292,283 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         ret
     }
 
+    /// Simulates a call to `functools.partial`. The stubs only keep the return type of the wrapped
+    /// function, so we check the bound arguments against its parameters ourselves and produce a
+    /// function with the bound parameters removed. The function stands in for the `partial`
+    /// object, so the object's attributes are still available.
+    pub fn call_functools_partial(
+        &self,
+        partial: &Type,
+        args: &[Expr],
+        keywords: &[Keyword],
+        callee_range: TextRange,
+        arg_range: TextRange,
+        hint: Option<HintRef>,
+        errors: &ErrorCollector,
+    ) -> Type {
+        let has_unpacking = args.iter().any(|x| matches!(x, Expr::Starred(_)))
+            || keywords.iter().any(|kw| kw.arg.is_none());
+        let (func, bound_args) = match args.split_first() {
+            Some(split) if !has_unpacking => split,
+            _ => {
+                return self.freeform_call_infer(
+                    partial.clone(),
+                    &args.map(CallArg::expr_maybe_starred),
+                    &keywords.map(CallKeyword::new),
+                    callee_range,
+                    arg_range,
+                    hint,
+                    errors,
+                );
+            }
+        };
+        let func_ty = self.expr_infer(func, errors);
+        let arg_tys = bound_args.map(|x| (self.expr_infer(x, errors), x.range()));
+        let kw_tys = keywords.map(|kw| (self.expr_infer(&kw.value, errors), kw.range));
+        // Construct the `partial` object as usual, which checks that the function is callable and
+        // solves its return type.
+        let partial_ty = self.freeform_call_infer(
+            partial.clone(),
+            &iter::once(CallArg::ty(&func_ty, func.range()))
+                .chain(arg_tys.iter().map(|(ty, range)| CallArg::ty(ty, *range)))
+                .collect::<Vec<_>>(),
+            &keywords
+                .iter()
+                .zip(&kw_tys)
+                .map(|(kw, (ty, range))| CallKeyword {
+                    range: *range,
+                    arg: kw.arg.as_ref(),
+                    value: TypeOrExpr::Type(ty, *range),
+                })
+                .collect::<Vec<_>>(),
+            callee_range,
+            arg_range,
+            hint,
+            errors,
+        );
+        let Type::ClassType(partial_cls) = &partial_ty else {
+            return partial_ty;
+        };
+        let Some(signatures) = self.partial_signatures(func_ty) else {
+            return partial_ty;
+        };
+        let kw_tys = keywords
+            .iter()
+            .zip(kw_tys)
+            .filter_map(|(kw, (ty, range))| Some((&kw.arg.as_ref()?.id, ty, range)))
+            .collect::<Vec<_>>();
+        let metadata = FuncMetadata {
+            kind: FunctionKind::CallbackProtocol(Box::new(partial_cls.clone())),
+            flags: FuncFlags::default(),
+        };
+        let mut bound = signatures
+            .into_iter()
+            .map(|(params, ret, func_kind)| {
+                let call_errors = self.error_collector();
+                let params = self.bind_partial_args(
+                    params,
+                    &arg_tys,
+                    &kw_tys,
+                    func_kind.as_ref(),
+                    arg_range,
+                    &call_errors,
+                );
+                let function = Function {
+                    signature: Callable::list(ParamList::new(params), ret),
+                    metadata: metadata.clone(),
+                };
+                (function, call_errors)
+            })
+            .collect::<Vec<_>>();
+        if bound.len() == 1 {
+            let (function, call_errors) = bound.pop().unwrap();
+            errors.extend(call_errors);
+            return Type::Function(Box::new(function));
+        }
+        // For an overloaded function, keep the overloads that accept the bound arguments.
+        let mut matched = bound
+            .into_iter()
+            .filter(|(_, call_errors)| call_errors.is_empty())
+            .map(|(function, _)| function)
+            .collect::<Vec<_>>();
+        if matched.len() == 1 {
+            return Type::Function(Box::new(matched.pop().unwrap()));
+        }
+        match Vec1::try_from_vec(matched) {
+            Ok(matched) => Type::Overload(Overload {
+                signatures: matched.mapped(OverloadType::Function),
+                metadata: Box::new(metadata),
+            }),
+            Err(_) => {
+                self.error(
+                    errors,
+                    arg_range,
+                    ErrorInfo::Kind(ErrorKind::NoMatchingOverload),
+                    "No overload of the function passed to `functools.partial` accepts the bound arguments"
+                        .to_owned(),
+                );
+                partial_ty
+            }
+        }
+    }
+
+    /// The signatures of a function passed to `functools.partial`, as its parameters, its return
+    /// type and the kind of function for error messages. Returns `None` for functions we can't
+    /// bind arguments to, like generic functions.
+    fn partial_signatures(
+        &self,
+        func: Type,
+    ) -> Option<Vec<(Vec<Param>, Type, Option<FunctionKind>)>> {
+        let signatures = match func {
+            Type::Callable(callable) => vec![(*callable, None)],
+            Type::Function(func) => vec![(func.signature, Some(func.metadata.kind))],
+            Type::Overload(overload) => overload
+                .signatures
+                .into_iter()
+                .map(|signature| match signature {
+                    OverloadType::Function(func) => {
+                        Some((func.signature, Some(func.metadata.kind)))
+                    }
+                    OverloadType::Forall(_) => None,
+                })
+                .collect::<Option<Vec<_>>>()?,
+            Type::BoundMethod(method) => {
+                let func =
+                    self.bind_boundmethod(&method, &mut |got, want| self.is_subset_eq(got, want))?;
+                return self.partial_signatures(func);
+            }
+            Type::ClassDef(cls) if self.get_class_tparams(&cls).is_empty() => {
+                let constructor = self.constructor_to_callable(&self.as_class_type_unchecked(&cls));
+                return self.partial_signatures(constructor);
+            }
+            _ => return None,
+        };
+        signatures
+            .into_iter()
+            .map(|(callable, func_kind)| match callable.params {
+                Params::List(params)
+                    if !params.items().iter().any(|p| {
+                        matches!(
+                            p,
+                            Param::VarArg(_, Type::Unpack(_)) | Param::Kwargs(_, Type::Unpack(_))
+                        )
+                    }) =>
+                {
+                    Some((params.items().to_vec(), callable.ret, func_kind))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Remove the parameters bound by the arguments of a `functools.partial` call, checking the
+    /// arguments against them. A parameter bound by keyword becomes keyword-only with a default,
+    /// and so do the positional parameters after it.
+    fn bind_partial_args(
+        &self,
+        params: Vec<Param>,
+        args: &[(Type, TextRange)],
+        keywords: &[(&Name, Type, TextRange)],
+        func_kind: Option<&FunctionKind>,
+        arg_range: TextRange,
+        errors: &ErrorCollector,
+    ) -> Vec<Param> {
+        let mut args = args.iter();
+        let mut num_positional_params = 0;
+        let mut remaining = Vec::with_capacity(params.len());
+        for param in params {
+            match &param {
+                Param::PosOnly(_, ty, _) | Param::Pos(_, ty, _) => {
+                    num_positional_params += 1;
+                    if let Some((arg_ty, range)) = args.next() {
+                        let name = match &param {
+                            Param::Pos(name, ..) | Param::PosOnly(Some(name), ..) => {
+                                Some(name.clone())
+                            }
+                            _ => None,
+                        };
+                        self.check_type(arg_ty, ty, *range, errors, &|| {
+                            TypeCheckContext::of_kind(TypeCheckKind::CallArgument(
+                                name.clone(),
+                                func_kind.cloned(),
+                            ))
+                        });
+                        continue;
+                    }
+                }
+                Param::VarArg(name, ty) => {
+                    for (arg_ty, range) in args.by_ref() {
+                        self.check_type(arg_ty, ty, *range, errors, &|| {
+                            TypeCheckContext::of_kind(TypeCheckKind::CallVarArgs(
+                                false,
+                                name.clone(),
+                                func_kind.cloned(),
+                            ))
+                        });
+                    }
+                }
+                Param::KwOnly(..) | Param::Kwargs(..) => {}
+            }
+            remaining.push(param);
+        }
+        let num_extra_args = args.count();
+        if num_extra_args > 0 {
+            self.error(
+                errors,
+                arg_range,
+                ErrorInfo::Kind(ErrorKind::BadArgumentCount),
+                format!(
+                    "Expected {}, got {}",
+                    count(num_positional_params, "positional argument"),
+                    num_positional_params + num_extra_args
+                ),
+            );
+        }
+        for (name, arg_ty, range) in keywords {
+            let position = remaining
+                .iter()
+                .position(|p| matches!(p, Param::Pos(n, ..) | Param::KwOnly(n, ..) if n == *name));
+            if let Some(i) = position {
+                let (Param::Pos(_, ty, _) | Param::KwOnly(_, ty, _)) = remaining[i].clone() else {
+                    unreachable!("found a named parameter")
+                };
+                self.check_type(arg_ty, &ty, *range, errors, &|| {
+                    TypeCheckContext::of_kind(TypeCheckKind::CallArgument(
+                        Some((*name).clone()),
+                        func_kind.cloned(),
+                    ))
+                });
+                remaining[i] = Param::KwOnly((*name).clone(), ty, Required::Optional(None));
+                // Positional arguments would now collide with the parameter bound by keyword, so
+                // the parameters after it can only be passed by keyword.
+                let after = remaining.split_off(i + 1);
+                remaining.extend(after.into_iter().filter_map(|p| match p {
+                    Param::Pos(name, ty, required) => Some(Param::KwOnly(name, ty, required)),
+                    Param::VarArg(..) => None,
+                    p => Some(p),
+                }));
+            } else if let Some(Param::Kwargs(kwargs_name, ty)) =
+                remaining.iter().find(|p| matches!(p, Param::Kwargs(..)))
+            {
+                self.check_type(arg_ty, ty, *range, errors, &|| {
+                    TypeCheckContext::of_kind(TypeCheckKind::CallKwArgs(
+                        Some((*name).clone()),
+                        kwargs_name.clone(),
+                        func_kind.cloned(),
+                    ))
+                });
+            } else {
+                self.error(
+                    errors,
+                    *range,
+                    ErrorInfo::Kind(ErrorKind::UnexpectedKeyword),
+                    format!("Unexpected keyword argument `{name}`"),
+                );
+            }
+        }
+        remaining
+    }
+
     pub fn call_isinstance(
         &self,
         obj: &Expr,
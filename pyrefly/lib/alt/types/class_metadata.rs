@@ -62,6 +62,8 @@ pub struct ClassMetadata {
     dataclass_transform_metadata: Option<DataclassTransformMetadata>,
     pydantic_model_kind: Option<PydanticModelKind>,
     django_model_metadata: Option<DjangoModelMetadata>,
+    /// The indices of the class synthesis plugins that handle this class.
+    class_synthesis_plugins: Vec<usize>,
 }
 
 impl VisitMut<Type> for ClassMetadata {
@@ -98,6 +100,7 @@ impl ClassMetadata {
         dataclass_transform_metadata: Option<DataclassTransformMetadata>,
         pydantic_model_kind: Option<PydanticModelKind>,
         django_model_metadata: Option<DjangoModelMetadata>,
+        class_synthesis_plugins: Vec<usize>,
     ) -> ClassMetadata {
         ClassMetadata {
             metaclass,
@@ -119,6 +122,7 @@ impl ClassMetadata {
             dataclass_transform_metadata,
             pydantic_model_kind,
             django_model_metadata,
+            class_synthesis_plugins,
         }
     }
 
@@ -143,6 +147,7 @@ impl ClassMetadata {
             dataclass_transform_metadata: None,
             pydantic_model_kind: None,
             django_model_metadata: None,
+            class_synthesis_plugins: Vec::new(),
         }
     }
 
@@ -178,6 +183,10 @@ impl ClassMetadata {
         self.django_model_metadata.is_some()
     }
 
+    pub fn class_synthesis_plugins(&self) -> &[usize] {
+        &self.class_synthesis_plugins
+    }
+
     pub fn pydantic_model_kind(&self) -> Option<PydanticModelKind> {
         self.pydantic_model_kind.clone()
     }
@@ -453,6 +462,17 @@ pub struct DataclassMetadata {
     pub init_defaults: InitDefaults,
     /// Whether a default can be passed positionally to field specifier calls
     pub default_can_be_positional: bool,
+    /// Set if this class was created by one of the `attrs` class decorators.
+    pub attrs: Option<AttrsMetadata>,
+}
+
+/// Options for classes created by `attrs`, which finds fields and names `__init__` parameters
+/// differently from standard dataclasses.
+#[derive(Clone, Debug, TypeEq, PartialEq, Eq)]
+pub struct AttrsMetadata {
+    /// Whether annotated attributes are fields without being assigned `attr.ib()`. This is off for
+    /// the classic `@attr.s` decorator unless it is passed `auto_attribs=True`.
+    pub auto_attribs: bool,
 }
 
 #[derive(Clone, Debug, TypeEq, PartialEq, Eq)]
@@ -582,6 +582,19 @@ pub enum BindingExpect {
     },
     /// Expression used in a boolean context (`bool()`, `if`, or `while`)
     Bool(Expr),
+    /// A call statement followed by more statements in the same block, which are unreachable
+    /// if the call never returns.
+    UnreachableAfterCall {
+        call: Idx<Key>,
+        unreachable_range: TextRange,
+    },
+    /// A `match` statement without an irrefutable case. The subject, narrowed by the negation
+    /// of every case, should be `Never` if the match is exhaustive.
+    MatchExhaustive {
+        subject: Idx<Key>,
+        narrowing: Box<NarrowOp>,
+        subject_range: TextRange,
+    },
 }
 
 impl DisplayWith<Bindings> for BindingExpect {
@@ -632,6 +645,20 @@ impl DisplayWith<Bindings> for BindingExpect {
                 ctx.display(*existing),
                 name
             ),
+            Self::UnreachableAfterCall {
+                call,
+                unreachable_range: _,
+            } => write!(f, "UnreachableAfterCall({})", ctx.display(*call)),
+            Self::MatchExhaustive {
+                subject,
+                narrowing,
+                subject_range: _,
+            } => write!(
+                f,
+                "MatchExhaustive({}, {})",
+                ctx.display(*subject),
+                narrowing.display_with(ctx.module())
+            ),
         }
     }
 }
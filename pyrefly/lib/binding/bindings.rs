@@ -16,6 +16,7 @@ use pyrefly_graph::index::Idx;
 use pyrefly_graph::index::Index;
 use pyrefly_graph::index_map::IndexMap;
 use pyrefly_python::ast::Ast;
+use pyrefly_python::ignore::SeverityDirective;
 use pyrefly_python::module_name::ModuleName;
 use pyrefly_python::nesting_context::NestingContext;
 use pyrefly_python::short_identifier::ShortIdentifier;
@@ -31,6 +32,7 @@ use ruff_python_ast::Identifier;
 use ruff_python_ast::ModModule;
 use ruff_python_ast::Parameter;
 use ruff_python_ast::Stmt;
+use ruff_python_ast::StmtExpr;
 use ruff_python_ast::TypeParam;
 use ruff_python_ast::TypeParams;
 use ruff_python_ast::name::Name;
@@ -50,6 +52,7 @@ use vec1::vec1;
 use crate::binding::binding::AnnotationTarget;
 use crate::binding::binding::Binding;
 use crate::binding::binding::BindingAnnotation;
+use crate::binding::binding::BindingExpect;
 use crate::binding::binding::BindingExport;
 use crate::binding::binding::BindingLegacyTypeParam;
 use crate::binding::binding::FirstUse;
@@ -58,6 +61,7 @@ use crate::binding::binding::Key;
 use crate::binding::binding::KeyAnnotation;
 use crate::binding::binding::KeyClass;
 use crate::binding::binding::KeyDecoratedFunction;
+use crate::binding::binding::KeyExpect;
 use crate::binding::binding::KeyExport;
 use crate::binding::binding::KeyLegacyTypeParam;
 use crate::binding::binding::KeyUndecoratedFunction;
@@ -74,9 +78,12 @@ use crate::binding::scope::ScopeTrace;
 use crate::binding::scope::Scopes;
 use crate::binding::scope::UnusedImport;
 use crate::binding::scope::UnusedParameter;
+use crate::binding::scope::UnusedPrivateFunction;
 use crate::binding::scope::UnusedVariable;
 use crate::binding::table::TableKeyed;
 use crate::config::base::UntypedDefBehavior;
+use crate::config::config::ImportRule;
+use crate::config::error::parse_severity_directive;
 use crate::config::error_kind::ErrorKind;
 use crate::error::collector::ErrorCollector;
 use crate::error::context::ErrorInfo;
@@ -135,11 +142,16 @@ pub enum InitializedInFlow {
 }
 
 impl InitializedInFlow {
-    pub fn as_error_message(&self, name: &Name) -> Option<String> {
+    pub fn as_error(&self, name: &Name) -> Option<(ErrorKind, String)> {
         match self {
             InitializedInFlow::Yes => None,
-            InitializedInFlow::Conditionally => Some(format!("`{name}` may be uninitialized")),
-            InitializedInFlow::No => Some(format!("`{name}` is uninitialized")),
+            InitializedInFlow::Conditionally => Some((
+                ErrorKind::PossiblyUnbound,
+                format!("`{name}` may be uninitialized"),
+            )),
+            InitializedInFlow::No => {
+                Some((ErrorKind::UnboundName, format!("`{name}` is uninitialized")))
+            }
         }
     }
 }
@@ -199,9 +211,13 @@ pub struct BindingsBuilder<'a> {
     pub scopes: Scopes,
     table: BindingTable,
     pub untyped_def_behavior: UntypedDefBehavior,
+    pub import_rules: &'a [ImportRule],
+    /// Whether to check for unreachable code after calls that never return.
+    check_unreachable_after_calls: bool,
     unused_parameters: Vec<UnusedParameter>,
     unused_imports: Vec<UnusedImport>,
     unused_variables: Vec<UnusedVariable>,
+    unused_private_functions: Vec<UnusedPrivateFunction>,
     semantic_checker: SemanticSyntaxChecker,
     semantic_syntax_errors: RefCell<Vec<SemanticSyntaxError>>,
 }
@@ -392,6 +408,8 @@ impl Bindings {
         uniques: &UniqueFactory,
         enable_trace: bool,
         untyped_def_behavior: UntypedDefBehavior,
+        import_rules: &[ImportRule],
+        check_unreachable_after_calls: bool,
     ) -> Self {
         let mut builder = BindingsBuilder {
             module_info: module_info.dupe(),
@@ -406,9 +424,12 @@ impl Bindings {
             scopes: Scopes::module(x.range, enable_trace),
             table: Default::default(),
             untyped_def_behavior,
+            import_rules,
+            check_unreachable_after_calls,
             unused_parameters: Vec::new(),
             unused_imports: Vec::new(),
             unused_variables: Vec::new(),
+            unused_private_functions: Vec::new(),
             semantic_checker: SemanticSyntaxChecker::new(),
             semantic_syntax_errors: RefCell::new(Vec::new()),
         };
@@ -432,8 +453,17 @@ impl Bindings {
             );
         }
 
-        let unused_imports = builder.scopes.collect_module_unused_imports();
+        // Imports in `__init__.py` and stub files, and explicitly re-exported imports, are
+        // part of the module's interface even if the module itself never uses them.
+        let unused_imports = if module_info.path().is_init() || module_info.path().is_interface() {
+            Vec::new()
+        } else {
+            let mut unused_imports = builder.scopes.collect_module_unused_imports();
+            unused_imports.retain(|x| !exports.is_explicit_reexport(&x.name));
+            unused_imports
+        };
         builder.record_unused_imports(unused_imports);
+        builder.report_unused();
         let scope_trace = builder.scopes.finish();
 
         let semantic_errors = builder.semantic_syntax_errors.into_inner();
@@ -447,6 +477,28 @@ impl Bindings {
             }
         }
 
+        // `ErrorDisplayConfig::with_directives` skips the directives we report here.
+        for directive in module_info.ignore().severity_directives() {
+            if let SeverityDirective::Kind {
+                kind,
+                severity,
+                line,
+            } = directive
+                && let Err(msg) = parse_severity_directive(kind, severity)
+            {
+                let lined_buffer = module_info.lined_buffer();
+                let text = lined_buffer.content_in_line_range(*line, *line);
+                builder.errors.add(
+                    TextRange::at(
+                        lined_buffer.line_start(*line),
+                        TextSize::of(text.trim_end()),
+                    ),
+                    ErrorInfo::Kind(ErrorKind::InvalidDirective),
+                    vec1![msg],
+                );
+            }
+        }
+
         let exported = exports.exports(lookup);
         for (name, exportable) in scope_trace.exportables().into_iter_hashed() {
             let binding = match exportable {
@@ -678,6 +730,60 @@ impl<'a> BindingsBuilder<'a> {
         self.unused_variables.extend(unused);
     }
 
+    pub fn record_unused_private_functions(&mut self, unused: Vec<UnusedPrivateFunction>) {
+        self.unused_private_functions.extend(unused);
+    }
+
+    /// Start tracking uses of undecorated private functions defined directly in a function
+    /// body. Module-level private functions aren't tracked, since other modules may import
+    /// them.
+    pub fn register_private_functions(&mut self, body: &[Stmt]) {
+        for stmt in body {
+            if let Stmt::FunctionDef(x) = stmt
+                && x.decorator_list.is_empty()
+                && x.name.id.starts_with('_')
+                && !(x.name.id.starts_with("__") && x.name.id.ends_with("__"))
+            {
+                self.scopes.register_private_function(&x.name);
+            }
+        }
+    }
+
+    /// Report the unused imports, parameters, local variables and private functions found while
+    /// binding the module.
+    fn report_unused(&self) {
+        for x in &self.unused_imports {
+            self.error(
+                x.range,
+                ErrorInfo::Kind(ErrorKind::UnusedImport),
+                format!("Import `{}` is unused", x.name),
+            );
+        }
+        for x in &self.unused_parameters {
+            self.error(
+                x.range,
+                ErrorInfo::Kind(ErrorKind::UnusedParameter),
+                format!("Parameter `{}` is unused", x.name),
+            );
+        }
+        for x in &self.unused_variables {
+            if !x.name.starts_with('_') {
+                self.error(
+                    x.range,
+                    ErrorInfo::Kind(ErrorKind::UnusedVariable),
+                    format!("Variable `{}` is unused", x.name),
+                );
+            }
+        }
+        for x in &self.unused_private_functions {
+            self.error(
+                x.range,
+                ErrorInfo::Kind(ErrorKind::UnusedPrivateFunction),
+                format!("Private function `{}` is unused", x.name),
+            );
+        }
+    }
+
     pub(crate) fn with_await_context<R>(
         &mut self,
         ctx: AwaitContext,
@@ -767,8 +873,43 @@ impl<'a> BindingsBuilder<'a> {
     }
 
     pub fn stmts(&mut self, xs: Vec<Stmt>, parent: &NestingContext) {
-        for x in xs {
+        // The rest of the block is unreachable once the flow terminates part way through it. If
+        // the flow had already terminated at the start of the block, we reported that outside it.
+        let mut reachable = !self.scopes.is_flow_terminated();
+        let block_end = xs.last().map_or(TextSize::default(), |x| x.range().end());
+        let mut xs = xs.into_iter().peekable();
+        while let Some(x) = xs.next() {
+            if reachable && self.scopes.is_flow_terminated() {
+                reachable = false;
+                self.error(
+                    TextRange::new(x.range().start(), block_end),
+                    ErrorInfo::Kind(ErrorKind::UnreachableCode),
+                    "This code is unreachable".to_owned(),
+                );
+            }
+            // We only know whether a call never returns once we have solved its type, so only
+            // check when `unreachable-code` is enabled, rather than for every call.
+            let call_range = match &x {
+                Stmt::Expr(StmtExpr { value, .. })
+                    if reachable && self.check_unreachable_after_calls && value.is_call_expr() =>
+                {
+                    Some(value.range())
+                }
+                _ => None,
+            };
             self.stmt(x, parent);
+            if let Some(call_range) = call_range
+                && let Some(next) = xs.peek()
+            {
+                let call = self.idx_for_promise(Key::StmtExpr(call_range));
+                self.insert_binding(
+                    KeyExpect(call_range),
+                    BindingExpect::UnreachableAfterCall {
+                        call,
+                        unreachable_range: TextRange::new(next.range().start(), block_end),
+                    },
+                );
+            }
         }
     }
 
@@ -941,6 +1082,7 @@ impl<'a> BindingsBuilder<'a> {
                 self.scopes.mark_parameter_used(name.key());
                 self.scopes.mark_import_used(name.key());
                 self.scopes.mark_variable_used(name.key());
+                self.scopes.mark_private_function_used(name.key());
                 NameLookupResult::Found {
                     idx,
                     initialized: is_initialized,
@@ -953,6 +1095,7 @@ impl<'a> BindingsBuilder<'a> {
                 self.scopes.mark_parameter_used(name.key());
                 self.scopes.mark_import_used(name.key());
                 self.scopes.mark_variable_used(name.key());
+                self.scopes.mark_private_function_used(name.key());
                 NameLookupResult::Found {
                     idx: self.table.types.0.insert(key),
                     initialized: is_initialized,
@@ -333,13 +333,9 @@ impl<'a> BindingsBuilder<'a> {
                 // nor a static type context.
                 if !used_in_static_type
                     && !self.module_info.path().is_interface()
-                    && let Some(error_message) = is_initialized.as_error_message(&name.id)
+                    && let Some((kind, error_message)) = is_initialized.as_error(&name.id)
                 {
-                    self.error(
-                        name.range,
-                        ErrorInfo::Kind(ErrorKind::UnboundName),
-                        error_message,
-                    );
+                    self.error(name.range, ErrorInfo::Kind(kind), error_message);
                 }
                 self.insert_binding(key, Binding::Forward(value))
             }
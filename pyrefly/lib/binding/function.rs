@@ -306,10 +306,13 @@ impl<'a> BindingsBuilder<'a> {
             .push_function_scope(range, func_name, class_key.is_some(), is_async);
         self.parameters(parameters, undecorated_idx, class_key, method_self_kind);
         self.init_static_scope(&body, false);
+        self.register_private_functions(&body);
         self.stmts(
             body,
             &NestingContext::function(ShortIdentifier::new(func_name), parent.dupe()),
         );
+        let unused_private_functions = self.scopes.collect_unused_private_functions();
+        self.record_unused_private_functions(unused_private_functions);
         let (yields_and_returns, self_assignments, unused_parameters, unused_variables) =
             self.scopes.pop_function_scope();
         (
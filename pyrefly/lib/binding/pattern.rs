@@ -18,6 +18,7 @@ use ruff_python_ast::Pattern;
 use ruff_python_ast::PatternKeyword;
 use ruff_python_ast::StmtMatch;
 use ruff_text_size::Ranged;
+use vec1::Vec1;
 
 use crate::binding::binding::Binding;
 use crate::binding::binding::BindingExpect;
@@ -29,6 +30,7 @@ use crate::binding::binding::UnpackedPosition;
 use crate::binding::bindings::BindingsBuilder;
 use crate::binding::expr::Usage;
 use crate::binding::narrow::AtomicNarrowOp;
+use crate::binding::narrow::FacetSubject;
 use crate::binding::narrow::NarrowOp;
 use crate::binding::narrow::NarrowOps;
 use crate::binding::narrow::NarrowingSubject;
@@ -37,8 +39,66 @@ use crate::binding::scope::FlowStyle;
 use crate::config::error_kind::ErrorKind;
 use crate::error::context::ErrorInfo;
 use crate::export::special::SpecialExport;
+use crate::types::facet::FacetChain;
 use crate::types::facet::FacetKind;
 
+/// The classes that a pattern matches every instance of, such as `int` in `case int()` or
+/// `case int(x)`.
+fn covered_classes(pattern: &Pattern, res: &mut Vec<Expr>) {
+    match pattern {
+        Pattern::MatchClass(x)
+            if x.arguments.patterns.iter().all(|p| p.is_irrefutable())
+                && x.arguments
+                    .keywords
+                    .iter()
+                    .all(|k| k.pattern.is_irrefutable()) =>
+        {
+            res.push((*x.cls).clone());
+        }
+        Pattern::MatchAs(x) => {
+            if let Some(pattern) = &x.pattern {
+                covered_classes(pattern, res);
+            }
+        }
+        Pattern::MatchOr(x) => {
+            for pattern in &x.patterns {
+                covered_classes(pattern, res);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Rewrite `op`, which narrows the name at the root of `subject`, into the narrowing of the
+/// value of `subject` itself, such as the value of `x.kind` for a `match x.kind:`. Returns `None`
+/// if `op` also narrows anything other than `subject` and its facets, as a guard might.
+fn narrowing_of_facet(op: &NarrowOp, subject: &FacetSubject) -> Option<NarrowOp> {
+    match op {
+        NarrowOp::Atomic(Some(facet), op) => {
+            let prefix = subject.chain.facets();
+            let rest = facet.chain.facets().strip_prefix(prefix.as_slice())?;
+            let remaining = Vec1::try_from_vec(rest.to_vec())
+                .ok()
+                .map(|chain| FacetSubject {
+                    chain: FacetChain::new(chain),
+                    origin: facet.origin,
+                });
+            Some(NarrowOp::Atomic(remaining, op.clone()))
+        }
+        NarrowOp::Atomic(None, _) => None,
+        NarrowOp::And(ops) => Some(NarrowOp::And(
+            ops.iter()
+                .map(|op| narrowing_of_facet(op, subject))
+                .collect::<Option<_>>()?,
+        )),
+        NarrowOp::Or(ops) => Some(NarrowOp::Or(
+            ops.iter()
+                .map(|op| narrowing_of_facet(op, subject))
+                .collect::<Option<_>>()?,
+        )),
+    }
+}
+
 impl<'a> BindingsBuilder<'a> {
     // Traverse a pattern and bind all the names; key is the reference for the value that's being matched on
     fn bind_pattern(
@@ -359,11 +419,17 @@ impl<'a> BindingsBuilder<'a> {
         // x is bound to Narrow(x, Eq(None)) in the first case, and the negation, Narrow(x, NotEq(None)),
         // is carried over to the fallback case.
         let mut negated_prev_ops = NarrowOps::new();
+        // Class patterns like `case int():` are never negated for narrowing, but do make a
+        // match over a union of classes exhaustive.
+        let mut covered = Vec::new();
         for case in x.cases {
             self.start_branch();
             if case.pattern.is_wildcard() || case.pattern.is_irrefutable() {
                 exhaustive = true;
             }
+            if case.guard.is_none() {
+                covered_classes(&case.pattern, &mut covered);
+            }
             self.bind_narrow_ops(
                 &negated_prev_ops,
                 NarrowUseLocation::Start(case.range),
@@ -394,6 +460,32 @@ impl<'a> BindingsBuilder<'a> {
         if exhaustive {
             self.finish_exhaustive_fork();
         } else {
+            let remaining = match &match_narrowing_subject {
+                Some(NarrowingSubject::Name(name)) => {
+                    negated_prev_ops.0.get(name).map(|(op, _)| op.clone())
+                }
+                Some(NarrowingSubject::Facets(name, subject)) => negated_prev_ops
+                    .0
+                    .get(name)
+                    .and_then(|(op, _)| narrowing_of_facet(op, subject)),
+                None => None,
+            };
+            if let Some(op) = remaining {
+                let mut ops = vec![op];
+                ops.extend(
+                    covered
+                        .into_iter()
+                        .map(|cls| NarrowOp::Atomic(None, AtomicNarrowOp::IsNotInstance(cls))),
+                );
+                self.insert_binding(
+                    KeyExpect(x.range),
+                    BindingExpect::MatchExhaustive {
+                        subject: subject_idx,
+                        narrowing: Box::new(NarrowOp::And(ops)),
+                        subject_range: x.subject.range(),
+                    },
+                );
+            }
             self.finish_non_exhaustive_fork(&negated_prev_ops);
         }
     }
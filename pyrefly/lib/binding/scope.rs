@@ -851,6 +851,12 @@ pub struct UnusedVariable {
     pub range: TextRange,
 }
 
+#[derive(Clone, Debug)]
+pub struct UnusedPrivateFunction {
+    pub name: Name,
+    pub range: TextRange,
+}
+
 impl Default for ScopeFunction {
     fn default() -> Self {
         Self::new(false)
@@ -978,6 +984,8 @@ pub struct Scope {
     imports: SmallMap<Name, ImportUsage>,
     /// Tracking variables in the current scope (module, function, and method scopes)
     variables: SmallMap<Name, VariableUsage>,
+    /// Tracking private functions in the current scope (function and method scopes only)
+    private_functions: SmallMap<Name, VariableUsage>,
     /// Depth of finally blocks we're in. Resets in new function scopes (PEP 765).
     finally_depth: usize,
 }
@@ -994,6 +1002,7 @@ impl Scope {
             forks: Default::default(),
             imports: SmallMap::new(),
             variables: SmallMap::new(),
+            private_functions: SmallMap::new(),
             finally_depth: 0,
         }
     }
@@ -1274,6 +1283,20 @@ impl Scopes {
         Self::collect_unused_imports(module_scope.scope.imports.clone())
     }
 
+    /// The private functions defined directly in the current function scope that nothing
+    /// refers to.
+    pub fn collect_unused_private_functions(&self) -> Vec<UnusedPrivateFunction> {
+        self.current()
+            .private_functions
+            .iter()
+            .filter(|(_, usage)| !usage.used)
+            .map(|(name, usage)| UnusedPrivateFunction {
+                name: name.clone(),
+                range: usage.range,
+            })
+            .collect()
+    }
+
     pub fn init_current_static(
         &mut self,
         x: &[Stmt],
@@ -1825,13 +1848,47 @@ impl Scopes {
         }
     }
 
+    /// Track a private function defined directly in a function body, so we can report it if
+    /// nothing refers to it. This is called before the body is traversed, since uses in nested
+    /// functions may come before the definition.
+    pub fn register_private_function(&mut self, name: &Identifier) {
+        if matches!(
+            self.current().kind,
+            ScopeKind::Function(_) | ScopeKind::Method(_)
+        ) {
+            self.current_mut()
+                .private_functions
+                .entry(name.id.clone())
+                .or_insert(VariableUsage {
+                    range: name.range,
+                    used: false,
+                });
+        }
+    }
+
+    pub fn mark_private_function_used(&mut self, name: &Name) {
+        for scope in self.iter_rev_mut() {
+            if let Some(info) = scope.private_functions.get_mut(name) {
+                info.used = true;
+                break;
+            }
+        }
+    }
+
     pub fn register_variable(&mut self, name: &Identifier) {
         // Track variables in Module, Function, and Method scopes
         // Module-level variables won't be reported as unused since they can be imported
         // by other modules, but function/method-level variables will be reported
+        // Names declared `global` or `nonlocal` are assigned for their effect on another scope
         if matches!(
             self.current().kind,
             ScopeKind::Module | ScopeKind::Function(_) | ScopeKind::Method(_)
+        ) && !matches!(
+            self.current().stat.0.get(&name.id),
+            Some(StaticInfo {
+                style: StaticStyle::MutableCapture(..),
+                ..
+            })
         ) {
             // Preserve the `used` flag if the variable was already marked as used
             // This handles cases like `foo = foo + 1` in loops where the variable
@@ -1913,6 +1970,10 @@ impl Scopes {
         self.current_mut().flow.has_terminated = true;
     }
 
+    pub fn is_flow_terminated(&self) -> bool {
+        self.current().flow.has_terminated
+    }
+
     /// Whenever we enter the scope of a method *and* we see a matching
     /// parameter, we record the name of it so that we can detect `self` assignments
     /// that might define class fields.
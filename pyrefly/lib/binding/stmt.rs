@@ -28,6 +28,7 @@ use ruff_python_ast::StmtReturn;
 use ruff_text_size::Ranged;
 use ruff_text_size::TextRange;
 use starlark_map::small_set::SmallSet;
+use vec1::vec1;
 
 use crate::binding::binding::AnnAssignHasValue;
 use crate::binding::binding::AnnotationTarget;
@@ -48,6 +49,7 @@ use crate::binding::narrow::NarrowOps;
 use crate::binding::scope::FlowStyle;
 use crate::binding::scope::LoopExit;
 use crate::binding::scope::Scope;
+use crate::config::config::ImportRule;
 use crate::config::error_kind::ErrorKind;
 use crate::error::context::ErrorInfo;
 use crate::export::definitions::MutableCaptureKind;
@@ -355,6 +357,29 @@ impl<'a> BindingsBuilder<'a> {
         self.error_multiline(range, ErrorInfo::new(kind, ctx.as_deref()), msg);
     }
 
+    /// The first of the config's `import-rules` that forbids this module from importing `imported`.
+    fn forbidding_import_rule(&self, imported: ModuleName) -> Option<&ImportRule> {
+        let importer = self.module_info.name();
+        self.import_rules
+            .iter()
+            .find(|rule| rule.forbids(importer, imported))
+    }
+
+    /// Report an error if the config's `import-rules` forbid importing `imported`.
+    fn check_import_rules(&self, imported: ModuleName, range: TextRange) {
+        let Some(rule) = self.forbidding_import_rule(imported) else {
+            return;
+        };
+        let mut msg = vec1![format!(
+            "`{}` may not import `{imported}`",
+            self.module_info.name()
+        )];
+        if let Some(message) = &rule.message {
+            msg.push(message.clone());
+        }
+        self.error_multiline(range, ErrorInfo::Kind(ErrorKind::ForbiddenImport), msg);
+    }
+
     /// Evaluate the statements and update the bindings.
     /// Every statement should end up in the bindings, perhaps with a location that is never used.
     pub fn stmt(&mut self, x: Stmt, parent: &NestingContext) {
@@ -951,8 +976,15 @@ impl<'a> BindingsBuilder<'a> {
             Stmt::Import(x) => {
                 for x in x.names {
                     let m = ModuleName::from_name(&x.name.id);
-                    if let Some(error) = self.lookup.get(m).error() {
-                        self.find_error(&error, x.range);
+                    // Forbidden even if the module can't be found.
+                    self.check_import_rules(m, x.range);
+                    match self.lookup.get(m) {
+                        FindingOrError::Finding(finding) => {
+                            if let Some(error) = finding.error {
+                                self.find_error(&error, x.range);
+                            }
+                        }
+                        FindingOrError::Error(error) => self.find_error(&error, x.range),
                     }
                     match x.asname {
                         Some(asname) => {
@@ -988,6 +1020,7 @@ impl<'a> BindingsBuilder<'a> {
                     x.level,
                     x.module.as_ref().map(|x| &x.id),
                 ) {
+                    self.check_import_rules(m, x.range);
                     match self.lookup.get(m) {
                         FindingOrError::Finding(module_exports) => {
                             if let Some(error) = module_exports.error {
@@ -1174,6 +1207,10 @@ impl<'a> BindingsBuilder<'a> {
                             format!("Could not import `{}` from `{m}`", x.name.id),
                         );
                     }
+                    // Only check the submodule if importing its parent wasn't already an error.
+                    if finding && self.forbidding_import_rule(m).is_none() {
+                        self.check_import_rules(x_as_module_name, x.range);
+                    }
                     if finding {
                         Binding::Module(x_as_module_name, x_as_module_name.components(), None)
                     } else if error {
@@ -11,11 +11,14 @@ use pyrefly_util::telemetry::Telemetry;
 use crate::commands::buck_check::BuckCheckArgs;
 use crate::commands::check::FullCheckArgs;
 use crate::commands::check::SnippetCheckArgs;
+use crate::commands::deps::DepsArgs;
 use crate::commands::dump_config::DumpConfigArgs;
 use crate::commands::infer::InferArgs;
 use crate::commands::init::InitArgs;
 use crate::commands::lsp::LspArgs;
 use crate::commands::report::ReportArgs;
+use crate::commands::stubgen::StubgenArgs;
+use crate::commands::suppress::SuppressArgs;
 use crate::commands::tsp::TspArgs;
 use crate::commands::util::CommandExitStatus;
 
@@ -48,6 +51,12 @@ pub enum Command {
     Infer(InferArgs),
     /// Generate reports from pyrefly type checking results.
     Report(ReportArgs),
+    /// Generate `.pyi` stub files from the inferred types of a file or project.
+    Stubgen(StubgenArgs),
+    /// Export the resolved import graph of a file or project as JSON or DOT.
+    Deps(DepsArgs),
+    /// Add suppression comments for the type errors in a file or project.
+    Suppress(SuppressArgs),
 }
 
 impl Command {
@@ -66,6 +75,9 @@ impl Command {
             Command::Infer(args) => args.run(),
             Command::DumpConfig(args) => args.run(),
             Command::Report(args) => args.run(),
+            Command::Stubgen(args) => args.run(),
+            Command::Deps(args) => args.run(),
+            Command::Suppress(args) => args.run(),
         }
     }
 }
@@ -13,6 +13,7 @@ use std::io::BufWriter;
 use std::io::Write;
 use std::path::Path;
 use std::path::PathBuf;
+use std::process::Command;
 use std::str::FromStr;
 use std::sync::Arc;
 use std::time::Duration;
@@ -31,6 +32,8 @@ use pyrefly_config::error_kind::ErrorKind;
 use pyrefly_config::finder::ConfigError;
 use pyrefly_python::module_name::ModuleName;
 use pyrefly_python::module_path::ModulePath;
+use pyrefly_python::module_path::ModulePathDetails;
+use pyrefly_python::sys_info::SysInfo;
 use pyrefly_util::arc_id::ArcId;
 use pyrefly_util::args::clap_env;
 use pyrefly_util::display;
@@ -47,23 +50,33 @@ use starlark_map::small_map::SmallMap;
 use starlark_map::small_set::SmallSet;
 use tracing::debug;
 use tracing::info;
+use tracing::warn;
 
 use crate::commands::files::FilesArgs;
 use crate::commands::util::CommandExitStatus;
 use crate::config::error_kind::Severity;
 use crate::config::finder::ConfigFinder;
+use crate::error::baseline;
+use crate::error::baseline::BaselineProcessor;
+use crate::error::collector::CollectedErrors;
+use crate::error::diff::ErrorDiff;
 use crate::error::error::Error;
 use crate::error::error::print_error_counts;
+use crate::error::legacy::LegacyError;
 use crate::error::legacy::LegacyErrors;
+use crate::error::sarif::SarifLog;
 use crate::error::summarize::print_error_summary;
 use crate::error::suppress;
 use crate::module::typeshed::stdlib_search_path;
 use crate::report;
+use crate::state::error_cache::ErrorCache;
 use crate::state::load::FileContents;
 use crate::state::require::Require;
 use crate::state::state::State;
 use crate::state::state::Transaction;
 use crate::state::subscriber::ProgressBarSubscriber;
+use crate::state::sys_info_sharing::SharedResults;
+use crate::state::sys_info_sharing::SysInfoSharing;
 
 /// Check the given files.
 #[deny(clippy::missing_docs_in_private_items)]
@@ -128,6 +141,8 @@ enum OutputFormat {
     FullText,
     /// JSON output
     Json,
+    /// SARIF 2.1.0 output, including errors suppressed inline or by a baseline
+    Sarif,
     /// Only show error count, omitting individual errors
     OmitErrors,
 }
@@ -177,6 +192,8 @@ impl SnippetCheckArgs {
                 expectations: false,
                 remove_unused_ignores: false,
                 all: false,
+                fix: false,
+                error_cache_dir: None,
             },
         };
         match check_args.run_once_with_snippet(self.code, config_finder) {
@@ -270,6 +287,16 @@ struct OutputArgs {
     /// When specified, emit a sorted/formatted JSON of the errors to the baseline file
     #[arg(long, requires("baseline"))]
     update_baseline: bool,
+
+    /// Only report the errors that are new compared to a previous revision, which is either a
+    /// JSON error report (from `--output-format json`) or a git revision. Errors are matched on
+    /// their kind, enclosing function and message, and the exit code only depends on new errors.
+    #[arg(long, value_name = "REPORT_OR_REVISION")]
+    diff_against: Option<String>,
+
+    /// Write the new, fixed and unchanged errors compared to `--diff-against` to this file as JSON.
+    #[arg(long, value_name = "OUTPUT_FILE", requires("diff_against"))]
+    diff_output: Option<PathBuf>,
 }
 
 #[derive(Clone, Debug, ValueEnum, Default, PartialEq, Eq)]
@@ -299,6 +326,23 @@ struct BehaviorArgs {
     /// If we are removing unused ignores, should we remove all unused ignores or only Pyrefly specific `pyrefly: ignore`s?
     #[arg(long, requires("remove_unused_ignores"))]
     all: bool,
+    /// Remove the unused imports and local variables reported in the input files.
+    /// Only names reported by enabled `unused-import` and `unused-variable` errors are removed.
+    /// Unused parameters and private functions are left alone.
+    #[arg(long)]
+    fix: bool,
+    /// Persist per-module errors to this directory, and reuse them on later runs for modules
+    /// whose contents, config and dependencies are unchanged. This is an error-only cache:
+    /// exports and types are not stored, so any change to a dependency, even to a comment,
+    /// means rechecking, and modules that are rechecked still compute the types of all their
+    /// dependencies. Ignored in watch mode.
+    #[arg(
+        long,
+        value_name = "DIR",
+        conflicts_with = "check_all",
+        env = clap_env("ERROR_CACHE_DIR")
+    )]
+    error_cache_dir: Option<PathBuf>,
 }
 
 impl OutputFormat {
@@ -365,25 +409,64 @@ impl OutputFormat {
         Self::buffered_write_error_json(stdout(), relative_to, errors)
     }
 
+    fn write_error_sarif(
+        writer: impl Write,
+        relative_to: &Path,
+        errors: &CollectedErrors,
+    ) -> anyhow::Result<()> {
+        let mut writer = BufWriter::new(writer);
+        let log = SarifLog::from_errors(
+            relative_to,
+            &errors.shown,
+            &errors.suppressed,
+            &errors.baseline,
+        );
+        serde_json::to_writer_pretty(&mut writer, &log)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    fn write_error_sarif_to_file(
+        path: &Path,
+        relative_to: &Path,
+        errors: &CollectedErrors,
+    ) -> anyhow::Result<()> {
+        fn f(path: &Path, relative_to: &Path, errors: &CollectedErrors) -> anyhow::Result<()> {
+            let file = File::create(path)?;
+            OutputFormat::write_error_sarif(file, relative_to, errors)
+        }
+        f(path, relative_to, errors)
+            .with_context(|| format!("while writing SARIF errors to `{}`", path.display()))
+    }
+
+    /// Write the shown errors. Only SARIF output includes the suppressed and baseline errors.
     fn write_errors_to_file(
         &self,
         path: &Path,
         relative_to: &Path,
-        errors: &[Error],
+        errors: &CollectedErrors,
     ) -> anyhow::Result<()> {
+        let shown = &errors.shown;
         match self {
-            Self::MinText => Self::write_error_text_to_file(path, relative_to, errors, false),
-            Self::FullText => Self::write_error_text_to_file(path, relative_to, errors, true),
-            Self::Json => Self::write_error_json_to_file(path, relative_to, errors),
+            Self::MinText => Self::write_error_text_to_file(path, relative_to, shown, false),
+            Self::FullText => Self::write_error_text_to_file(path, relative_to, shown, true),
+            Self::Json => Self::write_error_json_to_file(path, relative_to, shown),
+            Self::Sarif => Self::write_error_sarif_to_file(path, relative_to, errors),
             Self::OmitErrors => Ok(()),
         }
     }
 
-    fn write_errors_to_console(&self, relative_to: &Path, errors: &[Error]) -> anyhow::Result<()> {
+    fn write_errors_to_console(
+        &self,
+        relative_to: &Path,
+        errors: &CollectedErrors,
+    ) -> anyhow::Result<()> {
+        let shown = &errors.shown;
         match self {
-            Self::MinText => Self::write_error_text_to_console(relative_to, errors, false),
-            Self::FullText => Self::write_error_text_to_console(relative_to, errors, true),
-            Self::Json => Self::write_error_json_to_console(relative_to, errors),
+            Self::MinText => Self::write_error_text_to_console(relative_to, shown, false),
+            Self::FullText => Self::write_error_text_to_console(relative_to, shown, true),
+            Self::Json => Self::write_error_json_to_console(relative_to, shown),
+            Self::Sarif => Self::write_error_sarif(stdout(), relative_to, errors),
             Self::OmitErrors => Ok(()),
         }
     }
@@ -424,7 +507,11 @@ impl Handles {
         let reloaded_source_dbs = ConfigFile::query_source_db(&configs, false);
         let result = configs
             .iter()
-            .flat_map(|(c, files)| files.iter().map(|p| c.handle_from_module_path(p.dupe())))
+            .flat_map(|(c, files)| {
+                files
+                    .iter()
+                    .flat_map(|p| Self::with_python_matrix(c, c.handle_from_module_path(p.dupe())))
+            })
             .collect();
         let reloaded_configs = configs
             .into_iter()
@@ -438,6 +525,19 @@ impl Handles {
         (result, reloaded_configs, Vec::new())
     }
 
+    /// One handle per distinct Python version and platform in the config, including those in its
+    /// `python-matrix`, or just `handle` if there is no matrix.
+    fn with_python_matrix(config: &ConfigFile, handle: Handle) -> Vec<Handle> {
+        if config.python_matrix.is_empty() {
+            return vec![handle];
+        }
+        config
+            .get_sys_infos()
+            .into_iter()
+            .map(|sys_info| Handle::new(handle.module(), handle.path().dupe(), sys_info))
+            .collect()
+    }
+
     fn update<'a>(
         &mut self,
         created_files: impl Iterator<Item = &'a PathBuf>,
@@ -479,6 +579,144 @@ async fn get_watcher_events(watcher: &mut Watcher) -> anyhow::Result<Categorized
     }
 }
 
+/// The errors to compare against for `--diff-against`: either those in a JSON error report, or
+/// those from checking the same files in a temporary git worktree of the revision, so that their
+/// imports are resolved against the revision too.
+fn previous_errors(
+    against: &str,
+    config_finder: &ConfigFinder,
+    handles: &[Handle],
+    relative_to: &Path,
+) -> anyhow::Result<Vec<LegacyError>> {
+    let report = Path::new(against);
+    if report.is_file() {
+        let content = fs_anyhow::read_to_string(report)?;
+        let report: LegacyErrors = serde_json::from_str(&content)
+            .with_context(|| format!("while parsing the error report `{against}`"))?;
+        return Ok(report.errors);
+    }
+    let paths = handles
+        .iter()
+        .filter_map(|handle| match handle.path().details() {
+            ModulePathDetails::FileSystem(path) => Some((handle, path.canonicalize().ok()?)),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let git_dir = paths
+        .first()
+        .and_then(|(_, path)| path.parent())
+        .unwrap_or(Path::new("."));
+    if git(
+        git_dir,
+        &[
+            "rev-parse",
+            "--quiet",
+            "--verify",
+            &format!("{against}^{{commit}}"),
+        ],
+    )
+    .is_err()
+    {
+        return Err(anyhow::anyhow!(
+            "`{against}` is neither an error report nor a git revision"
+        ));
+    }
+    let root = PathBuf::from(git(git_dir, &["rev-parse", "--show-toplevel"])?.trim());
+    let worktree = tempfile::tempdir()?;
+    let worktree_path = worktree.path().to_string_lossy().into_owned();
+    git(
+        &root,
+        &["worktree", "add", "--detach", &worktree_path, against],
+    )?;
+    // Check the revision with the current config, moved to the worktree, so that it is checked
+    // the same way and its imports resolve within the worktree.
+    let mut rebased: SmallMap<ArcId<ConfigFile>, ArcId<ConfigFile>> = SmallMap::new();
+    let mut configs = SmallMap::new();
+    let mut previous_handles = Vec::new();
+    for (handle, path) in paths {
+        let Ok(relative) = path.strip_prefix(&root) else {
+            continue;
+        };
+        let path = worktree.path().join(relative);
+        if !path.exists() {
+            continue;
+        }
+        let config = config_finder.python_file(handle.module(), handle.path());
+        let config = rebased
+            .entry(config.dupe())
+            .or_insert_with(|| ArcId::new(config.rebase(&root, worktree.path())))
+            .dupe();
+        configs.insert(path.clone(), config);
+        previous_handles.push(Handle::new(
+            handle.module(),
+            ModulePath::filesystem(path),
+            handle.sys_info().dupe(),
+        ));
+    }
+    let errors = match rebased.values().next() {
+        Some(default_config) => check_revision(&previous_handles, configs, default_config.dupe()),
+        None => Vec::new(),
+    };
+    let previous_relative_to = relative_to
+        .canonicalize()
+        .ok()
+        .and_then(|dir| Some(worktree.path().join(dir.strip_prefix(&root).ok()?)))
+        .unwrap_or_else(|| worktree.path().to_path_buf());
+    let previous = LegacyErrors::from_errors_with_scopes(&previous_relative_to, &errors).errors;
+    if let Err(e) = git(&root, &["worktree", "remove", "--force", &worktree_path]) {
+        warn!("Failed to remove the worktree of `{against}`: {e:#}");
+    }
+    Ok(previous)
+}
+
+/// Check `handles` of another revision in a separate state, so they don't count towards this run,
+/// with `configs` for each of their paths. Modules the revision only imports use `default_config`.
+fn check_revision(
+    handles: &[Handle],
+    configs: SmallMap<PathBuf, ArcId<ConfigFile>>,
+    default_config: ArcId<ConfigFile>,
+) -> Vec<Error> {
+    let (default_load, default_fallback) = (default_config.dupe(), default_config.dupe());
+    let config_finder = ConfigFinder::new_custom(
+        Box::new(move |_, path| {
+            Ok(Some(
+                configs
+                    .get(path.as_path())
+                    .unwrap_or(&default_config)
+                    .dupe(),
+            ))
+        }),
+        Box::new(move |_| (default_load.dupe(), Vec::new())),
+        Box::new(move |_, _| default_fallback.dupe()),
+        Box::new(|| {}),
+    );
+    let state = Forgetter::new(State::new(config_finder), true);
+    let mut transaction =
+        Forgetter::new(state.as_ref().new_transaction(Require::Errors, None), true);
+    let transaction = transaction.as_mut();
+    transaction.run(handles, Require::Errors);
+    transaction.get_errors(handles).collect_errors().shown
+}
+
+/// Run git in `dir` and return its output, or an error if it fails.
+fn git(dir: &Path, args: &[&str]) -> anyhow::Result<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .with_context(|| format!("while running `git {}`", args.join(" ")))?;
+    if output.status.success() {
+        Ok(String::from_utf8(output.stdout)?)
+    } else {
+        Err(anyhow::anyhow!(
+            "`git {}` failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))
+    }
+}
+
 /// Structure accumulating timing information.
 struct Timings {
     /// The overall time we started.
@@ -697,15 +935,66 @@ impl CheckArgs {
         let mut memory_trace = MemoryUsageTrace::start(Duration::from_secs_f32(0.1));
 
         let type_check_start = Instant::now();
+        // The cache only stores errors, so can't be used if we need to retain anything else.
+        let cache = match &self.behavior.error_cache_dir {
+            Some(dir) if require == Require::Errors => Some(ErrorCache::new(dir.clone())),
+            _ => None,
+        };
+        // When checking against a `python-matrix`, each module has a handle per `SysInfo`, and we
+        // collect the errors for each `SysInfo` separately so we can tell where they occur.
+        let mut sys_info_handles: SmallMap<SysInfo, Vec<Handle>> = SmallMap::new();
+        for handle in handles {
+            sys_info_handles
+                .entry(handle.sys_info().dupe())
+                .or_default()
+                .push(handle.dupe());
+        }
+        // Each `SysInfo` is checked in turn, so a module can share the results of a `SysInfo` it
+        // was already checked with, if they can't differ.
+        let mut sharing = SysInfoSharing::default();
+        let mut sys_info_loads = Vec::with_capacity(sys_info_handles.len());
+        let mut checked_handles = Vec::new();
         transaction.set_subscriber(Some(Box::new(ProgressBarSubscriber::new())));
-        transaction.run(handles, require);
+        for (sys_info, handles) in sys_info_handles {
+            let (cached, uncached) = match &cache {
+                Some(cache) => cache.partition(&handles, transaction.config_finder()),
+                None => (Vec::new(), handles),
+            };
+            let mut checked = Vec::new();
+            let mut shared = SharedResults::default();
+            for handle in uncached {
+                match sharing.find(transaction, &handle) {
+                    Some(closure) => shared.add(closure),
+                    None => checked.push(handle),
+                }
+            }
+            if !shared.roots.is_empty() {
+                debug!(
+                    "Sharing the results of {} with `{sys_info}`",
+                    count(shared.roots.len(), "module"),
+                );
+            }
+            transaction.run(&checked, require);
+            sharing.add_checked(&checked);
+            checked_handles.extend(checked.iter().cloned());
+            sys_info_loads.push((sys_info, cached, checked, shared));
+        }
         transaction.set_subscriber(None);
 
         let loads = if self.behavior.check_all {
             transaction.get_all_errors()
         } else {
-            transaction.get_errors(handles)
+            let mut loads = transaction.get_errors(&checked_handles);
+            for (_, cached, _, _) in &sys_info_loads {
+                loads.extend(cached.clone());
+            }
+            loads
         };
+        if let Some(cache) = &cache
+            && let Err(e) = cache.save(transaction, &checked_handles)
+        {
+            warn!("Failed to write the error cache: {e:#}");
+        }
         timings.type_check = type_check_start.elapsed();
 
         let report_errors_start = Instant::now();
@@ -724,25 +1013,94 @@ impl CheckArgs {
             |x| PathBuf::from_str(x.as_str()).unwrap(),
         );
 
-        let errors = loads
-            .collect_errors_with_baseline(self.output.baseline.as_deref(), relative_to.as_path());
-        let shown_errors = if let Some(only) = &self.output.only {
-            let only = only.iter().collect::<SmallSet<_>>();
-            errors
-                .shown
-                .into_iter()
-                .filter(|e| only.contains(&e.error_kind()))
-                .collect()
+        // A missing baseline is created by `--update-baseline`, but one that can't be read is an
+        // error rather than silently reporting everything.
+        let baseline_processor = match &self.output.baseline {
+            Some(path) if path.exists() => Some(BaselineProcessor::from_file(path)?),
+            _ => None,
+        };
+        let mut errors = if sys_info_loads.len() > 1 {
+            let all_handles = if self.behavior.check_all {
+                transaction.handles()
+            } else {
+                Vec::new()
+            };
+            CollectedErrors::merge_sys_infos(
+                sys_info_loads
+                    .into_iter()
+                    .map(|(sys_info, cached, checked, shared)| {
+                        let mut loads = if self.behavior.check_all {
+                            // Every module checked with this `SysInfo`, including dependencies,
+                            // and those whose results are shared from another `SysInfo`.
+                            let own = all_handles
+                                .iter()
+                                .filter(|x| x.sys_info() == &sys_info)
+                                .collect::<Vec<_>>();
+                            let own_modules = own
+                                .iter()
+                                .map(|x| (x.module(), x.path().dupe()))
+                                .collect::<HashSet<_>>();
+                            transaction.get_errors(own.into_iter().chain(
+                                shared.closure.iter().filter(|x| {
+                                    !own_modules.contains(&(x.module(), x.path().dupe()))
+                                }),
+                            ))
+                        } else {
+                            transaction.get_errors(checked.iter().chain(&shared.roots))
+                        };
+                        loads.extend(cached);
+                        let errors = loads.collect_errors_with_baseline(
+                            baseline_processor.as_ref(),
+                            relative_to.as_path(),
+                        );
+                        (sys_info, errors)
+                    })
+                    .collect(),
+            )
         } else {
-            errors.shown
+            loads.collect_errors_with_baseline(baseline_processor.as_ref(), relative_to.as_path())
         };
+        let only = self
+            .output
+            .only
+            .as_ref()
+            .map(|only| only.iter().copied().collect::<SmallSet<_>>());
+        if let Some(only) = &only {
+            errors.shown.retain(|e| only.contains(&e.error_kind()));
+            errors.suppressed.retain(|e| only.contains(&e.error_kind()));
+            errors.disabled.retain(|e| only.contains(&e.error_kind()));
+        }
+
+        if let Some(processor) = &baseline_processor {
+            let checked_paths = handles
+                .iter()
+                .map(|handle| {
+                    let path = handle.path().as_path();
+                    path.strip_prefix(&relative_to)
+                        .unwrap_or(path)
+                        .to_string_lossy()
+                        .into_owned()
+                })
+                .collect::<HashSet<_>>();
+            let fixed =
+                processor.fixed_entries(&errors.baseline, &checked_paths, relative_to.as_path());
+            if !fixed.is_empty() {
+                info!(
+                    "{} in the baseline no longer occur and can be removed with `--update-baseline`:",
+                    count(fixed.len(), "error")
+                );
+                for entry in fixed {
+                    info!("  {}:{}: [{}]", entry.path, entry.line, entry.name);
+                }
+            }
+        }
 
         // We update the baseline file if requested, after reporting any new errors using the old baseline
         if self.output.update_baseline
             && let Some(baseline_path) = &self.output.baseline
         {
-            let mut new_baseline = shown_errors.clone();
-            new_baseline.extend(errors.baseline);
+            let mut new_baseline = errors.shown.clone();
+            new_baseline.extend(errors.baseline.iter().cloned());
             new_baseline.sort_by_cached_key(|error| {
                 (
                     error.path().to_string(),
@@ -751,24 +1109,50 @@ impl CheckArgs {
                     error.error_kind(),
                 )
             });
-            OutputFormat::write_error_json_to_file(
-                baseline_path,
+            baseline::write_baseline(baseline_path, relative_to.as_path(), &new_baseline)?;
+        }
+        // The whole baseline is needed to find fixed entries and update it, so only filter it
+        // once it is just reported.
+        if let Some(only) = &only {
+            errors.baseline.retain(|e| only.contains(&e.error_kind()));
+        }
+
+        if let Some(against) = &self.output.diff_against {
+            let previous = previous_errors(
+                against,
+                transaction.config_finder(),
+                handles,
                 relative_to.as_path(),
-                &new_baseline,
             )?;
+            let (diff, new_errors) = ErrorDiff::new(
+                previous,
+                std::mem::take(&mut errors.shown),
+                relative_to.as_path(),
+            );
+            info!(
+                "{} new, {} fixed and {} unchanged errors compared to `{against}`",
+                number_thousands(diff.new.len()),
+                number_thousands(diff.fixed.len()),
+                number_thousands(diff.unchanged.len()),
+            );
+            if let Some(path) = &self.output.diff_output {
+                fs_anyhow::write(path, serde_json::to_string_pretty(&diff)?)?;
+            }
+            errors.shown = new_errors;
+            // Configuration errors are not new, so don't affect the exit code.
+            config_errors_count = 0;
         }
 
         if let Some(path) = &self.output.output {
-            self.output.output_format.write_errors_to_file(
-                path,
-                relative_to.as_path(),
-                &shown_errors,
-            )?;
+            self.output
+                .output_format
+                .write_errors_to_file(path, relative_to.as_path(), &errors)?;
         } else {
             self.output
                 .output_format
-                .write_errors_to_console(relative_to.as_path(), &shown_errors)?;
+                .write_errors_to_console(relative_to.as_path(), &errors)?;
         }
+        let shown_errors = errors.shown;
         memory_trace.stop();
         if let Some(limit) = self.output.count_errors {
             print_error_counts(&shown_errors, limit);
@@ -804,7 +1188,9 @@ impl CheckArgs {
                 took {timings}; memory ({})",
                 count(handles.len(), "module"),
                 count(
-                    transaction.module_count() - handles.len(),
+                    transaction
+                        .module_count()
+                        .saturating_sub(checked_handles.len()),
                     "dependent module"
                 ),
                 count(user_lines + dep_lines, "line"),
@@ -852,6 +1238,9 @@ impl CheckArgs {
         if self.behavior.remove_unused_ignores {
             suppress::remove_unused_ignores(&loads, self.behavior.all);
         }
+        if self.behavior.fix {
+            suppress::remove_unused(&shown_errors);
+        }
         if self.behavior.expectations {
             loads.check_against_expectations()?;
             Ok((CommandExitStatus::Success, shown_errors))
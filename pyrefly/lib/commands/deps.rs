@@ -0,0 +1,542 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use std::fmt::Write as _;
+use std::path::PathBuf;
+
+use clap::Parser;
+use clap::ValueEnum;
+use dupe::Dupe;
+use pyrefly_build::handle::Handle;
+use pyrefly_config::args::ConfigOverrideArgs;
+use pyrefly_config::finder::ConfigFinder;
+use pyrefly_python::module_name::ModuleName;
+use pyrefly_python::module_path::ModulePathDetails;
+use pyrefly_util::forgetter::Forgetter;
+use pyrefly_util::fs_anyhow;
+use pyrefly_util::includes::Includes;
+use ruff_python_ast::ExceptHandler;
+use ruff_python_ast::Expr;
+use ruff_python_ast::Stmt;
+use ruff_python_ast::UnaryOp;
+use serde::Serialize;
+use starlark_map::small_map::SmallMap;
+use starlark_map::small_set::SmallSet;
+
+use crate::commands::check::Handles;
+use crate::commands::files::FilesArgs;
+use crate::commands::util::CommandExitStatus;
+use crate::state::require::Require;
+use crate::state::state::State;
+use crate::state::state::Transaction;
+
+/// The format to write the import graph in.
+#[derive(Debug, Clone, Copy, ValueEnum, Default, PartialEq, Eq)]
+enum DepsFormat {
+    /// A JSON object with `modules`, `imports` and `cycles`.
+    #[default]
+    Json,
+    /// A Graphviz DOT digraph.
+    Dot,
+}
+
+/// Arguments for the deps command which exports the module import graph
+#[deny(clippy::missing_docs_in_private_items)]
+#[derive(Debug, Parser, Clone)]
+pub struct DepsArgs {
+    /// Which files to compute the import graph of.
+    #[command(flatten)]
+    files: FilesArgs,
+
+    /// Type checking arguments and configuration
+    #[command(flatten)]
+    config_override: ConfigOverrideArgs,
+
+    /// The format to write the import graph in.
+    #[arg(long, value_enum, default_value_t)]
+    output_format: DepsFormat,
+
+    /// Write the import graph to this file, rather than to stdout.
+    #[arg(long, short = 'o')]
+    output: Option<PathBuf>,
+}
+
+impl DepsArgs {
+    pub fn run(self) -> anyhow::Result<CommandExitStatus> {
+        self.config_override.validate()?;
+        let (files_to_check, config_finder) = self.files.resolve(self.config_override)?;
+        let graph = Self::run_inner(files_to_check, config_finder)?;
+        let output = match self.output_format {
+            DepsFormat::Json => serde_json::to_string_pretty(&graph)?,
+            DepsFormat::Dot => graph.to_dot(),
+        };
+        match &self.output {
+            Some(path) => fs_anyhow::write(path, output)?,
+            None => println!("{output}"),
+        }
+        Ok(CommandExitStatus::Success)
+    }
+
+    fn run_inner(
+        files_to_check: Box<dyn Includes>,
+        config_finder: ConfigFinder,
+    ) -> anyhow::Result<ImportGraph> {
+        let expanded_file_list = config_finder.checkpoint(files_to_check.files())?;
+        let state = State::new(config_finder);
+        let holder = Forgetter::new(state, false);
+        let handles = Handles::new(expanded_file_list);
+        let mut forgetter = Forgetter::new(
+            holder.as_ref().new_transaction(Require::Exports, None),
+            true,
+        );
+        let transaction = forgetter.as_mut();
+
+        let (handles, _, sourcedb_errors) = handles.all(holder.as_ref().config_finder());
+        if !sourcedb_errors.is_empty() {
+            for error in sourcedb_errors {
+                error.print();
+            }
+            return Err(anyhow::anyhow!("Failed to query sourcedb."));
+        }
+        // Imports are only resolved when building bindings, and we need the AST afterwards.
+        transaction.run(&handles, Require::Everything);
+        Ok(ImportGraph::new(transaction, &handles))
+    }
+}
+
+/// Where an import was resolved to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+enum ImportSource {
+    /// A `.py` file in the project or on the search path.
+    Source,
+    /// A `.pyi` file in the project or on the search path.
+    Stub,
+    /// A namespace package, which has no file of its own.
+    Namespace,
+    /// Stubs bundled with Pyrefly, from typeshed or otherwise.
+    BundledTypeshed,
+    /// A file under one of the site-package paths.
+    SitePackages,
+}
+
+impl ImportSource {
+    fn new(transaction: &Transaction, importer: &Handle, imported: &Handle) -> Self {
+        let path = imported.path();
+        match path.details() {
+            ModulePathDetails::BundledTypeshed(_)
+            | ModulePathDetails::BundledTypeshedThirdParty(_)
+            | ModulePathDetails::BundledThirdParty(_) => return Self::BundledTypeshed,
+            ModulePathDetails::Namespace(_) => return Self::Namespace,
+            ModulePathDetails::FileSystem(_) | ModulePathDetails::Memory(_) => {}
+        }
+        if let Some(config) = transaction.get_config(importer)
+            && config
+                .site_package_path()
+                .any(|site_packages| path.as_path().starts_with(site_packages))
+        {
+            Self::SitePackages
+        } else if path.is_interface() {
+            Self::Stub
+        } else {
+            Self::Source
+        }
+    }
+}
+
+/// A module whose imports were analyzed.
+#[derive(Debug, Serialize)]
+struct ModuleNode {
+    name: ModuleName,
+    path: String,
+}
+
+/// A resolved import from one module to another.
+#[derive(Debug, Serialize)]
+struct ImportEdge {
+    from: ModuleName,
+    to: ModuleName,
+    /// Whether every import of `to` in `from` is guarded by `if TYPE_CHECKING:`.
+    type_checking_only: bool,
+    source: ImportSource,
+    path: String,
+}
+
+/// The import graph of a set of modules. Only imports Pyrefly resolved while checking are
+/// included, so imports in branches that are unreachable for the configured Python version
+/// and platform (or under `if not TYPE_CHECKING:`) are left out.
+#[derive(Debug, Serialize)]
+struct ImportGraph {
+    modules: Vec<ModuleNode>,
+    imports: Vec<ImportEdge>,
+    /// Groups of modules which (transitively) import each other, i.e. the strongly
+    /// connected components of the graph with more than one member, or a self import.
+    cycles: Vec<Vec<ModuleName>>,
+}
+
+impl ImportGraph {
+    fn new(transaction: &Transaction, handles: &[Handle]) -> Self {
+        let mut modules = Vec::new();
+        let mut imports = Vec::new();
+        for handle in handles {
+            let Some(ast) = transaction.get_ast(handle) else {
+                continue;
+            };
+            modules.push(ModuleNode {
+                name: handle.module(),
+                path: handle.path().as_path().display().to_string(),
+            });
+            let mut found = Vec::new();
+            collect_imports(
+                &ast.body,
+                handle.module(),
+                handle.path().is_init(),
+                false,
+                &mut found,
+            );
+            let imports_of: SmallMap<ModuleName, Handle> = transaction
+                .get_imports(handle)
+                .into_iter()
+                .map(|imported| (imported.module(), imported))
+                .collect();
+            // An import counts as runtime if any of its occurrences is.
+            let mut resolved: SmallMap<ModuleName, (Handle, bool)> = SmallMap::new();
+            for (name, type_checking) in found {
+                let Some(imported) = imports_of.get(&name) else {
+                    continue;
+                };
+                match resolved.get_mut(&name) {
+                    Some((_, tc)) => *tc &= type_checking,
+                    None => {
+                        resolved.insert(name, (imported.dupe(), type_checking));
+                    }
+                }
+            }
+            for (name, (imported, type_checking)) in resolved {
+                imports.push(ImportEdge {
+                    from: handle.module(),
+                    to: name,
+                    type_checking_only: type_checking,
+                    source: ImportSource::new(transaction, handle, &imported),
+                    path: imported.path().as_path().display().to_string(),
+                });
+            }
+        }
+        modules.sort_by(|a, b| a.name.as_str().cmp(b.name.as_str()));
+        imports.sort_by(|a, b| {
+            (a.from.as_str(), a.to.as_str()).cmp(&(b.from.as_str(), b.to.as_str()))
+        });
+        let cycles = find_cycles(&modules, &imports);
+        Self {
+            modules,
+            imports,
+            cycles,
+        }
+    }
+
+    fn to_dot(&self) -> String {
+        let mut res = "digraph imports {\n".to_owned();
+        let project: SmallSet<ModuleName> = self.modules.iter().map(|m| m.name).collect();
+        for module in &self.modules {
+            writeln!(res, "  \"{}\";", module.name).unwrap();
+        }
+        let external: SmallSet<ModuleName> = self
+            .imports
+            .iter()
+            .map(|e| e.to)
+            .filter(|m| !project.contains(m))
+            .collect();
+        for module in external {
+            writeln!(res, "  \"{module}\" [shape=box, color=gray];").unwrap();
+        }
+        for edge in &self.imports {
+            let style = if edge.type_checking_only {
+                ", style=dashed"
+            } else {
+                ""
+            };
+            let source = serde_json::to_value(edge.source).unwrap();
+            writeln!(
+                res,
+                "  \"{}\" -> \"{}\" [source={source}{style}];",
+                edge.from, edge.to
+            )
+            .unwrap();
+        }
+        for (i, cycle) in self.cycles.iter().enumerate() {
+            writeln!(res, "  subgraph cluster_cycle_{i} {{").unwrap();
+            writeln!(res, "    label=\"cycle\";\n    color=red;").unwrap();
+            for module in cycle {
+                writeln!(res, "    \"{module}\";").unwrap();
+            }
+            res.push_str("  }\n");
+        }
+        res.push_str("}\n");
+        res
+    }
+}
+
+/// Whether an `if` test is `TYPE_CHECKING` (`Some(true)`) or `not TYPE_CHECKING` (`Some(false)`).
+fn type_checking_test(test: &Expr) -> Option<bool> {
+    match test {
+        Expr::Name(x) if x.id == "TYPE_CHECKING" => Some(true),
+        Expr::Attribute(x) if x.attr.id == "TYPE_CHECKING" => Some(true),
+        Expr::UnaryOp(x) if x.op == UnaryOp::Not => type_checking_test(&x.operand).map(|b| !b),
+        _ => None,
+    }
+}
+
+/// Collect every module imported anywhere in `body`, along with whether the import is only
+/// reached when type checking. For `from x import y` we also record `x.y`, which is only
+/// kept if it resolves to a submodule.
+fn collect_imports(
+    body: &[Stmt],
+    module: ModuleName,
+    is_init: bool,
+    type_checking: bool,
+    res: &mut Vec<(ModuleName, bool)>,
+) {
+    let recurse = |body: &[Stmt], type_checking: bool, res: &mut Vec<(ModuleName, bool)>| {
+        collect_imports(body, module, is_init, type_checking, res)
+    };
+    for stmt in body {
+        match stmt {
+            Stmt::Import(x) => {
+                for alias in &x.names {
+                    res.push((ModuleName::from_name(&alias.name.id), type_checking));
+                }
+            }
+            Stmt::ImportFrom(x) => {
+                let Some(base) =
+                    module.new_maybe_relative(is_init, x.level, x.module.as_ref().map(|m| &m.id))
+                else {
+                    continue;
+                };
+                res.push((base, type_checking));
+                for alias in &x.names {
+                    if alias.name.as_str() != "*" {
+                        res.push((base.append(&alias.name.id), type_checking));
+                    }
+                }
+            }
+            Stmt::If(x) => {
+                let test = type_checking_test(&x.test);
+                recurse(&x.body, type_checking || test == Some(true), res);
+                for clause in &x.elif_else_clauses {
+                    let clause_type_checking = match &clause.test {
+                        None => test == Some(false),
+                        Some(test) => type_checking_test(test) == Some(true),
+                    };
+                    recurse(&clause.body, type_checking || clause_type_checking, res);
+                }
+            }
+            Stmt::FunctionDef(x) => recurse(&x.body, type_checking, res),
+            Stmt::ClassDef(x) => recurse(&x.body, type_checking, res),
+            Stmt::For(x) => {
+                recurse(&x.body, type_checking, res);
+                recurse(&x.orelse, type_checking, res);
+            }
+            Stmt::While(x) => {
+                recurse(&x.body, type_checking, res);
+                recurse(&x.orelse, type_checking, res);
+            }
+            Stmt::With(x) => recurse(&x.body, type_checking, res),
+            Stmt::Try(x) => {
+                recurse(&x.body, type_checking, res);
+                for ExceptHandler::ExceptHandler(handler) in &x.handlers {
+                    recurse(&handler.body, type_checking, res);
+                }
+                recurse(&x.orelse, type_checking, res);
+                recurse(&x.finalbody, type_checking, res);
+            }
+            Stmt::Match(x) => {
+                for case in &x.cases {
+                    recurse(&case.body, type_checking, res);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Find the import cycles among `modules` using Tarjan's strongly connected components
+/// algorithm. Each cycle is sorted, and the cycles are ordered by their first module.
+fn find_cycles(modules: &[ModuleNode], imports: &[ImportEdge]) -> Vec<Vec<ModuleName>> {
+    let index: SmallMap<ModuleName, usize> = modules
+        .iter()
+        .enumerate()
+        .map(|(i, m)| (m.name, i))
+        .collect();
+    let mut successors = vec![Vec::new(); modules.len()];
+    let mut self_imports = SmallSet::new();
+    for edge in imports {
+        if let (Some(&from), Some(&to)) = (index.get(&edge.from), index.get(&edge.to)) {
+            successors[from].push(to);
+            if from == to {
+                self_imports.insert(from);
+            }
+        }
+    }
+
+    struct Tarjan<'a> {
+        successors: &'a [Vec<usize>],
+        next_index: usize,
+        indices: Vec<Option<usize>>,
+        lowlinks: Vec<usize>,
+        on_stack: Vec<bool>,
+        stack: Vec<usize>,
+        components: Vec<Vec<usize>>,
+    }
+
+    impl Tarjan<'_> {
+        // Recursion depth is bounded by the length of the longest import chain.
+        fn visit(&mut self, v: usize) {
+            self.indices[v] = Some(self.next_index);
+            self.lowlinks[v] = self.next_index;
+            self.next_index += 1;
+            self.stack.push(v);
+            self.on_stack[v] = true;
+            for &w in &self.successors[v] {
+                match self.indices[w] {
+                    None => {
+                        self.visit(w);
+                        self.lowlinks[v] = self.lowlinks[v].min(self.lowlinks[w]);
+                    }
+                    Some(w_index) if self.on_stack[w] => {
+                        self.lowlinks[v] = self.lowlinks[v].min(w_index);
+                    }
+                    Some(_) => {}
+                }
+            }
+            if Some(self.lowlinks[v]) == self.indices[v] {
+                let mut component = Vec::new();
+                while let Some(w) = self.stack.pop() {
+                    self.on_stack[w] = false;
+                    component.push(w);
+                    if w == v {
+                        break;
+                    }
+                }
+                self.components.push(component);
+            }
+        }
+    }
+
+    let mut tarjan = Tarjan {
+        successors: &successors,
+        next_index: 0,
+        indices: vec![None; modules.len()],
+        lowlinks: vec![0; modules.len()],
+        on_stack: vec![false; modules.len()],
+        stack: Vec::new(),
+        components: Vec::new(),
+    };
+    for v in 0..modules.len() {
+        if tarjan.indices[v].is_none() {
+            tarjan.visit(v);
+        }
+    }
+    let mut cycles: Vec<Vec<ModuleName>> = tarjan
+        .components
+        .into_iter()
+        .filter(|c| c.len() > 1 || self_imports.contains(&c[0]))
+        .map(|c| {
+            let mut names: Vec<ModuleName> = c.into_iter().map(|i| modules[i].name).collect();
+            names.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+            names
+        })
+        .collect();
+    cycles.sort_by(|a, b| a[0].as_str().cmp(b[0].as_str()));
+    cycles
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::test::util::TestEnv;
+
+    fn import_graph(files: &[(&str, &str)]) -> ImportGraph {
+        let mut env = TestEnv::new();
+        for (name, code) in files {
+            env.add(name, code);
+        }
+        let (state, handle) = env.to_state();
+        let handles: Vec<Handle> = files.iter().map(|(name, _)| handle(name)).collect();
+        ImportGraph::new(&state.transaction(), &handles)
+    }
+
+    fn edges(graph: &ImportGraph) -> Vec<(String, String, bool)> {
+        graph
+            .imports
+            .iter()
+            .map(|e| (e.from.to_string(), e.to.to_string(), e.type_checking_only))
+            .collect()
+    }
+
+    #[test]
+    fn test_type_checking_imports() {
+        let graph = import_graph(&[
+            (
+                "a",
+                r#"
+from typing import TYPE_CHECKING
+import b
+if TYPE_CHECKING:
+    import c
+    import b
+else:
+    import os
+"#,
+            ),
+            ("b", ""),
+            ("c", ""),
+        ]);
+        assert_eq!(
+            edges(&graph),
+            vec![
+                ("a".to_owned(), "b".to_owned(), false),
+                ("a".to_owned(), "c".to_owned(), true),
+                ("a".to_owned(), "typing".to_owned(), false),
+            ]
+        );
+        let typing = graph.imports.iter().find(|e| e.to.as_str() == "typing");
+        assert_eq!(typing.unwrap().source, ImportSource::BundledTypeshed);
+        assert_eq!(graph.imports[0].source, ImportSource::Source);
+        assert!(graph.cycles.is_empty());
+    }
+
+    #[test]
+    fn test_cycles() {
+        let graph = import_graph(&[
+            ("a", "import b\n"),
+            ("b", "def f():\n    from a import missing\n"),
+            ("c", "import c\n"),
+            ("d", "from a import *\n"),
+        ]);
+        assert_eq!(
+            edges(&graph),
+            vec![
+                ("a".to_owned(), "b".to_owned(), false),
+                ("b".to_owned(), "a".to_owned(), false),
+                ("c".to_owned(), "c".to_owned(), false),
+                ("d".to_owned(), "a".to_owned(), false),
+            ]
+        );
+        assert_eq!(
+            graph.cycles,
+            vec![
+                vec![ModuleName::from_str("a"), ModuleName::from_str("b")],
+                vec![ModuleName::from_str("c")],
+            ]
+        );
+        let dot = graph.to_dot();
+        assert!(dot.contains("\"b\" -> \"a\" [source=\"source\"];"));
+        assert!(dot.contains("subgraph cluster_cycle_0"));
+    }
+}
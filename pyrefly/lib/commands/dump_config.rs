@@ -10,6 +10,7 @@ use std::path::Path;
 use std::str::FromStr;
 
 use clap::Parser;
+use clap::ValueEnum;
 use pyrefly_config::args::ConfigOverrideArgs;
 use pyrefly_python::module_path::ModulePath;
 use pyrefly_util::absolutize::Absolutize as _;
@@ -21,8 +22,10 @@ use crate::commands::check::FullCheckArgs;
 use crate::commands::check::Handles;
 use crate::commands::files::FilesArgs;
 use crate::commands::util::CommandExitStatus;
+use crate::config::base::ConfigBase;
 use crate::config::config::ConfigFile;
 use crate::config::config::ConfigSource;
+use crate::config::error_kind::ErrorKind;
 
 #[derive(Debug, Clone)]
 enum MaxFiles {
@@ -88,6 +91,31 @@ impl DumpConfigArgs {
     }
 }
 
+fn value_name(x: impl ValueEnum) -> String {
+    x.to_possible_value()
+        .map(|x| x.get_name().to_owned())
+        .unwrap_or_default()
+}
+
+/// Print the preset, `untyped-def-behavior` and the error severities that differ from the
+/// defaults. Presets have already been expanded into the other two by this point.
+fn print_error_settings(settings: &ConfigBase, indent: &str) {
+    if let Some(preset) = settings.preset {
+        println!("{indent}Preset: {}", value_name(preset));
+    }
+    if let Some(behavior) = settings.untyped_def_behavior {
+        println!("{indent}Untyped def behavior: {}", value_name(behavior));
+    }
+    if let Some(errors) = &settings.errors {
+        for kind in enum_iterator::all::<ErrorKind>() {
+            let severity = errors.severity(kind);
+            if severity != kind.default_severity() {
+                println!("{indent}{}: {severity}", kind.to_name());
+            }
+        }
+    }
+}
+
 fn dump_config(
     files: FilesArgs,
     config_override: ConfigOverrideArgs,
@@ -158,6 +186,18 @@ fn dump_config(
                 println!("    {path_part}");
             }
         }
+        println!("  Error settings:");
+        print_error_settings(&config.root, "    ");
+        for sub_config in &config.sub_configs {
+            let settings = &sub_config.settings;
+            if settings.preset.is_some()
+                || settings.untyped_def_behavior.is_some()
+                || settings.errors.is_some()
+            {
+                println!("  Error settings for `{}`:", sub_config.matches);
+                print_error_settings(settings, "    ");
+            }
+        }
     }
     Ok(CommandExitStatus::Success)
 }
@@ -14,6 +14,7 @@ use pyrefly_util::telemetry::Telemetry;
 
 use crate::commands::util::CommandExitStatus;
 use crate::lsp::non_wasm::server::capabilities;
+use crate::lsp::non_wasm::server::capabilities_to_json;
 use crate::lsp::non_wasm::server::lsp_loop;
 
 /// Pyrefly's indexing strategy for open projects when performing go-to-definition
@@ -87,8 +88,10 @@ fn initialize_connection(
     let (request_id, initialization_params) = connection.initialize_start()?;
     let initialization_params: InitializeParams =
         serde_json::from_value(initialization_params).unwrap();
-    let server_capabilities =
-        serde_json::to_value(capabilities(args.indexing_mode, &initialization_params)).unwrap();
+    let server_capabilities = capabilities_to_json(
+        args.indexing_mode,
+        capabilities(args.indexing_mode, &initialization_params),
+    );
     let initialize_data = serde_json::json!({
         "capabilities": server_capabilities,
         "serverInfo": {
@@ -9,6 +9,8 @@ pub mod all;
 pub mod buck_check;
 pub mod check;
 pub mod config_finder;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod deps;
 pub mod dump_config;
 pub mod files;
 #[cfg(not(target_arch = "wasm32"))]
@@ -20,6 +22,10 @@ pub mod lsp;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod report;
 #[cfg(not(target_arch = "wasm32"))]
+pub mod stubgen;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod suppress;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod tsp;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod util;
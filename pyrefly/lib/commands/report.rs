@@ -6,20 +6,26 @@
  */
 
 use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
 
 use clap::Parser;
+use clap::ValueEnum;
 use dupe::Dupe;
 use pyrefly_config::args::ConfigOverrideArgs;
 use pyrefly_config::finder::ConfigFinder;
 use pyrefly_python::module::Module;
 use pyrefly_python::module_name::ModuleName;
 use pyrefly_util::forgetter::Forgetter;
+use pyrefly_util::fs_anyhow;
 use pyrefly_util::includes::Includes;
 use regex::Regex;
 use ruff_python_ast::Parameters;
 use ruff_text_size::Ranged;
 use ruff_text_size::TextRange;
 use serde::Serialize;
+use tracing::info;
+use tracing::warn;
 
 use crate::binding::binding::Binding;
 use crate::binding::binding::BindingClass;
@@ -29,6 +35,8 @@ use crate::binding::bindings::Bindings;
 use crate::commands::check::Handles;
 use crate::commands::files::FilesArgs;
 use crate::commands::util::CommandExitStatus;
+use crate::report::coverage::CoverageReport;
+use crate::report::coverage::module_coverage;
 use crate::state::require::Require;
 use crate::state::state::State;
 
@@ -79,6 +87,16 @@ struct FileReport {
     suppressions: Vec<Suppression>,
 }
 
+/// Format of the type coverage report
+#[derive(Debug, Clone, Copy, Default, ValueEnum)]
+enum CoverageFormat {
+    /// Counts per module and package, with the line ranges of `Any`-typed expressions
+    #[default]
+    Json,
+    /// A page with the counts and the source of each module, highlighting `Any`-typed lines
+    Html,
+}
+
 /// Generate reports from pyrefly type checking results.
 #[deny(clippy::missing_docs_in_private_items)]
 #[derive(Debug, Clone, Parser)]
@@ -90,13 +108,38 @@ pub struct ReportArgs {
     /// Configuration override options
     #[command(flatten)]
     config_override: ConfigOverrideArgs,
+
+    /// Report type coverage instead: the share of annotated parameters and returns, and of
+    /// expressions whose type doesn't contain `Any`, per module and per package.
+    #[arg(long)]
+    coverage: bool,
+
+    /// The format of the type coverage report.
+    #[arg(long, value_enum, default_value_t, requires = "coverage")]
+    coverage_format: CoverageFormat,
+
+    /// Exit with an error if the percentage of expressions whose type doesn't contain `Any`
+    /// is below this threshold.
+    #[arg(long, value_name = "PERCENT", requires = "coverage")]
+    fail_under: Option<f64>,
+
+    /// Write the report to this file rather than stdout.
+    #[arg(long, short = 'o', value_name = "FILE")]
+    output: Option<PathBuf>,
 }
 
 impl ReportArgs {
     pub fn run(self) -> anyhow::Result<CommandExitStatus> {
         self.config_override.validate()?;
         let (files_to_check, config_finder) = self.files.resolve(self.config_override)?;
-        Self::run_inner(files_to_check, config_finder)
+        let coverage = self.coverage.then_some(self.coverage_format);
+        Self::run_inner(
+            files_to_check,
+            config_finder,
+            coverage,
+            self.fail_under,
+            self.output.as_deref(),
+        )
     }
 
     /// Helper to extract all parameters from Parameters struct
@@ -271,6 +314,9 @@ impl ReportArgs {
     fn run_inner(
         files_to_check: Box<dyn Includes>,
         config_finder: ConfigFinder,
+        coverage: Option<CoverageFormat>,
+        fail_under: Option<f64>,
+        output: Option<&Path>,
     ) -> anyhow::Result<CommandExitStatus> {
         let expanded_file_list = config_finder.checkpoint(files_to_check.files())?;
         let state = State::new(config_finder);
@@ -292,11 +338,14 @@ impl ReportArgs {
         }
 
         let mut report: HashMap<String, FileReport> = HashMap::new();
+        let mut modules = Vec::new();
 
         for handle in handles {
             transaction.run(&[handle.dupe()], Require::Everything);
 
-            if let Some(bindings) = transaction.get_bindings(&handle)
+            if coverage.is_some() {
+                modules.extend(module_coverage(transaction, &handle));
+            } else if let Some(bindings) = transaction.get_bindings(&handle)
                 && let Some(module) = transaction.get_module_info(&handle)
             {
                 let line_count = module.lined_buffer().line_index().line_count();
@@ -314,11 +363,39 @@ impl ReportArgs {
             }
         }
 
-        // Output JSON
-        let json = serde_json::to_string_pretty(&report)?;
-        println!("{}", json);
+        let mut status = CommandExitStatus::Success;
+        let output_str = match coverage {
+            None => serde_json::to_string_pretty(&report)?,
+            Some(format) => {
+                let report = CoverageReport::new(modules);
+                let total = &report.total;
+                info!(
+                    "Type coverage: {:.1}% of {} expressions have no `Any` in their type, {:.1}% of parameters and returns are annotated",
+                    total.expression_coverage(),
+                    total.expressions,
+                    total.annotation_coverage(),
+                );
+                if let Some(threshold) = fail_under
+                    && total.expression_coverage() < threshold
+                {
+                    warn!(
+                        "Type coverage of {:.1}% is below the `--fail-under` threshold of {threshold}%",
+                        total.expression_coverage()
+                    );
+                    status = CommandExitStatus::UserError;
+                }
+                match format {
+                    CoverageFormat::Json => serde_json::to_string_pretty(&report)?,
+                    CoverageFormat::Html => report.to_html(),
+                }
+            }
+        };
+        match output {
+            Some(path) => fs_anyhow::write(path, output_str)?,
+            None => println!("{output_str}"),
+        }
 
-        Ok(CommandExitStatus::Success)
+        Ok(status)
     }
 }
 
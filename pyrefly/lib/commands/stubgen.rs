@@ -0,0 +1,916 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use clap::Parser;
+use pyrefly_build::handle::Handle;
+use pyrefly_config::args::ConfigOverrideArgs;
+use pyrefly_config::finder::ConfigFinder;
+use pyrefly_python::module::Module;
+use pyrefly_python::module_name::ModuleName;
+use pyrefly_python::short_identifier::ShortIdentifier;
+use pyrefly_types::display::TypeDisplayContext;
+use pyrefly_types::simplify::unions;
+use pyrefly_types::tuple::Tuple;
+use pyrefly_types::types::Union;
+use pyrefly_util::forgetter::Forgetter;
+use pyrefly_util::fs_anyhow;
+use pyrefly_util::includes::Includes;
+use ruff_python_ast::Decorator;
+use ruff_python_ast::Expr;
+use ruff_python_ast::Parameter;
+use ruff_python_ast::Parameters;
+use ruff_python_ast::Stmt;
+use ruff_python_ast::StmtAnnAssign;
+use ruff_python_ast::StmtAssign;
+use ruff_python_ast::StmtClassDef;
+use ruff_python_ast::StmtFunctionDef;
+use ruff_python_ast::StmtIf;
+use ruff_python_ast::name::Name;
+use ruff_text_size::Ranged;
+use starlark_map::Hashed;
+use starlark_map::small_set::SmallSet;
+use tracing::info;
+
+use crate::binding::binding::Key;
+use crate::commands::check::Handles;
+use crate::commands::files::FilesArgs;
+use crate::commands::util::CommandExitStatus;
+use crate::state::require::Require;
+use crate::state::state::State;
+use crate::state::state::Transaction;
+use crate::types::stdlib::Stdlib;
+use crate::types::types::AnyStyle;
+use crate::types::types::Type;
+
+/// Arguments for the stubgen command which writes `.pyi` stubs from inferred types
+#[deny(clippy::missing_docs_in_private_items)]
+#[derive(Debug, Parser, Clone)]
+pub struct StubgenArgs {
+    /// Which files to generate stubs for.
+    #[command(flatten)]
+    files: FilesArgs,
+
+    /// Type checking arguments and configuration
+    #[command(flatten)]
+    config_override: ConfigOverrideArgs,
+
+    /// Directory to write the generated stubs to.
+    #[arg(long, short = 'o', default_value = "out")]
+    output_dir: PathBuf,
+}
+
+impl StubgenArgs {
+    pub fn run(self) -> anyhow::Result<CommandExitStatus> {
+        self.config_override.validate()?;
+        let (files_to_check, config_finder) = self.files.resolve(self.config_override)?;
+        Self::run_inner(files_to_check, config_finder, &self.output_dir)
+    }
+
+    pub fn run_inner(
+        files_to_check: Box<dyn Includes>,
+        config_finder: ConfigFinder,
+        output_dir: &Path,
+    ) -> anyhow::Result<CommandExitStatus> {
+        let expanded_file_list = config_finder.checkpoint(files_to_check.files())?;
+        let state = State::new(config_finder);
+        let holder = Forgetter::new(state, false);
+        let handles = Handles::new(expanded_file_list);
+        let mut forgetter = Forgetter::new(
+            holder.as_ref().new_transaction(Require::Everything, None),
+            true,
+        );
+        let transaction = forgetter.as_mut();
+
+        let (handles, _, sourcedb_errors) = handles.all(holder.as_ref().config_finder());
+        if !sourcedb_errors.is_empty() {
+            for error in sourcedb_errors {
+                error.print();
+            }
+            return Err(anyhow::anyhow!("Failed to query sourcedb."));
+        }
+        // Stubs are already what we would produce, so leave them alone.
+        let handles = handles
+            .into_iter()
+            .filter(|handle| !handle.path().is_interface())
+            .collect::<Vec<_>>();
+        transaction.run(&handles, Require::Everything);
+        for handle in handles {
+            let Some(stub) = generate_stub(transaction, &handle) else {
+                continue;
+            };
+            let path = stub_path(output_dir, handle.module(), handle.path().is_init());
+            if let Some(parent) = path.parent() {
+                fs_anyhow::create_dir_all(parent)?;
+            }
+            fs_anyhow::write(&path, stub)?;
+            info!(
+                "Wrote stub for `{}` to `{}`",
+                handle.module(),
+                path.display()
+            );
+        }
+        Ok(CommandExitStatus::Success)
+    }
+}
+
+/// The location of the stub for `module` underneath `output_dir`, mirroring the package layout.
+fn stub_path(output_dir: &Path, module: ModuleName, is_init: bool) -> PathBuf {
+    let mut path = output_dir.to_path_buf();
+    for component in module.components() {
+        path.push(component.as_str());
+    }
+    if is_init {
+        path.push("__init__.pyi");
+    } else {
+        path.set_extension("pyi");
+    }
+    path
+}
+
+/// Generate the contents of a `.pyi` stub for the module behind `handle`.
+/// Returns `None` if the module has not been loaded.
+pub fn generate_stub(transaction: &Transaction, handle: &Handle) -> Option<String> {
+    let ast = transaction.get_ast(handle)?;
+    let module_info = transaction.get_module_info(handle)?;
+    let mut writer = StubWriter {
+        transaction,
+        handle,
+        stdlib: transaction.get_stdlib(handle),
+        module_info,
+        imports: SmallSet::new(),
+        imported: SmallSet::new(),
+        uses_incomplete: false,
+        output: String::new(),
+    };
+    writer.write_body(&ast.body, 0, StubContext::Module);
+    Some(writer.finish())
+}
+
+/// Where the statements currently being written live, which changes how they are stubbed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StubContext {
+    Module,
+    Class { is_enum: bool },
+}
+
+struct StubWriter<'a, 'b> {
+    transaction: &'a Transaction<'b>,
+    handle: &'a Handle,
+    module_info: Module,
+    stdlib: Arc<Stdlib>,
+    /// Modules that qualified names in rendered types refer to.
+    imports: SmallSet<ModuleName>,
+    /// Modules the source already imports at the top level, which we keep as written.
+    imported: SmallSet<ModuleName>,
+    uses_incomplete: bool,
+    output: String,
+}
+
+impl StubWriter<'_, '_> {
+    fn finish(self) -> String {
+        let mut header = String::new();
+        for module in self.imports.iter().filter(|m| !self.imported.contains(*m)) {
+            header.push_str(&format!("import {module}\n"));
+        }
+        if self.uses_incomplete {
+            header.push_str("from _typeshed import Incomplete\n");
+        }
+        if !header.is_empty() && !self.output.is_empty() {
+            header.push('\n');
+        }
+        header + &self.output
+    }
+
+    fn code(&self, x: impl Ranged) -> String {
+        self.module_info.code_at(x.range()).to_owned()
+    }
+
+    fn write_line(&mut self, indent: usize, line: &str) {
+        for _ in 0..indent {
+            self.output.push_str("    ");
+        }
+        self.output.push_str(line);
+        self.output.push('\n');
+    }
+
+    fn incomplete(&mut self) -> String {
+        self.uses_incomplete = true;
+        "Incomplete".to_owned()
+    }
+
+    fn type_of_key(&self, key: &Key) -> Option<Type> {
+        let bindings = self.transaction.get_bindings(self.handle)?;
+        bindings.key_to_idx_hashed_opt(Hashed::new(key))?;
+        self.transaction.get_type(self.handle, key)
+    }
+
+    fn type_of_expr(&self, x: &Expr) -> Option<Type> {
+        self.transaction.get_type_trace(self.handle, x.range())
+    }
+
+    /// Render a type as it should appear in a stub, recording any modules it needs imported.
+    /// Types that can't be written down, and any unknown parts, become `Incomplete`.
+    fn render_type(&mut self, ty: Type) -> String {
+        let ty = ty.promote_literals(&self.stdlib).clean_var();
+        let ty = match ty {
+            Type::Union(box Union { members, .. }) => unions(members),
+            ty => ty,
+        };
+        if !is_stubbable(&ty) {
+            return self.incomplete();
+        }
+        ty.universe(&mut |t| {
+            if matches!(t, Type::Any(AnyStyle::Implicit | AnyStyle::Error)) {
+                self.uses_incomplete = true;
+            }
+        });
+        let current = self.handle.module();
+        let mut ctx = TypeDisplayContext::new(&[&ty]);
+        ctx.always_display_module_name_except_builtins();
+        ctx.set_implicit_any_name("Incomplete");
+        ctx.set_current_module(current);
+        let rendered = ctx.display(&ty).to_string();
+        self.imports.extend(
+            ctx.displayed_modules()
+                .into_iter()
+                .filter(|module| *module != current),
+        );
+        rendered
+    }
+
+    fn render_type_opt(&mut self, ty: Option<Type>) -> String {
+        match ty {
+            Some(ty) => self.render_type(ty),
+            None => self.incomplete(),
+        }
+    }
+
+    /// Write the stub for a block of statements, returning whether anything was written.
+    fn write_body(&mut self, body: &[Stmt], indent: usize, context: StubContext) -> bool {
+        let start = self.output.len();
+        // Names with `@overload` signatures, whose undecorated implementation should be dropped.
+        let mut overloaded: SmallSet<Name> = SmallSet::new();
+        for stmt in body {
+            match stmt {
+                Stmt::Import(x) if context == StubContext::Module => {
+                    if indent == 0 {
+                        for alias in x.names.iter().filter(|a| a.asname.is_none()) {
+                            self.imported
+                                .insert(ModuleName::from_str(alias.name.as_str()));
+                        }
+                    }
+                    self.write_line(indent, &self.code(stmt));
+                }
+                Stmt::ImportFrom(x) if context == StubContext::Module => {
+                    if x.module.as_ref().is_none_or(|m| m.as_str() != "__future__") {
+                        self.write_line(indent, &self.code(stmt));
+                    }
+                }
+                Stmt::ClassDef(x) => self.write_class(x, indent),
+                Stmt::FunctionDef(x) => {
+                    if x.decorator_list.iter().any(|d| self.is_overload(d)) {
+                        overloaded.insert(x.name.id.clone());
+                    } else if x.decorator_list.is_empty() && overloaded.contains(&x.name.id) {
+                        continue;
+                    }
+                    self.write_function(x, indent, context);
+                }
+                Stmt::Assign(x) => self.write_assign(x, indent, context),
+                Stmt::AnnAssign(x) => self.write_ann_assign(x, indent, context),
+                Stmt::AugAssign(x) if is_dunder_all(&x.target) => {
+                    self.write_line(indent, &self.code(stmt));
+                }
+                Stmt::Expr(x)
+                    if let Expr::Call(call) = &*x.value
+                        && let Expr::Attribute(attr) = &*call.func
+                        && is_dunder_all(&attr.value) =>
+                {
+                    self.write_line(indent, &self.code(stmt));
+                }
+                Stmt::TypeAlias(_) => self.write_line(indent, &self.code(stmt)),
+                Stmt::If(x) => self.write_if(x, indent, context),
+                Stmt::Try(x) => {
+                    self.write_body(&x.body, indent, context);
+                }
+                _ => {}
+            }
+        }
+        self.output.len() > start
+    }
+
+    fn write_if(&mut self, x: &StmtIf, indent: usize, context: StubContext) {
+        let start = self.output.len();
+        self.write_line(indent, &format!("if {}:", self.code(&*x.test)));
+        let mut wrote = self.write_body_or_pass(&x.body, indent + 1, context);
+        for clause in &x.elif_else_clauses {
+            match &clause.test {
+                Some(test) => self.write_line(indent, &format!("elif {}:", self.code(test))),
+                None => self.write_line(indent, "else:"),
+            }
+            wrote |= self.write_body_or_pass(&clause.body, indent + 1, context);
+        }
+        if !wrote {
+            self.output.truncate(start);
+        }
+    }
+
+    fn write_body_or_pass(&mut self, body: &[Stmt], indent: usize, context: StubContext) -> bool {
+        let wrote = self.write_body(body, indent, context);
+        if !wrote {
+            self.write_line(indent, "pass");
+        }
+        wrote
+    }
+
+    fn write_decorators(&mut self, decorators: &[Decorator], indent: usize) {
+        for decorator in decorators {
+            self.write_line(indent, &format!("@{}", self.code(&decorator.expression)));
+        }
+    }
+
+    fn is_overload(&self, decorator: &Decorator) -> bool {
+        let code = self.code(&decorator.expression);
+        code == "overload" || code.ends_with(".overload")
+    }
+
+    fn write_class(&mut self, x: &StmtClassDef, indent: usize) {
+        self.write_decorators(&x.decorator_list, indent);
+        self.write_line(
+            indent,
+            &format!(
+                "class {}{}{}:",
+                x.name,
+                x.type_params
+                    .as_ref()
+                    .map_or_else(String::new, |t| self.code(&**t)),
+                x.arguments
+                    .as_ref()
+                    .map_or_else(String::new, |a| self.code(&**a)),
+            ),
+        );
+        let is_enum = match self.type_of_key(&Key::Definition(ShortIdentifier::new(&x.name))) {
+            Some(Type::ClassDef(cls)) => self
+                .transaction
+                .ad_hoc_solve(self.handle, |solver| {
+                    solver.get_metadata_for_class(&cls).is_enum()
+                })
+                .unwrap_or(false),
+            _ => false,
+        };
+        let start = self.output.len();
+        self.write_instance_attributes(x, indent + 1);
+        self.write_body(&x.body, indent + 1, StubContext::Class { is_enum });
+        if self.output.len() == start {
+            self.write_line(indent + 1, "...");
+        }
+    }
+
+    /// Write declarations for attributes assigned through `self` in methods, which
+    /// aren't otherwise declared in the class body.
+    fn write_instance_attributes(&mut self, x: &StmtClassDef, indent: usize) {
+        let mut declared: SmallSet<Name> = SmallSet::new();
+        for stmt in &x.body {
+            match stmt {
+                Stmt::Assign(x) => {
+                    for target in &x.targets {
+                        if let Expr::Name(name) = target {
+                            declared.insert(name.id.clone());
+                        }
+                    }
+                }
+                Stmt::AnnAssign(x) if let Expr::Name(name) = &*x.target => {
+                    declared.insert(name.id.clone());
+                }
+                Stmt::FunctionDef(x) => {
+                    declared.insert(x.name.id.clone());
+                }
+                _ => {}
+            }
+        }
+        let mut attributes = Vec::new();
+        for stmt in &x.body {
+            let Stmt::FunctionDef(method) = stmt else {
+                continue;
+            };
+            if method.decorator_list.iter().any(|d| {
+                matches!(
+                    self.code(&d.expression).as_str(),
+                    "staticmethod" | "classmethod"
+                )
+            }) {
+                continue;
+            }
+            let Some(receiver) = first_parameter(&method.parameters) else {
+                continue;
+            };
+            collect_self_assignments(&method.body, &receiver.name.id, &mut attributes);
+        }
+        for (name, annotation, value) in attributes {
+            if declared.contains(name) {
+                continue;
+            }
+            declared.insert(name.clone());
+            let ty = match (annotation, value) {
+                (Some(annotation), _) => self.code(annotation),
+                (None, Some(value)) => {
+                    let ty = self.type_of_expr(value);
+                    self.render_type_opt(ty)
+                }
+                (None, None) => self.incomplete(),
+            };
+            self.write_line(indent, &format!("{name}: {ty}"));
+        }
+    }
+
+    fn write_function(&mut self, x: &StmtFunctionDef, indent: usize, context: StubContext) {
+        self.write_decorators(&x.decorator_list, indent);
+        let is_method = matches!(context, StubContext::Class { .. })
+            && !x
+                .decorator_list
+                .iter()
+                .any(|d| self.code(&d.expression) == "staticmethod");
+        let parameters = self.render_parameters(&x.parameters, is_method);
+        let returns = match &x.returns {
+            Some(returns) => self.code(&**returns),
+            None if is_method && x.name.as_str() == "__init__" => "None".to_owned(),
+            None => {
+                let ty = self.type_of_key(&Key::ReturnType(ShortIdentifier::new(&x.name)));
+                self.render_type_opt(ty)
+            }
+        };
+        self.write_line(
+            indent,
+            &format!(
+                "{}def {}{}({}) -> {}: ...",
+                if x.is_async { "async " } else { "" },
+                x.name,
+                x.type_params
+                    .as_ref()
+                    .map_or_else(String::new, |t| self.code(&**t)),
+                parameters,
+                returns,
+            ),
+        );
+    }
+
+    fn render_parameters(&mut self, x: &Parameters, is_method: bool) -> String {
+        let mut res = Vec::new();
+        // The receiver of a method doesn't need an annotation.
+        let mut implicit = is_method;
+        for p in &x.posonlyargs {
+            res.push(self.render_parameter(&p.parameter, p.default.as_deref(), implicit));
+            implicit = false;
+        }
+        if !x.posonlyargs.is_empty() {
+            res.push("/".to_owned());
+        }
+        for p in &x.args {
+            res.push(self.render_parameter(&p.parameter, p.default.as_deref(), implicit));
+            implicit = false;
+        }
+        if let Some(vararg) = &x.vararg {
+            res.push(format!("*{}", self.render_parameter(vararg, None, false)));
+        } else if !x.kwonlyargs.is_empty() {
+            res.push("*".to_owned());
+        }
+        for p in &x.kwonlyargs {
+            res.push(self.render_parameter(&p.parameter, p.default.as_deref(), false));
+        }
+        if let Some(kwarg) = &x.kwarg {
+            res.push(format!("**{}", self.render_parameter(kwarg, None, false)));
+        }
+        res.join(", ")
+    }
+
+    fn render_parameter(
+        &mut self,
+        x: &Parameter,
+        default: Option<&Expr>,
+        implicit: bool,
+    ) -> String {
+        let annotation = match &x.annotation {
+            Some(annotation) => Some(self.code(&**annotation)),
+            None if implicit => None,
+            None => Some(match default.and_then(|d| self.type_of_expr(d)) {
+                Some(Type::None) => format!("{} | None", self.incomplete()),
+                ty => self.render_type_opt(ty),
+            }),
+        };
+        match (annotation, default) {
+            (Some(annotation), Some(_)) => format!("{}: {annotation} = ...", x.name),
+            (Some(annotation), None) => format!("{}: {annotation}", x.name),
+            (None, Some(_)) => format!("{}=...", x.name),
+            (None, None) => x.name.to_string(),
+        }
+    }
+
+    fn write_assign(&mut self, x: &StmtAssign, indent: usize, context: StubContext) {
+        if context == (StubContext::Class { is_enum: true })
+            || x.targets.iter().any(is_dunder_all)
+            || is_type_constructor_call(&x.value)
+        {
+            self.write_line(indent, &self.code(x));
+            return;
+        }
+        let mut names = Vec::new();
+        for target in &x.targets {
+            collect_names(target, &mut names);
+        }
+        let single = x.targets.len() == 1 && matches!(x.targets[0], Expr::Name(_));
+        for name in names {
+            let ty = self
+                .type_of_key(&Key::Definition(ShortIdentifier::expr_name(name)))
+                .or_else(|| {
+                    if single {
+                        self.type_of_expr(&x.value)
+                    } else {
+                        None
+                    }
+                });
+            match ty {
+                // Aliases and type variables have to keep their definition to mean anything.
+                Some(
+                    Type::ClassDef(_)
+                    | Type::TypeAlias(_)
+                    | Type::TypeVar(_)
+                    | Type::ParamSpec(_)
+                    | Type::TypeVarTuple(_),
+                ) if single => self.write_line(indent, &self.code(x)),
+                ty => {
+                    let ty = self.render_type_opt(ty);
+                    self.write_line(indent, &format!("{}: {ty}", name.id));
+                }
+            }
+        }
+    }
+
+    fn write_ann_assign(&mut self, x: &StmtAnnAssign, indent: usize, context: StubContext) {
+        let Expr::Name(name) = &*x.target else {
+            return;
+        };
+        let annotation = self.code(&*x.annotation);
+        if x.value.is_some()
+            && (context == (StubContext::Class { is_enum: true })
+                || is_dunder_all(&x.target)
+                || annotation.contains("Final")
+                || annotation.contains("TypeAlias"))
+        {
+            self.write_line(indent, &self.code(x));
+        } else if x.value.is_some() && matches!(context, StubContext::Class { .. }) {
+            // Keep the default, as it matters for things like dataclass fields.
+            self.write_line(indent, &format!("{}: {annotation} = ...", name.id));
+        } else {
+            self.write_line(indent, &format!("{}: {annotation}", name.id));
+        }
+    }
+}
+
+/// Whether a type can be spelled in a stub. Unknown parts are allowed, as they become `Incomplete`.
+fn is_stubbable(ty: &Type) -> bool {
+    let mut ok = true;
+    ty.universe(&mut |t| match t {
+        Type::ClassType(_)
+        | Type::ClassDef(_)
+        | Type::TypedDict(_)
+        | Type::Tuple(Tuple::Concrete(_) | Tuple::Unbounded(_))
+        | Type::Union(_)
+        | Type::Literal(_)
+        | Type::LiteralString
+        | Type::Type(_)
+        | Type::Never(_)
+        | Type::Any(_)
+        | Type::None => {}
+        _ => ok = false,
+    });
+    ok
+}
+
+fn is_dunder_all(x: &Expr) -> bool {
+    matches!(x, Expr::Name(name) if name.id == "__all__")
+}
+
+/// Calls like `T = TypeVar("T")` or `UserId = NewType("UserId", int)` define something that
+/// has to be kept as written.
+fn is_type_constructor_call(x: &Expr) -> bool {
+    let Expr::Call(call) = x else {
+        return false;
+    };
+    let name = match &*call.func {
+        Expr::Name(x) => &x.id,
+        Expr::Attribute(x) => &x.attr.id,
+        _ => return false,
+    };
+    matches!(
+        name.as_str(),
+        "TypeVar" | "ParamSpec" | "TypeVarTuple" | "NewType" | "NamedTuple" | "TypedDict"
+    )
+}
+
+fn first_parameter(x: &Parameters) -> Option<&Parameter> {
+    x.posonlyargs
+        .first()
+        .or_else(|| x.args.first())
+        .map(|p| &p.parameter)
+}
+
+fn collect_names<'x>(x: &'x Expr, res: &mut Vec<&'x ruff_python_ast::ExprName>) {
+    match x {
+        Expr::Name(x) => res.push(x),
+        Expr::Tuple(x) => x.elts.iter().for_each(|x| collect_names(x, res)),
+        Expr::List(x) => x.elts.iter().for_each(|x| collect_names(x, res)),
+        Expr::Starred(x) => collect_names(&x.value, res),
+        _ => {}
+    }
+}
+
+/// Find `receiver.name = value` and `receiver.name: annotation = value` assignments in a method body,
+/// as `(name, annotation, value)`.
+fn collect_self_assignments<'x>(
+    body: &'x [Stmt],
+    receiver: &Name,
+    res: &mut Vec<(&'x Name, Option<&'x Expr>, Option<&'x Expr>)>,
+) {
+    let attribute = |x: &'x Expr| match x {
+        Expr::Attribute(attr)
+            if let Expr::Name(base) = &*attr.value
+                && &base.id == receiver =>
+        {
+            Some(&attr.attr.id)
+        }
+        _ => None,
+    };
+    for stmt in body {
+        match stmt {
+            Stmt::Assign(x) => {
+                for target in &x.targets {
+                    if let Some(name) = attribute(target) {
+                        res.push((name, None, Some(&*x.value)));
+                    }
+                }
+            }
+            Stmt::AnnAssign(x) => {
+                if let Some(name) = attribute(&x.target) {
+                    res.push((name, Some(&*x.annotation), x.value.as_deref()));
+                }
+            }
+            Stmt::If(x) => {
+                collect_self_assignments(&x.body, receiver, res);
+                for clause in &x.elif_else_clauses {
+                    collect_self_assignments(&clause.body, receiver, res);
+                }
+            }
+            Stmt::With(x) => collect_self_assignments(&x.body, receiver, res),
+            Stmt::For(x) => collect_self_assignments(&x.body, receiver, res),
+            Stmt::While(x) => collect_self_assignments(&x.body, receiver, res),
+            Stmt::Try(x) => {
+                collect_self_assignments(&x.body, receiver, res);
+                collect_self_assignments(&x.orelse, receiver, res);
+                collect_self_assignments(&x.finalbody, receiver, res);
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_str_eq;
+
+    use super::*;
+    use crate::test::util::TestEnv;
+
+    fn assert_stub(code: &str, expected: &str) {
+        let (state, handle) = TestEnv::one("main", code)
+            .with_default_require_level(Require::Everything)
+            .to_state();
+        let stub = generate_stub(&state.transaction(), &handle("main")).unwrap();
+        assert_str_eq!(expected.trim_start(), stub);
+    }
+
+    #[test]
+    fn test_inferred_returns_and_variables() {
+        assert_stub(
+            r#"
+import os
+
+X = 1
+NAMES = ["a", "b"]
+
+def f(x: int, y=0):
+    return str(x + y)
+
+async def g():
+    return None
+
+def h(*args, key=None, **kwargs):
+    return os.getcwd()
+"#,
+            r#"
+from _typeshed import Incomplete
+
+import os
+X: int
+NAMES: list[str]
+def f(x: int, y: int = ...) -> str: ...
+async def g() -> None: ...
+def h(*args: Incomplete, key: Incomplete | None = ..., **kwargs: Incomplete) -> str: ...
+"#,
+        );
+    }
+
+    #[test]
+    fn test_class_named_unknown() {
+        assert_stub(
+            r#"
+class Unknown: ...
+
+def make():
+    return Unknown()
+"#,
+            r#"
+class Unknown:
+    ...
+def make() -> Unknown: ...
+"#,
+        );
+    }
+
+    #[test]
+    fn test_classes() {
+        assert_stub(
+            r#"
+from dataclasses import dataclass
+from enum import Enum
+
+class Base: ...
+
+class Child(Base):
+    limit: int = 3
+
+    def __init__(self, name: str):
+        self.name = name
+        self.count: int = 0
+
+    @property
+    def upper(self):
+        return self.name.upper()
+
+    @staticmethod
+    def make(x: str) -> "Child":
+        return Child(x)
+
+@dataclass
+class Point:
+    x: int
+    y: int = 0
+
+class Color(Enum):
+    RED = 1
+    GREEN = 2
+"#,
+            r#"
+from dataclasses import dataclass
+from enum import Enum
+class Base:
+    ...
+class Child(Base):
+    name: str
+    count: int
+    limit: int = ...
+    def __init__(self, name: str) -> None: ...
+    @property
+    def upper(self) -> str: ...
+    @staticmethod
+    def make(x: str) -> "Child": ...
+@dataclass
+class Point:
+    x: int
+    y: int = ...
+class Color(Enum):
+    RED = 1
+    GREEN = 2
+"#,
+        );
+    }
+
+    #[test]
+    fn test_overloads_and_all() {
+        assert_stub(
+            r#"
+from typing import overload, TypeVar
+
+__all__ = ["f"]
+__all__ += ["T"]
+
+T = TypeVar("T")
+
+@overload
+def f(x: int) -> int: ...
+@overload
+def f(x: str) -> str: ...
+def f(x):
+    return x
+
+def _helper(x):
+    return x
+"#,
+            r#"
+from _typeshed import Incomplete
+
+from typing import overload, TypeVar
+__all__ = ["f"]
+__all__ += ["T"]
+T = TypeVar("T")
+@overload
+def f(x: int) -> int: ...
+@overload
+def f(x: str) -> str: ...
+def _helper(x: Incomplete) -> Incomplete: ...
+"#,
+        );
+    }
+
+    #[test]
+    fn test_qualified_names() {
+        assert_stub(
+            r#"
+import collections
+
+def counter():
+    return collections.Counter([1])
+
+class C: ...
+
+def make():
+    return C()
+
+if True:
+    pass
+"#,
+            r#"
+import collections
+def counter() -> collections.Counter[int]: ...
+class C:
+    ...
+def make() -> C: ...
+"#,
+        );
+    }
+
+    #[test]
+    fn test_submodule_of_package() {
+        let mut env = TestEnv::new().with_default_require_level(Require::Everything);
+        env.add_with_path("pkg.sub", "pkg/sub.py", "class Bar: ...");
+        env.add_with_path(
+            "pkg",
+            "pkg/__init__.py",
+            r#"
+from pkg.sub import Bar
+
+class Foo: ...
+
+def bar():
+    return Bar()
+
+def foo():
+    return Foo()
+"#,
+        );
+        let (state, handle) = env.to_state();
+        let stub = generate_stub(&state.transaction(), &handle("pkg")).unwrap();
+        assert_str_eq!(
+            r#"
+import pkg.sub
+
+from pkg.sub import Bar
+class Foo:
+    ...
+def bar() -> pkg.sub.Bar: ...
+def foo() -> Foo: ...
+"#
+            .trim_start(),
+            stub
+        );
+    }
+
+    #[test]
+    fn test_stub_path() {
+        let out = Path::new("out");
+        assert_eq!(
+            stub_path(out, ModuleName::from_str("a.b"), false),
+            Path::new("out/a/b.pyi")
+        );
+        assert_eq!(
+            stub_path(out, ModuleName::from_str("a.b"), true),
+            Path::new("out/a/b/__init__.pyi")
+        );
+    }
+}
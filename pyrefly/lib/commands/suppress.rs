@@ -0,0 +1,113 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use std::path::PathBuf;
+
+use clap::Parser;
+use pyrefly_config::args::ConfigOverrideArgs;
+use pyrefly_config::finder::ConfigFinder;
+use pyrefly_python::ignore::Tool;
+use pyrefly_util::forgetter::Forgetter;
+use pyrefly_util::includes::Includes;
+
+use crate::commands::check::Handles;
+use crate::commands::files::FilesArgs;
+use crate::commands::util::CommandExitStatus;
+use crate::error::suppress;
+use crate::error::suppress::SuppressibleError;
+use crate::error::suppress::SuppressionPlacement;
+use crate::error::suppress::SuppressionStyle;
+use crate::state::require::Require;
+use crate::state::state::State;
+
+/// Arguments for the suppress command which adds suppression comments for type errors
+#[deny(clippy::missing_docs_in_private_items)]
+#[derive(Debug, Parser, Clone)]
+pub struct SuppressArgs {
+    /// Which files to check and suppress errors in.
+    #[command(flatten)]
+    files: FilesArgs,
+
+    /// Type checking arguments and configuration
+    #[command(flatten)]
+    config_override: ConfigOverrideArgs,
+
+    /// Which tool's comment syntax to write, e.g. `# type: ignore[...]` for `type`.
+    /// Suppressions for tools other than `pyrefly` and `type` only take effect if the tool
+    /// is listed in `enabled-ignores`. `mypy` is not supported, as it has no per-line comments.
+    #[arg(long, value_enum, default_value_t = Tool::Pyrefly)]
+    tool: Tool,
+
+    /// Where to put the suppression comments.
+    #[arg(long, value_enum, default_value_t)]
+    placement: SuppressionPlacement,
+
+    /// Text to add after each new suppression comment as a separate comment, e.g. a ticket ID.
+    #[arg(long)]
+    tag: Option<String>,
+
+    /// Read the errors to suppress from the output of `pyrefly check --output-format json`,
+    /// rather than checking the files again. Paths in the file are resolved against the
+    /// current directory, or `--relative-to` if it was used to write them.
+    #[arg(long, value_name = "FILE", conflicts_with = "files")]
+    from_json: Option<PathBuf>,
+
+    /// The directory the paths in the `--from-json` file are relative to.
+    #[arg(long, value_name = "DIR", requires = "from_json")]
+    relative_to: Option<PathBuf>,
+}
+
+impl SuppressArgs {
+    pub fn run(self) -> anyhow::Result<CommandExitStatus> {
+        let style = SuppressionStyle::new(self.tool, self.placement, self.tag)?;
+        let errors = match &self.from_json {
+            Some(path) => {
+                let relative_to = match self.relative_to {
+                    Some(dir) => dir,
+                    None => std::env::current_dir()?,
+                };
+                SuppressibleError::from_json_file(path, &relative_to)?
+            }
+            None => {
+                self.config_override.validate()?;
+                let (files_to_check, config_finder) = self.files.resolve(self.config_override)?;
+                Self::check(files_to_check, config_finder)?
+            }
+        };
+        suppress::suppress_errors_with_style(errors, &style);
+        Ok(CommandExitStatus::Success)
+    }
+
+    fn check(
+        files_to_check: Box<dyn Includes>,
+        config_finder: ConfigFinder,
+    ) -> anyhow::Result<Vec<SuppressibleError>> {
+        let expanded_file_list = config_finder.checkpoint(files_to_check.files())?;
+        let state = State::new(config_finder);
+        let holder = Forgetter::new(state, false);
+        let handles = Handles::new(expanded_file_list);
+        let mut forgetter =
+            Forgetter::new(holder.as_ref().new_transaction(Require::Errors, None), true);
+        let transaction = forgetter.as_mut();
+
+        let (handles, _, sourcedb_errors) = handles.all(holder.as_ref().config_finder());
+        if !sourcedb_errors.is_empty() {
+            for error in sourcedb_errors {
+                error.print();
+            }
+            return Err(anyhow::anyhow!("Failed to query sourcedb."));
+        }
+        transaction.run(&handles, Require::Errors);
+        Ok(transaction
+            .get_errors(&handles)
+            .collect_errors()
+            .shown
+            .iter()
+            .filter_map(SuppressibleError::from_error)
+            .collect())
+    }
+}
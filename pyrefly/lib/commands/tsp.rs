@@ -14,6 +14,7 @@ use pyrefly_util::telemetry::Telemetry;
 use crate::commands::lsp::IndexingMode;
 use crate::commands::util::CommandExitStatus;
 use crate::lsp::non_wasm::queue::LspQueue;
+use crate::lsp::non_wasm::server::capabilities_to_json;
 use crate::tsp::server::tsp_capabilities;
 use crate::tsp::server::tsp_loop;
 
@@ -67,8 +68,10 @@ fn initialize_tsp_connection(
         serde_json::from_value(initialization_params).unwrap();
 
     // Use TSP-specific capabilities (same as LSP but without serverInfo)
-    let server_capabilities =
-        serde_json::to_value(tsp_capabilities(args.indexing_mode, &initialization_params)).unwrap();
+    let server_capabilities = capabilities_to_json(
+        args.indexing_mode,
+        tsp_capabilities(args.indexing_mode, &initialization_params),
+    );
     let initialize_data = serde_json::json!({
         "capabilities": server_capabilities,
         // Note: TSP doesn't include serverInfo, unlike LSP
@@ -5,17 +5,30 @@
  * LICENSE file in the root directory of this source tree.
  */
 
+use std::collections::HashMap;
 use std::collections::HashSet;
+use std::hash::Hash;
 use std::path::Path;
+use std::sync::LazyLock;
 
+use anyhow::Context as _;
 use anyhow::Result;
+use dupe::Dupe;
+use pyrefly_python::ast::Ast;
+use pyrefly_python::module_path::ModulePath;
 use pyrefly_util::fs_anyhow;
+use pyrefly_util::visit::Visit;
+use regex::Regex;
+use ruff_python_ast::Stmt;
+use ruff_text_size::Ranged;
+use ruff_text_size::TextRange;
 
 use crate::error::error::Error;
 use crate::error::legacy::LegacyError;
 use crate::error::legacy::LegacyErrors;
 
 /// If an error with an exactly matching path, error slug, and starting column exist in the baseline, we ignore it.
+/// Only used for baseline entries written before entries had fingerprints.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 struct BaselineKey {
     path: String,
@@ -35,36 +48,211 @@ impl From<&LegacyError> for BaselineKey {
 
 impl BaselineKey {
     fn from_error(error: &Error, relative_to: &Path) -> Self {
-        let error_path = error.path().as_path();
         Self {
-            path: error_path
-                .strip_prefix(relative_to)
-                .unwrap_or(error_path)
-                .to_string_lossy()
-                .into_owned(),
+            path: relative_path(error, relative_to),
             name: error.error_kind().to_name().to_owned(),
             column: error.display_range().start.column().get() as usize,
         }
     }
 }
 
+fn relative_path(error: &Error, relative_to: &Path) -> String {
+    let error_path = error.path().as_path();
+    error_path
+        .strip_prefix(relative_to)
+        .unwrap_or(error_path)
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// The names of the functions and classes enclosing each error, outermost first, joined by `.`,
+/// e.g. `C.method`. Each module is parsed the first time one of its errors is looked up.
+#[derive(Default)]
+pub(crate) struct Scopes(HashMap<ModulePath, Vec<(TextRange, String)>>);
+
+impl Scopes {
+    pub(crate) fn of(&mut self, error: &Error) -> String {
+        fn collect(stmt: &Stmt, scope: &str, res: &mut Vec<(TextRange, String)>) {
+            let name = match stmt {
+                Stmt::FunctionDef(x) => &x.name,
+                Stmt::ClassDef(x) => &x.name,
+                _ => {
+                    stmt.recurse(&mut |x| collect(x, scope, res));
+                    return;
+                }
+            };
+            let scope = if scope.is_empty() {
+                name.id.to_string()
+            } else {
+                format!("{scope}.{}", name.id)
+            };
+            stmt.recurse(&mut |x| collect(x, &scope, res));
+            res.push((stmt.range(), scope));
+        }
+
+        let module = error.module();
+        let scopes = self.0.entry(module.path().dupe()).or_insert_with(|| {
+            let (ast, _, _) = Ast::parse(module.contents(), module.source_type());
+            let mut res = Vec::new();
+            ast.body.visit(&mut |x| collect(x, "", &mut res));
+            res
+        });
+        // Inner scopes come before the scopes that contain them.
+        scopes
+            .iter()
+            .find(|(range, _)| range.contains(error.range().start()))
+            .map_or_else(String::new, |(_, scope)| scope.clone())
+    }
+}
+
+/// Remove the qualifiers from dotted names, e.g. `main.C.f` becomes `f`, since module names
+/// change when files are renamed. Also collapses whitespace.
+pub(crate) fn normalize_message(msg: &str) -> String {
+    static QUALIFIED: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r"\b(?:[A-Za-z_]\w*\.)+([A-Za-z_]\w*)").unwrap());
+    QUALIFIED
+        .replace_all(msg, "$1")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// A hash identifying an error independently of its file's path and the line it is on, so that
+/// baseline entries keep matching after code is moved or a file is renamed. It combines the
+/// enclosing scope, the error kind, the normalized message and the text of the line the error
+/// starts on.
+pub fn fingerprint(error: &Error, scope: &str) -> String {
+    let line = error.display_range().start.line_within_file();
+    let line_text = error
+        .lined_buffer()
+        .content_in_line_range(line, line)
+        .trim();
+    let key = format!(
+        "{scope}\n{}\n{}\n{line_text}",
+        error.error_kind().to_name(),
+        normalize_message(error.msg_header()),
+    );
+    blake3::hash(key.as_bytes()).to_hex()[..16].to_owned()
+}
+
+/// Match each error, given as its key, path and line, to at most one entry with the same key.
+/// An entry in the same file on the nearest line is preferred, and then one in any file (in case
+/// the file was renamed). `candidates` maps each key to the indices of its entries.
+pub(crate) fn match_nearest<K: Eq + Hash>(
+    entries: &[LegacyError],
+    candidates: &HashMap<K, Vec<usize>>,
+    errors: &[(K, String, usize)],
+) -> Vec<Option<usize>> {
+    let mut res = vec![None; errors.len()];
+    let mut used = vec![false; entries.len()];
+    for same_path in [true, false] {
+        for (i, (key, path, line)) in errors.iter().enumerate() {
+            if res[i].is_some() {
+                continue;
+            }
+            let Some(candidates) = candidates.get(key) else {
+                continue;
+            };
+            let best = candidates
+                .iter()
+                .filter(|&&j| !used[j] && (!same_path || &entries[j].path == path))
+                .min_by_key(|&&j| entries[j].line.abs_diff(*line));
+            if let Some(&j) = best {
+                used[j] = true;
+                res[i] = Some(j);
+            }
+        }
+    }
+    res
+}
+
+/// Writes the errors to a baseline file, in the `--output-format json` format with a
+/// fingerprint for each error.
+pub fn write_baseline(path: &Path, relative_to: &Path, errors: &[Error]) -> Result<()> {
+    let mut scopes = Scopes::default();
+    let baseline = LegacyErrors {
+        errors: errors
+            .iter()
+            .map(|e| {
+                let scope = scopes.of(e);
+                LegacyError::from_error(relative_to, e)
+                    .with_fingerprint(fingerprint(e, &scope))
+                    .with_scope(scope)
+            })
+            .collect(),
+    };
+    fs_anyhow::write(path, serde_json::to_string_pretty(&baseline)?)
+        .with_context(|| format!("while writing the baseline to `{}`", path.display()))
+}
+
 pub struct BaselineProcessor {
-    baseline_keys: HashSet<BaselineKey>,
+    entries: Vec<LegacyError>,
+    /// The entries with a fingerprint, by fingerprint.
+    fingerprints: HashMap<String, Vec<usize>>,
+    /// The entries without a fingerprint, by path, error kind and column.
+    baseline_keys: HashMap<BaselineKey, Vec<usize>>,
 }
 
 impl BaselineProcessor {
     pub fn from_file(baseline_path: &Path) -> Result<Self> {
         let content = fs_anyhow::read_to_string(baseline_path)?;
-        let baseline_file: LegacyErrors = serde_json::from_str(&content)?;
+        let baseline_file: LegacyErrors = serde_json::from_str(&content)
+            .with_context(|| format!("while parsing the baseline `{}`", baseline_path.display()))?;
+        Ok(Self::new(baseline_file.errors))
+    }
 
-        let baseline_keys = baseline_file.errors.iter().map(BaselineKey::from).collect();
+    fn new(entries: Vec<LegacyError>) -> Self {
+        let mut fingerprints: HashMap<String, Vec<usize>> = HashMap::new();
+        let mut baseline_keys: HashMap<BaselineKey, Vec<usize>> = HashMap::new();
+        for (i, entry) in entries.iter().enumerate() {
+            match &entry.fingerprint {
+                Some(fingerprint) => fingerprints.entry(fingerprint.clone()).or_default().push(i),
+                None => baseline_keys
+                    .entry(BaselineKey::from(entry))
+                    .or_default()
+                    .push(i),
+            }
+        }
+        Self {
+            entries,
+            fingerprints,
+            baseline_keys,
+        }
+    }
 
-        Ok(Self { baseline_keys })
+    /// Find the baseline entry each error matches, if any.
+    ///
+    /// Each entry with a fingerprint matches at most one error with the same fingerprint,
+    /// preferring an error in the same file on the nearest line, and then an error in any file
+    /// (in case the file was renamed). Entries without a fingerprint match every error with the
+    /// same path, error kind and column.
+    fn match_errors(&self, errors: &[Error], relative_to: &Path) -> Vec<Option<usize>> {
+        let mut scopes = Scopes::default();
+        let keys = errors
+            .iter()
+            .map(|e| {
+                (
+                    fingerprint(e, &scopes.of(e)),
+                    relative_path(e, relative_to),
+                    e.display_range().start.line_within_cell().get() as usize,
+                )
+            })
+            .collect::<Vec<_>>();
+        let mut res = match_nearest(&self.entries, &self.fingerprints, &keys);
+        for (i, error) in errors.iter().enumerate() {
+            if res[i].is_none()
+                && let Some(entries) = self
+                    .baseline_keys
+                    .get(&BaselineKey::from_error(error, relative_to))
+            {
+                res[i] = Some(entries[0]);
+            }
+        }
+        res
     }
 
     pub fn matches_baseline(&self, error: &Error, relative_to: &Path) -> bool {
-        let key = BaselineKey::from_error(error, relative_to);
-        self.baseline_keys.contains(&key)
+        self.match_errors(std::slice::from_ref(error), relative_to)[0].is_some()
     }
 
     /// Baseline suppressions are processed last, after inline and config suppressions
@@ -74,10 +262,11 @@ impl BaselineProcessor {
         baseline_errors: &mut Vec<Error>,
         relative_to: &Path,
     ) {
+        let matches = self.match_errors(shown_errors, relative_to);
         let mut remaining_errors = Vec::new();
 
-        for error in shown_errors.drain(..) {
-            if self.matches_baseline(&error, relative_to) {
+        for (error, matched) in shown_errors.drain(..).zip(matches) {
+            if matched.is_some() {
                 baseline_errors.push(error);
             } else {
                 remaining_errors.push(error);
@@ -86,6 +275,40 @@ impl BaselineProcessor {
 
         *shown_errors = remaining_errors;
     }
+
+    /// The baseline entries in the checked files that don't match any of the given errors, i.e.
+    /// errors that have been fixed since the baseline was written. `checked_paths` are relative
+    /// to `relative_to`, like the paths in the baseline.
+    pub fn fixed_entries(
+        &self,
+        baseline_errors: &[Error],
+        checked_paths: &HashSet<String>,
+        relative_to: &Path,
+    ) -> Vec<&LegacyError> {
+        let mut matched = vec![false; self.entries.len()];
+        for j in self
+            .match_errors(baseline_errors, relative_to)
+            .into_iter()
+            .flatten()
+        {
+            matched[j] = true;
+        }
+        // An entry without a fingerprint stands for all the entries with the same key.
+        for entries in self.baseline_keys.values() {
+            if entries.iter().any(|&j| matched[j]) {
+                for &j in entries {
+                    matched[j] = true;
+                }
+            }
+        }
+        self.entries
+            .iter()
+            .zip(matched)
+            .filter_map(|(entry, matched)| {
+                (!matched && checked_paths.contains(&entry.path)).then_some(entry)
+            })
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -146,10 +369,7 @@ mod tests {
         "#;
 
         let baseline_file: LegacyErrors = serde_json::from_str(baseline_json).unwrap();
-        let baseline_keys: HashSet<BaselineKey> =
-            baseline_file.errors.iter().map(BaselineKey::from).collect();
-
-        let processor = BaselineProcessor { baseline_keys };
+        let processor = BaselineProcessor::new(baseline_file.errors);
 
         let module = Module::new(
             ModuleName::from_str("test_module"),
@@ -198,4 +418,77 @@ mod tests {
         );
         assert!(!processor.matches_baseline(&error4, Path::new("/")));
     }
+
+    fn error_at(module: &str, contents: &str, needle: &str) -> Error {
+        let module_info = Module::new(
+            ModuleName::from_str(module),
+            ModulePath::filesystem(PathBuf::from(format!("{module}.py"))),
+            Arc::new(contents.to_owned()),
+        );
+        let start = TextSize::new(contents.find(needle).unwrap() as u32);
+        Error::new(
+            module_info,
+            TextRange::at(start, TextSize::new(needle.len() as u32)),
+            vec1![format!("`{module}.X` is not assignable to `int`")],
+            ErrorKind::BadReturn,
+        )
+    }
+
+    #[test]
+    fn test_normalize_message() {
+        assert_eq!(
+            normalize_message("`main.C`  is not\nassignable to `builtins.int`"),
+            "`C` is not assignable to `int`"
+        );
+    }
+
+    #[test]
+    fn test_scopes() {
+        let contents =
+            "class C:\n    # comment\n    async def f(self):\n\n        x = 1\n    y = 2\nz = 3\n";
+        let mut scopes = Scopes::default();
+        let mut scope = |needle| scopes.of(&error_at("foo", contents, needle));
+        assert_eq!(scope("x = 1"), "C.f");
+        assert_eq!(scope("y = 2"), "C");
+        assert_eq!(scope("z = 3"), "");
+    }
+
+    #[test]
+    fn test_baseline_fingerprints() {
+        let root = Path::new("/");
+        let contents = "class C:\n    def f(self) -> int:\n        return X()\n";
+        let old = error_at("foo", contents, "X()");
+        let processor = BaselineProcessor::new(vec![
+            LegacyError::from_error(root, &old).with_fingerprint(fingerprint(&old, "C.f")),
+        ]);
+
+        // Still matches after the code moves down and the file is renamed.
+        let moved = error_at("bar", &format!("import os\n\n{contents}"), "X()");
+        assert!(processor.matches_baseline(&moved, root));
+
+        // The same line in a different scope doesn't match.
+        let other_scope = error_at("foo", &contents.replace("class C", "class D"), "X()");
+        assert!(!processor.matches_baseline(&other_scope, root));
+
+        // Each entry only matches one error.
+        let mut shown = vec![moved.clone(), moved];
+        let mut baseline_errors = Vec::new();
+        processor.process_errors(&mut shown, &mut baseline_errors, root);
+        assert_eq!(shown.len(), 1);
+        assert_eq!(baseline_errors.len(), 1);
+
+        let checked = HashSet::from(["foo.py".to_owned()]);
+        assert!(
+            processor
+                .fixed_entries(&baseline_errors, &checked, root)
+                .is_empty()
+        );
+        assert_eq!(processor.fixed_entries(&[], &checked, root).len(), 1);
+        // Entries in files that weren't checked aren't fixed.
+        assert!(
+            processor
+                .fixed_entries(&[], &HashSet::new(), root)
+                .is_empty()
+        );
+    }
 }
@@ -10,9 +10,11 @@ use std::mem;
 
 use dupe::Dupe;
 use pyrefly_config::error_kind::ErrorKind;
+use pyrefly_python::sys_info::SysInfo;
 use pyrefly_util::lock::Mutex;
 use ruff_text_size::Ranged;
 use ruff_text_size::TextRange;
+use starlark_map::small_map::SmallMap;
 use vec1::Vec1;
 
 use crate::config::error::ErrorConfig;
@@ -89,7 +91,8 @@ impl ModuleErrors {
 pub struct CollectedErrors {
     /// Errors that will be reported to the user.
     pub shown: Vec<Error>,
-    /// Errors that are suppressed with inline ignore comments.
+    /// Errors that are suppressed with inline ignore comments, with their configured severity,
+    /// which is `Ignore` if they would be disabled anyway.
     pub suppressed: Vec<Error>,
     /// Errors that are disabled with configuration options.
     pub disabled: Vec<Error>,
@@ -97,6 +100,64 @@ pub struct CollectedErrors {
     pub baseline: Vec<Error>,
 }
 
+impl CollectedErrors {
+    /// Merge the errors from checking the same modules against several Python versions and
+    /// platforms. An error that occurs with only some of them is tagged with those it occurs with.
+    pub fn merge_sys_infos(results: Vec<(SysInfo, CollectedErrors)>) -> Self {
+        let count = results.len();
+        let mut shown = Vec::with_capacity(count);
+        let mut suppressed = Vec::with_capacity(count);
+        let mut disabled = Vec::with_capacity(count);
+        let mut baseline = Vec::with_capacity(count);
+        for (sys_info, errors) in results {
+            shown.push((sys_info.dupe(), errors.shown));
+            suppressed.push((sys_info.dupe(), errors.suppressed));
+            disabled.push((sys_info.dupe(), errors.disabled));
+            baseline.push((sys_info, errors.baseline));
+        }
+        Self {
+            shown: merge_sys_info_errors(shown, count),
+            suppressed: merge_sys_info_errors(suppressed, count),
+            disabled: merge_sys_info_errors(disabled, count),
+            baseline: merge_sys_info_errors(baseline, count),
+        }
+    }
+}
+
+fn merge_sys_info_errors(results: Vec<(SysInfo, Vec<Error>)>, count: usize) -> Vec<Error> {
+    let mut merged: SmallMap<_, (Error, Vec<SysInfo>)> = SmallMap::new();
+    for (sys_info, errors) in results {
+        for err in errors {
+            let key = (err.path().dupe(), err.range(), err.error_kind(), err.msg());
+            let (_, sys_infos) = merged.entry(key).or_insert_with(|| (err, Vec::new()));
+            if sys_infos.last() != Some(&sys_info) {
+                sys_infos.push(sys_info.dupe());
+            }
+        }
+    }
+    let mut res = merged
+        .into_values()
+        .map(|(err, sys_infos)| {
+            if sys_infos.len() == count {
+                err
+            } else {
+                err.with_sys_infos(sys_infos)
+            }
+        })
+        .collect::<Vec<_>>();
+    // Errors that only occur with later configurations were added last, so restore the usual
+    // order of module, then source range.
+    res.sort_by_key(|x| {
+        (
+            x.module().name(),
+            x.path().dupe(),
+            x.range().start(),
+            x.range().end(),
+        )
+    });
+    res
+}
+
 /// Collects the user errors (e.g. type errors) associated with a module.
 // Deliberately don't implement Clone,
 #[derive(Debug)]
@@ -139,6 +200,18 @@ impl ErrorCollector {
         self.errors.lock().push(err);
     }
 
+    /// Add an error that has already been constructed, e.g. one restored from a cache.
+    pub fn add_error(&self, err: Error) {
+        if self.style != ErrorStyle::Never {
+            self.errors.lock().push(err);
+        }
+    }
+
+    /// All the errors in this collector, including those that will later be ignored.
+    pub fn errors(&self) -> Vec<Error> {
+        self.errors.lock().iter().cloned().collect()
+    }
+
     pub fn module(&self) -> &ModuleInfo {
         &self.module_info
     }
@@ -157,22 +230,32 @@ impl ErrorCollector {
 
     pub fn collect_into(&self, error_config: &ErrorConfig, result: &mut CollectedErrors) {
         let mut errors = self.errors.lock();
+        // File-level `# pyrefly: strict` and `# pyrefly: <error-kind>=<severity>` directives
+        // override the config for this module.
+        let directives = self.module_info.ignore().severity_directives();
+        let overridden;
+        let display_config = if directives.is_empty() {
+            error_config.display_config
+        } else {
+            overridden = error_config.display_config.with_directives(directives);
+            &overridden
+        };
         if !(self.module_info.is_generated() && error_config.ignore_errors_in_generated_code) {
             for err in errors.iter() {
+                let kind = err.error_kind();
+                let raw_severity = display_config.severity(kind);
+                let severity = match (kind, raw_severity, error_config.ignore_missing_source) {
+                    // If missing-source is set to Ignore (the default), and
+                    // ignore-missing-source is  to false (the default is true, so false must
+                    // have been explicitly set by the user), enable missing-source. Note that
+                    // this means that if `missing-source` and `--ignore-missing-source` are in
+                    // conflict,  the error is enabled if either setting says it should be.
+                    (ErrorKind::MissingSource, Severity::Ignore, false) => Severity::Error,
+                    _ => raw_severity,
+                };
                 if err.is_ignored(&error_config.enabled_ignores) {
-                    result.suppressed.push(err.clone());
+                    result.suppressed.push(err.with_severity(severity));
                 } else {
-                    let kind = err.error_kind();
-                    let raw_severity = error_config.display_config.severity(kind);
-                    let severity = match (kind, raw_severity, error_config.ignore_missing_source) {
-                        // If missing-source is set to Ignore (the default), and
-                        // ignore-missing-source is  to false (the default is true, so false must
-                        // have been explicitly set by the user), enable missing-source. Note that
-                        // this means that if `missing-source` and `--ignore-missing-source` are in
-                        // conflict,  the error is enabled if either setting says it should be.
-                        (ErrorKind::MissingSource, Severity::Ignore, false) => Severity::Error,
-                        _ => raw_severity,
-                    };
                     match severity {
                         Severity::Error => result.shown.push(err.with_severity(Severity::Error)),
                         Severity::Warn => result.shown.push(err.with_severity(Severity::Warn)),
@@ -201,6 +284,8 @@ mod tests {
     use pyrefly_python::ignore::Tool;
     use pyrefly_python::module_name::ModuleName;
     use pyrefly_python::module_path::ModulePath;
+    use pyrefly_python::sys_info::PythonPlatform;
+    use pyrefly_python::sys_info::PythonVersion;
     use pyrefly_util::prelude::SliceExt;
     use ruff_python_ast::name::Name;
     use ruff_text_size::TextSize;
@@ -319,6 +404,50 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_error_collector_with_severity_directives() {
+        let mi = ModuleInfo::new(
+            ModuleName::from_name(&Name::new_static("main")),
+            ModulePath::filesystem(Path::new("main.py").to_owned()),
+            Arc::new(
+                "# pyrefly: strict\n# pyrefly: bad-return=warn, not-async=ignore\n".to_owned(),
+            ),
+        );
+        let errors = ErrorCollector::new(mi.dupe(), ErrorStyle::Delayed);
+        for (kind, msg) in [
+            (ErrorKind::ImplicitAny, "a"),
+            (ErrorKind::BadReturn, "b"),
+            (ErrorKind::NotAsync, "c"),
+            (ErrorKind::UnreachableCode, "d"),
+            (ErrorKind::BadAssignment, "e"),
+        ] {
+            add(
+                &errors,
+                TextRange::new(TextSize::new(1), TextSize::new(3)),
+                kind,
+                msg.to_owned(),
+            );
+        }
+
+        // Directives override the config, but strict mode never lowers a severity.
+        let display_config = ErrorDisplayConfig::new(HashMap::from([
+            (ErrorKind::NotAsync, Severity::Error),
+            (ErrorKind::BadAssignment, Severity::Warn),
+        ]));
+        let config = ErrorConfig::new(&display_config, false, Tool::default_enabled(), true);
+        assert_eq!(
+            errors
+                .collect(&config)
+                .shown
+                .map(|x| (x.msg(), x.severity())),
+            vec![
+                ("a".to_owned(), Severity::Error),
+                ("b".to_owned(), Severity::Warn),
+                ("e".to_owned(), Severity::Warn),
+            ]
+        );
+    }
+
     #[test]
     fn test_error_collector_generated_code() {
         let mi = ModuleInfo::new(
@@ -375,4 +504,43 @@ mod tests {
             vec!["Overload", "A specific error"]
         );
     }
+
+    #[test]
+    fn test_merge_sys_infos() {
+        let mi = ModuleInfo::new(
+            ModuleName::from_name(&Name::new_static("main")),
+            ModulePath::filesystem(PathBuf::from("main.py")),
+            Arc::new("contents".to_owned()),
+        );
+        let config = ErrorDisplayConfig::default();
+        let config = ErrorConfig::new(&config, false, Tool::default_enabled(), true);
+        let collect = |msgs: &[(u32, &str)]| {
+            let errors = ErrorCollector::new(mi.dupe(), ErrorStyle::Delayed);
+            for (start, msg) in msgs {
+                add(
+                    &errors,
+                    TextRange::new(TextSize::new(*start), TextSize::new(*start + 1)),
+                    ErrorKind::InternalError,
+                    (*msg).to_owned(),
+                );
+            }
+            errors.collect(&config)
+        };
+        let py39 = SysInfo::new(PythonVersion::new(3, 9, 0), PythonPlatform::linux());
+        let py313 = SysInfo::new(PythonVersion::new(3, 13, 0), PythonPlatform::linux());
+        let merged = CollectedErrors::merge_sys_infos(vec![
+            (py39.dupe(), collect(&[(1, "both"), (4, "old")])),
+            (py313.dupe(), collect(&[(1, "both"), (2, "new")])),
+        ]);
+        assert_eq!(
+            merged
+                .shown
+                .map(|x| (x.msg(), x.sys_infos().map(|x| x.to_string()))),
+            vec![
+                ("both".to_owned(), vec![]),
+                ("new".to_owned(), vec!["3.13.0/linux".to_owned()]),
+                ("old".to_owned(), vec!["3.9.0/linux".to_owned()]),
+            ]
+        );
+    }
 }
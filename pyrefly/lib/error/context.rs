@@ -172,6 +172,16 @@ pub enum TypeCheckKind {
     UnexpectedBareYield,
     /// Check on the type of the dataclass `__post_init__` method.
     PostInit,
+    /// Check of the return type of a dataclass field's `converter` against the field type.
+    FieldConverter(Name),
+    /// Check of an `attrs` field's `validator` against a validator accepting the field type, as
+    /// (field name, field type).
+    FieldValidator(Name, Type),
+    /// Check of the class a `functools.singledispatch` implementation is registered for against
+    /// the first parameter of the named function.
+    SingleDispatchRegistration(Name),
+    /// Check of a `pytest.mark.parametrize` value against the annotation of the named parameter.
+    PytestParametrize(Name),
     /// Consistency check for overload return types.
     OverloadReturn,
     /// Consistency check for overload input signature, as (overload_signature, implementation_signature)
@@ -220,6 +230,10 @@ impl TypeCheckKind {
             Self::YieldFrom => ErrorKind::InvalidYield,
             Self::UnexpectedBareYield => ErrorKind::InvalidYield,
             Self::PostInit => ErrorKind::BadFunctionDefinition,
+            Self::FieldConverter(..) => ErrorKind::BadArgumentType,
+            Self::FieldValidator(..) => ErrorKind::BadArgumentType,
+            Self::SingleDispatchRegistration(_) => ErrorKind::BadArgumentType,
+            Self::PytestParametrize(_) => ErrorKind::BadArgumentType,
             Self::OverloadReturn => ErrorKind::InconsistentOverload,
             Self::OverloadInput(..) => ErrorKind::InconsistentOverload,
             Self::TypeVarSpecialization(..) => ErrorKind::BadSpecialization,
@@ -0,0 +1,181 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Compare the errors of a project against those of a previous revision, to find the errors
+//! that a change introduced. Errors are matched on their kind, enclosing scope and message, so
+//! they keep matching when code moves around or a file is renamed. Previous errors from a report
+//! written without scopes are matched on their kind and message alone.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::error::baseline::match_nearest;
+use crate::error::baseline::normalize_message;
+use crate::error::error::Error;
+use crate::error::legacy::LegacyError;
+use crate::error::legacy::LegacyErrors;
+
+/// What an error must have in common with a previous error to match it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct DiffKey {
+    name: String,
+    scope: Option<String>,
+    message: String,
+}
+
+impl DiffKey {
+    fn new(error: &LegacyError, use_scope: bool) -> Self {
+        Self {
+            name: error.name.clone(),
+            scope: if use_scope { error.scope.clone() } else { None },
+            message: normalize_message(&error.concise_description),
+        }
+    }
+}
+
+/// The errors of the current revision compared to a previous one.
+#[derive(Debug, Serialize)]
+pub struct ErrorDiff {
+    /// Errors that don't match any previous error.
+    pub new: Vec<LegacyError>,
+    /// Previous errors that don't match any current error.
+    pub fixed: Vec<LegacyError>,
+    /// Errors that match a previous error.
+    pub unchanged: Vec<LegacyError>,
+}
+
+impl ErrorDiff {
+    /// Match each current error to at most one previous error with the same kind, scope and
+    /// message, preferring one in the same file on the nearest line, and then one in any file.
+    /// Returns the diff, along with the current errors that are new.
+    pub fn new(
+        previous: Vec<LegacyError>,
+        current: Vec<Error>,
+        relative_to: &Path,
+    ) -> (Self, Vec<Error>) {
+        // A report from `--output-format json` has no scopes, so we can't match on them.
+        let use_scope = previous.iter().all(|e| e.scope.is_some());
+        let current_legacy = if use_scope {
+            LegacyErrors::from_errors_with_scopes(relative_to, &current).errors
+        } else {
+            LegacyErrors::from_errors(relative_to, &current).errors
+        };
+        let mut candidates: HashMap<DiffKey, Vec<usize>> = HashMap::new();
+        for (i, error) in previous.iter().enumerate() {
+            candidates
+                .entry(DiffKey::new(error, use_scope))
+                .or_default()
+                .push(i);
+        }
+        let keys = current_legacy
+            .iter()
+            .map(|e| (DiffKey::new(e, use_scope), e.path.clone(), e.line))
+            .collect::<Vec<_>>();
+        let matched = match_nearest(&previous, &candidates, &keys);
+        let mut used = vec![false; previous.len()];
+        for &j in matched.iter().flatten() {
+            used[j] = true;
+        }
+
+        let mut new = Vec::new();
+        let mut new_errors = Vec::new();
+        let mut unchanged = Vec::new();
+        for ((error, legacy), matched) in current.into_iter().zip(current_legacy).zip(matched) {
+            if matched.is_some() {
+                unchanged.push(legacy);
+            } else {
+                new.push(legacy);
+                new_errors.push(error);
+            }
+        }
+        let fixed = previous
+            .into_iter()
+            .zip(used)
+            .filter_map(|(error, used)| (!used).then_some(error))
+            .collect();
+        (
+            Self {
+                new,
+                fixed,
+                unchanged,
+            },
+            new_errors,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use std::sync::Arc;
+
+    use pyrefly_python::module::Module;
+    use pyrefly_python::module_name::ModuleName;
+    use pyrefly_python::module_path::ModulePath;
+    use ruff_text_size::TextRange;
+    use ruff_text_size::TextSize;
+    use vec1::vec1;
+
+    use super::*;
+    use crate::config::error_kind::ErrorKind;
+
+    fn errors(module: &str, contents: &str, needles: &[&str]) -> Vec<Error> {
+        let module_info = Module::new(
+            ModuleName::from_str(module),
+            ModulePath::filesystem(PathBuf::from(format!("{module}.py"))),
+            Arc::new(contents.to_owned()),
+        );
+        needles
+            .iter()
+            .map(|needle| {
+                let start = TextSize::new(contents.find(needle).unwrap() as u32);
+                Error::new(
+                    module_info.clone(),
+                    TextRange::at(start, TextSize::new(needle.len() as u32)),
+                    vec1![format!("`{module}.{needle}` is not assignable to `int`")],
+                    ErrorKind::BadReturn,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_error_diff() {
+        let root = Path::new("/");
+        let before = "def f() -> int:\n    return A\n\ndef g() -> int:\n    return B\n";
+        let previous =
+            LegacyErrors::from_errors_with_scopes(root, &errors("foo", before, &["A", "B"])).errors;
+
+        // `A` moved to a renamed file, `B` was fixed and `C` is new.
+        let after = "import os\n\ndef f() -> int:\n    return A\n\ndef h() -> int:\n    return C\n";
+        let (diff, new_errors) = ErrorDiff::new(previous, errors("bar", after, &["A", "C"]), root);
+        assert_eq!(new_errors.len(), 1);
+        assert_eq!(diff.new.len(), 1);
+        assert_eq!(diff.new[0].scope.as_deref(), Some("h"));
+        assert_eq!(diff.unchanged.len(), 1);
+        assert_eq!(diff.unchanged[0].scope.as_deref(), Some("f"));
+        assert_eq!(diff.fixed.len(), 1);
+        assert_eq!(diff.fixed[0].scope.as_deref(), Some("g"));
+    }
+
+    #[test]
+    fn test_error_diff_without_scopes() {
+        let root = Path::new("/");
+        let before = "def f() -> int:\n    return A\n\ndef g() -> int:\n    return B\n";
+        let previous = LegacyErrors::from_errors(root, &errors("foo", before, &["A", "B"])).errors;
+
+        // Without scopes, `A` still matches after moving to another function.
+        let after = "def h() -> int:\n    return A\n";
+        let (diff, new_errors) = ErrorDiff::new(previous, errors("foo", after, &["A"]), root);
+        assert!(new_errors.is_empty());
+        assert_eq!(diff.unchanged.len(), 1);
+        assert_eq!(diff.unchanged[0].scope, None);
+        assert_eq!(diff.fixed.len(), 1);
+    }
+}
@@ -235,6 +235,30 @@ impl TypeCheckKind {
             Self::PostInit => format!(
                 "`__post_init__` type `{got}` is not assignable to expected type `{want}` generated from the dataclass's `InitVar` fields"
             ),
+            Self::FieldConverter(name) => format!(
+                "Converter returns `{}`, which is not assignable to field `{}` with type `{}`",
+                ctx.display(got),
+                name,
+                ctx.display(want),
+            ),
+            Self::FieldValidator(name, field_ty) => format!(
+                "Validator `{}` does not accept field `{}` with type `{}`",
+                ctx.display(got),
+                name,
+                ctx.display(field_ty),
+            ),
+            Self::SingleDispatchRegistration(name) => format!(
+                "Dispatch type `{}` is not assignable to the first parameter of `{}` with type `{}`",
+                ctx.display(got),
+                name,
+                ctx.display(want),
+            ),
+            Self::PytestParametrize(name) => format!(
+                "Parametrized value `{}` is not assignable to parameter `{}` with type `{}`",
+                ctx.display(got),
+                name,
+                ctx.display(want),
+            ),
             Self::OverloadReturn => format!(
                 "Overload return type `{got}` is not assignable to implementation return type `{want}`",
             ),
@@ -14,10 +14,12 @@ use std::path::Path;
 use itertools::Itertools;
 use lsp_types::CodeDescription;
 use lsp_types::Diagnostic;
+use lsp_types::DiagnosticTag;
 use lsp_types::Url;
 use pyrefly_python::ignore::Tool;
 use pyrefly_python::module::Module;
 use pyrefly_python::module_path::ModulePath;
+use pyrefly_python::sys_info::SysInfo;
 use pyrefly_util::display::number_thousands;
 use pyrefly_util::lined_buffer::DisplayRange;
 use pyrefly_util::lined_buffer::LineNumber;
@@ -48,6 +50,9 @@ pub struct Error {
     /// The rest of the error message after the first line.
     /// Note that this is formatted for pretty-printing, with two spaces at the beginning and after every newline.
     msg_details: Option<Box<str>>,
+    /// The Python versions and platforms this error occurs with, when we checked against several
+    /// of them and it doesn't occur with all of them. Otherwise empty.
+    sys_infos: Box<[SysInfo]>,
 }
 
 impl Ranged for Error {
@@ -66,10 +71,11 @@ impl Error {
         if verbose && self.severity.is_enabled() {
             writeln!(
                 f,
-                "{} {} [{}]",
+                "{} {} [{}]{}",
                 self.severity.label(),
                 self.msg_header,
                 self.error_kind.to_name(),
+                self.sys_infos_suffix(),
             )?;
             let origin = self.path_string_with_fragment(project_root);
             let snippet = self.get_source_snippet(&origin);
@@ -81,12 +87,13 @@ impl Error {
         } else if self.severity.is_enabled() {
             writeln!(
                 f,
-                "{} {}:{}: {} [{}]",
+                "{} {}:{}: {} [{}]{}",
                 self.severity.label(),
                 self.path_string_with_fragment(project_root),
                 self.display_range,
                 self.msg_header,
                 self.error_kind.to_name(),
+                self.sys_infos_suffix(),
             )?;
         }
         Ok(())
@@ -95,10 +102,11 @@ impl Error {
     pub fn print_colors(&self, project_root: &Path, verbose: bool) {
         if verbose && self.severity.is_enabled() {
             anstream::println!(
-                "{} {} {}",
+                "{} {} {}{}",
                 self.severity.painted(),
                 Paint::new(&*self.msg_header),
                 Paint::dim(format!("[{}]", self.error_kind().to_name()).as_str()),
+                Paint::dim(self.sys_infos_suffix().as_str()),
             );
             let origin = self.path_string_with_fragment(project_root);
             let snippet = self.get_source_snippet(&origin);
@@ -109,16 +117,26 @@ impl Error {
             }
         } else if self.severity.is_enabled() {
             anstream::println!(
-                "{} {}:{}: {} {}",
+                "{} {}:{}: {} {}{}",
                 self.severity.painted(),
                 Paint::blue(&self.path_string_with_fragment(project_root)),
                 Paint::dim(self.display_range()),
                 Paint::new(&*self.msg_header),
                 Paint::dim(format!("[{}]", self.error_kind().to_name()).as_str()),
+                Paint::dim(self.sys_infos_suffix().as_str()),
             );
         }
     }
 
+    /// Name the Python versions and platforms this error is limited to, if any.
+    fn sys_infos_suffix(&self) -> String {
+        if self.sys_infos.is_empty() {
+            String::new()
+        } else {
+            format!(" (only with {})", self.sys_infos.iter().join(", "))
+        }
+    }
+
     /// Return the path with a cell fragment if the error is in a notebook cell.
     fn path_string_with_fragment(&self, project_root: &Path) -> String {
         let path = self.path().as_path();
@@ -199,7 +217,14 @@ impl Error {
             message: self.msg().to_owned(),
             code: Some(lsp_types::NumberOrString::String(code)),
             code_description,
-            tags: None,
+            tags: match self.error_kind() {
+                ErrorKind::UnreachableCode
+                | ErrorKind::UnusedImport
+                | ErrorKind::UnusedParameter
+                | ErrorKind::UnusedPrivateFunction
+                | ErrorKind::UnusedVariable => Some(vec![DiagnosticTag::UNNECESSARY]),
+                _ => None,
+            },
             ..Default::default()
         }
     }
@@ -262,6 +287,29 @@ impl Error {
             severity: error_kind.default_severity(),
             msg_header,
             msg_details,
+            sys_infos: Box::default(),
+        }
+    }
+
+    /// Recreate an error from the parts previously returned by `msg_header`, `msg_details` and
+    /// `sys_infos`, e.g. when restoring it from a cache.
+    pub fn restore(
+        module: Module,
+        range: TextRange,
+        error_kind: ErrorKind,
+        msg_header: Box<str>,
+        msg_details: Option<Box<str>>,
+        sys_infos: Vec<SysInfo>,
+    ) -> Self {
+        Self {
+            display_range: module.display_range(range),
+            module,
+            range,
+            error_kind,
+            severity: error_kind.default_severity(),
+            msg_header,
+            msg_details,
+            sys_infos: sys_infos.into_boxed_slice(),
         }
     }
 
@@ -304,6 +352,16 @@ impl Error {
     pub fn error_kind(&self) -> ErrorKind {
         self.error_kind
     }
+
+    pub fn sys_infos(&self) -> &[SysInfo] {
+        &self.sys_infos
+    }
+
+    /// Record that this error only occurs with these Python versions and platforms.
+    pub fn with_sys_infos(mut self, sys_infos: Vec<SysInfo>) -> Self {
+        self.sys_infos = sys_infos.into_boxed_slice();
+        self
+    }
 }
 
 #[cfg(test)]
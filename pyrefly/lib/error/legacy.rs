@@ -12,6 +12,7 @@ use pyrefly_util::prelude::SliceExt;
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::error::baseline::Scopes;
 use crate::error::error::Error;
 
 fn severity_to_str(severity: Severity) -> String {
@@ -33,7 +34,7 @@ fn default_severity() -> String {
 /// Used to serialize errors in a Pyre1-compatible format.
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
 pub struct LegacyError {
-    line: usize,
+    pub line: usize,
     pub column: usize,
     stop_line: usize,
     stop_column: usize,
@@ -43,13 +44,26 @@ pub struct LegacyError {
     /// The kebab-case name of the error kind.
     pub name: String,
     description: String,
-    concise_description: String,
+    pub concise_description: String,
     /// This field is not part of Pyre1 error format. But it's useful for Pyrefly clients
     #[serde(default = "default_severity")]
-    severity: String,
+    pub severity: String,
     /// Optional notebook cell number for errors in notebook files
     #[serde(skip_serializing_if = "Option::is_none")]
-    cell: Option<usize>,
+    pub cell: Option<usize>,
+    /// The Python versions and platforms the error occurs with, when checking against a
+    /// `python-matrix` and it doesn't occur with all of them.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    configurations: Vec<String>,
+    /// The functions and classes enclosing the error, e.g. `C.method`, or empty at the top
+    /// level. Not part of the Pyre1 error format, but used to match errors across revisions.
+    /// Only computed when diffing errors and written to baseline files.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+    /// Identifies the error independently of its path and line, so baseline entries survive
+    /// code moving. Only written to baseline files.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fingerprint: Option<String>,
 }
 
 impl LegacyError {
@@ -73,8 +87,21 @@ impl LegacyError {
             description: error.msg(),
             concise_description: error.msg_header().to_owned(),
             severity: severity_to_str(error.severity()),
+            configurations: error.sys_infos().map(|x| x.to_string()),
+            scope: None,
+            fingerprint: None,
         }
     }
+
+    pub fn with_fingerprint(mut self, fingerprint: String) -> Self {
+        self.fingerprint = Some(fingerprint);
+        self
+    }
+
+    pub fn with_scope(mut self, scope: String) -> Self {
+        self.scope = Some(scope);
+        self
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
@@ -88,4 +115,14 @@ impl LegacyErrors {
             errors: errors.map(|e| LegacyError::from_error(relative_to, e)),
         }
     }
+
+    /// Like `from_errors`, but with the scope of each error, which means parsing every module
+    /// with errors again.
+    pub fn from_errors_with_scopes(relative_to: &Path, errors: &[Error]) -> Self {
+        let mut scopes = Scopes::default();
+        Self {
+            errors: errors
+                .map(|e| LegacyError::from_error(relative_to, e).with_scope(scopes.of(e))),
+        }
+    }
 }
@@ -8,10 +8,12 @@
 pub mod baseline;
 pub mod collector;
 pub mod context;
+pub mod diff;
 pub mod display;
 pub mod error;
 pub mod expectation;
 pub mod legacy;
+pub mod sarif;
 pub mod style;
 pub mod summarize;
 pub mod suppress;
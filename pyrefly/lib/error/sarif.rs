@@ -0,0 +1,388 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Serialization of errors as a [SARIF 2.1.0](https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html)
+//! log, as consumed by code scanning tools.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use lsp_types::Url;
+use pyrefly_config::error_kind::ErrorKind;
+use pyrefly_config::error_kind::Severity;
+use serde::Serialize;
+
+use crate::error::error::Error;
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+/// The base that relative artifact URIs are resolved against.
+const SRCROOT: &str = "%SRCROOT%";
+
+fn severity_to_level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Ignore => "none",
+        Severity::Info => "note",
+        Severity::Warn => "warning",
+        Severity::Error => "error",
+    }
+}
+
+#[derive(Serialize, Debug)]
+struct Message {
+    text: String,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct ReportingConfiguration {
+    enabled: bool,
+    level: &'static str,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct ReportingDescriptor {
+    /// The kebab-case name of the error kind.
+    id: &'static str,
+    /// The PascalCase name of the error kind.
+    name: String,
+    short_description: Message,
+    help_uri: String,
+    default_configuration: ReportingConfiguration,
+}
+
+impl ReportingDescriptor {
+    fn from_error_kind(kind: ErrorKind) -> Self {
+        let severity = kind.default_severity();
+        Self {
+            id: kind.to_name(),
+            name: kind.to_string(),
+            short_description: Message {
+                text: kind.description(),
+            },
+            help_uri: kind.docs_url(),
+            default_configuration: ReportingConfiguration {
+                enabled: severity.is_enabled(),
+                level: severity_to_level(severity),
+            },
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct ToolComponent {
+    name: &'static str,
+    version: &'static str,
+    information_uri: &'static str,
+    rules: Vec<ReportingDescriptor>,
+}
+
+#[derive(Serialize, Debug)]
+struct Tool {
+    driver: ToolComponent,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct ArtifactLocation {
+    uri: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    uri_base_id: Option<&'static str>,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct Region {
+    start_line: u32,
+    start_column: u32,
+    end_line: u32,
+    end_column: u32,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct PhysicalLocation {
+    artifact_location: ArtifactLocation,
+    region: Region,
+}
+
+#[derive(Serialize, Debug)]
+struct LocationProperties {
+    /// The notebook cell containing the location, in which case the region is relative to the
+    /// start of the cell.
+    cell: u32,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct Location {
+    physical_location: PhysicalLocation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    properties: Option<LocationProperties>,
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+enum SuppressionKind {
+    /// Suppressed by an inline ignore comment.
+    InSource,
+    /// Suppressed by a baseline file.
+    External,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct Suppression {
+    kind: SuppressionKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    justification: Option<&'static str>,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct SarifResult {
+    rule_id: &'static str,
+    rule_index: usize,
+    level: &'static str,
+    message: Message,
+    locations: Vec<Location>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    suppressions: Vec<Suppression>,
+}
+
+impl SarifResult {
+    fn from_error(relative_to: &Path, error: &Error, suppression: Option<SuppressionKind>) -> Self {
+        let kind = error.error_kind();
+        let range = error.display_range();
+        let path = error.path().as_path();
+        let (uri, uri_base_id) = match path.strip_prefix(relative_to) {
+            Ok(relative) => (relative.to_string_lossy().replace('\\', "/"), Some(SRCROOT)),
+            Err(_) => (
+                Url::from_file_path(path)
+                    .map_or_else(|_| path.to_string_lossy().into_owned(), |x| x.to_string()),
+                None,
+            ),
+        };
+        Self {
+            rule_id: kind.to_name(),
+            rule_index: kind as usize,
+            level: severity_to_level(error.severity()),
+            message: Message { text: error.msg() },
+            locations: vec![Location {
+                physical_location: PhysicalLocation {
+                    artifact_location: ArtifactLocation { uri, uri_base_id },
+                    region: Region {
+                        start_line: range.start.line_within_cell().get(),
+                        start_column: range.start.column().get(),
+                        end_line: range.end.line_within_cell().get(),
+                        end_column: range.end.column().get(),
+                    },
+                },
+                properties: range
+                    .start
+                    .cell()
+                    .map(|cell| LocationProperties { cell: cell.get() }),
+            }],
+            suppressions: suppression
+                .map(|kind| Suppression {
+                    kind,
+                    justification: match kind {
+                        SuppressionKind::InSource => None,
+                        SuppressionKind::External => Some("baseline"),
+                    },
+                })
+                .into_iter()
+                .collect(),
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct Run {
+    tool: Tool,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    original_uri_base_ids: BTreeMap<&'static str, ArtifactLocation>,
+    /// Our columns count characters, rather than UTF-16 code units.
+    column_kind: &'static str,
+    results: Vec<SarifResult>,
+}
+
+/// A SARIF log with a single run, containing a rule for every error kind.
+#[derive(Serialize, Debug)]
+pub struct SarifLog {
+    #[serde(rename = "$schema")]
+    schema: &'static str,
+    version: &'static str,
+    runs: Vec<Run>,
+}
+
+impl SarifLog {
+    /// Create a log from the errors that are shown, along with those that were suppressed inline
+    /// or by a baseline, which are reported as SARIF suppressions. Errors suppressed inline whose
+    /// kind is disabled are left out, as they would not be reported without the suppression.
+    pub fn from_errors(
+        relative_to: &Path,
+        shown: &[Error],
+        suppressed: &[Error],
+        baseline: &[Error],
+    ) -> Self {
+        let results =
+            shown
+                .iter()
+                .map(|e| SarifResult::from_error(relative_to, e, None))
+                .chain(
+                    suppressed
+                        .iter()
+                        .filter(|e| e.severity().is_enabled())
+                        .map(|e| {
+                            SarifResult::from_error(relative_to, e, Some(SuppressionKind::InSource))
+                        }),
+                )
+                .chain(baseline.iter().map(|e| {
+                    SarifResult::from_error(relative_to, e, Some(SuppressionKind::External))
+                }))
+                .collect();
+        let mut original_uri_base_ids = BTreeMap::new();
+        if let Ok(root) = Url::from_directory_path(relative_to) {
+            original_uri_base_ids.insert(
+                SRCROOT,
+                ArtifactLocation {
+                    uri: root.to_string(),
+                    uri_base_id: None,
+                },
+            );
+        }
+        Self {
+            schema: SARIF_SCHEMA,
+            version: SARIF_VERSION,
+            runs: vec![Run {
+                tool: Tool {
+                    driver: ToolComponent {
+                        name: "Pyrefly",
+                        version: env!("CARGO_PKG_VERSION"),
+                        information_uri: "https://pyrefly.org",
+                        rules: enum_iterator::all::<ErrorKind>()
+                            .map(ReportingDescriptor::from_error_kind)
+                            .collect(),
+                    },
+                },
+                original_uri_base_ids,
+                column_kind: "unicodeCodePoints",
+                results,
+            }],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use std::sync::Arc;
+
+    use pyrefly_python::module::Module;
+    use pyrefly_python::module_name::ModuleName;
+    use pyrefly_python::module_path::ModulePath;
+    use ruff_text_size::TextRange;
+    use ruff_text_size::TextSize;
+    use serde_json::json;
+    use vec1::vec1;
+
+    use super::*;
+
+    fn error(kind: ErrorKind) -> Error {
+        let module = Module::new(
+            ModuleName::from_str("test_module"),
+            ModulePath::filesystem(PathBuf::from("/root/test/path.py")),
+            Arc::new("x = 1\ny = 2\n".to_owned()),
+        );
+        Error::new(
+            module,
+            TextRange::new(TextSize::new(6), TextSize::new(7)),
+            vec1!["Test error message".to_owned()],
+            kind,
+        )
+    }
+
+    #[test]
+    fn test_sarif_results() {
+        let log = SarifLog::from_errors(
+            Path::new("/root"),
+            &[error(ErrorKind::BadReturn)],
+            &[error(ErrorKind::BadAssignment)],
+            &[error(ErrorKind::RevealType)],
+        );
+        let json = serde_json::to_value(&log).unwrap();
+        let run = &json["runs"][0];
+        let results = run["results"].as_array().unwrap();
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0]["ruleId"], "bad-return");
+        assert_eq!(
+            results[0]["ruleIndex"],
+            json!(ErrorKind::BadReturn as usize)
+        );
+        assert_eq!(
+            run["tool"]["driver"]["rules"][ErrorKind::BadReturn as usize]["id"],
+            "bad-return"
+        );
+        assert_eq!(
+            results[0]["locations"][0]["physicalLocation"],
+            json!({
+                "artifactLocation": {"uri": "test/path.py", "uriBaseId": "%SRCROOT%"},
+                "region": {"startLine": 2, "startColumn": 1, "endLine": 2, "endColumn": 2},
+            })
+        );
+        assert!(results[0].get("suppressions").is_none());
+        assert_eq!(results[1]["suppressions"], json!([{"kind": "inSource"}]));
+        assert_eq!(
+            results[2]["suppressions"],
+            json!([{"kind": "external", "justification": "baseline"}])
+        );
+    }
+
+    #[test]
+    fn test_sarif_configured_severity() {
+        let log = SarifLog::from_errors(
+            Path::new("/root"),
+            &[error(ErrorKind::BadReturn).with_severity(Severity::Warn)],
+            &[
+                error(ErrorKind::BadAssignment).with_severity(Severity::Info),
+                error(ErrorKind::ImplicitAny).with_severity(Severity::Ignore),
+            ],
+            &[],
+        );
+        let json = serde_json::to_value(&log).unwrap();
+        let results = json["runs"][0]["results"].as_array().unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0]["level"], "warning");
+        assert_eq!(results[1]["ruleId"], "bad-assignment");
+        assert_eq!(results[1]["level"], "note");
+    }
+
+    #[test]
+    fn test_sarif_rules() {
+        let log = SarifLog::from_errors(Path::new("/root"), &[], &[], &[]);
+        let json = serde_json::to_value(&log).unwrap();
+        let rules = json["runs"][0]["tool"]["driver"]["rules"]
+            .as_array()
+            .unwrap();
+        assert_eq!(rules.len(), enum_iterator::all::<ErrorKind>().count());
+        let reveal_type = &rules[ErrorKind::RevealType as usize];
+        assert_eq!(reveal_type["id"], "reveal-type");
+        assert_eq!(
+            reveal_type["defaultConfiguration"],
+            json!({"enabled": true, "level": "note"})
+        );
+        let implicit_any = &rules[ErrorKind::ImplicitAny as usize];
+        assert_eq!(
+            implicit_any["defaultConfiguration"],
+            json!({"enabled": false, "level": "none"})
+        );
+    }
+}
@@ -5,11 +5,12 @@
  * LICENSE file in the root directory of this source tree.
  */
 
-use std::collections::HashSet;
 use std::path::Path;
 use std::path::PathBuf;
+use std::sync::Arc;
 
 use anyhow::anyhow;
+use clap::ValueEnum;
 use pyrefly_config::error_kind::Severity;
 use pyrefly_python::ast::Ast;
 use pyrefly_python::ignore::Tool;
@@ -17,36 +18,206 @@ use pyrefly_python::module::GENERATED_TOKEN;
 use pyrefly_python::module_path::ModulePathDetails;
 use pyrefly_util::fs_anyhow;
 use pyrefly_util::lined_buffer::LineNumber;
+use pyrefly_util::lined_buffer::LinedBuffer;
+use pyrefly_util::visit::Visit;
 use regex::Regex;
+use ruff_python_ast::Expr;
 use ruff_python_ast::PySourceType;
+use ruff_text_size::Ranged;
+use ruff_text_size::TextRange;
 use starlark_map::small_map::SmallMap;
 use starlark_map::small_set::SmallSet;
 use tracing::error;
 use tracing::info;
 
 use crate::error::error::Error;
+use crate::error::legacy::LegacyErrors;
 use crate::state::errors::Errors;
+use crate::state::lsp::quick_fixes::remove_unused;
+
+/// Where to put a new suppression comment.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum SuppressionPlacement {
+    /// On its own line, above the line the error starts on.
+    #[default]
+    PrecedingLine,
+    /// At the end of the line the error starts on. Lines that end inside a multi-line string
+    /// or with a `\` continuation fall back to the preceding line.
+    Inline,
+}
+
+/// How to write suppression comments: which tool's syntax to use, where to put them,
+/// and an optional tag (e.g. a ticket ID) to add after them.
+#[derive(Debug, Clone)]
+pub struct SuppressionStyle {
+    placement: SuppressionPlacement,
+    tag: Option<String>,
+    /// The start of a suppression comment, up to the `[` of the error codes.
+    comment_start: &'static str,
+    /// Matches an existing suppression comment with error codes, capturing the codes.
+    regex: Regex,
+}
+
+impl Default for SuppressionStyle {
+    fn default() -> Self {
+        Self::new(Tool::Pyrefly, SuppressionPlacement::default(), None).unwrap()
+    }
+}
+
+impl SuppressionStyle {
+    pub fn new(
+        tool: Tool,
+        placement: SuppressionPlacement,
+        tag: Option<String>,
+    ) -> anyhow::Result<Self> {
+        let (comment_start, pattern) = match tool {
+            Tool::Pyrefly => ("# pyrefly: ignore ", r"pyrefly:\s*ignore"),
+            Tool::Type => ("# type: ignore", r"type:\s*ignore"),
+            Tool::Pyright => ("# pyright: ignore", r"pyright:\s*ignore"),
+            Tool::Ty => ("# ty: ignore", r"ty:\s*ignore"),
+            Tool::Pyre => ("# pyre-fixme", r"pyre-(?:fixme|ignore)"),
+            Tool::Mypy => {
+                return Err(anyhow!(
+                    "Mypy only supports whole-file suppressions (`# mypy: ignore-errors`)"
+                ));
+            }
+        };
+        if let Some(tag) = &tag
+            && (tag.trim().is_empty() || tag.contains('\n'))
+        {
+            return Err(anyhow!("Suppression tag must be a single non-empty line"));
+        }
+        Ok(Self {
+            placement,
+            tag: tag.map(|tag| tag.trim().to_owned()),
+            comment_start,
+            regex: Regex::new(&format!(r"#\s*{pattern}\s*\[([^\]]*)\]")).unwrap(),
+        })
+    }
+
+    /// A suppression comment for the given error codes, without the tag.
+    fn format_codes(&self, codes: &[String]) -> String {
+        format!("{}[{}]", self.comment_start, codes.join(", "))
+    }
+
+    /// A new suppression comment for the given error codes, including the tag.
+    fn comment(&self, codes: &[String]) -> String {
+        match &self.tag {
+            Some(tag) => format!("{}  # {tag}", self.format_codes(codes)),
+            None => self.format_codes(codes),
+        }
+    }
+
+    /// Extracts error codes from an existing ignore comment in this style.
+    /// Returns Some(Vec<String>) if the line contains a valid ignore comment, None otherwise.
+    fn parse_ignore_comment(&self, line: &str) -> Option<Vec<String>> {
+        self.regex.captures(line).map(|caps| {
+            caps.get(1)
+                .map(|m| {
+                    m.as_str()
+                        .split(',')
+                        .map(|s| s.trim().to_owned())
+                        .filter(|s| !s.is_empty())
+                        .collect()
+                })
+                .unwrap_or_default()
+        })
+    }
+
+    /// Merges new error codes with existing ones in a suppression comment.
+    /// Returns the updated comment string with merged and sorted error codes.
+    fn merge_error_codes(&self, existing_codes: Vec<String>, new_codes: &[String]) -> String {
+        let mut all_codes: SmallSet<String> = SmallSet::new();
+        for code in existing_codes {
+            all_codes.insert(code);
+        }
+        for code in new_codes {
+            all_codes.insert(code.clone());
+        }
+        let mut sorted_codes: Vec<_> = all_codes.into_iter().collect();
+        sorted_codes.sort();
+        self.format_codes(&sorted_codes)
+    }
+
+    /// Replaces the ignore comment in a line with the merged version.
+    /// Preserves the rest of the line content, including any tag after the comment.
+    fn replace_ignore_comment(&self, line: &str, merged_comment: &str) -> String {
+        self.regex.replace(line, merged_comment).to_string()
+    }
+
+    /// The text after the ignore comment in a line, e.g. a tag added with the comment.
+    fn after_ignore_comment<'a>(&self, line: &'a str) -> &'a str {
+        self.regex
+            .find(line)
+            .map_or("", |m| line[m.end()..].trim_end())
+    }
+}
+
+/// An error to add a suppression comment for.
+#[derive(Debug, Clone)]
+pub struct SuppressibleError {
+    path: PathBuf,
+    /// The zero-indexed line the error starts on.
+    line: usize,
+    /// The kebab-case name of the error kind.
+    name: String,
+}
+
+impl SuppressibleError {
+    /// Errors are suppressed if they are at least warnings and in a file on disk.
+    pub fn from_error(error: &Error) -> Option<Self> {
+        if error.severity() >= Severity::Warn
+            && let ModulePathDetails::FileSystem(path) = error.path().details()
+        {
+            Some(Self {
+                path: (**path).clone(),
+                line: error
+                    .display_range()
+                    .start
+                    .line_within_file()
+                    .to_zero_indexed() as usize,
+                name: error.error_kind().to_name().to_owned(),
+            })
+        } else {
+            None
+        }
+    }
 
-/// Combines all errors that affect one line into a single entry.
-/// The current format is: `# pyrefly: ignore [error1, error2, ...]`
-fn dedup_errors(errors: &[Error]) -> SmallMap<usize, String> {
-    let mut deduped_errors: SmallMap<usize, HashSet<String>> = SmallMap::new();
+    /// Reads the errors written by `pyrefly check --output-format json`, whose paths are
+    /// relative to `relative_to`.
+    pub fn from_json_file(path: &Path, relative_to: &Path) -> anyhow::Result<Vec<Self>> {
+        let content = fs_anyhow::read_to_string(path)?;
+        let errors: LegacyErrors = serde_json::from_str(&content)
+            .map_err(|e| anyhow!("Failed to parse `{}`: {e}", path.display()))?;
+        Ok(errors
+            .errors
+            .into_iter()
+            .filter(|e| {
+                e.cell.is_none() && matches!(e.severity.as_str(), "warn" | "error") && e.line > 0
+            })
+            .map(|e| Self {
+                path: relative_to.join(&e.path),
+                line: e.line - 1,
+                name: e.name,
+            })
+            .collect())
+    }
+}
+
+/// Combines all errors that affect one line into a single entry, with sorted error codes.
+fn dedup_errors(errors: &[SuppressibleError]) -> SmallMap<usize, Vec<String>> {
+    let mut deduped_errors: SmallMap<usize, SmallSet<&str>> = SmallMap::new();
     for error in errors {
-        let line = error
-            .display_range()
-            .start
-            .line_within_file()
-            .to_zero_indexed() as usize;
-        let error_name = error.error_kind().to_name().to_owned();
-        deduped_errors.entry(line).or_default().insert(error_name);
+        deduped_errors
+            .entry(error.line)
+            .or_default()
+            .insert(&error.name);
     }
     let mut formatted_errors = SmallMap::new();
     for (line, error_set) in deduped_errors {
-        let mut error_codes: Vec<_> = error_set.into_iter().collect();
+        let mut error_codes: Vec<_> = error_set.into_iter().map(|x| x.to_owned()).collect();
         error_codes.sort();
-        let error_codes_str = error_codes.join(", ");
-        let comment = format!("# pyrefly: ignore [{}]", error_codes_str);
-        formatted_errors.insert(line, comment);
+        formatted_errors.insert(line, error_codes);
     }
     formatted_errors
 }
@@ -75,21 +246,39 @@ fn read_and_validate_file(path: &Path) -> anyhow::Result<String> {
     }
 }
 
-/// Extracts error codes from an existing pyrefly ignore comment.
-/// Returns Some(Vec<String>) if the line contains a valid ignore comment, None otherwise.
-fn parse_ignore_comment(line: &str) -> Option<Vec<String>> {
-    let regex = Regex::new(r"#\s*pyrefly:\s*ignore\s*\[([^\]]*)\]").unwrap();
-    regex.captures(line).map(|caps| {
-        caps.get(1)
-            .map(|m| {
-                m.as_str()
-                    .split(',')
-                    .map(|s| s.trim().to_owned())
-                    .filter(|s| !s.is_empty())
-                    .collect()
-            })
-            .unwrap_or_default()
-    })
+/// Returns the zero-indexed lines that a comment can't be appended to, because they end inside
+/// a multi-line string or with a line continuation.
+fn lines_without_trailing_comments(file: &str) -> SmallSet<usize> {
+    fn string_ranges(x: &Expr, res: &mut Vec<TextRange>) {
+        match x {
+            Expr::StringLiteral(_)
+            | Expr::BytesLiteral(_)
+            | Expr::FString(_)
+            | Expr::TString(_) => res.push(x.range()),
+            _ => x.recurse(&mut |x| string_ranges(x, res)),
+        }
+    }
+
+    let (ast, _, _) = Ast::parse(file, PySourceType::Python);
+    let mut ranges = Vec::new();
+    ast.visit(&mut |x: &Expr| string_ranges(x, &mut ranges));
+    let lined_buffer = LinedBuffer::new(Arc::new(file.to_owned()));
+    let line_of = |pos| {
+        lined_buffer
+            .display_pos(pos, None)
+            .line_within_file()
+            .to_zero_indexed() as usize
+    };
+    let mut res: SmallSet<usize> = ranges
+        .into_iter()
+        .flat_map(|range| line_of(range.start())..line_of(range.end()))
+        .collect();
+    for (idx, line) in file.lines().enumerate() {
+        if line.trim_end().ends_with('\\') {
+            res.insert(idx);
+        }
+    }
+    res
 }
 
 /// Location where a suppression comment exists relative to an error line.
@@ -132,34 +321,13 @@ fn get_indentation(line: &str) -> &str {
     }
 }
 
-/// Merges new error codes with existing ones in a suppression comment.
-/// Returns the updated comment string with merged and sorted error codes.
-fn merge_error_codes(existing_codes: Vec<String>, new_codes: &[String]) -> String {
-    let mut all_codes: SmallSet<String> = SmallSet::new();
-    for code in existing_codes {
-        all_codes.insert(code);
-    }
-    for code in new_codes {
-        all_codes.insert(code.clone());
-    }
-    let mut sorted_codes: Vec<_> = all_codes.into_iter().collect();
-    sorted_codes.sort();
-    format!("# pyrefly: ignore [{}]", sorted_codes.join(", "))
-}
-
-/// Replaces the ignore comment in a line with the merged version.
-/// Preserves the rest of the line content.
-fn replace_ignore_comment(line: &str, merged_comment: &str) -> String {
-    let regex = Regex::new(r"#\s*pyrefly:\s*ignore\s*\[[^\]]*\]").unwrap();
-    regex.replace(line, merged_comment).to_string()
-}
-
 /// Adds error suppressions for the given errors in the given files.
 /// Returns a list of files that failed to be patched, and a list of files that were patched.
 /// The list of failures includes the error that occurred, which may be a read or write error.
-fn add_suppressions(
-    path_errors: &SmallMap<PathBuf, Vec<Error>>,
-) -> (Vec<(&PathBuf, anyhow::Error)>, Vec<&PathBuf>) {
+fn add_suppressions<'a>(
+    path_errors: &'a SmallMap<PathBuf, Vec<SuppressibleError>>,
+    style: &SuppressionStyle,
+) -> (Vec<(&'a PathBuf, anyhow::Error)>, Vec<&'a PathBuf>) {
     let mut failures = vec![];
     let mut successes = vec![];
     for (path, errors) in path_errors {
@@ -170,7 +338,7 @@ fn add_suppressions(
                 continue;
             }
         };
-        let mut deduped_errors = dedup_errors(errors);
+        let deduped_errors = dedup_errors(errors);
 
         // Pre-scan to find existing suppressions and merge with new error codes
         let lines: Vec<&str> = file.lines().collect();
@@ -178,33 +346,49 @@ fn add_suppressions(
         // Build a map of lines that have existing suppressions
         let mut existing_suppressions: SmallMap<usize, Vec<String>> = SmallMap::new();
         for (idx, line) in lines.iter().enumerate() {
-            if let Some(codes) = parse_ignore_comment(line) {
+            if let Some(codes) = style.parse_ignore_comment(line) {
                 existing_suppressions.insert(idx, codes);
             }
         }
 
+        let no_trailing_comments = match style.placement {
+            SuppressionPlacement::Inline => lines_without_trailing_comments(&file),
+            SuppressionPlacement::PrecedingLine => SmallSet::new(),
+        };
+
+        // The comment to write for each error line
+        let mut error_comments: SmallMap<usize, String> = SmallMap::new();
         // Track which suppression lines should be skipped because they're being merged
         let mut lines_to_skip: SmallSet<usize> = SmallSet::new();
         // Track which error lines have inline suppressions that were merged (so we replace inline)
         let mut has_inline_suppression: SmallSet<usize> = SmallSet::new();
+        // Track which error lines get a new inline suppression appended
+        let mut add_inline_suppression: SmallSet<usize> = SmallSet::new();
 
         // Merge existing suppressions with new ones
-        for (&error_line, new_comment) in deduped_errors.iter_mut() {
-            let new_codes = extract_error_codes(new_comment);
-
+        for (&error_line, new_codes) in deduped_errors.iter() {
             if let Some((location, existing_codes)) =
                 find_existing_suppression(error_line, &lines, &existing_suppressions)
             {
-                *new_comment = merge_error_codes(existing_codes, &new_codes);
-
+                let mut comment = style.merge_error_codes(existing_codes, new_codes);
                 match location {
                     SuppressionLocation::Above => {
+                        // Keep anything after the old comment, such as its tag.
+                        comment.push_str(style.after_ignore_comment(lines[error_line - 1]));
                         lines_to_skip.insert(error_line - 1);
                     }
                     SuppressionLocation::Inline => {
                         has_inline_suppression.insert(error_line);
                     }
                 }
+                error_comments.insert(error_line, comment);
+            } else {
+                if style.placement == SuppressionPlacement::Inline
+                    && !no_trailing_comments.contains(&error_line)
+                {
+                    add_inline_suppression.insert(error_line);
+                }
+                error_comments.insert(error_line, style.comment(new_codes));
             }
         }
 
@@ -215,15 +399,20 @@ fn add_suppressions(
                 continue;
             }
 
-            // Separate line mode
-            if let Some(error_comment) = deduped_errors.get(&idx) {
+            if let Some(error_comment) = error_comments.get(&idx) {
                 // Check if this line had an inline suppression that was merged
                 if has_inline_suppression.contains(&idx) {
                     // Replace the inline suppression with the merged version
-                    let updated_line = replace_ignore_comment(line, error_comment);
+                    let updated_line = style.replace_ignore_comment(line, error_comment);
                     buf.push_str(&updated_line);
                     buf.push('\n');
+                } else if add_inline_suppression.contains(&idx) {
+                    buf.push_str(line.trim_end());
+                    buf.push_str("  ");
+                    buf.push_str(error_comment);
+                    buf.push('\n');
                 } else {
+                    // Separate line mode
                     // Calculate once whether suppression goes below this line
                     let suppression_below =
                         idx + 1 < lines.len() && lines_to_skip.contains(&(idx + 1));
@@ -261,26 +450,33 @@ fn add_suppressions(
     (failures, successes)
 }
 
-/// Extracts error codes from a comment string like "# pyrefly: ignore [code1, code2]".
-fn extract_error_codes(comment: &str) -> Vec<String> {
-    parse_ignore_comment(comment).unwrap_or_default()
+/// Adds `# pyrefly: ignore` comments above the lines of the given errors.
+pub fn suppress_errors(errors: Vec<Error>) {
+    suppress_errors_with_style(
+        errors
+            .iter()
+            .filter_map(SuppressibleError::from_error)
+            .collect(),
+        &SuppressionStyle::default(),
+    );
 }
 
-pub fn suppress_errors(errors: Vec<Error>) {
-    let mut path_errors: SmallMap<PathBuf, Vec<Error>> = SmallMap::new();
+/// Adds suppression comments in the given style for the given errors.
+/// Returns the number of files that were patched.
+pub fn suppress_errors_with_style(
+    errors: Vec<SuppressibleError>,
+    style: &SuppressionStyle,
+) -> usize {
+    let mut path_errors: SmallMap<PathBuf, Vec<SuppressibleError>> = SmallMap::new();
     for e in errors {
-        if e.severity() >= Severity::Warn
-            && let ModulePathDetails::FileSystem(path) = e.path().details()
-        {
-            path_errors.entry((**path).clone()).or_default().push(e);
-        }
+        path_errors.entry(e.path.clone()).or_default().push(e);
     }
     if path_errors.is_empty() {
         info!("No errors to suppress!");
-        return;
+        return 0;
     }
     info!("Inserting error suppressions...");
-    let (failures, successes) = add_suppressions(&path_errors);
+    let (failures, successes) = add_suppressions(&path_errors, style);
     info!(
         "Finished suppressing errors in {}/{} files",
         successes.len(),
@@ -292,6 +488,7 @@ pub fn suppress_errors(errors: Vec<Error>) {
             info!("  {path:#?}: {e}");
         }
     }
+    successes.len()
 }
 
 pub fn find_unused_ignores<'a>(
@@ -400,6 +597,49 @@ pub fn remove_unused_ignores(loads: &Errors, all: bool) -> usize {
     removals
 }
 
+/// Removes the unused imports and local variables reported by `errors` from
+/// the files they were found in. Returns the number of names removed.
+pub fn remove_unused(errors: &[Error]) -> usize {
+    let mut path_ranges: SmallMap<&PathBuf, Vec<TextRange>> = SmallMap::new();
+    for e in errors {
+        if remove_unused::is_fixable_error_kind(e.error_kind())
+            && let ModulePathDetails::FileSystem(path) = e.path().details()
+        {
+            path_ranges.entry(path).or_default().push(e.range());
+        }
+    }
+    let mut removed: SmallMap<&PathBuf, usize> = SmallMap::new();
+    for (path, ranges) in path_ranges {
+        match read_and_validate_file(path) {
+            Ok(file) => {
+                let (ast, _, _) = Ast::parse(&file, PySourceType::Python);
+                let edits = remove_unused::remove_unused_edits(&file, &ast, &ranges);
+                if edits.is_empty() {
+                    continue;
+                }
+                // An edit may remove several names at once, so count the names that are gone.
+                let count = ranges
+                    .iter()
+                    .filter(|r| edits.iter().any(|(x, _)| x.contains_range(**r)))
+                    .count();
+                if let Err(e) = fs_anyhow::write(path, remove_unused::apply_edits(&file, &edits)) {
+                    error!("Failed to remove unused code: {e}");
+                } else {
+                    removed.insert(path, count);
+                }
+            }
+            Err(e) => error!("Failed to remove unused code in {}: {e}", path.display()),
+        }
+    }
+    let removals = removed.values().sum::<usize>();
+    info!(
+        "Removed {} unused name(s) in {} file(s)",
+        removals,
+        removed.len(),
+    );
+    removals
+}
+
 #[cfg(test)]
 mod tests {
     use std::path::PathBuf;
@@ -434,6 +674,21 @@ mod tests {
         assert_eq!(after, got_file);
     }
 
+    fn assert_suppress_errors_with_style(before: &str, after: &str, style: SuppressionStyle) {
+        let (errors, tdir) = get_errors(before);
+        suppress::suppress_errors_with_style(
+            errors
+                .collect_errors()
+                .shown
+                .iter()
+                .filter_map(SuppressibleError::from_error)
+                .collect(),
+            &style,
+        );
+        let got_file = fs_anyhow::read_to_string(&get_path(&tdir)).unwrap();
+        assert_eq!(after, got_file);
+    }
+
     fn assert_remove_ignores(before: &str, after: &str, all: bool, expected_removals: usize) {
         let (errors, tdir) = get_errors(before);
         let removals = suppress::remove_unused_ignores(&errors, all);
@@ -570,6 +825,81 @@ x: int = foo("Hello")
         );
     }
 
+    #[test]
+    fn test_add_suppressions_inline_with_tag() {
+        assert_suppress_errors_with_style(
+            r#"
+def foo() -> str:
+    return 1
+x: int = """
+multi-line"""
+y: int = "" \
+    + ""
+"#,
+            r#"
+def foo() -> str:
+    return 1  # type: ignore[bad-return]  # T123
+# type: ignore[bad-assignment]  # T123
+x: int = """
+multi-line"""
+# type: ignore[bad-assignment]  # T123
+y: int = "" \
+    + ""
+"#,
+            SuppressionStyle::new(
+                Tool::Type,
+                SuppressionPlacement::Inline,
+                Some("T123".to_owned()),
+            )
+            .unwrap(),
+        );
+    }
+
+    #[test]
+    fn test_add_suppressions_merge_keeps_tag() {
+        assert_suppress_errors_with_style(
+            r#"
+def foo() -> str:
+    # pyre-fixme[unsupported-operation]  # T1
+    return 1 + []
+"#,
+            r#"
+def foo() -> str:
+    # pyre-fixme[bad-return, unsupported-operation]  # T1
+    return 1 + []
+"#,
+            SuppressionStyle::new(
+                Tool::Pyre,
+                SuppressionPlacement::PrecedingLine,
+                Some("T2".to_owned()),
+            )
+            .unwrap(),
+        );
+    }
+
+    #[test]
+    fn test_suppression_style_mypy() {
+        assert!(SuppressionStyle::new(Tool::Mypy, SuppressionPlacement::Inline, None).is_err());
+    }
+
+    #[test]
+    fn test_suppress_errors_from_json() {
+        let (errors, tdir) = get_errors("\nx: str = 1\n");
+        let json = tdir.path().join("errors.json");
+        let legacy_errors = LegacyErrors::from_errors(tdir.path(), &errors.collect_errors().shown);
+        fs_anyhow::write(&json, serde_json::to_string(&legacy_errors).unwrap()).unwrap();
+        let errors = SuppressibleError::from_json_file(&json, tdir.path()).unwrap();
+        suppress::suppress_errors_with_style(
+            errors,
+            &SuppressionStyle::new(Tool::Pyrefly, SuppressionPlacement::Inline, None).unwrap(),
+        );
+        let got_file = fs_anyhow::read_to_string(&get_path(&tdir)).unwrap();
+        assert_eq!(
+            "\nx: str = 1  # pyrefly: ignore [bad-assignment]\n",
+            got_file
+        );
+    }
+
     #[test]
     fn test_add_suppressions_unparsable_line_break() {
         assert_suppress_errors(
@@ -769,12 +1099,13 @@ def g() -> str:
     }
     #[test]
     fn test_parse_ignore_comment() {
+        let style = SuppressionStyle::default();
         let line = "    # pyrefly: ignore [unsupported-operation]";
-        let codes = parse_ignore_comment(line);
+        let codes = style.parse_ignore_comment(line);
         assert_eq!(codes, Some(vec!["unsupported-operation".to_owned()]));
 
         let line2 = "    # pyrefly: ignore [bad-return, unsupported-operation]";
-        let codes2 = parse_ignore_comment(line2);
+        let codes2 = style.parse_ignore_comment(line2);
         assert_eq!(
             codes2,
             Some(vec![
@@ -784,7 +1115,7 @@ def g() -> str:
         );
 
         let line3 = "    return 1 + []";
-        let codes3 = parse_ignore_comment(line3);
+        let codes3 = style.parse_ignore_comment(line3);
         assert_eq!(codes3, None);
     }
 
@@ -792,10 +1123,40 @@ def g() -> str:
     fn test_merge_error_codes() {
         let existing = vec!["unsupported-operation".to_owned()];
         let new = vec!["bad-return".to_owned()];
-        let merged = merge_error_codes(existing, &new);
+        let merged = SuppressionStyle::default().merge_error_codes(existing, &new);
         assert_eq!(
             merged,
             "# pyrefly: ignore [bad-return, unsupported-operation]"
         );
     }
+
+    #[test]
+    fn test_remove_unused() {
+        let before = r#"
+import os
+import sys
+
+def f() -> None:
+    def _unused() -> None:
+        pass
+    x = 1
+    y = print(sys.argv)
+"#;
+        let (errors, tdir) = get_errors(before);
+        // The unused-* error kinds are disabled by default.
+        let removals = suppress::remove_unused(&errors.collect_errors().disabled);
+        let got_file = fs_anyhow::read_to_string(&get_path(&tdir)).unwrap();
+        assert_eq!(
+            r#"
+import sys
+
+def f() -> None:
+    def _unused() -> None:
+        pass
+    print(sys.argv)
+"#,
+            got_file
+        );
+        assert_eq!(removals, 3);
+    }
 }
@@ -35,6 +35,8 @@ use starlark_map::small_map::SmallMap;
 use starlark_map::small_set::SmallSet;
 
 use crate::export::deprecation::parse_deprecation;
+use crate::export::pytest::parse_fixture;
+use crate::export::pytest::parse_pytest_plugins;
 use crate::export::special::SpecialExport;
 use crate::types::globals::ImplicitGlobal;
 
@@ -141,6 +143,11 @@ pub struct Definitions {
     pub deprecated: SmallMap<Name, Deprecation>,
     /// Special exports defined in this module
     pub special_exports: SmallMap<Name, SpecialExport>,
+    /// Pytest fixtures defined in this scope, from the name the fixture is requested by
+    /// to the name of the decorated function.
+    pub pytest_fixtures: SmallMap<Name, Name>,
+    /// The modules listed in a `pytest_plugins = [...]` assignment.
+    pub pytest_plugins: Vec<ModuleName>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -470,6 +477,9 @@ impl<'a> DefinitionsBuilder<'a> {
                     if DunderAllEntry::is_all(t) {
                         self.inner.dunder_all = DunderAllEntry::as_list(&x.value);
                     }
+                    if matches!(t, Expr::Name(x) if x.id == "pytest_plugins") {
+                        self.inner.pytest_plugins = parse_pytest_plugins(&x.value);
+                    }
                 }
             }
             Stmt::AnnAssign(x) => {
@@ -572,6 +582,9 @@ impl<'a> DefinitionsBuilder<'a> {
                     if deprecated_decoration.is_none() {
                         deprecated_decoration = parse_deprecation(&d.expression);
                     }
+                    if let Some(fixture) = parse_fixture(&d.expression, &name.id) {
+                        self.inner.pytest_fixtures.insert(fixture, name.id.clone());
+                    }
                 }
                 // If the function is not an overload and decorated with
                 // `@deprecated`, we mark it as deprecated.
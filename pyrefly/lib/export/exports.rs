@@ -154,6 +154,17 @@ impl Exports {
         self.0.docstring_range
     }
 
+    /// The pytest fixtures defined at the top level of this module, from the name the fixture
+    /// is requested by to the name of the decorated function.
+    pub fn pytest_fixtures(&self) -> &SmallMap<Name, Name> {
+        &self.0.definitions.pytest_fixtures
+    }
+
+    /// The plugin modules this module loads with `pytest_plugins`.
+    pub fn pytest_plugins(&self) -> &[ModuleName] {
+        &self.0.definitions.pytest_plugins
+    }
+
     pub fn is_submodule_imported_implicitly(&self, name: &Name) -> bool {
         self.0
             .definitions
@@ -161,6 +172,29 @@ impl Exports {
             .contains(name)
     }
 
+    /// Is `name` explicitly listed in a user-defined `__all__`? Synthesized `__all__`s don't count.
+    pub fn is_in_explicit_dunder_all(&self, name: &Name) -> bool {
+        self.0.definitions.definitions.contains_key(&dunder::ALL)
+            && self
+                .0
+                .definitions
+                .dunder_all
+                .iter()
+                .any(|x| matches!(x, DunderAllEntry::Name(_, x) if x == name))
+    }
+
+    /// Is `name` re-exported by this module, either by being listed in an explicit `__all__`
+    /// or by being imported with a redundant alias (`import x as x`, `from m import x as x`)?
+    pub fn is_explicit_reexport(&self, name: &Name) -> bool {
+        self.is_in_explicit_dunder_all(name)
+            || self
+                .0
+                .definitions
+                .definitions
+                .get(name)
+                .is_some_and(|x| matches!(x.style, DefinitionStyle::ImportAsEq(_)))
+    }
+
     /// Returns entries in `__all__` that don't exist in the module's definitions.
     /// Only validates explicitly user-defined `__all__` entries, not synthesized ones.
     /// Returns a vector of (range, name) tuples for invalid entries.
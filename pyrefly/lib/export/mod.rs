@@ -8,4 +8,5 @@
 pub mod definitions;
 pub mod deprecation;
 pub mod exports;
+pub mod pytest;
 pub mod special;
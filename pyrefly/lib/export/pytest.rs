@@ -0,0 +1,207 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Syntactic recognition of pytest fixtures, marks and test functions.
+//! Fixtures found here are only candidates: whether the decorator really is
+//! `pytest.fixture` is checked against its type when the function is solved.
+
+use std::slice;
+
+use pyrefly_python::module_name::ModuleName;
+use pyrefly_python::module_path::ModulePath;
+use ruff_python_ast::Expr;
+use ruff_python_ast::ExprAttribute;
+use ruff_python_ast::ExprCall;
+use ruff_python_ast::name::Name;
+
+use crate::export::special::SpecialExport;
+
+fn is_fixture_target(e: &Expr) -> bool {
+    let (base, value) = match e {
+        Expr::Name(x) => (None, &x.id),
+        Expr::Attribute(ExprAttribute {
+            value: box Expr::Name(base),
+            attr,
+            ..
+        }) => (Some(&base.id), &attr.id),
+        _ => return false,
+    };
+    SpecialExport::new(value).is_some_and(|special| {
+        special == SpecialExport::PytestFixture
+            && base.is_none_or(|base| special.defined_in(ModuleName::from_name(base)))
+    })
+}
+
+/// If a decorator is `@pytest.fixture` or `@pytest.fixture(...)`, return the name the fixture
+/// is requested by: the `name=` argument if given, otherwise the name of the function.
+pub fn parse_fixture(e: &Expr, func_name: &Name) -> Option<Name> {
+    match e {
+        Expr::Call(call) if is_fixture_target(&call.func) => Some(
+            call.arguments
+                .find_keyword("name")
+                .and_then(|kw| kw.value.as_string_literal_expr())
+                .map_or_else(|| func_name.clone(), |x| Name::new(x.value.to_str())),
+        ),
+        _ if is_fixture_target(e) => Some(func_name.clone()),
+        _ => None,
+    }
+}
+
+/// Is this decorator a pytest mark, such as `@pytest.mark.skip` or
+/// `@pytest.mark.parametrize(...)`? Returns the name of the mark.
+pub fn parse_mark(e: &Expr) -> Option<&Name> {
+    let e = match e {
+        Expr::Call(call) => &*call.func,
+        _ => e,
+    };
+    match e {
+        Expr::Attribute(ExprAttribute {
+            value: box mark,
+            attr,
+            ..
+        }) => match mark {
+            Expr::Name(x) if x.id == "mark" => Some(&attr.id),
+            Expr::Attribute(ExprAttribute {
+                value: box Expr::Name(base),
+                attr: mark,
+                ..
+            }) if base.id == "pytest" && mark.id == "mark" => Some(&attr.id),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// The argument names and value rows of a `@pytest.mark.parametrize(argnames, argvalues)` decorator.
+pub struct Parametrize<'a> {
+    pub names: Vec<Name>,
+    /// One entry per row of `argvalues` that is written as a literal, with one expression per
+    /// argument name. Rows that are not literals, or have the wrong number of values, are skipped.
+    pub rows: Vec<Vec<&'a Expr>>,
+}
+
+/// The elements of a list or tuple literal.
+fn literal_elts(e: &Expr) -> Option<&[Expr]> {
+    match e {
+        Expr::List(x) => Some(&x.elts),
+        Expr::Tuple(x) => Some(&x.elts),
+        _ => None,
+    }
+}
+
+fn parse_argnames(e: &Expr) -> Option<Vec<Name>> {
+    match e {
+        Expr::StringLiteral(x) => Some(
+            x.value
+                .to_str()
+                .split(',')
+                .map(str::trim)
+                .filter(|x| !x.is_empty())
+                .map(Name::new)
+                .collect(),
+        ),
+        _ => literal_elts(e)?
+            .iter()
+            .map(|x| Some(Name::new(x.as_string_literal_expr()?.value.to_str())))
+            .collect(),
+    }
+}
+
+fn is_param_target(e: &Expr) -> bool {
+    match e {
+        Expr::Name(x) => x.id == "param",
+        Expr::Attribute(ExprAttribute {
+            value: box Expr::Name(base),
+            attr,
+            ..
+        }) => base.id == "pytest" && attr.id == "param",
+        _ => false,
+    }
+}
+
+/// Unwrap `pytest.param(...)`, which wraps the values of a row with marks or an id.
+fn unwrap_param(e: &Expr) -> Option<&[Expr]> {
+    match e {
+        Expr::Call(ExprCall {
+            func, arguments, ..
+        }) if is_param_target(func) => Some(&arguments.args),
+        _ => None,
+    }
+}
+
+/// Parse a `@pytest.mark.parametrize(argnames, argvalues)` decorator. Indirect parametrization,
+/// where the values are passed to fixtures of the same name, is not treated as parametrization.
+pub fn parse_parametrize(e: &Expr) -> Option<Parametrize<'_>> {
+    let call = e.as_call_expr()?;
+    if parse_mark(e).is_none_or(|mark| mark != "parametrize") {
+        return None;
+    }
+    let argnames = call
+        .arguments
+        .find_argument_value("argnames", 0)
+        .and_then(parse_argnames)?;
+    if call
+        .arguments
+        .find_keyword("indirect")
+        .is_some_and(|kw| !matches!(kw.value, Expr::BooleanLiteral(ref x) if !x.value))
+    {
+        return None;
+    }
+    let rows = call
+        .arguments
+        .find_argument_value("argvalues", 1)
+        .and_then(literal_elts)
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|row| {
+            let values: Vec<&Expr> = match (unwrap_param(row), argnames.len()) {
+                (Some(args), _) => args.iter().collect(),
+                (None, 1) => vec![row],
+                (None, _) => literal_elts(row)?.iter().collect(),
+            };
+            (values.len() == argnames.len()
+                && !values.iter().any(|x| matches!(x, Expr::Starred(_))))
+            .then_some(values)
+        })
+        .collect();
+    Some(Parametrize {
+        names: argnames,
+        rows,
+    })
+}
+
+/// Parse the value of a `pytest_plugins = ...` assignment.
+pub fn parse_pytest_plugins(e: &Expr) -> Vec<ModuleName> {
+    let elts = match e {
+        Expr::StringLiteral(_) => Some(slice::from_ref(e)),
+        _ => literal_elts(e),
+    };
+    elts.unwrap_or_default()
+        .iter()
+        .filter_map(|x| {
+            Some(ModuleName::from_str(
+                x.as_string_literal_expr()?.value.to_str(),
+            ))
+        })
+        .collect()
+}
+
+/// Would pytest collect tests from this file? Uses pytest's default `python_files` patterns,
+/// `test_*.py` and `*_test.py`.
+pub fn is_test_file(path: &ModulePath) -> bool {
+    path.as_path()
+        .file_stem()
+        .and_then(|x| x.to_str())
+        .is_some_and(|x| x.starts_with("test_") || x.ends_with("_test"))
+}
+
+/// Would pytest collect this function as a test? Uses pytest's default `python_functions` and
+/// `python_classes` patterns: the function name starts with `test`, and it is either top-level or
+/// in a class whose name starts with `Test`.
+pub fn is_test_function(name: &Name, class_name: Option<&Name>) -> bool {
+    name.starts_with("test") && class_name.is_none_or(|x| x.starts_with("Test"))
+}
@@ -63,6 +63,7 @@ pub enum SpecialExport {
     BuiltinsTuple,
     TypingTuple,
     PytestNoReturn,
+    PytestFixture,
     BuiltinsInt,
     BuiltinsStr,
     BuiltinsBytes,
@@ -125,6 +126,7 @@ impl SpecialExport {
             "tuple" => Some(Self::BuiltinsTuple),
             "Tuple" => Some(Self::TypingTuple),
             "fail" | "xfail" | "skip" => Some(Self::PytestNoReturn),
+            "fixture" => Some(Self::PytestFixture),
             "int" => Some(Self::BuiltinsInt),
             "str" => Some(Self::BuiltinsStr),
             "bytes" => Some(Self::BuiltinsBytes),
@@ -198,6 +200,7 @@ impl SpecialExport {
                 "typing" | "typing_extensions" | "collections.abc"
             ),
             Self::PytestNoReturn => matches!(m.as_str(), "pytest"),
+            Self::PytestFixture => matches!(m.as_str(), "pytest" | "_pytest.fixtures"),
             Self::Deprecated => matches!(m.as_str(), "warnings" | "typing_extensions"),
         }
     }
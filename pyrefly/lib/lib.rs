@@ -58,12 +58,16 @@ pub mod library {
     pub mod library {
         pub mod library {
             pub mod library {
+                pub use crate::alt::class::plugin::ClassSynthesisContext;
+                pub use crate::alt::class::plugin::ClassSynthesisPlugin;
                 pub use crate::commands::all::Command;
                 pub use crate::commands::check::CheckArgs;
                 pub use crate::commands::check::FullCheckArgs;
                 pub use crate::commands::config_finder::default_config_finder;
                 pub use crate::commands::config_finder::default_config_finder_with_overrides;
                 pub use crate::commands::util;
+                pub use crate::error::collector::ErrorCollector;
+                pub use crate::state::state::State;
             }
         }
     }
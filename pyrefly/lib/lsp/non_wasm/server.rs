@@ -42,6 +42,8 @@ use lsp_types::ConfigurationItem;
 use lsp_types::ConfigurationParams;
 use lsp_types::DeclarationCapability;
 use lsp_types::Diagnostic;
+use lsp_types::DiagnosticOptions;
+use lsp_types::DiagnosticServerCapabilities;
 use lsp_types::DiagnosticSeverity;
 use lsp_types::DiagnosticTag;
 use lsp_types::DidChangeConfigurationParams;
@@ -114,16 +116,27 @@ use lsp_types::TextDocumentSyncCapability;
 use lsp_types::TextDocumentSyncKind;
 use lsp_types::TextEdit;
 use lsp_types::TypeDefinitionProviderCapability;
+use lsp_types::TypeHierarchyItem;
+use lsp_types::TypeHierarchyPrepareParams;
+use lsp_types::TypeHierarchySubtypesParams;
+use lsp_types::TypeHierarchySupertypesParams;
+use lsp_types::UnchangedDocumentDiagnosticReport;
 use lsp_types::Unregistration;
 use lsp_types::UnregistrationParams;
 use lsp_types::Url;
 use lsp_types::VersionedTextDocumentIdentifier;
 use lsp_types::WatchKind;
 use lsp_types::WorkspaceClientCapabilities;
+use lsp_types::WorkspaceDiagnosticParams;
+use lsp_types::WorkspaceDiagnosticReport;
+use lsp_types::WorkspaceDiagnosticReportResult;
+use lsp_types::WorkspaceDocumentDiagnosticReport;
 use lsp_types::WorkspaceEdit;
 use lsp_types::WorkspaceFoldersServerCapabilities;
+use lsp_types::WorkspaceFullDocumentDiagnosticReport;
 use lsp_types::WorkspaceServerCapabilities;
 use lsp_types::WorkspaceSymbolResponse;
+use lsp_types::WorkspaceUnchangedDocumentDiagnosticReport;
 use lsp_types::notification::Cancel;
 use lsp_types::notification::DidChangeConfiguration;
 use lsp_types::notification::DidChangeTextDocument;
@@ -163,9 +176,14 @@ use lsp_types::request::SemanticTokensFullRequest;
 use lsp_types::request::SemanticTokensRangeRequest;
 use lsp_types::request::SemanticTokensRefresh;
 use lsp_types::request::SignatureHelpRequest;
+use lsp_types::request::TypeHierarchyPrepare;
+use lsp_types::request::TypeHierarchySubtypes;
+use lsp_types::request::TypeHierarchySupertypes;
 use lsp_types::request::UnregisterCapability;
 use lsp_types::request::WillRenameFiles;
 use lsp_types::request::WorkspaceConfiguration;
+use lsp_types::request::WorkspaceDiagnosticRefresh;
+use lsp_types::request::WorkspaceDiagnosticRequest;
 use lsp_types::request::WorkspaceSymbolRequest;
 use pyrefly_build::SourceDatabase;
 use pyrefly_build::handle::Handle;
@@ -203,6 +221,7 @@ use tracing::info;
 use crate::ModuleInfo;
 use crate::commands::lsp::IndexingMode;
 use crate::config::config::ConfigFile;
+use crate::config::error_kind::ErrorKind;
 use crate::error::error::Error;
 use crate::lsp::module_helpers::to_real_path;
 use crate::lsp::non_wasm::build_system::should_requery_build_system;
@@ -250,6 +269,7 @@ use crate::state::state::CancellableTransaction;
 use crate::state::state::CommittingTransaction;
 use crate::state::state::State;
 use crate::state::state::Transaction;
+use crate::types::class::Class;
 
 #[derive(Clone, Copy, Debug, Serialize)]
 #[serde(rename_all = "kebab-case")]
@@ -298,6 +318,19 @@ pub trait TspInterface: Send + Sync {
     ) -> anyhow::Result<ProcessEvent>;
 
     fn telemetry_state(&self) -> TelemetryServerState;
+
+    /// The state that type server queries are answered from
+    fn state(&self) -> &State;
+
+    /// Create a handle for `uri`, or `None` if language services are disabled for it
+    fn make_handle_if_enabled(&self, uri: &Url, method: Option<&str>) -> Option<Handle>;
+
+    /// Bring the open files in `transaction` up to date with their in-memory contents
+    fn validate_in_memory_for_transaction(
+        &self,
+        transaction: &mut Transaction<'_>,
+        telemetry: &mut TelemetryEvent,
+    );
 }
 
 struct ServerConnection(Connection);
@@ -370,6 +403,9 @@ pub struct Server {
     /// performed with best effort up to certain limit of user files. When the workspace changes,
     /// we rely on file watchers to catch up.
     indexed_workspaces: Mutex<HashSet<PathBuf>>,
+    /// Project files that `workspace/diagnostic` has already queued for a check. After that,
+    /// rechecks of the main `State` keep their errors up to date.
+    workspace_diagnostic_handles: Mutex<HashSet<Handle>>,
     cancellation_handles: Mutex<HashMap<RequestId, CancellationHandle>>,
     workspaces: Arc<Workspaces>,
     outgoing_request_id: AtomicI32,
@@ -472,6 +508,23 @@ pub fn dispatch_lsp_events(connection: &Connection, lsp_queue: &LspQueue) {
     let _ = lsp_queue.send(LspEvent::Exit);
 }
 
+/// Whether the client pulls diagnostics instead of having us publish them. Clients opt in with the
+/// `pyrefly.pullDiagnostics` initialization option, so that clients which support both don't see
+/// every diagnostic twice.
+fn uses_pull_diagnostics(initialization_params: &InitializeParams) -> bool {
+    let opted_in = initialization_params
+        .initialization_options
+        .as_ref()
+        .and_then(|x| x.get("pyrefly")?.get("pullDiagnostics")?.as_bool())
+        .unwrap_or(false);
+    opted_in
+        && initialization_params
+            .capabilities
+            .text_document
+            .as_ref()
+            .is_some_and(|c| c.diagnostic.is_some())
+}
+
 pub fn capabilities(
     indexing_mode: IndexingMode,
     initialization_params: &InitializeParams,
@@ -496,6 +549,7 @@ pub fn capabilities(
             code_action_kinds: Some(vec![
                 CodeActionKind::QUICKFIX,
                 CodeActionKind::REFACTOR_EXTRACT,
+                CodeActionKind::SOURCE_FIX_ALL,
             ]),
             ..Default::default()
         })),
@@ -573,6 +627,15 @@ pub fn capabilities(
                 ..Default::default()
             }),
         }),
+        diagnostic_provider: if uses_pull_diagnostics(initialization_params) {
+            Some(DiagnosticServerCapabilities::Options(DiagnosticOptions {
+                inter_file_dependencies: true,
+                workspace_diagnostics: true,
+                ..Default::default()
+            }))
+        } else {
+            None
+        },
         notebook_document_sync: Some(OneOf::Left(NotebookDocumentSyncOptions {
             notebook_selector: vec![NotebookSelector::ByCells {
                 notebook: None,
@@ -586,6 +649,23 @@ pub fn capabilities(
     }
 }
 
+/// Serialize the capabilities for the `initialize` response, adding the ones that
+/// `ServerCapabilities` has no field for.
+pub fn capabilities_to_json(
+    indexing_mode: IndexingMode,
+    capabilities: ServerCapabilities,
+) -> serde_json::Value {
+    let mut value = serde_json::to_value(capabilities).unwrap();
+    // Finding subtypes needs to know all the files, the same as call hierarchy.
+    match indexing_mode {
+        IndexingMode::None => {}
+        IndexingMode::LazyNonBlockingBackground | IndexingMode::LazyBlocking => {
+            value["typeHierarchyProvider"] = serde_json::Value::Bool(true);
+        }
+    }
+    value
+}
+
 pub enum ProcessEvent {
     Continue,
     Exit,
@@ -1126,6 +1206,17 @@ impl Server {
                             Ok(self.document_diagnostics(&transaction, params)),
                         ));
                     }
+                } else if let Some(params) = as_request::<WorkspaceDiagnosticRequest>(&x) {
+                    if let Some(params) = self
+                        .extract_request_params_or_send_err_response::<WorkspaceDiagnosticRequest>(
+                            params, &x.id,
+                        )
+                    {
+                        self.send_response(new_response(
+                            x.id,
+                            Ok(self.workspace_diagnostics(&transaction, params)),
+                        ));
+                    }
                 } else if let Some(params) = as_request::<ProvideType>(&x) {
                     if let Some(params) = self
                         .extract_request_params_or_send_err_response::<ProvideType>(params, &x.id)
@@ -1169,6 +1260,36 @@ impl Server {
                             .unwrap_or_default();
                         self.send_response(new_response(x.id, Ok(result)));
                     }
+                } else if let Some(params) = as_request::<TypeHierarchyPrepare>(&x) {
+                    if let Some(params) = self
+                        .extract_request_params_or_send_err_response::<TypeHierarchyPrepare>(
+                            params, &x.id,
+                        )
+                    {
+                        self.send_response(new_response(
+                            x.id,
+                            Ok(self.prepare_type_hierarchy(&transaction, params)),
+                        ));
+                    }
+                } else if let Some(params) = as_request::<TypeHierarchySupertypes>(&x) {
+                    if let Some(params) = self
+                        .extract_request_params_or_send_err_response::<TypeHierarchySupertypes>(
+                            params, &x.id,
+                        )
+                    {
+                        self.send_response(new_response(
+                            x.id,
+                            Ok(self.type_hierarchy_supertypes(&mut transaction, params)),
+                        ));
+                    }
+                } else if let Some(params) = as_request::<TypeHierarchySubtypes>(&x) {
+                    if let Some(params) = self
+                        .extract_request_params_or_send_err_response::<TypeHierarchySubtypes>(
+                            params, &x.id,
+                        )
+                    {
+                        self.async_type_hierarchy_subtypes(x.id, &transaction, params);
+                    }
                 } else if let Some(params) = as_request::<CallHierarchyPrepare>(&x) {
                     if let Some(params) = self
                         .extract_request_params_or_send_err_response::<CallHierarchyPrepare>(
@@ -1275,6 +1396,7 @@ impl Server {
             unsaved_file_tracker: UnsavedFileTracker::new(),
             indexed_configs: Mutex::new(HashSet::new()),
             indexed_workspaces: Mutex::new(HashSet::new()),
+            workspace_diagnostic_handles: Mutex::new(HashSet::new()),
             cancellation_handles: Mutex::new(HashMap::new()),
             workspaces,
             outgoing_request_id: AtomicI32::new(1),
@@ -1358,54 +1480,58 @@ impl Server {
         open_files: &HashMap<PathBuf, Arc<LspFile>>,
         cell_uri: Option<&Url>, // If the file is a notebook, only show diagnostics for the matching cell
     ) -> Option<(PathBuf, Diagnostic)> {
-        if let Some(path) = to_real_path(e.path()) {
-            // When no file covers this, we'll get the default configured config which includes "everything"
-            // and excludes `.<file>`s.
-            let config = self
-                .state
-                .config_finder()
-                .python_file(ModuleName::unknown(), e.path());
+        if let Some(path) = to_real_path(e.path())
+            && let Some(lsp_file) = open_files.get(&path)
+            && self.should_show_error(e, &path)
+        {
+            return match &**lsp_file {
+                LspFile::Notebook(notebook) => {
+                    let error_cell = e.get_notebook_cell()?;
+                    let error_cell_uri = notebook.get_cell_url(error_cell)?;
+                    if let Some(filter_cell) = cell_uri
+                        && error_cell_uri != filter_cell
+                    {
+                        None
+                    } else {
+                        Some((PathBuf::from(error_cell_uri.to_string()), e.to_diagnostic()))
+                    }
+                }
+                LspFile::Source(_) => Some((path.to_path_buf(), e.to_diagnostic())),
+            };
+        }
+        None
+    }
 
-            let type_error_status = self.type_error_display_status(e.path().as_path());
+    /// Whether an error in the file at `path` should be shown to the user, regardless of
+    /// whether the file is open.
+    fn should_show_error(&self, e: &Error, path: &Path) -> bool {
+        // When no file covers this, we'll get the default configured config which includes "everything"
+        // and excludes `.<file>`s.
+        let config = self
+            .state
+            .config_finder()
+            .python_file(ModuleName::unknown(), e.path());
 
-            let should_show_stdlib_error =
-                should_show_stdlib_error(&config, type_error_status, &path);
+        let type_error_status = self.type_error_display_status(e.path().as_path());
 
-            if is_python_stdlib_file(&path) && !should_show_stdlib_error {
-                return None;
-            }
+        let should_show_stdlib_error = should_show_stdlib_error(&config, type_error_status, path);
 
-            // Check if we should filter based on error kind for ErrorMissingImports mode
-            let display_type_errors_mode = self
-                .workspaces
-                .get_with(path.to_path_buf(), |(_, w)| w.display_type_errors);
+        if is_python_stdlib_file(path) && !should_show_stdlib_error {
+            return false;
+        }
 
-            if !should_show_error_for_display_mode(e, display_type_errors_mode) {
-                return None;
-            }
+        // Check if we should filter based on error kind for ErrorMissingImports mode
+        let display_type_errors_mode = self
+            .workspaces
+            .get_with(path.to_path_buf(), |(_, w)| w.display_type_errors);
 
-            if let Some(lsp_file) = open_files.get(&path)
-                && config.project_includes.covers(&path)
-                && !config.project_excludes.covers(&path)
-                && type_error_status.is_enabled()
-            {
-                return match &**lsp_file {
-                    LspFile::Notebook(notebook) => {
-                        let error_cell = e.get_notebook_cell()?;
-                        let error_cell_uri = notebook.get_cell_url(error_cell)?;
-                        if let Some(filter_cell) = cell_uri
-                            && error_cell_uri != filter_cell
-                        {
-                            None
-                        } else {
-                            Some((PathBuf::from(error_cell_uri.to_string()), e.to_diagnostic()))
-                        }
-                    }
-                    LspFile::Source(_) => Some((path.to_path_buf(), e.to_diagnostic())),
-                };
-            }
+        if !should_show_error_for_display_mode(e, display_type_errors_mode) {
+            return false;
         }
-        None
+
+        config.project_includes.covers(path)
+            && !config.project_excludes.covers(path)
+            && type_error_status.is_enabled()
     }
 
     fn provide_type(
@@ -1524,6 +1650,21 @@ impl Server {
         let handles = self.validate_in_memory_for_transaction(transaction, telemetry);
 
         let publish = |transaction: &Transaction| {
+            if uses_pull_diagnostics(&self.initialize_params) {
+                // The client asks for diagnostics itself, so just let it know they may have changed.
+                if self
+                    .initialize_params
+                    .capabilities
+                    .workspace
+                    .as_ref()
+                    .and_then(|w| w.diagnostic.as_ref())
+                    .and_then(|d| d.refresh_support)
+                    .unwrap_or(false)
+                {
+                    self.send_request::<WorkspaceDiagnosticRefresh>(());
+                }
+                return;
+            }
             let mut diags: SmallMap<PathBuf, Vec<Diagnostic>> = SmallMap::new();
             let open_files = self.open_files.read();
             let open_notebook_cells = self.open_notebook_cells.read();
@@ -2514,6 +2655,9 @@ impl Server {
         if let Some(refactors) = transaction.extract_function_code_actions(&handle, range) {
             push_refactor_actions(refactors);
         }
+        if let Some(fixes) = transaction.remove_unused_code_actions(&handle) {
+            push_refactor_actions(fixes);
+        }
         if actions.is_empty() {
             None
         } else {
@@ -2955,6 +3099,24 @@ impl Server {
                     data: None,
                 });
             }
+            // Code after a `return` or `raise` is greyed out even when `unreachable-code` errors
+            // are disabled. When enabled, the error itself is tagged, and we also check for code
+            // after calls that never return, which is too costly to do for every call otherwise.
+            for error in transaction.get_errors([handle]).collect_errors().disabled {
+                if error.error_kind() == ErrorKind::UnreachableCode && seen.insert(error.range()) {
+                    items.push(Diagnostic {
+                        range: module_info.to_lsp_range(error.range()),
+                        severity: Some(DiagnosticSeverity::HINT),
+                        source: Some("Pyrefly".to_owned()),
+                        message: error.msg_header().to_owned(),
+                        code: Some(NumberOrString::String("unreachable-code".to_owned())),
+                        code_description: None,
+                        related_information: None,
+                        tags: Some(vec![DiagnosticTag::UNNECESSARY]),
+                        data: None,
+                    });
+                }
+            }
         }
     }
 
@@ -2967,6 +3129,9 @@ impl Server {
             let module_info = bindings.module();
             for unused in bindings.unused_parameters() {
                 let lsp_range = module_info.to_lsp_range(unused.range);
+                if Self::has_diagnostic(items, lsp_range, ErrorKind::UnusedParameter) {
+                    continue;
+                }
                 items.push(Diagnostic {
                     range: lsp_range,
                     severity: Some(DiagnosticSeverity::HINT),
@@ -2982,6 +3147,14 @@ impl Server {
         }
     }
 
+    /// Whether the errors already contain a diagnostic of `kind` at `range`, for when an `unused-*`
+    /// error kind is enabled and we don't want a duplicate hint.
+    fn has_diagnostic(items: &[Diagnostic], range: Range, kind: ErrorKind) -> bool {
+        items.iter().any(|x| {
+            x.range == range && x.code == Some(NumberOrString::String(kind.to_name().to_owned()))
+        })
+    }
+
     fn append_unused_import_diagnostics(
         transaction: &Transaction<'_>,
         handle: &Handle,
@@ -2991,6 +3164,9 @@ impl Server {
             let module_info = bindings.module();
             for unused in bindings.unused_imports() {
                 let lsp_range = module_info.to_lsp_range(unused.range);
+                if Self::has_diagnostic(items, lsp_range, ErrorKind::UnusedImport) {
+                    continue;
+                }
                 items.push(Diagnostic {
                     range: lsp_range,
                     severity: Some(DiagnosticSeverity::HINT),
@@ -3015,6 +3191,9 @@ impl Server {
             let module_info = bindings.module();
             for unused in bindings.unused_variables() {
                 let lsp_range = module_info.to_lsp_range(unused.range);
+                if Self::has_diagnostic(items, lsp_range, ErrorKind::UnusedVariable) {
+                    continue;
+                }
                 items.push(Diagnostic {
                     range: lsp_range,
                     severity: Some(DiagnosticSeverity::HINT),
@@ -3143,6 +3322,134 @@ impl Server {
         })
     }
 
+    /// Report the errors of every project file in the workspace that isn't open. Open files are
+    /// left to `textDocument/diagnostic`, which also includes IDE-specific diagnostics.
+    ///
+    /// Each report carries a result ID derived from its diagnostics, so files whose diagnostics
+    /// match the client's previous result ID are reported as unchanged.
+    ///
+    /// We answer from the current state, skipping files that haven't been loaded yet, and check
+    /// any files we haven't seen before on the recheck queue. Once that check is committed, the
+    /// client is asked to refresh its diagnostics.
+    fn workspace_diagnostics(
+        &self,
+        transaction: &Transaction<'_>,
+        params: WorkspaceDiagnosticParams,
+    ) -> WorkspaceDiagnosticReportResult {
+        let previous_result_ids: HashMap<Url, String> = params
+            .previous_result_ids
+            .into_iter()
+            .map(|x| (x.uri, x.value))
+            .collect();
+        let handles = self.workspace_project_handles();
+        let unchecked = {
+            let mut checked = self.workspace_diagnostic_handles.lock();
+            handles
+                .iter()
+                .filter(|x| checked.insert((*x).dupe()))
+                .cloned()
+                .collect_vec()
+        };
+        if !unchecked.is_empty() {
+            self.recheck_queue.queue_task(
+                TelemetryEventKind::WorkspaceDiagnostics,
+                Box::new(move |server, telemetry| {
+                    let mut transaction = server
+                        .state
+                        .new_committable_transaction(Require::indexing(), None);
+                    transaction.as_mut().run(&unchecked, Require::Errors);
+                    server
+                        .state
+                        .commit_transaction(transaction, Some(telemetry));
+                    // Revalidating the open files sends the client a diagnostic refresh.
+                    let _ = server.lsp_queue.send(LspEvent::RecheckFinished);
+                }),
+            );
+        }
+        let handles = handles
+            .into_iter()
+            .filter(|x| transaction.get_load(x).is_some())
+            .collect_vec();
+        let mut diags: SmallMap<PathBuf, Vec<Diagnostic>> = handles
+            .iter()
+            .filter_map(|x| Some((to_real_path(x.path())?, Vec::new())))
+            .collect();
+        for e in transaction.get_errors(&handles).collect_errors().shown {
+            if let Some(path) = to_real_path(e.path())
+                && let Some(items) = diags.get_mut(&path)
+                && self.should_show_error(&e, &path)
+            {
+                items.push(e.to_diagnostic());
+            }
+        }
+        let items = diags
+            .into_iter()
+            .filter_map(|(path, items)| {
+                let uri = Url::from_file_path(&path).ok()?;
+                let result_id = Self::diagnostics_result_id(&items);
+                Some(if previous_result_ids.get(&uri) == Some(&result_id) {
+                    WorkspaceDocumentDiagnosticReport::Unchanged(
+                        WorkspaceUnchangedDocumentDiagnosticReport {
+                            uri,
+                            version: None,
+                            unchanged_document_diagnostic_report:
+                                UnchangedDocumentDiagnosticReport { result_id },
+                        },
+                    )
+                } else {
+                    WorkspaceDocumentDiagnosticReport::Full(WorkspaceFullDocumentDiagnosticReport {
+                        uri,
+                        version: None,
+                        full_document_diagnostic_report: FullDocumentDiagnosticReport {
+                            items,
+                            result_id: Some(result_id),
+                        },
+                    })
+                })
+            })
+            .collect();
+        WorkspaceDiagnosticReportResult::Report(WorkspaceDiagnosticReport { items })
+    }
+
+    /// Handles for all the files in the projects of the workspace folders, other than the open
+    /// ones.
+    fn workspace_project_handles(&self) -> Vec<Handle> {
+        let unknown = ModuleName::unknown();
+        let open_files = self.open_files.read();
+        let mut configs = SmallSet::new();
+        let mut handles = Vec::new();
+        for root in self.workspaces.roots() {
+            let Some(config) = self.state.config_finder().directory(&root) else {
+                continue;
+            };
+            if !configs.insert(config.dupe()) {
+                continue;
+            }
+            for path in config.get_filtered_globs(None).files().unwrap_or_default() {
+                if open_files.contains_key(&path) {
+                    continue;
+                }
+                let module_path = ModulePath::filesystem(path);
+                if self
+                    .state
+                    .config_finder()
+                    .python_file(unknown, &module_path)
+                    != config
+                {
+                    continue;
+                }
+                handles.push(handle_from_module_path(&self.state, module_path));
+            }
+        }
+        handles
+    }
+
+    /// A result ID that changes whenever the diagnostics of a file do.
+    fn diagnostics_result_id(items: &[Diagnostic]) -> String {
+        let json = serde_json::to_string(items).unwrap_or_default();
+        blake3::hash(json.as_bytes()).to_hex().to_string()
+    }
+
     /// Converts a [`WatchPattern`] into a [`GlobPattern`] that can be used and watched
     /// by VSCode, provided its `relative_pattern_support`.
     fn get_pattern_to_watch(
@@ -3598,6 +3905,118 @@ impl Server {
         }
         None
     }
+
+    fn type_hierarchy_item(cls: &Class) -> Option<TypeHierarchyItem> {
+        let module = cls.module();
+        let range = module.to_lsp_range(cls.range());
+        Some(TypeHierarchyItem {
+            name: cls.name().to_string(),
+            kind: lsp_types::SymbolKind::CLASS,
+            tags: None,
+            detail: Some(format!("{}.{}", module.name(), cls.name())),
+            uri: module_info_to_uri(module)?,
+            range,
+            selection_range: range,
+            data: None,
+        })
+    }
+
+    /// Prepares the type hierarchy for the class at the cursor, which can be either the
+    /// definition of the class or a use of it.
+    fn prepare_type_hierarchy(
+        &self,
+        transaction: &Transaction<'_>,
+        params: TypeHierarchyPrepareParams,
+    ) -> Option<Vec<TypeHierarchyItem>> {
+        let uri = &params.text_document_position_params.text_document.uri;
+        let handle = self.make_handle_if_enabled(uri, Some(TypeHierarchyPrepare::METHOD))?;
+        let module_info = transaction.get_module_info(&handle)?;
+        let position = self.from_lsp_position(
+            uri,
+            &module_info,
+            params.text_document_position_params.position,
+        );
+        let cls = transaction.class_at(&handle, position)?;
+        Some(vec![Self::type_hierarchy_item(&cls)?])
+    }
+
+    /// Finds the direct base classes of a type hierarchy item.
+    fn type_hierarchy_supertypes(
+        &self,
+        transaction: &mut Transaction<'_>,
+        params: TypeHierarchySupertypesParams,
+    ) -> Option<Vec<TypeHierarchyItem>> {
+        let uri = &params.item.uri;
+        let handle = self.make_handle_if_enabled(uri, Some(TypeHierarchySupertypes::METHOD))?;
+        // The item may come from a file we haven't fully checked yet.
+        transaction.run(&[handle.dupe()], Require::Everything);
+        let module_info = transaction.get_module_info(&handle)?;
+        let position = self.from_lsp_position(uri, &module_info, params.item.selection_range.start);
+        let cls = transaction.class_at(&handle, position)?;
+        Some(
+            transaction
+                .find_direct_superclasses(&handle, &cls)
+                .iter()
+                .filter_map(Self::type_hierarchy_item)
+                .collect(),
+        )
+    }
+
+    /// Asynchronously finds the classes that directly derive from a type hierarchy item.
+    ///
+    /// This queues work on the find_reference_queue, since subclasses may be anywhere in the
+    /// indexed workspace.
+    fn async_type_hierarchy_subtypes<'a>(
+        &'a self,
+        request_id: RequestId,
+        transaction: &Transaction<'a>,
+        params: TypeHierarchySubtypesParams,
+    ) {
+        let uri = params.item.uri.clone();
+        let Some(handle) = self.make_handle_if_enabled(&uri, Some(TypeHierarchySubtypes::METHOD))
+        else {
+            return self.send_response(new_response::<Option<Vec<TypeHierarchyItem>>>(
+                request_id,
+                Ok(None),
+            ));
+        };
+        // The TypeHierarchyItem we receive is already at the definition position.
+        self.async_find_from_definition_helper(
+            request_id,
+            transaction,
+            handle,
+            &uri,
+            params.item.selection_range.start,
+            FindPreference::default(),
+            |transaction, handle, definition| {
+                transaction.find_global_subclasses_from_definition(
+                    handle.sys_info(),
+                    TextRangeWithModule::new(definition.module, definition.definition_range),
+                )
+            },
+            |subclasses| {
+                let mut items = Vec::new();
+                for subclass in subclasses {
+                    let Some(uri) = module_info_to_uri(&subclass.module) else {
+                        continue;
+                    };
+                    let name = subclass.module.code_at(subclass.range).to_owned();
+                    let range = subclass.module.to_lsp_range(subclass.range);
+                    items.push(TypeHierarchyItem {
+                        detail: Some(format!("{}.{}", subclass.module.name(), name)),
+                        name,
+                        kind: lsp_types::SymbolKind::CLASS,
+                        tags: None,
+                        uri,
+                        range,
+                        selection_range: range,
+                        data: None,
+                    });
+                }
+                items
+            },
+        );
+    }
 }
 
 impl TspInterface for Server {
@@ -3641,4 +4060,20 @@ impl TspInterface for Server {
     fn telemetry_state(&self) -> TelemetryServerState {
         self.telemetry_state()
     }
+
+    fn state(&self) -> &State {
+        &self.state
+    }
+
+    fn make_handle_if_enabled(&self, uri: &Url, method: Option<&str>) -> Option<Handle> {
+        self.make_handle_if_enabled(uri, method)
+    }
+
+    fn validate_in_memory_for_transaction(
+        &self,
+        transaction: &mut Transaction<'_>,
+        telemetry: &mut TelemetryEvent,
+    ) {
+        self.validate_in_memory_for_transaction(transaction, telemetry);
+    }
 }
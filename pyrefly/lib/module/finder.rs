@@ -577,6 +577,11 @@ pub fn find_import_filtered(
         && let Some(path) = sourcedb.lookup(module, origin, style_filter)
     {
         FindingOrError::new_finding(path.clone())
+    } else if let Some(sourcedb) = config.source_db.as_ref()
+        && let Some(owner) = sourcedb.undeclared_dependency(module, origin)
+        && let Some(target) = sourcedb.get_target(origin)
+    {
+        FindingOrError::Error(FindError::UndeclaredDependency(module, owner, target))
     } else if let Some(path) = find_module(
         module,
         config.search_path(),
@@ -626,7 +631,15 @@ pub fn find_import_filtered(
         typeshed_third_party_stub.clone(),
         from_real_config_file,
     ) {
-        path
+        if let Some(sourcedb) = config.source_db.as_ref()
+            && let FindingOrError::Finding(finding) = &path
+            && let Some(owner) = sourcedb.undeclared_distribution(finding.finding.as_path(), origin)
+            && let Some(target) = sourcedb.get_target(origin)
+        {
+            FindingOrError::Error(FindError::UndeclaredDependency(module, owner, target))
+        } else {
+            path
+        }
     } else if let Some(namespace) = namespaces_found.into_iter().next() &&
         // only use namespaces if style filter is none, since otherwise we might be
         // skipping a result that's more preferable, but excluded because of the style
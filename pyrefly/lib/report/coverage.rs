@@ -0,0 +1,425 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Type coverage metrics: how many parameters and returns are annotated, and how many
+//! expressions have a solved type that contains `Any`.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+
+use pyrefly_build::handle::Handle;
+use pyrefly_python::module::Module;
+use pyrefly_types::types::AnyStyle;
+use pyrefly_types::types::Type;
+use ruff_python_ast::Expr;
+use serde::Serialize;
+use serde::ser::SerializeStruct;
+
+use crate::binding::binding::Binding;
+use crate::binding::binding::Key;
+use crate::state::state::Transaction;
+
+/// Counts of annotated and `Any`-typed code, for a module or an aggregate of modules.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Coverage {
+    pub parameters: usize,
+    pub annotated_parameters: usize,
+    pub returns: usize,
+    pub annotated_returns: usize,
+    pub expressions: usize,
+    /// Expressions whose type contains an `Any` that the user wrote.
+    pub explicit_any_expressions: usize,
+    /// Expressions whose type contains an `Any` that was inferred, e.g. from a missing annotation.
+    pub implicit_any_expressions: usize,
+}
+
+fn percent(part: usize, total: usize) -> f64 {
+    if total == 0 {
+        100.0
+    } else {
+        100.0 * part as f64 / total as f64
+    }
+}
+
+impl Coverage {
+    fn add(&mut self, other: &Self) {
+        self.parameters += other.parameters;
+        self.annotated_parameters += other.annotated_parameters;
+        self.returns += other.returns;
+        self.annotated_returns += other.annotated_returns;
+        self.expressions += other.expressions;
+        self.explicit_any_expressions += other.explicit_any_expressions;
+        self.implicit_any_expressions += other.implicit_any_expressions;
+    }
+
+    /// The percentage of parameters and returns that are annotated.
+    pub fn annotation_coverage(&self) -> f64 {
+        percent(
+            self.annotated_parameters + self.annotated_returns,
+            self.parameters + self.returns,
+        )
+    }
+
+    /// The percentage of expressions whose type doesn't contain `Any`.
+    pub fn expression_coverage(&self) -> f64 {
+        percent(
+            self.expressions - self.explicit_any_expressions - self.implicit_any_expressions,
+            self.expressions,
+        )
+    }
+}
+
+impl Serialize for Coverage {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("Coverage", 9)?;
+        s.serialize_field("parameters", &self.parameters)?;
+        s.serialize_field("annotated_parameters", &self.annotated_parameters)?;
+        s.serialize_field("returns", &self.returns)?;
+        s.serialize_field("annotated_returns", &self.annotated_returns)?;
+        s.serialize_field("expressions", &self.expressions)?;
+        s.serialize_field("explicit_any_expressions", &self.explicit_any_expressions)?;
+        s.serialize_field("implicit_any_expressions", &self.implicit_any_expressions)?;
+        s.serialize_field("annotation_coverage", &self.annotation_coverage())?;
+        s.serialize_field("expression_coverage", &self.expression_coverage())?;
+        s.end()
+    }
+}
+
+/// Where an `Any` came from. An `Any` introduced by an error counts as implicit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AnyKind {
+    Explicit,
+    Implicit,
+}
+
+impl AnyKind {
+    /// The kind of `Any` in a type, preferring implicit if it contains both.
+    fn of(ty: &Type) -> Option<Self> {
+        let mut res = None;
+        ty.universe(&mut |ty| {
+            if let Type::Any(style) = ty {
+                let kind = match style {
+                    AnyStyle::Explicit => AnyKind::Explicit,
+                    AnyStyle::Implicit | AnyStyle::Error => AnyKind::Implicit,
+                };
+                res = res.max(Some(kind));
+            }
+        });
+        res
+    }
+}
+
+/// Consecutive lines that start an expression with an `Any` type, with 1-based line numbers.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AnyLines {
+    pub start_line: usize,
+    pub end_line: usize,
+    pub kind: AnyKind,
+}
+
+/// The coverage of a single module.
+#[derive(Debug, Serialize)]
+pub struct ModuleCoverage {
+    pub name: String,
+    pub path: String,
+    #[serde(flatten)]
+    pub coverage: Coverage,
+    pub any_lines: Vec<AnyLines>,
+    #[serde(skip)]
+    module: Module,
+}
+
+/// The coverage of a set of modules, aggregated per package and in total.
+#[derive(Debug, Serialize)]
+pub struct CoverageReport {
+    pub total: Coverage,
+    pub packages: BTreeMap<String, Coverage>,
+    pub modules: Vec<ModuleCoverage>,
+}
+
+/// Compute the coverage of a module, which must have been checked with `Require::Everything`
+/// so that the types of its expressions were kept.
+pub fn module_coverage(transaction: &Transaction, handle: &Handle) -> Option<ModuleCoverage> {
+    let module = transaction.get_module_info(handle)?;
+    let bindings = transaction.get_bindings(handle)?;
+    let answers = transaction.get_answers(handle)?;
+    let mut coverage = Coverage::default();
+
+    for idx in bindings.keys::<Key>() {
+        if let Key::Definition(_) = bindings.idx_to_key(idx)
+            && let Binding::Function(x, _pred, _class_meta) = bindings.get(idx)
+        {
+            let fun = bindings.get(bindings.get(*x).undecorated_idx);
+            let is_staticmethod = fun.def.decorator_list.iter().any(
+                |d| matches!(&d.expression, Expr::Name(name) if name.id.as_str() == "staticmethod"),
+            );
+            let params = &fun.def.parameters;
+            // The `self` or `cls` parameter of a method doesn't need an annotation.
+            let skip = usize::from(fun.class_key.is_some() && !is_staticmethod);
+            for param in params
+                .posonlyargs
+                .iter()
+                .chain(&params.args)
+                .map(|p| &p.parameter)
+                .chain(params.vararg.as_deref())
+                .chain(params.kwonlyargs.iter().map(|p| &p.parameter))
+                .chain(params.kwarg.as_deref())
+                .skip(skip)
+            {
+                coverage.parameters += 1;
+                if param.annotation.is_some() {
+                    coverage.annotated_parameters += 1;
+                }
+            }
+            coverage.returns += 1;
+            if fun.def.returns.is_some() {
+                coverage.annotated_returns += 1;
+            }
+        }
+    }
+
+    let mut any_lines: BTreeMap<usize, AnyKind> = BTreeMap::new();
+    for (range, ty) in answers.get_all_type_traces().unwrap_or_default() {
+        coverage.expressions += 1;
+        if let Some(kind) = AnyKind::of(&ty) {
+            match kind {
+                AnyKind::Explicit => coverage.explicit_any_expressions += 1,
+                AnyKind::Implicit => coverage.implicit_any_expressions += 1,
+            }
+            let line = module.display_pos(range.start()).line_within_file().get() as usize;
+            let entry = any_lines.entry(line).or_insert(kind);
+            *entry = (*entry).max(kind);
+        }
+    }
+
+    Some(ModuleCoverage {
+        name: module.name().to_string(),
+        path: module.path().to_string(),
+        coverage,
+        any_lines: merge_lines(any_lines),
+        module,
+    })
+}
+
+/// Merge consecutive lines with the same kind of `Any` into ranges.
+fn merge_lines(lines: BTreeMap<usize, AnyKind>) -> Vec<AnyLines> {
+    let mut res: Vec<AnyLines> = Vec::new();
+    for (line, kind) in lines {
+        match res.last_mut() {
+            Some(last) if last.end_line + 1 == line && last.kind == kind => last.end_line = line,
+            _ => res.push(AnyLines {
+                start_line: line,
+                end_line: line,
+                kind,
+            }),
+        }
+    }
+    res
+}
+
+impl CoverageReport {
+    pub fn new(mut modules: Vec<ModuleCoverage>) -> Self {
+        modules.sort_by(|a, b| a.name.cmp(&b.name).then_with(|| a.path.cmp(&b.path)));
+        let mut total = Coverage::default();
+        let mut packages: BTreeMap<String, Coverage> = BTreeMap::new();
+        for m in &modules {
+            total.add(&m.coverage);
+            // A module counts towards every package that contains it, and a package's
+            // `__init__` module counts towards the package itself.
+            let components = m.name.split('.').collect::<Vec<_>>();
+            let depth = if m.module.path().is_init() {
+                components.len()
+            } else {
+                components.len() - 1
+            };
+            for i in 1..=depth {
+                packages
+                    .entry(components[..i].join("."))
+                    .or_default()
+                    .add(&m.coverage);
+            }
+        }
+        Self {
+            total,
+            packages,
+            modules,
+        }
+    }
+
+    /// Render the report as a standalone HTML page, with the source of each module and the
+    /// lines with `Any`-typed expressions highlighted.
+    pub fn to_html(&self) -> String {
+        fn escape(x: &str) -> String {
+            x.replace('&', "&amp;")
+                .replace('<', "&lt;")
+                .replace('>', "&gt;")
+                .replace('"', "&quot;")
+        }
+        fn row(out: &mut String, name: &str, coverage: &Coverage) {
+            writeln!(
+                out,
+                "<tr><td>{}</td><td>{:.1}%</td><td>{:.1}%</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                name,
+                coverage.expression_coverage(),
+                coverage.annotation_coverage(),
+                coverage.expressions,
+                coverage.explicit_any_expressions,
+                coverage.implicit_any_expressions,
+            )
+            .unwrap();
+        }
+        const HEADER: &str = "<tr><th>Name</th><th>Expressions without Any</th><th>Annotated</th><th>Expressions</th><th>Explicit Any</th><th>Implicit Any</th></tr>";
+
+        let mut out = String::new();
+        out.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+        out.push_str("<title>Pyrefly type coverage</title>\n<style>\n");
+        out.push_str("body { font-family: sans-serif; }\n");
+        out.push_str("table { border-collapse: collapse; }\n");
+        out.push_str("td, th { border: 1px solid #ccc; padding: 2px 8px; text-align: left; }\n");
+        out.push_str("pre { line-height: 1.3; }\n");
+        out.push_str(".explicit { background-color: #fff3b0; }\n");
+        out.push_str(".implicit { background-color: #ffc9c9; }\n");
+        out.push_str("</style>\n</head>\n<body>\n<h1>Pyrefly type coverage</h1>\n");
+        out.push_str(
+            "<p>Lines highlighted in <span class=\"implicit\">red</span> start an expression with an inferred <code>Any</code> type, and lines in <span class=\"explicit\">yellow</span> one with an explicit <code>Any</code>.</p>\n",
+        );
+
+        out.push_str("<h2>Packages</h2>\n<table>\n");
+        out.push_str(HEADER);
+        out.push('\n');
+        row(&mut out, "<b>Total</b>", &self.total);
+        for (name, coverage) in &self.packages {
+            row(&mut out, &escape(name), coverage);
+        }
+        out.push_str("</table>\n<h2>Modules</h2>\n<table>\n");
+        out.push_str(HEADER);
+        out.push('\n');
+        for (i, m) in self.modules.iter().enumerate() {
+            row(
+                &mut out,
+                &format!("<a href=\"#module-{i}\">{}</a>", escape(&m.name)),
+                &m.coverage,
+            );
+        }
+        out.push_str("</table>\n");
+
+        for (i, m) in self.modules.iter().enumerate() {
+            writeln!(
+                out,
+                "<h3 id=\"module-{i}\">{} <small>{}</small></h3>",
+                escape(&m.name),
+                escape(&m.path)
+            )
+            .unwrap();
+            out.push_str("<pre>");
+            let mut ranges = m.any_lines.iter().peekable();
+            for (line, text) in m.module.lined_buffer().lines().enumerate() {
+                let line = line + 1;
+                while ranges.next_if(|r| r.end_line < line).is_some() {}
+                let text = escape(text);
+                match ranges.peek() {
+                    Some(r) if r.start_line <= line => {
+                        let kind = match r.kind {
+                            AnyKind::Explicit => "explicit",
+                            AnyKind::Implicit => "implicit",
+                        };
+                        writeln!(out, "<span class=\"{kind}\">{line:>5}  {text}</span>").unwrap();
+                    }
+                    _ => writeln!(out, "{line:>5}  {text}").unwrap(),
+                }
+            }
+            out.push_str("</pre>\n");
+        }
+        out.push_str("</body>\n</html>\n");
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::require::Require;
+    use crate::test::util::TestEnv;
+
+    #[test]
+    fn test_merge_lines() {
+        let lines = BTreeMap::from([
+            (1, AnyKind::Implicit),
+            (2, AnyKind::Implicit),
+            (3, AnyKind::Explicit),
+            (5, AnyKind::Explicit),
+        ]);
+        assert_eq!(
+            merge_lines(lines),
+            vec![
+                AnyLines {
+                    start_line: 1,
+                    end_line: 2,
+                    kind: AnyKind::Implicit,
+                },
+                AnyLines {
+                    start_line: 3,
+                    end_line: 3,
+                    kind: AnyKind::Explicit,
+                },
+                AnyLines {
+                    start_line: 5,
+                    end_line: 5,
+                    kind: AnyKind::Explicit,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_module_coverage() {
+        let code = r#"
+def f(x: int, y) -> int:
+    return x
+class C:
+    def g(self, z: int) -> int:
+        return z
+    @staticmethod
+    def h(w: int) -> None: ...
+def k(v):
+    return v
+"#;
+        let (state, handle_fn) = TestEnv::one("pkg.test", code)
+            .with_default_require_level(Require::Everything)
+            .to_state();
+        let handle = handle_fn("pkg.test");
+        let transaction = state.transaction();
+        let module = module_coverage(&transaction, &handle).unwrap();
+
+        assert_eq!(module.coverage.parameters, 5);
+        assert_eq!(module.coverage.annotated_parameters, 3);
+        assert_eq!(module.coverage.returns, 4);
+        assert_eq!(module.coverage.annotated_returns, 3);
+        assert!(module.coverage.expressions > 0);
+        // Only the `v` in `return v` has an `Any` type.
+        assert_eq!(module.coverage.explicit_any_expressions, 0);
+        assert_eq!(module.coverage.implicit_any_expressions, 1);
+        assert_eq!(
+            module.any_lines,
+            vec![AnyLines {
+                start_line: 10,
+                end_line: 10,
+                kind: AnyKind::Implicit,
+            }]
+        );
+
+        let report = CoverageReport::new(vec![module]);
+        assert_eq!(report.packages.keys().collect::<Vec<_>>(), vec!["pkg"]);
+        assert_eq!(report.packages["pkg"], report.total);
+        assert!(
+            report
+                .to_html()
+                .contains("<span class=\"implicit\">   10      return v")
+        );
+    }
+}
@@ -6,6 +6,7 @@
  */
 
 pub mod binding_memory;
+pub mod coverage;
 pub mod debug_info;
 pub mod glean;
 pub mod pysa;
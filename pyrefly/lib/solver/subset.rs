@@ -1036,6 +1036,14 @@ impl<'a, Ans: LookupAnswer> Subset<'a, Ans> {
                 }),
                 Type::ClassType(cls),
             ) if cls.has_qname("types", "FunctionType") => Ok(()),
+            // Functions that stand in for callable objects, like `functools.partial`, are
+            // instances of the object's class.
+            (Type::Function(box Function { metadata, .. }), Type::ClassType(want_cls))
+                if let Some(cls) = metadata.instance_class()
+                    && !self.type_order.is_protocol(want_cls.class_object()) =>
+            {
+                self.is_subset_eq(&cls.clone().to_type(), want)
+            }
             (Type::BoundMethod(_), Type::ClassType(cls))
                 if cls.has_qname("types", "MethodType") =>
             {
@@ -0,0 +1,359 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! A persistent, on-disk cache of the errors of each module, so that repeated CLI runs only
+//! need to recheck the modules whose inputs have changed.
+//!
+//! Each checked module gets one cache file, keyed by its name, path and `SysInfo`. The file
+//! stores a fingerprint of the module's own inputs (contents, config, `SysInfo` and the Pyrefly
+//! version), the contents hash of every module it transitively depends on, the imports of those
+//! modules that failed to resolve, and the raw errors it produced. A module whose fingerprint and
+//! dependency hashes all still match, and whose unresolved imports still don't resolve, is not
+//! recomputed; its errors are restored instead, and go through the normal config, suppression
+//! and baseline processing.
+//!
+//! This is an error-only cache: exports and solutions are not stored. Dependencies are compared
+//! by the hash of their contents rather than of their exports, so any edit to a dependency, even
+//! to a comment, invalidates every module that depends on it, and checking whether an entry is
+//! still valid reads the whole dependency closure. A module that does need checking still
+//! computes the exports and types of all its dependencies, including cached ones, so the cache
+//! saves the most when few modules have changed.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use dupe::Dupe;
+use itertools::Itertools;
+use pyrefly_build::handle::Handle;
+use pyrefly_python::module::Module;
+use pyrefly_python::module_name::ModuleName;
+use pyrefly_python::module_path::ModulePath;
+use pyrefly_python::module_path::ModulePathDetails;
+use pyrefly_python::sys_info::PythonPlatform;
+use pyrefly_python::sys_info::PythonVersion;
+use pyrefly_python::sys_info::SysInfo;
+use pyrefly_util::arc_id::ArcId;
+use pyrefly_util::fs_anyhow;
+use ruff_text_size::Ranged;
+use ruff_text_size::TextRange;
+use ruff_text_size::TextSize;
+use serde::Deserialize;
+use serde::Serialize;
+use tracing::debug;
+
+use crate::config::config::ConfigFile;
+use crate::config::error_kind::ErrorKind;
+use crate::config::finder::ConfigFinder;
+use crate::error::collector::ErrorCollector;
+use crate::error::error::Error;
+use crate::error::style::ErrorStyle;
+use crate::module::finder::find_import;
+use crate::state::load::FileContents;
+use crate::state::load::Load;
+use crate::state::memory::MemoryFiles;
+use crate::state::memory::MemoryFilesLookup;
+use crate::state::memory::MemoryFilesOverlay;
+use crate::state::state::Transaction;
+
+/// A dependency of a cached module, along with the hash of its contents when it was checked.
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedDep {
+    path: PathBuf,
+    hash: String,
+}
+
+/// An import of a cached module, or of one of its dependencies, that failed to resolve. If it
+/// resolves now, e.g. because the module was created, the cached results are stale.
+#[derive(Debug, Serialize, Deserialize)]
+struct UnresolvedImport {
+    /// The name and path of the importing module.
+    importer: String,
+    importer_path: PathBuf,
+    module: String,
+}
+
+/// One of the `SysInfo`s a cached error is limited to.
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedSysInfo {
+    version: PythonVersion,
+    platform: PythonPlatform,
+}
+
+/// An error produced by a cached module, before any config or suppressions were applied.
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedError {
+    start: u32,
+    end: u32,
+    kind: ErrorKind,
+    header: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    details: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    sys_infos: Vec<CachedSysInfo>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheEntry {
+    /// Fingerprint of the module's own inputs.
+    fingerprint: String,
+    /// All the on-disk modules this module transitively depends on.
+    deps: Vec<CachedDep>,
+    /// The imports of this module and its dependencies that failed to resolve.
+    unresolved: Vec<UnresolvedImport>,
+    errors: Vec<CachedError>,
+}
+
+pub struct ErrorCache {
+    dir: PathBuf,
+}
+
+impl ErrorCache {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// Split `handles` into those whose results can be restored from the cache, and those that
+    /// need to be checked.
+    pub fn partition(
+        &self,
+        handles: &[Handle],
+        config_finder: &ConfigFinder,
+    ) -> (Vec<(Arc<Load>, ArcId<ConfigFile>)>, Vec<Handle>) {
+        let mut hashes = HashMap::new();
+        let mut cached = Vec::new();
+        let mut uncached = Vec::new();
+        for handle in handles {
+            let config = config_finder.python_file(handle.module(), handle.path());
+            match self.get(handle, &config, config_finder, &mut hashes) {
+                Some(load) => cached.push((Arc::new(load), config)),
+                None => uncached.push(handle.dupe()),
+            }
+        }
+        debug!(
+            "Restored {} of {} modules from the error cache",
+            cached.len(),
+            handles.len()
+        );
+        (cached, uncached)
+    }
+
+    /// Record the results of `handles`, which must have been checked by `transaction`.
+    pub fn save(&self, transaction: &Transaction, handles: &[Handle]) -> anyhow::Result<()> {
+        fs_anyhow::create_dir_all(&self.dir)?;
+        for handle in handles {
+            let Some(load) = transaction.get_load(handle) else {
+                continue;
+            };
+            let Some(config) = transaction.get_config(handle) else {
+                continue;
+            };
+            let Some((deps, unresolved)) = Self::transitive_deps(transaction, handle) else {
+                // Depends on something we can't fingerprint, so don't cache it.
+                continue;
+            };
+            let entry = CacheEntry {
+                fingerprint: Self::fingerprint(handle, &config, load.module_info.contents()),
+                deps,
+                unresolved,
+                errors: load
+                    .errors
+                    .errors()
+                    .iter()
+                    .map(|e| CachedError {
+                        start: u32::from(e.range().start()),
+                        end: u32::from(e.range().end()),
+                        kind: e.error_kind(),
+                        header: e.msg_header().to_owned(),
+                        details: e.msg_details().map(|x| x.to_owned()),
+                        sys_infos: e
+                            .sys_infos()
+                            .iter()
+                            .map(|x| CachedSysInfo {
+                                version: x.version(),
+                                platform: x.platform().clone(),
+                            })
+                            .collect(),
+                    })
+                    .collect(),
+            };
+            fs_anyhow::write(&self.entry_path(handle), serde_json::to_vec(&entry)?)?;
+        }
+        Ok(())
+    }
+
+    fn get(
+        &self,
+        handle: &Handle,
+        config: &ArcId<ConfigFile>,
+        config_finder: &ConfigFinder,
+        hashes: &mut HashMap<PathBuf, Option<String>>,
+    ) -> Option<Load> {
+        let ModulePathDetails::FileSystem(path) = handle.path().details() else {
+            return None;
+        };
+        let entry: CacheEntry =
+            serde_json::from_slice(&fs_anyhow::read(&self.entry_path(handle)).ok()?).ok()?;
+        let contents = Self::read_contents(path)?;
+        let module = match contents {
+            FileContents::Source(code) => Module::new(handle.module(), handle.path().dupe(), code),
+            FileContents::Notebook(notebook) => {
+                Module::new_notebook(handle.module(), handle.path().dupe(), notebook)
+            }
+        };
+        if entry.fingerprint != Self::fingerprint(handle, config, module.contents()) {
+            return None;
+        }
+        for dep in &entry.deps {
+            let hash = hashes
+                .entry(dep.path.clone())
+                .or_insert_with(|| Self::read_contents(&dep.path).map(|x| Self::contents_hash(&x)));
+            if hash.as_ref() != Some(&dep.hash) {
+                return None;
+            }
+        }
+        for import in &entry.unresolved {
+            let importer = ModuleName::from_str(&import.importer);
+            let importer_path = ModulePath::filesystem(import.importer_path.clone());
+            let config = config_finder.python_file(importer, &importer_path);
+            if find_import(
+                &config,
+                ModuleName::from_str(&import.module),
+                Some(&importer_path),
+            )
+            .finding()
+            .is_some()
+            {
+                return None;
+            }
+        }
+        let errors = ErrorCollector::new(module.dupe(), ErrorStyle::Delayed);
+        for e in entry.errors {
+            errors.add_error(Error::restore(
+                module.dupe(),
+                TextRange::new(TextSize::new(e.start), TextSize::new(e.end)),
+                e.kind,
+                e.header.into_boxed_str(),
+                e.details.map(|x| x.into_boxed_str()),
+                e.sys_infos
+                    .into_iter()
+                    .map(|x| SysInfo::new(x.version, x.platform))
+                    .collect(),
+            ));
+        }
+        Some(Load {
+            errors,
+            module_info: module,
+        })
+    }
+
+    /// Compute the transitive dependencies of `handle`, with their content hashes, and the
+    /// imports of `handle` and its dependencies that failed to resolve.
+    /// Returns `None` if any of them can't be fingerprinted from disk.
+    fn transitive_deps(
+        transaction: &Transaction,
+        handle: &Handle,
+    ) -> Option<(Vec<CachedDep>, Vec<UnresolvedImport>)> {
+        let mut seen = HashSet::new();
+        let mut todo = vec![handle.dupe()];
+        let mut deps = Vec::new();
+        let mut unresolved = Vec::new();
+        while let Some(dep) = todo.pop() {
+            if !seen.insert(dep.dupe()) {
+                continue;
+            }
+            match dep.path().details() {
+                ModulePathDetails::FileSystem(path) => {
+                    if &dep != handle {
+                        let load = transaction.get_load(&dep)?;
+                        deps.push(CachedDep {
+                            path: path.to_path_buf(),
+                            hash: Self::hash(load.module_info.contents()),
+                        });
+                    }
+                    for module in transaction.get_failed_imports(&dep) {
+                        unresolved.push(UnresolvedImport {
+                            importer: dep.module().to_string(),
+                            importer_path: path.to_path_buf(),
+                            module: module.to_string(),
+                        });
+                    }
+                }
+                // Bundled modules can only change along with the Pyrefly version, which is part
+                // of the fingerprint. Namespace packages have no contents.
+                ModulePathDetails::BundledTypeshed(_)
+                | ModulePathDetails::BundledTypeshedThirdParty(_)
+                | ModulePathDetails::BundledThirdParty(_)
+                | ModulePathDetails::Namespace(_) => {}
+                ModulePathDetails::Memory(_) => return None,
+            }
+            todo.extend(transaction.get_imports(&dep));
+        }
+        deps.sort_by(|a, b| a.path.cmp(&b.path));
+        unresolved
+            .sort_by(|a, b| (&a.importer_path, &a.module).cmp(&(&b.importer_path, &b.module)));
+        Some((deps, unresolved))
+    }
+
+    fn entry_path(&self, handle: &Handle) -> PathBuf {
+        let key = format!(
+            "{}\n{}\n{}\n{}",
+            handle.module(),
+            handle.path(),
+            handle.sys_info().version(),
+            handle.sys_info().platform(),
+        );
+        self.dir
+            .join(format!("{}.json", blake3::hash(key.as_bytes()).to_hex()))
+    }
+
+    /// Everything other than the dependencies that can influence the errors of a module.
+    fn fingerprint(handle: &Handle, config: &ConfigFile, contents: &str) -> String {
+        // The config serialization skips a few fields that influence import resolution,
+        // so add them explicitly.
+        let config = format!(
+            "{}\n{:?}\n{:?}",
+            serde_json::to_value(config)
+                .map(|x| x.to_string())
+                .unwrap_or_default(),
+            config.search_path().collect_vec(),
+            config.site_package_path().collect_vec(),
+        );
+        Self::hash(&format!(
+            "{}\n{}\n{}\n{}\n{}\n{}",
+            env!("CARGO_PKG_VERSION"),
+            handle.sys_info().version(),
+            handle.sys_info().platform(),
+            config,
+            Self::hash(contents),
+            handle.module(),
+        ))
+    }
+
+    fn read_contents(path: &Path) -> Option<FileContents> {
+        let memory = MemoryFiles::default();
+        let overlay = MemoryFilesOverlay::default();
+        let (contents, err) = Load::load_from_path(
+            &ModulePath::filesystem(path.to_path_buf()),
+            &MemoryFilesLookup::new(&memory, &overlay),
+        );
+        if err.is_some() { None } else { Some(contents) }
+    }
+
+    fn contents_hash(contents: &FileContents) -> String {
+        match contents {
+            FileContents::Source(code) => Self::hash(code),
+            FileContents::Notebook(notebook) => Self::hash(notebook.source_code()),
+        }
+    }
+
+    fn hash(x: &str) -> String {
+        blake3::hash(x.as_bytes()).to_hex().to_string()
+    }
+}
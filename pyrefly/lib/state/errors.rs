@@ -33,6 +33,13 @@ impl Errors {
         Self { loads }
     }
 
+    /// Add more modules, e.g. those whose results were restored from a cache.
+    pub fn extend(&mut self, loads: Vec<(Arc<Load>, ArcId<ConfigFile>)>) {
+        self.loads.extend(loads);
+        self.loads
+            .sort_by_key(|x| (x.0.module_info.name(), x.0.module_info.path().dupe()));
+    }
+
     pub fn collect_errors(&self) -> CollectedErrors {
         let mut errors = CollectedErrors::default();
         for (load, config) in &self.loads {
@@ -44,13 +51,11 @@ impl Errors {
 
     pub fn collect_errors_with_baseline(
         &self,
-        baseline_path: Option<&Path>,
+        baseline: Option<&BaselineProcessor>,
         relative_to: &Path,
     ) -> CollectedErrors {
         let mut errors = self.collect_errors();
-        if let Some(baseline_path) = baseline_path
-            && let Ok(processor) = BaselineProcessor::from_file(baseline_path)
-        {
+        if let Some(processor) = baseline {
             processor.process_errors(&mut errors.shown, &mut errors.baseline, relative_to);
         }
         errors
@@ -9,6 +9,7 @@ use std::fmt::Debug;
 use std::sync::Arc;
 
 use dupe::Dupe;
+use pyrefly_build::source_db::Target;
 use pyrefly_config::error_kind::ErrorKind;
 use pyrefly_python::module_name::ModuleName;
 use pyrefly_python::module_path::ModulePath;
@@ -40,6 +41,10 @@ pub enum FindError {
     MissingStubs(ModuleName, Arc<String>),
     /// This is the condition where we are using stubs but we do not have the source files
     NoSourceForStubs(ModuleName),
+    /// The module is owned by a build system target or installed distribution (the first
+    /// `Target`) that the importing file's target (the second `Target`) does not declare as
+    /// a dependency.
+    UndeclaredDependency(ModuleName, Target, Target),
 }
 
 impl FindError {
@@ -109,12 +114,18 @@ impl FindError {
                 Some(Box::new(|| ErrorContext::ImportNotTyped(*source_package))),
                 vec1![format!("Hint: install the `{stubs_package}` package")],
             ),
+            Self::UndeclaredDependency(module, owner, target) => (
+                Some(Box::new(|| ErrorContext::ImportNotFound(*module))),
+                vec1![format!(
+                    "`{module}` is provided by `{owner}`, which is not a declared dependency of `{target}`"
+                )],
+            ),
         }
     }
 
     pub fn kind(&self) -> Option<ErrorKind> {
         match self {
-            Self::NotFound(..) => Some(ErrorKind::MissingImport),
+            Self::NotFound(..) | Self::UndeclaredDependency(..) => Some(ErrorKind::MissingImport),
             Self::NoSource(..) => Some(ErrorKind::MissingSource),
             Self::NoSourceForStubs(..) => Some(ErrorKind::MissingSourceForStubs),
             Self::MissingStubs(..) => Some(ErrorKind::UntypedImport),
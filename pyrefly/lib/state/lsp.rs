@@ -85,11 +85,12 @@ use crate::state::state::CancellableTransaction;
 use crate::state::state::Transaction;
 use crate::state::state::TransactionHandle;
 use crate::types::callable::Param;
+use crate::types::class::Class;
 use crate::types::module::ModuleType;
 use crate::types::type_var::Restriction;
 use crate::types::types::Type;
 
-mod quick_fixes;
+pub(crate) mod quick_fixes;
 
 pub(crate) use self::quick_fixes::extract_function::LocalRefactorCodeAction;
 
@@ -507,7 +508,7 @@ impl<'a> Transaction<'a> {
         ans.get_type_trace(range)
     }
 
-    fn get_chosen_overload_trace(&self, handle: &Handle, range: TextRange) -> Option<Type> {
+    pub fn get_chosen_overload_trace(&self, handle: &Handle, range: TextRange) -> Option<Type> {
         let ans = self.get_answers(handle)?;
         ans.get_chosen_overload_trace(range)
     }
@@ -1838,6 +1839,87 @@ impl<'a> Transaction<'a> {
                         }
                     }
                 }
+                kind if quick_fixes::remove_unused::is_removable_error_kind(kind)
+                    && error.range().contains_range(range) =>
+                {
+                    let edits = quick_fixes::remove_unused::remove_unused_edits(
+                        module_info.contents(),
+                        &ast,
+                        &[error.range()],
+                    );
+                    if let [(range, replacement)] = edits.as_slice() {
+                        let what = match kind {
+                            ErrorKind::UnusedImport => "import",
+                            ErrorKind::UnusedPrivateFunction => "function",
+                            _ => "variable",
+                        };
+                        code_actions.push((
+                            format!(
+                                "Remove unused {what} `{}`",
+                                module_info.code_at(error.range())
+                            ),
+                            module_info.dupe(),
+                            *range,
+                            replacement.clone(),
+                            false,
+                            false,
+                        ));
+                    }
+                }
+                ErrorKind::UnusedParameter if error.range().contains_range(range) => {
+                    if let Some((range, replacement)) =
+                        quick_fixes::remove_unused::remove_parameter_edit(
+                            module_info.contents(),
+                            &ast,
+                            error.range(),
+                        )
+                    {
+                        code_actions.push((
+                            format!(
+                                "Remove unused parameter `{}`",
+                                module_info.code_at(error.range())
+                            ),
+                            module_info.dupe(),
+                            range,
+                            replacement,
+                            false,
+                            false,
+                        ));
+                    }
+                }
+                ErrorKind::NonExhaustiveMatch if error.range().contains_range(range) => {
+                    if let Some(stmt) =
+                        quick_fixes::match_exhaustiveness::find_match(&ast, error.range())
+                        && let Some(cases) = self
+                            .ad_hoc_solve(handle, |solver| {
+                                solver.missing_cases_of_match(stmt.range)
+                            })
+                            .flatten()
+                        && let Some((range, insert_text)) =
+                            quick_fixes::match_exhaustiveness::add_missing_cases_edit(
+                                module_info.contents(),
+                                stmt,
+                                &cases.map(|case| {
+                                    case.pattern(|cls| {
+                                        quick_fixes::match_exhaustiveness::class_reference(
+                                            &ast,
+                                            module_info.name(),
+                                            cls,
+                                        )
+                                    })
+                                }),
+                            )
+                    {
+                        code_actions.push((
+                            "Add missing `case` arms".to_owned(),
+                            module_info.dupe(),
+                            range,
+                            insert_text,
+                            false,
+                            false,
+                        ));
+                    }
+                }
                 _ => {}
             }
         }
@@ -1889,6 +1971,13 @@ impl<'a> Transaction<'a> {
         quick_fixes::extract_variable::extract_variable_code_actions(self, handle, selection)
     }
 
+    pub fn remove_unused_code_actions(
+        &self,
+        handle: &Handle,
+    ) -> Option<Vec<LocalRefactorCodeAction>> {
+        quick_fixes::remove_unused::remove_unused_code_actions(self, handle)
+    }
+
     /// Determines whether a module is a third-party package.
     ///
     /// Checks if the module's path is located within any of the configured
@@ -3222,9 +3311,54 @@ impl<'a> Transaction<'a> {
         res.sort_by_key(|(score, _, _, _)| Reverse(*score));
         res.into_map(|(_, handle, name, export)| (handle, name, export))
     }
+
+    /// The class whose name is at `position`, either at its definition or at a use.
+    pub fn class_at(&self, handle: &Handle, position: TextSize) -> Option<Class> {
+        match self.get_type_at(handle, position)? {
+            Type::ClassDef(cls) => Some(cls),
+            _ => None,
+        }
+    }
+
+    /// The direct base classes of `cls`, in the order they are listed in the class definition.
+    pub fn find_direct_superclasses(&self, handle: &Handle, cls: &Class) -> Vec<Class> {
+        self.ad_hoc_solve(handle, |solver| {
+            solver
+                .get_base_types_for_class(cls)
+                .iter()
+                .map(|base| base.class_object().dupe())
+                .collect()
+        })
+        .unwrap_or_default()
+    }
 }
 
 impl<'a> CancellableTransaction<'a> {
+    /// Finds the classes that directly derive from the class definition.
+    /// Returns the ranges of the names of the child classes.
+    fn find_child_classes(
+        &self,
+        handle: &Handle,
+        definition: &TextRangeWithModule,
+    ) -> Vec<TextRange> {
+        let mut child_classes = Vec::new();
+
+        if let Some(solutions) = self.as_ref().get_solutions(handle)
+            && let Some(index) = solutions.get_index()
+        {
+            let index_lock = index.lock();
+            for (child_range, bases) in &index_lock.base_classes_map {
+                if bases.iter().any(|(base_module_path, base_range)| {
+                    base_module_path == definition.module.path() && *base_range == definition.range
+                }) {
+                    child_classes.push(*child_range);
+                }
+            }
+        }
+
+        child_classes
+    }
+
     /// Finds child class implementations of a method definition.
     /// Returns the ranges of child methods that reimplement the given parent method.
     fn find_child_implementations(
@@ -3513,6 +3647,42 @@ impl<'a> CancellableTransaction<'a> {
         Ok(all_implementations)
     }
 
+    /// Finds all classes that directly derive from the class definition at the given position.
+    /// This searches through transitive reverse dependencies, the same way as
+    /// `find_global_implementations_from_definition`.
+    /// Returns Err if the request is canceled in the middle of a run.
+    pub fn find_global_subclasses_from_definition(
+        &mut self,
+        sys_info: &SysInfo,
+        definition: TextRangeWithModule,
+    ) -> Result<Vec<TextRangeWithModule>, Cancelled> {
+        let results = self.process_rdeps_with_definition(
+            sys_info,
+            &definition,
+            |transaction, handle, patched_definition| {
+                let child_classes = transaction.find_child_classes(handle, patched_definition);
+                if !child_classes.is_empty()
+                    && let Some(module_info) = transaction.as_ref().get_module_info(handle)
+                {
+                    Some(
+                        child_classes
+                            .into_iter()
+                            .map(|range| TextRangeWithModule::new(module_info.dupe(), range))
+                            .collect::<Vec<_>>(),
+                    )
+                } else {
+                    None
+                }
+            },
+        )?;
+
+        let mut all_subclasses: Vec<TextRangeWithModule> = results.into_iter().flatten().collect();
+        all_subclasses.sort_by_key(|x| (x.module.path().dupe(), x.range.start()));
+        all_subclasses.dedup_by_key(|x| (x.module.path().dupe(), x.range.start()));
+
+        Ok(all_subclasses)
+    }
+
     /// Finds all incoming calls(functions that call this function) of a function across the entire codebase.
     ///
     /// This searches transitive reverse dependencies to find all locations where
@@ -0,0 +1,100 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use pyrefly_python::ast::Ast;
+use pyrefly_python::module_name::ModuleName;
+use pyrefly_types::class::Class;
+use ruff_python_ast::AnyNodeRef;
+use ruff_python_ast::ModModule;
+use ruff_python_ast::Stmt;
+use ruff_python_ast::StmtMatch;
+use ruff_text_size::Ranged;
+use ruff_text_size::TextRange;
+use ruff_text_size::TextSize;
+
+/// The `match` statement whose subject is at `subject_range`, where a `non-exhaustive-match`
+/// error is reported.
+pub(crate) fn find_match(ast: &ModModule, subject_range: TextRange) -> Option<&StmtMatch> {
+    Ast::locate_node(ast, subject_range.start())
+        .into_iter()
+        .find_map(|node| match node {
+            AnyNodeRef::StmtMatch(x) if x.subject.range() == subject_range => Some(x),
+            _ => None,
+        })
+}
+
+/// Builds the insertion that adds a `case` arm for each of `cases` after the last arm of the
+/// `match` statement.
+///
+/// Returns `None` when there are no cases to add.
+pub(crate) fn add_missing_cases_edit(
+    source: &str,
+    stmt: &StmtMatch,
+    cases: &[String],
+) -> Option<(TextRange, String)> {
+    if cases.is_empty() {
+        return None;
+    }
+    let last = stmt.cases.last()?;
+    let (case_indent, case_line) = line_indent_and_start(source, last.range.start());
+    let body_indent = match last.body.first() {
+        Some(body) if line_indent_and_start(source, body.range().start()).1 != case_line => {
+            line_indent_and_start(source, body.range().start())
+                .0
+                .to_owned()
+        }
+        _ => format!("{case_indent}    "),
+    };
+    let mut insert_text = String::new();
+    for case in cases {
+        insert_text.push_str(&format!("\n{case_indent}case {case}:\n{body_indent}pass"));
+    }
+    Some((
+        TextRange::at(last.range.end(), TextSize::new(0)),
+        insert_text,
+    ))
+}
+
+/// How `module` refers to `cls`, going by its top-level imports: `Color` if it is defined in
+/// `module` or imported with `from colors import Color`, `c.Color` after `import colors as c`, and
+/// so on. Falls back to the bare class name.
+pub(crate) fn class_reference(ast: &ModModule, module: ModuleName, cls: &Class) -> String {
+    let name = cls.name();
+    if cls.module_name() == module {
+        return name.to_string();
+    }
+    for stmt in &ast.body {
+        match stmt {
+            // A class is often imported from a module that re-exports it, so only match the name.
+            Stmt::ImportFrom(x) => {
+                if let Some(alias) = x.names.iter().find(|alias| alias.name.id == *name) {
+                    return alias.asname.as_ref().unwrap_or(&alias.name).id.to_string();
+                }
+            }
+            Stmt::Import(x) => {
+                if let Some(alias) = x
+                    .names
+                    .iter()
+                    .find(|alias| ModuleName::from_name(&alias.name.id) == cls.module_name())
+                {
+                    let prefix = alias.asname.as_ref().unwrap_or(&alias.name);
+                    return format!("{}.{name}", prefix.id);
+                }
+            }
+            _ => {}
+        }
+    }
+    name.to_string()
+}
+
+fn line_indent_and_start(source: &str, position: TextSize) -> (&str, usize) {
+    let idx = position.to_usize().min(source.len());
+    let line_start = source[..idx].rfind('\n').map_or(0, |start| start + 1);
+    let line = &source[line_start..idx];
+    let indent_len = line.len() - line.trim_start_matches([' ', '\t']).len();
+    (&line[..indent_len], line_start)
+}
@@ -7,3 +7,5 @@
 
 pub(crate) mod extract_function;
 pub(crate) mod extract_variable;
+pub(crate) mod match_exhaustiveness;
+pub(crate) mod remove_unused;
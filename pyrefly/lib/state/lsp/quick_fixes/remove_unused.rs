@@ -0,0 +1,436 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use dupe::Dupe;
+use lsp_types::CodeActionKind;
+use pyrefly_build::handle::Handle;
+use pyrefly_config::error_kind::ErrorKind;
+use ruff_python_ast::Alias;
+use ruff_python_ast::ExceptHandler;
+use ruff_python_ast::Expr;
+use ruff_python_ast::ModModule;
+use ruff_python_ast::ParameterWithDefault;
+use ruff_python_ast::Parameters;
+use ruff_python_ast::Stmt;
+use ruff_text_size::Ranged;
+use ruff_text_size::TextRange;
+use ruff_text_size::TextSize;
+
+use crate::state::lsp::Transaction;
+use crate::state::lsp::quick_fixes::extract_function::LocalRefactorCodeAction;
+
+/// How a single statement changes when unused names are removed from it.
+enum Removal {
+    /// The whole statement goes away.
+    Delete,
+    /// Part of the statement is replaced, e.g. one name in an import list.
+    Replace(TextRange, String),
+}
+
+/// Is this one of the error kinds whose subject `remove_unused_edits` knows how to remove?
+pub(crate) fn is_removable_error_kind(kind: ErrorKind) -> bool {
+    matches!(
+        kind,
+        ErrorKind::UnusedImport | ErrorKind::UnusedPrivateFunction | ErrorKind::UnusedVariable
+    )
+}
+
+/// Is this one of the error kinds that `--fix` and `source.fixAll` remove the subject of?
+/// Unused private functions are only removed one at a time, on request, and unused parameters
+/// are left alone, since removing them changes the function's signature.
+pub(crate) fn is_fixable_error_kind(kind: ErrorKind) -> bool {
+    matches!(kind, ErrorKind::UnusedImport | ErrorKind::UnusedVariable)
+}
+
+/// Builds a `source.fixAll` action that removes every unused import and local variable in the
+/// module. Only the errors that are shown are acted on, so nothing is removed unless the
+/// corresponding `unused-*` error kind is enabled.
+pub(crate) fn remove_unused_code_actions(
+    transaction: &Transaction<'_>,
+    handle: &Handle,
+) -> Option<Vec<LocalRefactorCodeAction>> {
+    let module_info = transaction.get_module_info(handle)?;
+    let ast = transaction.get_ast(handle)?;
+    let errors = transaction.get_errors(vec![handle]).collect_errors();
+    let ranges: Vec<_> = errors
+        .shown
+        .iter()
+        .filter(|e| is_fixable_error_kind(e.error_kind()))
+        .map(|e| e.range())
+        .collect();
+    let edits = remove_unused_edits(module_info.contents(), &ast, &ranges);
+    if edits.is_empty() {
+        return None;
+    }
+    Some(vec![LocalRefactorCodeAction {
+        title: "Remove unused imports and variables".to_owned(),
+        edits: edits
+            .into_iter()
+            .map(|(range, text)| (module_info.dupe(), range, text))
+            .collect(),
+        kind: CodeActionKind::SOURCE_FIX_ALL,
+    }])
+}
+
+/// Builds the edits that remove the imports, local variables and private functions whose names
+/// are at `ranges`, as reported by the `unused-import`, `unused-variable` and
+/// `unused-private-function` errors.
+///
+/// Edits are returned in source order and never overlap. Names that can't be removed on their own,
+/// such as variables bound by a `for` loop or by unpacking, are left alone. An assignment whose
+/// value may have side effects keeps its value as an expression statement.
+pub(crate) fn remove_unused_edits(
+    source: &str,
+    ast: &ModModule,
+    ranges: &[TextRange],
+) -> Vec<(TextRange, String)> {
+    let mut edits = Vec::new();
+    if !ranges.is_empty() {
+        remove_in_body(source, &ast.body, ranges, false, &mut edits);
+    }
+    edits
+}
+
+/// Builds the edit that removes the parameter whose name is at `range`, as reported by an
+/// `unused-parameter` error. Returns `None` if removing it would leave a `/` or `*` separator
+/// with no parameters to apply to.
+pub(crate) fn remove_parameter_edit(
+    source: &str,
+    ast: &ModModule,
+    range: TextRange,
+) -> Option<(TextRange, String)> {
+    let parameters = find_parameters(&ast.body, range)?;
+    let is_target = |x: &ParameterWithDefault| x.parameter.name.range == range;
+    let param = parameters
+        .posonlyargs
+        .iter()
+        .chain(&parameters.args)
+        .chain(&parameters.kwonlyargs)
+        .find(|x| is_target(x))?;
+    let alone_in = |group: &[ParameterWithDefault]| matches!(group, [x] if is_target(x));
+    if alone_in(&parameters.posonlyargs)
+        || (alone_in(&parameters.kwonlyargs) && parameters.vararg.is_none())
+    {
+        return None;
+    }
+    let param = param.range();
+    let after = &source[param.end().to_usize()..];
+    let removed = if let Some(rest) = after.trim_start().strip_prefix(',') {
+        // Remove the parameter, its comma and the space up to the next parameter.
+        let end = source.len() - rest.trim_start().len();
+        TextRange::new(param.start(), TextSize::new(end as u32))
+    } else {
+        // The last parameter: remove the comma before it instead.
+        let before = source[..param.start().to_usize()].trim_end();
+        let start = before
+            .strip_suffix(',')
+            .map_or(param.start(), |x| TextSize::new(x.trim_end().len() as u32));
+        TextRange::new(start, param.end())
+    };
+    Some((removed, String::new()))
+}
+
+/// Find the parameters of the function that has a parameter named at `range`.
+fn find_parameters(body: &[Stmt], range: TextRange) -> Option<&Parameters> {
+    body.iter().find_map(|stmt| {
+        if let Stmt::FunctionDef(x) = stmt
+            && x.parameters.range().contains_range(range)
+        {
+            return Some(&*x.parameters);
+        }
+        child_bodies(stmt)
+            .into_iter()
+            .find_map(|child| find_parameters(child, range))
+    })
+}
+
+/// Applies edits produced by `remove_unused_edits` to `source`.
+pub(crate) fn apply_edits(source: &str, edits: &[(TextRange, String)]) -> String {
+    let mut result = source.to_owned();
+    for (range, replacement) in edits.iter().rev() {
+        result.replace_range(
+            range.start().to_usize()..range.end().to_usize(),
+            replacement,
+        );
+    }
+    result
+}
+
+fn remove_in_body(
+    source: &str,
+    body: &[Stmt],
+    ranges: &[TextRange],
+    nested: bool,
+    edits: &mut Vec<(TextRange, String)>,
+) {
+    let removals: Vec<_> = body.iter().map(|x| removal(source, x, ranges)).collect();
+    // A nested block can't be empty, so keep a `pass` in place of its last statement.
+    let keep_pass = nested && removals.iter().all(|x| matches!(x, Some(Removal::Delete)));
+    for (i, (stmt, removal)) in body.iter().zip(removals).enumerate() {
+        match removal {
+            Some(Removal::Delete) if keep_pass && i == body.len() - 1 => {
+                edits.push((stmt.range(), "pass".to_owned()));
+            }
+            Some(Removal::Delete) => {
+                edits.push((deletion_range(source, stmt.range()), String::new()))
+            }
+            Some(Removal::Replace(range, text)) => edits.push((range, text)),
+            None => {
+                for child in child_bodies(stmt) {
+                    remove_in_body(source, child, ranges, true, edits);
+                }
+            }
+        }
+    }
+}
+
+fn removal(source: &str, stmt: &Stmt, ranges: &[TextRange]) -> Option<Removal> {
+    match stmt {
+        Stmt::Import(x) => alias_removal(source, &x.names, ranges),
+        Stmt::ImportFrom(x) => alias_removal(source, &x.names, ranges),
+        Stmt::Assign(x) => match x.targets.as_slice() {
+            [Expr::Name(target)] if ranges.contains(&target.range) => {
+                Some(value_removal(source, stmt.range(), target.range, &x.value))
+            }
+            _ => None,
+        },
+        Stmt::AnnAssign(x) => match (&*x.target, &x.value) {
+            (Expr::Name(target), Some(value)) if ranges.contains(&target.range) => Some(
+                value_removal(source, stmt.range(), x.annotation.range(), value),
+            ),
+            (Expr::Name(target), None) if ranges.contains(&target.range) => Some(Removal::Delete),
+            _ => None,
+        },
+        Stmt::FunctionDef(x) if ranges.contains(&x.name.range) => Some(Removal::Delete),
+        _ => None,
+    }
+}
+
+fn alias_removal(source: &str, names: &[Alias], ranges: &[TextRange]) -> Option<Removal> {
+    let (removed, kept): (Vec<_>, Vec<_>) = names
+        .iter()
+        .partition(|alias| ranges.iter().any(|r| alias.range.contains_range(*r)));
+    if removed.is_empty() {
+        None
+    } else if kept.is_empty() {
+        Some(Removal::Delete)
+    } else {
+        let range = TextRange::new(names.first()?.range.start(), names.last()?.range.end());
+        let text = kept
+            .iter()
+            .map(|alias| &source[alias.range])
+            .collect::<Vec<_>>()
+            .join(", ");
+        Some(Removal::Replace(range, text))
+    }
+}
+
+/// Removes an assignment, keeping its value around if evaluating it might do something.
+/// `before_eq` is the range of whatever comes just before the `=`.
+fn value_removal(source: &str, stmt: TextRange, before_eq: TextRange, value: &Expr) -> Removal {
+    if is_side_effect_free(value) {
+        return Removal::Delete;
+    }
+    let rest = &source[before_eq.end().to_usize()..];
+    let Some(eq) = rest.find('=') else {
+        return Removal::Delete;
+    };
+    let after_eq = &rest[eq + 1..];
+    let value_start = eq + 1 + (after_eq.len() - after_eq.trim_start_matches([' ', '\t']).len());
+    Removal::Replace(
+        TextRange::new(
+            stmt.start(),
+            before_eq.end() + TextSize::new(value_start as u32),
+        ),
+        String::new(),
+    )
+}
+
+fn is_side_effect_free(x: &Expr) -> bool {
+    match x {
+        Expr::StringLiteral(_)
+        | Expr::BytesLiteral(_)
+        | Expr::NumberLiteral(_)
+        | Expr::BooleanLiteral(_)
+        | Expr::NoneLiteral(_)
+        | Expr::EllipsisLiteral(_)
+        | Expr::Name(_)
+        | Expr::Lambda(_) => true,
+        Expr::Tuple(x) => x.elts.iter().all(is_side_effect_free),
+        Expr::List(x) => x.elts.iter().all(is_side_effect_free),
+        Expr::Dict(x) => x.items.iter().all(|item| {
+            item.key.as_ref().is_none_or(is_side_effect_free) && is_side_effect_free(&item.value)
+        }),
+        _ => false,
+    }
+}
+
+/// The range to delete to remove a statement: its whole line when it is alone on it
+/// (keeping nothing but a trailing comment), otherwise the statement and its `;` separator.
+fn deletion_range(source: &str, range: TextRange) -> TextRange {
+    let start = range.start().to_usize();
+    let end = range.end().to_usize();
+    let line_start = source[..start].rfind('\n').map_or(0, |x| x + 1);
+    let line_end = source[end..]
+        .find('\n')
+        .map_or(source.len(), |x| end + x + 1);
+    let before = &source[line_start..start];
+    let after = source[end..line_end].trim_start();
+    let at = |x: usize| TextSize::new(x as u32);
+    if before.trim().is_empty() && (after.is_empty() || after.starts_with('#')) {
+        TextRange::new(at(line_start), at(line_end))
+    } else if let Some(after_semicolon) = after.strip_prefix(';') {
+        let kept = after_semicolon.trim_start_matches([' ', '\t']).len();
+        TextRange::new(range.start(), at(line_end - kept))
+    } else if let Some(before_semicolon) = before.trim_end().strip_suffix(';') {
+        TextRange::new(at(line_start + before_semicolon.len()), range.end())
+    } else {
+        range
+    }
+}
+
+fn child_bodies(stmt: &Stmt) -> Vec<&[Stmt]> {
+    match stmt {
+        Stmt::FunctionDef(x) => vec![x.body.as_slice()],
+        Stmt::ClassDef(x) => vec![x.body.as_slice()],
+        Stmt::If(x) => {
+            let mut res = vec![x.body.as_slice()];
+            res.extend(x.elif_else_clauses.iter().map(|x| x.body.as_slice()));
+            res
+        }
+        Stmt::While(x) => vec![x.body.as_slice(), x.orelse.as_slice()],
+        Stmt::For(x) => vec![x.body.as_slice(), x.orelse.as_slice()],
+        Stmt::With(x) => vec![x.body.as_slice()],
+        Stmt::Try(x) => {
+            let mut res = vec![x.body.as_slice()];
+            res.extend(x.handlers.iter().map(|x| {
+                let ExceptHandler::ExceptHandler(x) = x;
+                x.body.as_slice()
+            }));
+            res.push(x.orelse.as_slice());
+            res.push(x.finalbody.as_slice());
+            res
+        }
+        Stmt::Match(x) => x.cases.iter().map(|x| x.body.as_slice()).collect(),
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pyrefly_python::ast::Ast;
+    use ruff_python_ast::PySourceType;
+
+    use super::*;
+
+    /// Removes the first whole-word occurrence of each of `names`.
+    fn remove(source: &str, names: &[&str]) -> String {
+        let (ast, _, _) = Ast::parse(source, PySourceType::Python);
+        let is_ident = |c: Option<char>| c.is_some_and(|c| c.is_alphanumeric() || c == '_');
+        let ranges: Vec<_> = names
+            .iter()
+            .map(|name| {
+                let start = source
+                    .match_indices(name)
+                    .map(|(i, _)| i)
+                    .find(|i| {
+                        !is_ident(source[..*i].chars().next_back())
+                            && !is_ident(source[i + name.len()..].chars().next())
+                    })
+                    .unwrap();
+                TextRange::at(
+                    TextSize::new(start as u32),
+                    TextSize::new(name.len() as u32),
+                )
+            })
+            .collect();
+        apply_edits(source, &remove_unused_edits(source, &ast, &ranges))
+    }
+
+    #[test]
+    fn test_remove_imports() {
+        assert_eq!(
+            remove("import os\nimport sys\nprint(sys)\n", &["os"]),
+            "import sys\nprint(sys)\n"
+        );
+        assert_eq!(
+            remove(
+                "from typing import Any, List, Dict\nx: List[int] = []\n",
+                &["Any", "Dict"]
+            ),
+            "from typing import List\nx: List[int] = []\n"
+        );
+        assert_eq!(
+            remove("if True:\n    import os\nx = 1\n", &["os"]),
+            "if True:\n    pass\nx = 1\n"
+        );
+        assert_eq!(remove("import os ; x = 1\n", &["os"]), "x = 1\n");
+    }
+
+    #[test]
+    fn test_remove_variables() {
+        assert_eq!(
+            remove(
+                "def f():\n    x = 1\n    y = g()\n    z: int = (g())\n    return 0\n",
+                &["x", "y", "z"]
+            ),
+            "def f():\n    g()\n    (g())\n    return 0\n"
+        );
+        assert_eq!(
+            remove("def f():\n    for x in []:\n        pass\n", &["x"]),
+            "def f():\n    for x in []:\n        pass\n"
+        );
+    }
+
+    /// Removes the parameter named `name`.
+    fn remove_parameter(source: &str, name: &str) -> Option<String> {
+        let (ast, _, _) = Ast::parse(source, PySourceType::Python);
+        let start = source.find(&format!("{name}:")).unwrap();
+        let range = TextRange::at(
+            TextSize::new(start as u32),
+            TextSize::new(name.len() as u32),
+        );
+        let edit = remove_parameter_edit(source, &ast, range)?;
+        Some(apply_edits(source, &[edit]))
+    }
+
+    #[test]
+    fn test_remove_parameter() {
+        assert_eq!(
+            remove_parameter("def f(a: int, b: int) -> None: ...\n", "a").as_deref(),
+            Some("def f(b: int) -> None: ...\n")
+        );
+        assert_eq!(
+            remove_parameter("def f(a: int, b: int = 1) -> None: ...\n", "b").as_deref(),
+            Some("def f(a: int) -> None: ...\n")
+        );
+        assert_eq!(
+            remove_parameter(
+                "class C:\n    def m(self, x: int, *, y: str) -> None: ...\n",
+                "x"
+            )
+            .as_deref(),
+            Some("class C:\n    def m(self, *, y: str) -> None: ...\n")
+        );
+        assert_eq!(
+            remove_parameter("def f(a: int, /, b: int) -> None: ...\n", "a"),
+            None
+        );
+        assert_eq!(
+            remove_parameter("def f(a: int, *, b: int) -> None: ...\n", "b"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_remove_private_function() {
+        assert_eq!(
+            remove("def _f():\n    return 1\n\ndef g():\n    pass\n", &["_f"]),
+            "\ndef g():\n    pass\n"
+        );
+    }
+}
@@ -7,6 +7,7 @@
 
 pub mod dirty;
 pub mod epoch;
+pub mod error_cache;
 pub mod errors;
 pub mod ide;
 pub mod load;
@@ -20,3 +21,4 @@ pub mod semantic_tokens;
 pub mod state;
 pub mod steps;
 pub mod subscriber;
+pub mod sys_info_sharing;
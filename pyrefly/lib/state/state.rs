@@ -70,6 +70,8 @@ use crate::alt::answers::SolutionsEntry;
 use crate::alt::answers::SolutionsTable;
 use crate::alt::answers_solver::AnswersSolver;
 use crate::alt::answers_solver::ThreadState;
+use crate::alt::class::plugin::ClassSynthesisPlugin;
+use crate::alt::class::plugin::ClassSynthesisPlugins;
 use crate::alt::traits::Solve;
 use crate::binding::binding::Exported;
 use crate::binding::binding::KeyExport;
@@ -462,6 +464,30 @@ impl<'a> Transaction<'a> {
         transitive_rdeps
     }
 
+    /// The handles that the given module imports, as resolved when it was last computed.
+    /// Does not include imports that failed to resolve.
+    pub fn get_imports(&self, handle: &Handle) -> Vec<Handle> {
+        if let Some(v) = self.data.updated_modules.get(handle) {
+            v.deps.read().values().flatten().cloned().collect()
+        } else if let Some(v) = self.readable.modules.get(handle) {
+            v.deps.values().flatten().cloned().collect()
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// The modules that the given module imports but that failed to resolve when it was last
+    /// computed.
+    pub fn get_failed_imports(&self, handle: &Handle) -> Vec<ModuleName> {
+        if let Some(v) = self.data.updated_modules.get(handle) {
+            v.failed_deps.read().keys().copied().collect()
+        } else if let Some(v) = self.readable.modules.get(handle) {
+            v.failed_deps.keys().copied().collect()
+        } else {
+            Vec::new()
+        }
+    }
+
     /// Return all handles for which there is data, in a non-deterministic order.
     pub fn handles(&self) -> Vec<Handle> {
         if self.data.updated_modules.is_empty() {
@@ -812,11 +838,14 @@ impl<'a> Transaction<'a> {
                 memory: &self.memory_lookup(),
                 uniques: &self.data.state.uniques,
                 stdlib: &stdlib,
+                class_synthesis_plugins: &self.data.state.class_synthesis_plugins,
                 lookup: &self.lookup(module_data.dupe()),
                 untyped_def_behavior: config
                     .untyped_def_behavior(module_data.handle.path().as_path()),
                 infer_with_first_use: config
                     .infer_with_first_use(module_data.handle.path().as_path()),
+                import_rules: &config.import_rules,
+                errors: config.errors(module_data.handle.path().as_path()),
             });
             {
                 let mut changed = false;
@@ -1128,6 +1157,7 @@ impl<'a> Transaction<'a> {
                     &answers.0,
                     &load.errors,
                     &stdlib,
+                    &self.data.state.class_synthesis_plugins,
                     &self.data.state.uniques,
                     key,
                     thread_state,
@@ -1314,6 +1344,7 @@ impl<'a> Transaction<'a> {
             &self.data.state.uniques,
             &recurser,
             &stdlib,
+            &self.data.state.class_synthesis_plugins,
             &thread_state,
         );
         let result = solve(solver);
@@ -1531,9 +1562,12 @@ impl<'a> Transaction<'a> {
                 memory: &self.memory_lookup(),
                 uniques: &self.data.state.uniques,
                 stdlib: &stdlib,
+                class_synthesis_plugins: &self.data.state.class_synthesis_plugins,
                 lookup: &self.lookup(m.dupe()),
                 untyped_def_behavior: config.untyped_def_behavior(m.handle.path().as_path()),
                 infer_with_first_use: config.infer_with_first_use(m.handle.path().as_path()),
+                import_rules: &config.import_rules,
+                errors: config.errors(m.handle.path().as_path()),
             };
             let mut step = Step::Load; // Start at AST (Load.next)
             alt.load = lock.steps.load.dupe();
@@ -1762,6 +1796,7 @@ pub struct State {
     threads: ThreadPool,
     uniques: UniqueFactory,
     config_finder: ConfigFinder,
+    class_synthesis_plugins: ClassSynthesisPlugins,
     state: RwLock<StateData>,
     run_count: AtomicUsize,
     committing_transaction_lock: Mutex<()>,
@@ -1773,6 +1808,7 @@ impl State {
             threads: ThreadPool::new(),
             uniques: UniqueFactory::new(),
             config_finder,
+            class_synthesis_plugins: ClassSynthesisPlugins::default(),
             state: RwLock::new(StateData::new()),
             run_count: AtomicUsize::new(0),
             committing_transaction_lock: Mutex::new(()),
@@ -1783,6 +1819,11 @@ impl State {
         &self.config_finder
     }
 
+    /// Add a plugin for framework classes, which is consulted after the ones already registered.
+    pub fn register_class_synthesis_plugin(&mut self, plugin: Arc<dyn ClassSynthesisPlugin>) {
+        self.class_synthesis_plugins.register(plugin);
+    }
+
     fn get_config(&self, name: ModuleName, path: &ModulePath) -> ArcId<ConfigFile> {
         if matches!(path.details(), ModulePathDetails::BundledTypeshed(_)) {
             BundledTypeshedStdlib::config()
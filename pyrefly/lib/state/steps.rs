@@ -20,8 +20,12 @@ use ruff_python_ast::ModModule;
 use crate::alt::answers::Answers;
 use crate::alt::answers::LookupAnswer;
 use crate::alt::answers::Solutions;
+use crate::alt::class::plugin::ClassSynthesisPlugins;
 use crate::binding::bindings::Bindings;
 use crate::config::base::UntypedDefBehavior;
+use crate::config::config::ImportRule;
+use crate::config::error::ErrorDisplayConfig;
+use crate::config::error_kind::ErrorKind;
 use crate::error::style::ErrorStyle;
 use crate::export::exports::Exports;
 use crate::export::exports::LookupExport;
@@ -40,9 +44,12 @@ pub struct Context<'a, Lookup> {
     pub memory: &'a MemoryFilesLookup<'a>,
     pub uniques: &'a UniqueFactory,
     pub stdlib: &'a Stdlib,
+    pub class_synthesis_plugins: &'a ClassSynthesisPlugins,
     pub lookup: &'a Lookup,
     pub untyped_def_behavior: UntypedDefBehavior,
     pub infer_with_first_use: bool,
+    pub import_rules: &'a [ImportRule],
+    pub errors: &'a ErrorDisplayConfig,
 }
 
 #[derive(Debug, Default, Dupe, Clone)]
@@ -174,6 +181,15 @@ impl Step {
         let solver = Solver::new(ctx.infer_with_first_use);
         let enable_index = ctx.require.keep_index();
         let enable_trace = ctx.require.keep_answers_trace();
+        // Checking for unreachable code after every call is only worth it if we report it.
+        let directives = load.module_info.ignore().severity_directives();
+        let unreachable_code = if directives.is_empty() {
+            ctx.errors.severity(ErrorKind::UnreachableCode)
+        } else {
+            ctx.errors
+                .with_directives(directives)
+                .severity(ErrorKind::UnreachableCode)
+        };
         let bindings = Bindings::new(
             Arc::unwrap_or_clone(ast),
             load.module_info.dupe(),
@@ -185,6 +201,8 @@ impl Step {
             ctx.uniques,
             enable_trace,
             ctx.untyped_def_behavior,
+            ctx.import_rules,
+            unreachable_code.is_enabled(),
         );
         let answers = Answers::new(&bindings, solver, enable_index, enable_trace);
         Arc::new((bindings, Arc::new(answers)))
@@ -202,6 +220,7 @@ impl Step {
             &answers.0,
             &load.errors,
             ctx.stdlib,
+            ctx.class_synthesis_plugins,
             ctx.uniques,
             ctx.require.compute_errors()
                 || ctx.require.keep_answers_trace()
@@ -0,0 +1,163 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Sharing the results of a module between the `SysInfo`s of a `python-matrix`.
+//!
+//! A module checked with one `SysInfo` gives the same results with another if the two have the
+//! same `major.minor` Python version, which is all that version tests and syntax checks look at,
+//! and the module and everything it transitively imports take the same branches of
+//! `sys.version_info` and `sys.platform` tests with both. Every module in the closure then prunes
+//! to the same AST, so it binds the same imports and solves to the same types, and there is no
+//! need to check the module again.
+
+use std::collections::HashMap;
+
+use dupe::Dupe;
+use pyrefly_build::handle::Handle;
+use pyrefly_python::ast::Ast;
+use pyrefly_python::module_name::ModuleName;
+use pyrefly_python::module_path::ModulePath;
+use pyrefly_python::sys_info::SysInfo;
+use pyrefly_util::visit::Visit;
+use ruff_python_ast::Expr;
+use ruff_python_ast::Stmt;
+use starlark_map::small_set::SmallSet;
+
+use crate::state::state::Transaction;
+
+/// The results a `SysInfo` shares from the others.
+#[derive(Default)]
+pub struct SharedResults {
+    /// The checked handles whose results are shared, one per module that would otherwise have
+    /// been checked again.
+    pub roots: Vec<Handle>,
+    /// Those handles and everything they transitively import.
+    pub closure: SmallSet<Handle>,
+}
+
+impl SharedResults {
+    /// Add the result of [`SysInfoSharing::find`].
+    pub fn add(&mut self, closure: Vec<Handle>) {
+        if let Some(root) = closure.first() {
+            self.roots.push(root.dupe());
+        }
+        self.closure.extend(closure);
+    }
+}
+
+#[derive(Default)]
+pub struct SysInfoSharing {
+    /// The handles that have been checked, by module.
+    checked: HashMap<(ModuleName, ModulePath), Vec<Handle>>,
+    /// Whether a module takes the same branches with the `SysInfo` of its handle as with the
+    /// other `SysInfo`.
+    same_branches: HashMap<(Handle, SysInfo), bool>,
+}
+
+impl SysInfoSharing {
+    /// Record that `handles` have been checked, so their results may be shared.
+    pub fn add_checked(&mut self, handles: &[Handle]) {
+        for handle in handles {
+            self.checked
+                .entry((handle.module(), handle.path().dupe()))
+                .or_default()
+                .push(handle.dupe());
+        }
+    }
+
+    /// Find a checked handle for the same module as `handle`, but with another `SysInfo`, whose
+    /// results `handle` would share. Returns the modules whose results are shared, starting with
+    /// the module itself and followed by everything it transitively imports.
+    pub fn find(&mut self, transaction: &Transaction, handle: &Handle) -> Option<Vec<Handle>> {
+        let checked = self
+            .checked
+            .get(&(handle.module(), handle.path().dupe()))?
+            .clone();
+        checked
+            .iter()
+            .filter(|x| x.sys_info() != handle.sys_info())
+            .find_map(|x| self.closure(transaction, x, handle.sys_info()))
+    }
+
+    /// The modules that `handle` transitively imports, including itself, if `sys_info` gives
+    /// them all the same results as the `SysInfo` of `handle`.
+    fn closure(
+        &mut self,
+        transaction: &Transaction,
+        handle: &Handle,
+        sys_info: &SysInfo,
+    ) -> Option<Vec<Handle>> {
+        let (version, other) = (handle.sys_info().version(), sys_info.version());
+        if (version.major, version.minor) != (other.major, other.minor) {
+            return None;
+        }
+        let mut seen = SmallSet::new();
+        let mut todo = vec![handle.dupe()];
+        while let Some(x) = todo.pop() {
+            if !seen.insert(x.dupe()) {
+                continue;
+            }
+            if !self.same_branches(transaction, &x, sys_info) {
+                return None;
+            }
+            todo.extend(transaction.get_imports(&x));
+        }
+        Some(seen.into_iter().collect())
+    }
+
+    fn same_branches(
+        &mut self,
+        transaction: &Transaction,
+        handle: &Handle,
+        other: &SysInfo,
+    ) -> bool {
+        if let Some(same) = self.same_branches.get(&(handle.dupe(), other.dupe())) {
+            return *same;
+        }
+        // The AST is usually dropped once the module is bound, so parse it again.
+        let same = transaction.get_load(handle).is_some_and(|load| {
+            let (ast, _, _) =
+                Ast::parse(load.module_info.contents(), load.module_info.source_type());
+            ast.body
+                .iter()
+                .all(|x| stmt_same_branches(x, handle.sys_info(), other))
+        });
+        self.same_branches.insert((handle.dupe(), other.dupe()), same);
+        same
+    }
+}
+
+/// Does `x` take the same branches with `a` as with `b`? Branches that neither takes are not
+/// looked at, just as binding prunes them.
+fn stmt_same_branches(x: &Stmt, a: &SysInfo, b: &SysInfo) -> bool {
+    let same_test = |test: &Expr| a.evaluate_bool(test) == b.evaluate_bool(test);
+    match x {
+        Stmt::If(x) => {
+            for (test, body) in Ast::if_branches(x) {
+                let taken = match test {
+                    Some(test) if !same_test(test) => return false,
+                    Some(test) => a.evaluate_bool(test),
+                    None => Some(true),
+                };
+                if taken != Some(false) && !body.iter().all(|x| stmt_same_branches(x, a, b)) {
+                    return false;
+                }
+                if taken == Some(true) {
+                    break;
+                }
+            }
+            true
+        }
+        Stmt::While(x) if !same_test(&x.test) => false,
+        Stmt::Assert(x) if !same_test(&x.test) => false,
+        _ => {
+            let mut same = true;
+            x.recurse(&mut |x| same = same && stmt_same_branches(x, a, b));
+            same
+        }
+    }
+}
@@ -0,0 +1,174 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use crate::attrs_testcase;
+
+attrs_testcase!(
+    test_attr_s_unannotated_fields,
+    r#"
+from typing import assert_type
+import attr
+
+@attr.s
+class C:
+    x = attr.ib()
+    y = attr.ib(default=0)
+    z: int = 5
+    w = 5
+    def method(self) -> None: ...
+
+C(1)
+C(1, 2)
+C(1, y=2)
+C()  # E: Missing argument `x`
+C(1, 2, 3)  # E: Expected 2 positional arguments, got 3
+C(1, y="a")  # E: Argument `Literal['a']` is not assignable to parameter `y` with type `int`
+C(1, z=1)  # E: Unexpected keyword argument `z`
+assert_type(C(1).y, int)
+"#,
+);
+
+attrs_testcase!(
+    test_attr_s_auto_attribs,
+    r#"
+import attr
+
+@attr.s(auto_attribs=True)
+class C:
+    a: int
+    b: str = "b"
+    c: list[int] = attr.Factory(list)
+
+C(1)
+C(1, "x", [1])
+C("a")  # E: Argument `Literal['a']` is not assignable to parameter `a` with type `int`
+"#,
+);
+
+attrs_testcase!(
+    test_attr_s_aliases,
+    r#"
+import attr
+
+@attr.attrs
+class C:
+    x = attr.attrib(type=int)
+
+@attr.attributes
+class D:
+    x = attr.attr(type=int)
+
+C(x=1)
+D(x=1)
+C(x="a")  # E: Argument `Literal['a']` is not assignable to parameter `x` with type `int`
+"#,
+);
+
+attrs_testcase!(
+    test_field_options,
+    r#"
+import attr
+
+@attr.s
+class C:
+    _secret = attr.ib(type=int)
+    hidden = attr.ib(init=False, default=0)
+    flag = attr.ib(kw_only=True, default=False)
+    items = attr.ib(factory=list)
+
+C(secret=1)
+C(1, flag=True, items=[1])
+C(_secret=1)  # E: Unexpected keyword argument `_secret`
+C(1, hidden=1)  # E: Unexpected keyword argument `hidden`
+C(1, [1], True)  # E: Expected 2 positional arguments, got 3
+"#,
+);
+
+attrs_testcase!(
+    test_private_names_next_gen,
+    r#"
+from attrs import define, field
+
+@define
+class C:
+    _x: int
+    _y: str = field(alias="why")
+
+C(x=1, why="a")
+C(x=1, why="a", y="a")  # E: Unexpected keyword argument `y`
+"#,
+);
+
+attrs_testcase!(
+    test_inherited_fields,
+    r#"
+import attr
+
+@attr.s
+class A:
+    x = attr.ib(type=int)
+
+@attr.s
+class B(A):
+    y = attr.ib(type=str)
+
+B(1, "a")
+B("a", 1)  # E: Argument `Literal['a']` is not assignable to parameter `x` with type `int`  # E: Argument `Literal[1]` is not assignable to parameter `y` with type `str`
+"#,
+);
+
+attrs_testcase!(
+    test_converter,
+    r#"
+import attr
+
+def to_int(x: str) -> int: ...
+def to_str(x: int) -> str: ...
+
+@attr.s(auto_attribs=True)
+class C:
+    a: int = attr.ib(converter=to_int)
+    b: int = attr.ib(converter=to_str)  # E: Converter returns `str`, which is not assignable to field `b` with type `int`
+
+C("1", 1)
+C(1, 1)  # E: Argument `Literal[1]` is not assignable to parameter `a` with type `str`
+"#,
+);
+
+attrs_testcase!(
+    test_validator,
+    r#"
+import attr
+
+def check_int(inst: object, attribute: object, value: int) -> None: ...
+
+@attr.s(auto_attribs=True)
+class C:
+    a: int = attr.ib(default=0, validator=check_int)
+    b: int = attr.ib(default=0, validator=attr.validators.instance_of(int))
+    c: str = attr.ib(default="", validator=check_int)  # E: No matching overload found # E: does not accept field `c` with type `str`
+"#,
+);
+
+attrs_testcase!(
+    test_evolve,
+    r#"
+from typing import assert_type
+import attr
+
+@attr.s
+class C:
+    _x = attr.ib(type=int)
+    y = attr.ib(type=str, default="")
+
+c = C(1)
+assert_type(attr.evolve(c, x=2), C)
+assert_type(attr.evolve(c, y="a"), C)
+attr.evolve(c, y=1)  # E: Argument `Literal[1]` is not assignable to parameter `y` with type `str`
+attr.evolve(c, z=1)  # E: Unexpected keyword argument `z`
+"#,
+);
@@ -0,0 +1,8 @@
+from typing import Any, TypeVar
+
+from . import _ValidatorType
+
+_T = TypeVar("_T")
+
+def instance_of(type: type[_T]) -> _ValidatorType[_T]: ...
+def optional(validator: _ValidatorType[_T]) -> _ValidatorType[_T | None]: ...
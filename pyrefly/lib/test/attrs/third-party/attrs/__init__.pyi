@@ -0,0 +1,8 @@
+from attr import Attribute as Attribute
+from attr import Factory as Factory
+from attr import define as define
+from attr import evolve as evolve
+from attr import field as field
+from attr import frozen as frozen
+from attr import mutable as mutable
+from attr import validators as validators
@@ -0,0 +1,183 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use std::sync::Arc;
+
+use dupe::Dupe;
+use pyrefly_python::dunder;
+use pyrefly_types::annotation::Annotation;
+use pyrefly_types::callable::Callable;
+use pyrefly_types::callable::FuncMetadata;
+use pyrefly_types::callable::Function;
+use pyrefly_types::callable::Param;
+use pyrefly_types::callable::ParamList;
+use pyrefly_types::callable::Required;
+use pyrefly_types::class::Class;
+use pyrefly_types::keywords::DataclassFieldKeywords;
+use pyrefly_types::types::Type;
+use ruff_python_ast::Expr;
+use ruff_python_ast::name::Name;
+use ruff_text_size::TextRange;
+use starlark_map::small_map::SmallMap;
+
+use crate::alt::class::plugin::ClassSynthesisContext;
+use crate::alt::class::plugin::ClassSynthesisPlugin;
+use crate::alt::types::class_metadata::ClassSynthesizedField;
+use crate::alt::types::class_metadata::ClassSynthesizedFields;
+use crate::config::error_kind::ErrorKind;
+use crate::error::collector::ErrorCollector;
+use crate::test::util::TestEnv;
+use crate::test::util::testcase_for_macro;
+use crate::testcase;
+
+/// A framework whose classes get a `tracked_id` attribute and an `__init__` taking a name, whose
+/// `Column[T]` fields are read as `T`, and whose fields may not be called `id`.
+struct TrackedPlugin;
+
+impl ClassSynthesisPlugin for TrackedPlugin {
+    fn name(&self) -> &'static str {
+        "tracked"
+    }
+
+    fn base_classes(&self) -> &[(&'static str, &'static str)] {
+        &[("tracked", "Tracked")]
+    }
+
+    fn decorators(&self) -> &[(&'static str, &'static str)] {
+        &[("tracked", "tracked")]
+    }
+
+    fn synthesized_fields(
+        &self,
+        ctx: &dyn ClassSynthesisContext,
+        _cls: &Class,
+    ) -> Option<ClassSynthesizedFields> {
+        Some(ClassSynthesizedFields::new(SmallMap::from_iter([(
+            Name::new_static("tracked_id"),
+            ClassSynthesizedField::new(ctx.stdlib().int().clone().to_type()),
+        )])))
+    }
+
+    fn init_method(&self, ctx: &dyn ClassSynthesisContext, cls: &Class) -> Option<Type> {
+        let params = vec![
+            ctx.self_param(cls),
+            Param::Pos(
+                Name::new_static("name"),
+                ctx.stdlib().str().clone().to_type(),
+                Required::Required,
+            ),
+        ];
+        Some(Type::Function(Box::new(Function {
+            signature: Callable::list(ParamList::new(params), Type::None),
+            metadata: FuncMetadata::def(ctx.module().dupe(), cls.dupe(), dunder::INIT),
+        })))
+    }
+
+    fn field_type(
+        &self,
+        _ctx: &dyn ClassSynthesisContext,
+        _cls: &Class,
+        _name: &Name,
+        ty: &Type,
+        _initial_value: Option<&Expr>,
+    ) -> Option<Type> {
+        match ty {
+            Type::ClassType(cls) if cls.has_toplevel_qname("tracked", "Column") => {
+                cls.targs().as_slice().first().cloned()
+            }
+            _ => None,
+        }
+    }
+
+    fn check_field(
+        &self,
+        ctx: &dyn ClassSynthesisContext,
+        _cls: &Class,
+        name: &Name,
+        _annotation: &Annotation,
+        _keywords: Option<&DataclassFieldKeywords>,
+        range: TextRange,
+        errors: &ErrorCollector,
+    ) {
+        if name.as_str() == "id" {
+            ctx.add_error(
+                errors,
+                range,
+                ErrorKind::BadClassDefinition,
+                "Tracked classes may not declare `id`".to_owned(),
+            );
+        }
+    }
+}
+
+/// The `tracked` module, without registering the plugin.
+fn env_unregistered() -> TestEnv {
+    TestEnv::one(
+        "tracked",
+        r#"
+from typing import Generic, TypeVar
+T = TypeVar("T")
+class Column(Generic[T]): ...
+class Tracked: ...
+def tracked(cls: type[T]) -> type[T]: ...
+"#,
+    )
+}
+
+fn env() -> TestEnv {
+    env_unregistered().with_class_synthesis_plugin(Arc::new(TrackedPlugin))
+}
+
+#[test]
+fn test_class_synthesis_plugin() -> anyhow::Result<()> {
+    testcase_for_macro(
+        env(),
+        r#"
+from typing import assert_type
+from tracked import Column, Tracked, tracked
+
+class A(Tracked):
+    x: Column[int]
+    id: int  # E: Tracked classes may not declare `id`
+
+class B(A):
+    pass
+
+@tracked
+class C:
+    y: Column[str]
+
+class D:
+    z: Column[int]
+
+assert_type(A("a").tracked_id, int)
+assert_type(A("a").x, int)
+assert_type(B("b").tracked_id, int)
+assert_type(C("c").y, str)
+A()  # E: Missing argument `name`
+assert_type(D().z, Column[int])
+D().tracked_id  # E: Object of class `D` has no attribute `tracked_id`
+"#,
+        file!(),
+        line!(),
+    )
+}
+
+// Plugins are registered on a `State`, so they don't affect other checks.
+testcase!(
+    test_class_synthesis_plugin_unregistered,
+    env_unregistered(),
+    r#"
+from tracked import Tracked
+
+class A(Tracked):
+    pass
+
+A("a")  # E: Expected 0 positional arguments
+A().tracked_id  # E: Object of class `A` has no attribute `tracked_id`
+"#,
+);
@@ -1418,3 +1418,74 @@ for _ in []:
         continue
     "#,
 );
+
+testcase!(
+    test_possibly_unbound_kind,
+    r#"
+def condition() -> bool: ...
+if condition():
+    x = 1
+print(x)  # E: `x` may be uninitialized
+    "#,
+);
+
+testcase!(
+    test_unreachable_code,
+    TestEnv::new().enable_unreachable_code_error(),
+    r#"
+import sys
+from typing import NoReturn
+
+def fail() -> NoReturn: ...
+
+def f(x: int) -> int:
+    return x
+    print(x)  # E: This code is unreachable
+
+def g() -> None:
+    raise ValueError()
+    print(1)  # E: This code is unreachable
+    print(2)
+
+def h() -> None:
+    fail()
+    print(1)  # E: This code is unreachable
+
+def i() -> None:
+    sys.exit(1)
+    print(1)  # E: This code is unreachable
+
+def j(x: int) -> None:
+    while True:
+        if x:
+            break
+        continue
+        print(x)  # E: This code is unreachable
+    print(x)
+
+def k(cond: bool) -> int:
+    if cond:
+        return 1
+    else:
+        raise ValueError()
+    return 2  # E: This code is unreachable
+
+def l() -> int:
+    try:
+        return 1
+    finally:
+        print("cleanup")
+    "#,
+);
+
+testcase!(
+    test_unreachable_code_after_call_directive,
+    r#"
+# pyrefly: unreachable-code=error
+import sys
+
+def f() -> None:
+    sys.exit(1)
+    print(1)  # E: This code is unreachable
+    "#,
+);
@@ -0,0 +1,127 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use crate::testcase;
+
+testcase!(
+    test_partial_positional,
+    r#"
+from functools import partial
+from typing import reveal_type
+
+def f(a: int, b: str) -> float: ...
+
+p = partial(f, 1)
+reveal_type(p)  # E: revealed type: (b: str) -> float
+p("x")
+p(1)  # E: Argument `Literal[1]` is not assignable to parameter `b` with type `str`
+partial(f, "x")  # E: Argument `Literal['x']` is not assignable to parameter `a` with type `int` in function `f`
+partial(f, 1, "x", 2)  # E: Expected 2 positional arguments, got 3
+"#,
+);
+
+testcase!(
+    test_partial_keyword,
+    r#"
+from functools import partial
+from typing import reveal_type
+
+def h(a: int, b: str, c: bytes) -> None: ...
+
+reveal_type(partial(h, b="x"))  # E: revealed type: (a: int, *, b: str = ..., c: bytes) -> None
+partial(h, b="x")(1, c=b"")
+partial(h, d=1)  # E: Unexpected keyword argument `d`
+partial(h, b=1)  # E: Argument `Literal[1]` is not assignable to parameter `b` with type `str` in function `h`
+"#,
+);
+
+testcase!(
+    test_partial_object,
+    r#"
+from functools import partial
+from typing import Callable, assert_type
+
+def f(a: int, b: str) -> float: ...
+
+p = partial(f, 1)
+assert_type(p.func, Callable[..., float])
+x: partial[float] = p
+y: Callable[[str], float] = p
+"#,
+);
+
+testcase!(
+    test_partial_overload,
+    r#"
+from functools import partial
+from typing import overload, reveal_type
+
+@overload
+def g(a: int, b: int) -> int: ...
+@overload
+def g(a: str, b: str) -> str: ...
+def g(a, b): ...
+
+reveal_type(partial(g, 1))  # E: revealed type: (b: int) -> int
+partial(g, b"")  # E: No overload of the function passed to `functools.partial` accepts the bound arguments
+"#,
+);
+
+testcase!(
+    test_lru_cache_keeps_signature,
+    r#"
+from functools import cache, lru_cache
+from typing import reveal_type
+
+@lru_cache(maxsize=None)
+def f(x: int) -> str: ...
+
+@cache
+def g(x: int, y: str = "") -> int: ...
+
+class C:
+    @lru_cache
+    def m(self, x: int) -> int: ...
+
+reveal_type(f)  # E: revealed type: (x: int) -> str
+f("a")  # E: Argument `Literal['a']` is not assignable to parameter `x` with type `int` in function `f`
+g(1, y="a")
+f.cache_clear()
+f.cache_info()
+C().m(1)
+C().m("a")  # E: Argument `Literal['a']` is not assignable to parameter `x` with type `int` in function `C.m`
+C.m.cache_clear()
+"#,
+);
+
+testcase!(
+    test_singledispatch_register,
+    r#"
+from functools import singledispatch
+
+@singledispatch
+def process(arg: int | str, verbose: bool = False) -> str: ...
+
+@process.register
+def _(arg: int, verbose: bool = False) -> str: ...
+
+@process.register(str)
+def _(arg: str, verbose: bool = False) -> str: ...
+
+@process.register  # E: Dispatch type `bytes` is not assignable to the first parameter of `process` with type `int | str`
+def _(arg: bytes, verbose: bool = False) -> str: ...
+
+@process.register  # E: `_` is registered without a dispatch class, so its first parameter needs an annotation
+def _(arg, verbose=False) -> str: ...
+
+@process.register(float)  # E: first parameter of `_` with type `int`  # E: first parameter of `process` with type `int | str`
+def _(arg: int) -> str: ...
+
+process(1)
+process.registry
+"#,
+);
@@ -10,6 +10,7 @@ use pyrefly_python::module_name::ModuleName;
 use pyrefly_python::module_path::ModulePath;
 use pyrefly_util::fs_anyhow;
 
+use crate::config::config::ImportRule;
 use crate::test::util::TestEnv;
 use crate::testcase;
 
@@ -149,6 +150,53 @@ foo.qux.wibble.wobble # E: No attribute `qux` in module `foo`
 "#,
 );
 
+fn env_import_rules() -> TestEnv {
+    let rules = [
+        r#"
+modules = ["main"]
+forbidden = ["app.web.*"]
+"#,
+        r#"
+forbidden = ["boto3.*"]
+message = "Use the clients in `app.domain` instead"
+"#,
+    ];
+    let mut t = TestEnv::new().with_import_rules(
+        rules
+            .iter()
+            .map(|rule| toml::from_str::<ImportRule>(rule).unwrap())
+            .collect(),
+    );
+    t.add_with_path("app", "app/__init__.py", "");
+    t.add_with_path("app.web", "app/web/__init__.py", "");
+    t.add_with_path(
+        "app.web.views",
+        "app/web/views.py",
+        "def render() -> None: ...",
+    );
+    t.add_with_path("app.domain", "app/domain/__init__.py", "");
+    t.add_with_path("app.domain.models", "app/domain/models.py", "x: int = 1");
+    t.add_with_path("boto3", "boto3/__init__.py", "");
+    t
+}
+
+testcase!(
+    test_import_rules,
+    env_import_rules(),
+    r#"
+import boto3  # E: `main` may not import `boto3`\nUse the clients in `app.domain` instead
+import app.web.views  # E: `main` may not import `app.web.views`
+from app import web  # E: `main` may not import `app.web`
+from app.web.views import render  # E: `main` may not import `app.web.views`
+from app.web import views  # E: `main` may not import `app.web`
+import app.web.missing  # E: `main` may not import `app.web.missing`  # E: Could not find import of `app.web.missing`
+from boto3.missing import x  # E: `main` may not import `boto3.missing`  # E: Could not find import of `boto3.missing`
+import app.domain.models
+from app.domain import models
+from app.domain.models import x
+"#,
+);
+
 fn env_star_reexport() -> TestEnv {
     let mut t = TestEnv::new();
     t.add("base", "class Foo: ...");
@@ -15,6 +15,7 @@ use crate::module::module_info::ModuleInfo;
 use crate::state::lsp::ImportFormat;
 use crate::state::require::Require;
 use crate::state::state::State;
+use crate::test::util::TestEnv;
 use crate::test::util::get_batched_lsp_operations_report_allow_error;
 use crate::test::util::mk_multi_file_state_assert_no_errors;
 
@@ -475,6 +476,87 @@ my_func()
     );
 }
 
+#[test]
+fn add_missing_match_cases() {
+    let code = "from enum import Enum\nclass Color(Enum):\n    RED = 1\n    GREEN = 2\n    BLUE = 3\ndef f(c: Color):\n    match c:\n        case Color.RED:\n            pass";
+    // The non-exhaustive-match error kind is disabled by default.
+    let mut env = TestEnv::new().enable_non_exhaustive_match_error();
+    env.add("main", code);
+    let (state, handle) = env.to_state();
+    let position = TextSize::new(code.find("c:").unwrap() as u32);
+    let report = get_test_report(&state, &handle("main"), position);
+    assert_eq!(
+        r#"
+Code Actions Results:
+# Title: Add missing `case` arms
+
+## Before:
+from enum import Enum
+class Color(Enum):
+    RED = 1
+    GREEN = 2
+    BLUE = 3
+def f(c: Color):
+    match c:
+        case Color.RED:
+            pass
+## After:
+from enum import Enum
+class Color(Enum):
+    RED = 1
+    GREEN = 2
+    BLUE = 3
+def f(c: Color):
+    match c:
+        case Color.RED:
+            pass
+        case Color.GREEN:
+            pass
+        case Color.BLUE:
+            pass
+"#
+        .trim(),
+        report.trim()
+    );
+}
+
+#[test]
+fn add_missing_match_cases_module_qualified() {
+    let code = "import colors as c\ndef f(x: c.Color):\n    match x:\n        case c.Color.RED:\n            pass";
+    let mut env = TestEnv::new().enable_non_exhaustive_match_error();
+    env.add(
+        "colors",
+        "from enum import Enum\nclass Color(Enum):\n    RED = 1\n    GREEN = 2",
+    );
+    env.add("main", code);
+    let (state, handle) = env.to_state();
+    let position = TextSize::new(code.find("x:\n").unwrap() as u32);
+    let report = get_test_report(&state, &handle("main"), position);
+    assert_eq!(
+        r#"
+Code Actions Results:
+# Title: Add missing `case` arms
+
+## Before:
+import colors as c
+def f(x: c.Color):
+    match x:
+        case c.Color.RED:
+            pass
+## After:
+import colors as c
+def f(x: c.Color):
+    match x:
+        case c.Color.RED:
+            pass
+        case c.Color.GREEN:
+            pass
+"#
+        .trim(),
+        report.trim()
+    );
+}
+
 #[test]
 fn extract_function_basic_refactor() {
     let code = r#"
@@ -944,3 +1026,51 @@ def outer(xs):
         "expected at least one extract action when control flow is simple"
     );
 }
+
+#[test]
+fn remove_unused_fix_all() {
+    let code = r#"
+import os
+from typing import Any, List
+
+def f() -> List[int]:
+    def _unused() -> None:
+        pass
+    x = 1
+    y = len("")
+    return []
+"#;
+    // The unused-* error kinds are disabled by default, and then nothing is removed.
+    let (handles, state) =
+        mk_multi_file_state_assert_no_errors(&[("main", code)], Require::Everything);
+    let handle = handles.get("main").unwrap();
+    assert!(
+        state
+            .transaction()
+            .remove_unused_code_actions(handle)
+            .is_none()
+    );
+
+    let mut env = TestEnv::new().enable_unused_error();
+    env.add("main", code);
+    let (state, handle) = env.to_state();
+    let handle = handle("main");
+    let transaction = state.transaction();
+    let module_info = transaction.get_module_info(&handle).unwrap();
+    let actions = transaction
+        .remove_unused_code_actions(&handle)
+        .unwrap_or_default();
+    assert_eq!(1, actions.len());
+    assert_eq!(
+        r#"
+from typing import List
+
+def f() -> List[int]:
+    def _unused() -> None:
+        pass
+    len("")
+    return []
+"#,
+        apply_refactor_edits_for_module(&module_info, &actions[0].edits)
+    );
+}
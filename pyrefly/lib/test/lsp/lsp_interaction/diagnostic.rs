@@ -5,10 +5,16 @@
  * LICENSE file in the root directory of this source tree.
  */
 
+use std::cell::RefCell;
+use std::collections::HashMap;
+
 use lsp_server::Message;
 use lsp_server::Notification;
 use lsp_types::DocumentDiagnosticReportResult;
 use lsp_types::Url;
+use lsp_types::WorkspaceDiagnosticReportResult;
+use lsp_types::WorkspaceDocumentDiagnosticReport;
+use lsp_types::request::WorkspaceDiagnosticRefresh;
 use pyrefly_config::environment::environment::PythonEnvironment;
 use serde_json::json;
 
@@ -224,6 +230,54 @@ fn test_unreachable_branch_diagnostic() {
     interaction.shutdown().unwrap();
 }
 
+#[test]
+fn test_unreachable_after_return_diagnostic() {
+    let test_files_root = get_test_files_root();
+    let mut interaction = LspInteraction::new();
+    interaction.set_root(test_files_root.path().to_path_buf());
+    interaction
+        .initialize(InitializeSettings {
+            configuration: Some(None),
+            ..Default::default()
+        })
+        .unwrap();
+
+    interaction.client.did_change_configuration();
+
+    interaction
+        .client
+        .expect_configuration_request(None)
+        .unwrap()
+        .send_configuration_response(json!([
+            {"pyrefly": {"displayTypeErrors": "force-on"}}
+        ]));
+
+    interaction.client.did_open("unreachable_after_return.py");
+
+    interaction
+        .client
+        .diagnostic("unreachable_after_return.py")
+        .expect_response(json!({
+            "items": [
+                {
+                    "code": "unreachable-code",
+                    "message": "This code is unreachable",
+                    "range": {
+                        "end": {"character": 12, "line": 7},
+                        "start": {"character": 4, "line": 7}
+                    },
+                    "severity": 4,
+                    "source": "Pyrefly",
+                    "tags": [1]
+                }
+            ],
+            "kind": "full"
+        }))
+        .unwrap();
+
+    interaction.shutdown().unwrap();
+}
+
 #[test]
 fn test_unused_parameter_diagnostic() {
     let test_files_root = get_test_files_root();
@@ -1048,3 +1102,92 @@ fn test_untyped_import_diagnostic() {
 
     interaction.shutdown().unwrap();
 }
+
+#[test]
+fn test_workspace_diagnostics() {
+    let test_files_root = get_test_files_root();
+    let root = test_files_root.path().join("workspace_diagnostics");
+    let mut interaction = LspInteraction::new();
+    interaction.set_root(root.clone());
+    interaction
+        .initialize(InitializeSettings {
+            workspace_folders: Some(vec![(
+                "test".to_owned(),
+                Url::from_file_path(&root).unwrap(),
+            )]),
+            capabilities: Some(json!({
+                "textDocument": {"diagnostic": {}},
+                "workspace": {"diagnostic": {"refreshSupport": true}},
+            })),
+            initialization_options: Some(json!({"pyrefly": {"pullDiagnostics": true}})),
+            ..Default::default()
+        })
+        .unwrap();
+
+    // Clients that pull diagnostics are asked to refresh instead of being sent them.
+    interaction.client.did_open("opened.py");
+    interaction
+        .client
+        .expect_request::<WorkspaceDiagnosticRefresh>(json!(null))
+        .unwrap()
+        .send_response(json!(null));
+
+    // The first request answers from the current state, which hasn't loaded the other files yet,
+    // and checks them in the background.
+    interaction
+        .client
+        .workspace_diagnostic(json!([]))
+        .expect_response_with(|result| {
+            matches!(result, WorkspaceDiagnosticReportResult::Report(report) if report.items.is_empty())
+        })
+        .unwrap();
+    interaction
+        .client
+        .expect_request::<WorkspaceDiagnosticRefresh>(json!(null))
+        .unwrap()
+        .send_response(json!(null));
+
+    let uri = |file: &str| Url::from_file_path(root.join(file)).unwrap();
+    let result_ids = RefCell::new(Vec::new());
+    interaction
+        .client
+        .workspace_diagnostic(json!([]))
+        .expect_response_with(|result| {
+            let WorkspaceDiagnosticReportResult::Report(report) = result else {
+                return false;
+            };
+            let mut errors = HashMap::new();
+            for item in report.items {
+                let WorkspaceDocumentDiagnosticReport::Full(full) = item else {
+                    return false;
+                };
+                let report = full.full_document_diagnostic_report;
+                result_ids
+                    .borrow_mut()
+                    .push(json!({"uri": full.uri.to_string(), "value": report.result_id}));
+                errors.insert(full.uri, report.items.len());
+            }
+            // Open files are left to `textDocument/diagnostic`.
+            errors.len() == 2
+                && errors.get(&uri("errors.py")) == Some(&1)
+                && errors.get(&uri("clean.py")) == Some(&0)
+        })
+        .unwrap();
+
+    interaction
+        .client
+        .workspace_diagnostic(json!(result_ids.into_inner()))
+        .expect_response_with(|result| {
+            let WorkspaceDiagnosticReportResult::Report(report) = result else {
+                return false;
+            };
+            report.items.len() == 2
+                && report
+                    .items
+                    .iter()
+                    .all(|x| matches!(x, WorkspaceDocumentDiagnosticReport::Unchanged(_)))
+        })
+        .unwrap();
+
+    interaction.shutdown().unwrap();
+}
@@ -34,6 +34,7 @@ mod provide_type;
 mod references;
 mod rename;
 mod type_definition;
+mod type_hierarchy;
 mod unsaved_file;
 mod util;
 mod will_rename_files;
@@ -62,6 +62,7 @@ use lsp_types::request::SignatureHelpRequest;
 use lsp_types::request::UnregisterCapability;
 use lsp_types::request::WillRenameFiles;
 use lsp_types::request::WorkspaceConfiguration;
+use lsp_types::request::WorkspaceDiagnosticRequest;
 use pretty_assertions::assert_eq;
 use pyrefly_util::fs_anyhow::read_to_string;
 use pyrefly_util::lock::FinishHandle;
@@ -471,6 +472,15 @@ impl TestClient {
         }}))
     }
 
+    pub fn workspace_diagnostic(
+        &self,
+        previous_result_ids: Value,
+    ) -> ClientRequestHandle<'_, WorkspaceDiagnosticRequest> {
+        self.send_request(json!({
+            "previousResultIds": previous_result_ids,
+        }))
+    }
+
     pub fn hover(
         &self,
         file: &'static str,
@@ -0,0 +1,156 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use lsp_types::SymbolKind;
+use lsp_types::Url;
+use lsp_types::request::TypeHierarchyPrepare;
+use lsp_types::request::TypeHierarchySubtypes;
+use lsp_types::request::TypeHierarchySupertypes;
+use serde_json::Value;
+use serde_json::json;
+
+use crate::commands::lsp::IndexingMode;
+use crate::test::lsp::lsp_interaction::object_model::InitializeSettings;
+use crate::test::lsp::lsp_interaction::object_model::LspInteraction;
+use crate::test::lsp::lsp_interaction::util::get_test_files_root;
+
+fn type_hierarchy_item(uri: &Url, name: &str, module: &str, line: u32, start: u32) -> Value {
+    let range = json!({
+        "start": {"line": line, "character": start},
+        "end": {"line": line, "character": start + name.len() as u32}
+    });
+    json!({
+        "name": name,
+        "kind": SymbolKind::CLASS,
+        "detail": format!("{module}.{name}"),
+        "uri": uri.to_string(),
+        "range": range,
+        "selectionRange": range,
+    })
+}
+
+#[test]
+fn test_prepare_type_hierarchy_on_use() {
+    let root = get_test_files_root();
+    let mut interaction = LspInteraction::new_with_indexing_mode(IndexingMode::None);
+    let test_root = root.path().join("references_cross_file_method_inheritance");
+    interaction.set_root(test_root.clone());
+    interaction
+        .initialize(InitializeSettings {
+            workspace_folders: Some(vec![(
+                "test".to_owned(),
+                Url::from_file_path(test_root.clone()).unwrap(),
+            )]),
+            ..Default::default()
+        })
+        .unwrap();
+    interaction.client.did_open("child.py");
+    let child_uri = Url::from_file_path(test_root.join("child.py")).unwrap();
+    let base_uri = Url::from_file_path(test_root.join("base.py")).unwrap();
+
+    // `Base` in `class Child(Base)`
+    interaction
+        .client
+        .send_request::<TypeHierarchyPrepare>(json!({
+            "textDocument": {"uri": child_uri.to_string()},
+            "position": {"line": 8, "character": 13},
+        }))
+        .expect_response_with(|result| {
+            let items = serde_json::to_value(result).unwrap();
+            items.as_array().is_some_and(|x| x.len() == 1)
+                && items[0]["name"] == "Base"
+                && items[0]["uri"] == base_uri.to_string()
+                && items[0]["selectionRange"]["start"] == json!({"line": 6, "character": 6})
+        })
+        .unwrap();
+
+    interaction.shutdown().unwrap();
+}
+
+#[test]
+fn test_type_hierarchy_supertypes() {
+    let root = get_test_files_root();
+    let mut interaction = LspInteraction::new_with_indexing_mode(IndexingMode::None);
+    let test_root = root.path().join("references_cross_file_method_inheritance");
+    interaction.set_root(test_root.clone());
+    interaction
+        .initialize(InitializeSettings {
+            workspace_folders: Some(vec![(
+                "test".to_owned(),
+                Url::from_file_path(test_root.clone()).unwrap(),
+            )]),
+            ..Default::default()
+        })
+        .unwrap();
+    interaction.client.did_open("child_of_child.py");
+    let child_of_child_uri = Url::from_file_path(test_root.join("child_of_child.py")).unwrap();
+    let child_uri = Url::from_file_path(test_root.join("child.py")).unwrap();
+    let base_uri = Url::from_file_path(test_root.join("base.py")).unwrap();
+
+    interaction
+        .client
+        .send_request::<TypeHierarchySupertypes>(json!({
+            "item": type_hierarchy_item(&child_of_child_uri, "ChildOfChild", "child_of_child", 8, 6)
+        }))
+        .expect_response_with(|result| {
+            let items = serde_json::to_value(result).unwrap();
+            items.as_array().is_some_and(|x| x.len() == 1)
+                && items[0]["name"] == "Child"
+                && items[0]["detail"] == "child.Child"
+                && items[0]["uri"] == child_uri.to_string()
+        })
+        .unwrap();
+
+    // Walk one level further up, into a file that isn't open.
+    interaction
+        .client
+        .send_request::<TypeHierarchySupertypes>(json!({
+            "item": type_hierarchy_item(&child_uri, "Child", "child", 8, 6)
+        }))
+        .expect_response_with(|result| {
+            let items = serde_json::to_value(result).unwrap();
+            items.as_array().is_some_and(|x| x.len() == 1)
+                && items[0]["name"] == "Base"
+                && items[0]["uri"] == base_uri.to_string()
+        })
+        .unwrap();
+
+    interaction.shutdown().unwrap();
+}
+
+#[test]
+fn test_type_hierarchy_subtypes() {
+    let root = get_test_files_root();
+    let mut interaction = LspInteraction::new_with_indexing_mode(IndexingMode::LazyBlocking);
+    let test_root = root.path().join("references_cross_file_method_inheritance");
+    interaction.set_root(test_root.clone());
+    interaction
+        .initialize(InitializeSettings {
+            workspace_folders: Some(vec![(
+                "test".to_owned(),
+                Url::from_file_path(test_root.clone()).unwrap(),
+            )]),
+            ..Default::default()
+        })
+        .unwrap();
+    interaction.client.did_open("base.py");
+    let base_uri = Url::from_file_path(test_root.join("base.py")).unwrap();
+    let child_uri = Url::from_file_path(test_root.join("child.py")).unwrap();
+
+    // Only direct subclasses are returned, so `ChildOfChild` is not included.
+    interaction
+        .client
+        .send_request::<TypeHierarchySubtypes>(json!({
+            "item": type_hierarchy_item(&base_uri, "Base", "base", 6, 6)
+        }))
+        .expect_response(json!([type_hierarchy_item(
+            &child_uri, "Child", "child", 8, 6
+        )]))
+        .unwrap();
+
+    interaction.shutdown().unwrap();
+}
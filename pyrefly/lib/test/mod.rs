@@ -11,12 +11,14 @@ mod abstract_methods;
 mod assign;
 mod attribute_narrow;
 mod attributes;
+mod attrs;
 mod callable;
 mod calls;
 mod class_keywords;
 mod class_overrides;
 mod class_subtyping;
 mod class_super;
+mod class_synthesis_plugin;
 mod constructors;
 mod contextual;
 mod cycles;
@@ -30,6 +32,7 @@ mod django;
 mod enums;
 mod flow_branching;
 mod flow_looping;
+mod functools;
 mod generic_basic;
 mod generic_legacy;
 mod generic_restrictions;
@@ -53,11 +56,13 @@ mod perf;
 mod protocol;
 mod pydantic;
 mod pysa;
+mod pytest;
 mod redundant_cast;
 mod returns;
 mod scope;
 mod semantic_syntax_errors;
 mod simple;
+mod sqlalchemy;
 mod state;
 mod subscript_narrow;
 mod suppression;
@@ -70,6 +75,7 @@ mod typed_dict;
 mod typing_self;
 mod unnecessary_comparison;
 mod untyped_def_behaviors;
+mod unused;
 pub mod util;
 mod var_resolution;
 mod variance_inference;
@@ -5,6 +5,7 @@
  * LICENSE file in the root directory of this source tree.
  */
 
+use crate::test::util::TestEnv;
 use crate::testcase;
 
 testcase!(
@@ -99,3 +100,116 @@ def f0(x: int | str):
             assert_type(x, str)  # E: assert_type(int | str, str)
 "#,
 );
+
+testcase!(
+    test_non_exhaustive_match,
+    TestEnv::new().enable_non_exhaustive_match_error(),
+    r#"
+from enum import Enum
+from typing import Literal
+
+class Color(Enum):
+    RED = 1
+    GREEN = 2
+    BLUE = 3
+
+def f(c: Color, b: bool, s: Literal["a", "b"]):
+    match c:  # E: Match is not exhaustive, missing case `Color.BLUE`
+        case Color.RED:
+            pass
+        case Color.GREEN:
+            pass
+    match b:  # E: Match is not exhaustive, missing case `False`
+        case True:
+            pass
+    match s:  # E: Match is not exhaustive, missing cases `'a'`, `'b'`
+        case "c":
+            pass
+"#,
+);
+
+testcase!(
+    test_non_exhaustive_match_attribute,
+    TestEnv::new().enable_non_exhaustive_match_error(),
+    r#"
+from enum import Enum
+
+class Color(Enum):
+    RED = 1
+    GREEN = 2
+
+class Shape:
+    color: Color
+
+    def f(self):
+        match self.color:  # E: Match is not exhaustive, missing case `Color.GREEN`
+            case Color.RED:
+                pass
+
+def g(shape: Shape):
+    match shape.color:
+        case Color.RED:
+            pass
+        case Color.GREEN:
+            pass
+"#,
+);
+
+testcase!(
+    test_exhaustive_match,
+    TestEnv::new().enable_non_exhaustive_match_error(),
+    r#"
+from enum import Enum
+
+class Color(Enum):
+    RED = 1
+    GREEN = 2
+
+class A: ...
+class B: ...
+
+def f(c: Color, b: bool | None, x: A | B):
+    match c:
+        case Color.RED | Color.GREEN:
+            pass
+    match b:
+        case True:
+            pass
+        case False:
+            pass
+        case None:
+            pass
+    match x:
+        case A():
+            pass
+        case B() as y:
+            pass
+"#,
+);
+
+testcase!(
+    test_non_exhaustive_match_class_union,
+    TestEnv::new().enable_non_exhaustive_match_error(),
+    r#"
+from typing import final
+
+@final
+class A:
+    x: int
+    __match_args__ = ("x",)
+@final
+class B: ...
+class C: ...
+
+def f(x: A | B | None, y: A | C):
+    match x:  # E: Match is not exhaustive, missing cases `A()`, `None`
+        case A(1):
+            pass
+        case B():
+            pass
+    # `C` may have subclasses, so the union isn't sealed.
+    match y:
+        case A():
+            pass
+"#,
+);
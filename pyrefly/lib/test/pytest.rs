@@ -0,0 +1,249 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use crate::test::util::TestEnv;
+use crate::testcase;
+
+/// Pytest only collects tests from files named `test_*.py` or `*_test.py`, so the code under
+/// test goes in extra modules rather than in `main`.
+fn env_pytest(modules: &[(&str, &str, &str)]) -> TestEnv {
+    let mut t = TestEnv::new().enable_unknown_fixture_error();
+    t.add_with_path(
+        "pytest",
+        "pytest.pyi",
+        r#"
+from typing import Any, AnyStr, Callable, Generic, TypeVar, overload
+
+_F = TypeVar("_F", bound=Callable[..., Any])
+
+@overload
+def fixture(fixture_function: _F) -> _F: ...
+@overload
+def fixture(*, scope: str = ..., name: str | None = ...) -> Callable[[_F], _F]: ...
+
+class _Mark:
+    def __getattr__(self, name: str) -> Any: ...
+
+mark: _Mark
+
+def param(*values: object, id: str | None = None) -> Any: ...
+
+class FixtureRequest: ...
+class MonkeyPatch: ...
+class CaptureFixture(Generic[AnyStr]): ...
+"#,
+    );
+    for (name, path, code) in modules {
+        t.add_with_path(name, path, code);
+    }
+    t
+}
+
+testcase!(
+    test_fixture_types,
+    env_pytest(&[(
+        "test_app",
+        "test_app.py",
+        r#"
+import pytest
+from typing import AsyncIterator, Iterator, reveal_type
+
+@pytest.fixture
+def user() -> str:
+    return "alice"
+
+@pytest.fixture(name="count")
+def count_fixture() -> int:
+    return 1
+
+@pytest.fixture
+def numbers(count) -> Iterator[int]:
+    reveal_type(count)  # E: revealed type: int
+    yield count
+
+@pytest.fixture
+async def token() -> bytes:
+    return b""
+
+@pytest.fixture
+async def client() -> AsyncIterator[float]:
+    yield 1.0
+
+def test_fixtures(user, count, numbers, token, client):
+    reveal_type(user)  # E: revealed type: str
+    reveal_type(count)  # E: revealed type: int
+    reveal_type(numbers)  # E: revealed type: int
+    reveal_type(token)  # E: revealed type: bytes
+    reveal_type(client)  # E: revealed type: float
+
+def test_builtin(request, monkeypatch, capsys):
+    reveal_type(request)  # E: revealed type: FixtureRequest
+    reveal_type(monkeypatch)  # E: revealed type: MonkeyPatch
+    reveal_type(capsys)  # E: revealed type: CaptureFixture[str]
+
+def test_unknown(usr, default=1):  # E: Could not find fixture `usr`
+    pass
+
+def helper(usr):
+    pass
+"#,
+    )]),
+    "",
+);
+
+testcase!(
+    test_fixture_scopes,
+    env_pytest(&[
+        (
+            "conftest",
+            "conftest.py",
+            r#"
+import pytest
+
+pytest_plugins = ["plugin"]
+
+@pytest.fixture
+def user() -> str:
+    return "alice"
+"#,
+        ),
+        (
+            "plugin",
+            "plugin.py",
+            r#"
+import pytest
+
+@pytest.fixture
+def from_plugin() -> complex:
+    return 1j
+"#,
+        ),
+        (
+            "tests.conftest",
+            "tests/conftest.py",
+            r#"
+import pytest
+
+@pytest.fixture
+def db() -> dict[str, int]:
+    return {}
+"#,
+        ),
+        (
+            "tests.helpers",
+            "tests/helpers.py",
+            r#"
+import pytest
+
+@pytest.fixture
+def shared() -> float:
+    return 1.0
+"#,
+        ),
+        (
+            "tests.unit.test_db",
+            "tests/unit/test_db.py",
+            r#"
+import pytest
+from typing import reveal_type
+from tests.helpers import shared
+
+@pytest.fixture
+def user(user) -> list[str]:
+    reveal_type(user)  # E: revealed type: str
+    return [user]
+
+def test_db(db, user, shared, from_plugin):
+    reveal_type(db)  # E: revealed type: dict[str, int]
+    reveal_type(user)  # E: revealed type: list[str]
+    reveal_type(shared)  # E: revealed type: float
+    reveal_type(from_plugin)  # E: revealed type: complex
+
+class TestDb:
+    @pytest.fixture
+    def db(self) -> bytes:
+        return b""
+
+    def test_class_fixture(self, db, user):
+        reveal_type(db)  # E: revealed type: bytes
+        reveal_type(user)  # E: revealed type: list[str]
+"#,
+        ),
+    ]),
+    "",
+);
+
+testcase!(
+    test_parametrize,
+    env_pytest(&[(
+        "test_params",
+        "test_params.py",
+        r#"
+import pytest
+from unittest import mock
+
+@pytest.mark.parametrize("x, y", [(1, "a"), (2, 3)])  # E: Parametrized value `Literal[3]` is not assignable to parameter `y` with type `str`
+def test_pairs(x: int, y: str):
+    pass
+
+@pytest.mark.parametrize("x", [1, pytest.param("a", id="bad")])  # E: Parametrized value `Literal['a']` is not assignable to parameter `x` with type `int`
+def test_single(x: int):
+    pass
+
+@pytest.mark.parametrize(("x", "y"), [([1.0], None)])
+def test_hint(x: list[float], y):
+    pass
+
+@pytest.mark.parametrize("x", [1], indirect=True)
+def test_indirect(x):  # E: Could not find fixture `x`
+    pass
+
+@mock.patch("os.getcwd")
+def test_patched(getcwd):
+    pass
+
+@pytest.mark.parametrize("x", [1])
+class TestParametrized:
+    def test_x(self, x):
+        pass
+"#,
+    )]),
+    "",
+);
+
+testcase!(
+    test_other_fixture_decorator,
+    env_pytest(&[
+        (
+            "mylib",
+            "mylib.py",
+            r#"
+from typing import Callable
+
+def fixture(f: Callable[[], int]) -> Callable[[], str]: ...
+"#,
+        ),
+        (
+            "test_other",
+            "test_other.py",
+            r#"
+from typing import reveal_type
+from mylib import fixture
+
+@fixture
+def value() -> int:
+    return 1
+
+reveal_type(value)  # E: revealed type: () -> str
+
+def test_value(value):  # E: Could not find fixture `value`
+    pass
+"#,
+        ),
+    ]),
+    "",
+);
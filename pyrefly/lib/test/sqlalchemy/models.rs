@@ -0,0 +1,189 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use crate::sqlalchemy_testcase;
+use crate::test::sqlalchemy::util::sqlalchemy_env;
+use crate::test::util::testcase_for_macro;
+
+sqlalchemy_testcase!(
+    test_model_init,
+    r#"
+from sqlalchemy import String
+from sqlalchemy.orm import DeclarativeBase, Mapped, mapped_column
+
+class Base(DeclarativeBase):
+    pass
+
+class User(Base):
+    __tablename__ = "users"
+    id: Mapped[int] = mapped_column(primary_key=True)
+    name: Mapped[str] = mapped_column(String(50))
+
+class Admin(User):
+    level: Mapped[int]
+
+User(id=1, name="a")
+User(name="a")
+User()
+User(name=1)  # E: Argument `Literal[1]` is not assignable to parameter `name` with type `str`
+User(1)  # E: Expected 0 positional arguments
+User(unknown=1)  # E: Unexpected keyword argument `unknown`
+Admin(id=1, name="a", level=2)
+"#,
+);
+
+sqlalchemy_testcase!(
+    test_custom_init,
+    r#"
+from sqlalchemy.orm import DeclarativeBase, Mapped
+
+class Base(DeclarativeBase):
+    def __init__(self, x: int) -> None: ...
+
+class User(Base):
+    id: Mapped[int]
+
+User(1)
+User(id=1)  # E: Unexpected keyword argument `id`
+"#,
+);
+
+sqlalchemy_testcase!(
+    test_registry_mapped,
+    r#"
+from sqlalchemy.orm import Mapped, registry
+
+reg = registry()
+
+@reg.mapped
+class User:
+    id: Mapped[int]
+
+User(id=1)
+User(id="a")  # E: Argument `Literal['a']` is not assignable to parameter `id` with type `int`
+"#,
+);
+
+sqlalchemy_testcase!(
+    test_attribute_types,
+    r#"
+from typing import Optional, assert_type
+from sqlalchemy import Integer, String
+from sqlalchemy.orm import DeclarativeBase, Mapped, mapped_column
+
+class Base(DeclarativeBase):
+    pass
+
+class User(Base):
+    id = mapped_column(Integer, primary_key=True)
+    name = mapped_column(String(50), nullable=False)
+    nickname = mapped_column(String)
+    email: Mapped[Optional[str]]
+    age: Mapped[int] = mapped_column(nullable=True)
+
+def f(user: User):
+    assert_type(user.id, int)
+    assert_type(user.name, str)
+    assert_type(user.nickname, str | None)
+    assert_type(user.email, str | None)
+    assert_type(user.age, int | None)
+
+User(nickname=None)
+User(name=None)  # E: Argument `None` is not assignable to parameter `name` with type `str`
+"#,
+);
+
+sqlalchemy_testcase!(
+    test_relationship,
+    r#"
+from typing import assert_type
+from sqlalchemy import ForeignKey
+from sqlalchemy.orm import DeclarativeBase, Mapped, mapped_column, relationship
+
+class Base(DeclarativeBase):
+    pass
+
+class User(Base):
+    id: Mapped[int] = mapped_column(primary_key=True)
+    addresses = relationship("Address", uselist=True)
+    tags = relationship("Tag", collection_class=set)
+    profile = relationship("Profile", uselist=False)
+    unknown = relationship("Profile")
+
+class Address(Base):
+    id: Mapped[int] = mapped_column(primary_key=True)
+    user_id: Mapped[int] = mapped_column(ForeignKey("user.id"))
+    user: Mapped[User] = relationship(back_populates="addresses")
+
+class Tag(Base):
+    id: Mapped[int] = mapped_column(primary_key=True)
+
+class Profile(Base):
+    id: Mapped[int] = mapped_column(primary_key=True)
+
+def f(user: User, address: Address):
+    assert_type(user.addresses, list[Address])
+    assert_type(user.tags, set[Tag])
+    assert_type(user.profile, Profile | None)
+    assert_type(address.user, User)
+"#,
+);
+
+#[test]
+fn test_relationship_in_base_module() -> anyhow::Result<()> {
+    let mut env = sqlalchemy_env();
+    env.add(
+        "db",
+        r#"
+from sqlalchemy.orm import DeclarativeBase, Mapped, mapped_column
+
+class Base(DeclarativeBase):
+    pass
+
+class Address(Base):
+    id: Mapped[int] = mapped_column(primary_key=True)
+"#,
+    );
+    testcase_for_macro(
+        env,
+        r#"
+from typing import reveal_type
+from sqlalchemy.orm import relationship
+from db import Base
+
+class User(Base):
+    addresses = relationship("Address", uselist=True)
+
+def f(user: User):
+    reveal_type(user.addresses)  # E: revealed type: list[Address]
+"#,
+        file!(),
+        line!(),
+    )
+}
+
+sqlalchemy_testcase!(
+    test_select,
+    r#"
+from typing import assert_type
+from sqlalchemy import String, select
+from sqlalchemy.orm import DeclarativeBase, InstrumentedAttribute, Mapped, mapped_column
+from sqlalchemy.sql import Select
+
+class Base(DeclarativeBase):
+    pass
+
+class User(Base):
+    id: Mapped[int] = mapped_column(primary_key=True)
+    name = mapped_column(String, nullable=False)
+
+assert_type(User.id, InstrumentedAttribute[int])
+assert_type(User.name, InstrumentedAttribute[str])
+assert_type(select(User.id, User.name), Select[tuple[int, str]])
+assert_type(select(User), Select[tuple[User]])
+"#,
+);
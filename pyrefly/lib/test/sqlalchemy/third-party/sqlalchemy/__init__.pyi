@@ -0,0 +1,4 @@
+from .schema import ForeignKey as ForeignKey
+from .sql import select as select
+from .sql.sqltypes import Integer as Integer
+from .sql.sqltypes import String as String
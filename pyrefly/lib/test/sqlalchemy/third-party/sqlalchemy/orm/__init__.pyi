@@ -0,0 +1,9 @@
+from ._orm_constructors import mapped_column as mapped_column
+from ._orm_constructors import relationship as relationship
+from .attributes import InstrumentedAttribute as InstrumentedAttribute
+from .base import Mapped as Mapped
+from .decl_api import DeclarativeBase as DeclarativeBase
+from .decl_api import DeclarativeBaseNoMeta as DeclarativeBaseNoMeta
+from .decl_api import registry as registry
+from .properties import MappedColumn as MappedColumn
+from .relationships import Relationship as Relationship
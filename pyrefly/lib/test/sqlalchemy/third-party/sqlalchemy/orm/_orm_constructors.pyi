@@ -0,0 +1,23 @@
+from typing import Any, Callable, Collection
+
+from ..schema import ForeignKey
+from ..sql.type_api import TypeEngine
+from .properties import MappedColumn
+from .relationships import Relationship
+
+def mapped_column(
+    __name_pos: str | type[TypeEngine[Any]] | TypeEngine[Any] | ForeignKey | None = None,
+    __type_pos: type[TypeEngine[Any]] | TypeEngine[Any] | ForeignKey | None = None,
+    *args: ForeignKey,
+    nullable: bool | None = None,
+    primary_key: bool | None = False,
+    **kw: Any,
+) -> MappedColumn[Any]: ...
+def relationship(
+    argument: str | type[Any] | Callable[[], type[Any]] | None = None,
+    *,
+    uselist: bool | None = None,
+    collection_class: type[Collection[Any]] | None = None,
+    back_populates: str | None = None,
+    **kw: Any,
+) -> Relationship[Any]: ...
@@ -0,0 +1,7 @@
+from typing import TypeVar
+
+from .base import Mapped
+
+_T_co = TypeVar("_T_co", covariant=True)
+
+class InstrumentedAttribute(Mapped[_T_co]): ...
@@ -0,0 +1,12 @@
+from typing import Any, Generic, TypeVar, overload
+
+from .attributes import InstrumentedAttribute
+
+_T_co = TypeVar("_T_co", covariant=True)
+
+class Mapped(Generic[_T_co]):
+    @overload
+    def __get__(self, instance: None, owner: Any) -> InstrumentedAttribute[_T_co]: ...
+    @overload
+    def __get__(self, instance: object, owner: Any) -> _T_co: ...
+    def __set__(self, instance: Any, value: Any) -> None: ...
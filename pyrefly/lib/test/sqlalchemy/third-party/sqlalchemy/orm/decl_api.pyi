@@ -0,0 +1,12 @@
+from typing import Any, TypeVar
+
+_T = TypeVar("_T")
+
+class DeclarativeBase:
+    def __init__(self, **kw: Any) -> None: ...
+
+class DeclarativeBaseNoMeta:
+    def __init__(self, **kw: Any) -> None: ...
+
+class registry:
+    def mapped(self, cls: type[_T]) -> type[_T]: ...
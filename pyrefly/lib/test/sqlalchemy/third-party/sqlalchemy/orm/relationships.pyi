@@ -0,0 +1,7 @@
+from typing import TypeVar
+
+from .base import Mapped
+
+_T = TypeVar("_T")
+
+class Relationship(Mapped[_T]): ...
@@ -0,0 +1,4 @@
+from typing import Any
+
+class ForeignKey:
+    def __init__(self, column: str, **kw: Any) -> None: ...
@@ -0,0 +1,2 @@
+from ._selectable_constructors import select as select
+from .selectable import Select as Select
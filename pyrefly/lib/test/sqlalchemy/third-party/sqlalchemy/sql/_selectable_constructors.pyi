@@ -0,0 +1,18 @@
+from typing import Any, TypeVar, overload
+
+from ..orm.attributes import InstrumentedAttribute
+from .selectable import Select
+
+_T0 = TypeVar("_T0")
+_T1 = TypeVar("_T1")
+
+@overload
+def select(__ent0: InstrumentedAttribute[_T0] | type[_T0], /) -> Select[tuple[_T0]]: ...
+@overload
+def select(
+    __ent0: InstrumentedAttribute[_T0] | type[_T0],
+    __ent1: InstrumentedAttribute[_T1] | type[_T1],
+    /,
+) -> Select[tuple[_T0, _T1]]: ...
+@overload
+def select(*entities: Any) -> Select[Any]: ...
@@ -0,0 +1,6 @@
+from typing import Any, Generic, TypeVar
+
+_TP = TypeVar("_TP", bound=tuple[Any, ...])
+
+class Select(Generic[_TP]):
+    def where(self, *whereclause: Any) -> Select[_TP]: ...
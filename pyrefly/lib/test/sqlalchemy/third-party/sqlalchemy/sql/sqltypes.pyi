@@ -0,0 +1,6 @@
+from .type_api import TypeEngine
+
+class Integer(TypeEngine[int]): ...
+
+class String(TypeEngine[str]):
+    def __init__(self, length: int | None = None) -> None: ...
@@ -21,6 +21,7 @@ use pyrefly_python::sys_info::PythonPlatform;
 use pyrefly_python::sys_info::PythonVersion;
 use pyrefly_python::sys_info::SysInfo;
 use pyrefly_util::arc_id::ArcId;
+use pyrefly_util::fs_anyhow;
 use pyrefly_util::lock::Mutex;
 use pyrefly_util::prelude::SliceExt;
 
@@ -28,6 +29,8 @@ use crate::config::config::ConfigFile;
 use crate::config::finder::ConfigFinder;
 use crate::error::error::print_errors;
 use crate::module::finder::find_import;
+use crate::state::error_cache::ErrorCache;
+use crate::state::errors::Errors;
 use crate::state::load::FileContents;
 use crate::state::require::Require;
 use crate::state::state::State;
@@ -384,3 +387,49 @@ fn test_sequential_committable_transactions() {
     let lock = counter.lock();
     assert_eq!(10, *lock);
 }
+
+#[test]
+fn test_error_cache() {
+    let root = tempfile::tempdir().unwrap();
+    let lib = root.path().join("lib.py");
+    let main = root.path().join("main.py");
+    fs_anyhow::write(&lib, "x: int = 1").unwrap();
+    fs_anyhow::write(&main, "import lib\ny: str = lib.x").unwrap();
+    let mut test_env = TestEnv::new();
+    test_env.add_real_path("lib", lib.clone());
+    test_env.add_real_path("main", main.clone());
+    let config_finder = test_env.config_finder();
+    let handle = Handle::new(
+        ModuleName::from_str("main"),
+        ModulePath::filesystem(main),
+        test_env.sys_info(),
+    );
+    let handles = [handle.dupe()];
+    let cache = ErrorCache::new(root.path().join("cache"));
+
+    let state = State::new(test_env.config_finder());
+    let mut transaction = state.new_transaction(Require::Exports, None);
+    transaction.run(&handles, Require::Errors);
+    assert_eq!(
+        transaction
+            .get_errors(&handles)
+            .collect_errors()
+            .shown
+            .len(),
+        1
+    );
+    cache.save(&transaction, &handles).unwrap();
+
+    // Nothing changed, so the result is restored from the cache.
+    let (cached, uncached) = cache.partition(&handles, &config_finder);
+    assert!(uncached.is_empty());
+    let mut errors = Errors::new(Vec::new());
+    errors.extend(cached);
+    assert_eq!(errors.collect_errors().shown.len(), 1);
+
+    // Changing a dependency invalidates the cached result, even if only a comment changed.
+    fs_anyhow::write(&lib, "x: int = 1  # comment").unwrap();
+    let (cached, uncached) = cache.partition(&handles, &config_finder);
+    assert!(cached.is_empty());
+    assert_eq!(uncached, vec![handle]);
+}
@@ -5,6 +5,8 @@
  * LICENSE file in the root directory of this source tree.
  */
 
+use crate::config::error_kind::Severity;
+use crate::test::util::TestEnv;
 use crate::testcase;
 
 testcase!(
@@ -186,3 +188,23 @@ x: int = "1"  # type: ignore
 y: int = "2"  # E: is not assignable
 "#,
 );
+
+#[test]
+fn test_invalid_severity_directive() {
+    let env = TestEnv::one(
+        "main",
+        "# pyrefly: bad-retrun=warn, bad-return=loud\n# pyrefly: bad-assignment=warn\nx: str = 1\n",
+    );
+    let (state, handle) = env.to_state();
+    let errs = state
+        .transaction()
+        .get_errors([&handle("main")])
+        .collect_errors()
+        .shown;
+    let msgs = errs.iter().map(|e| e.msg()).collect::<Vec<_>>();
+    assert_eq!(msgs.len(), 3, "{msgs:?}");
+    assert!(msgs[0].contains("Unknown error kind `bad-retrun`"));
+    assert!(msgs[1].contains("Invalid severity `loud` for `bad-return`"));
+    // The valid directive still applies.
+    assert_eq!(errs[2].severity(), Severity::Warn);
+}
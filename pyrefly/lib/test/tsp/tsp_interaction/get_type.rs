@@ -0,0 +1,90 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Tests for TSP getType and the requests that operate on the types it returns
+
+use lsp_server::ErrorCode;
+use tempfile::TempDir;
+
+use crate::test::tsp::tsp_interaction::object_model::TspInteraction;
+
+fn node(uri: &str, line: u32, start: u32, end: u32) -> serde_json::Value {
+    serde_json::json!({
+        "uri": uri,
+        "range": {
+            "start": {"line": line, "character": start},
+            "end": {"line": line, "character": end},
+        },
+    })
+}
+
+#[test]
+fn test_tsp_get_type() {
+    let temp_dir = TempDir::new().unwrap();
+    std::fs::write(
+        temp_dir.path().join("test.py"),
+        "class Foo: ...\nfoo = Foo()\n",
+    )
+    .unwrap();
+    std::fs::write(temp_dir.path().join("pyproject.toml"), "").unwrap();
+
+    let mut tsp = TspInteraction::new();
+    tsp.set_root(temp_dir.path().to_path_buf());
+    tsp.initialize(Default::default());
+    tsp.server.did_open("test.py");
+    tsp.client.expect_any_message();
+
+    tsp.server.get_snapshot();
+    let snapshot = tsp.client.receive_response().result.unwrap();
+
+    let uri = tsp.server.file_uri("test.py");
+    tsp.server.send_request(
+        "typeServer/getType",
+        serde_json::json!({"node": node(&uri, 1, 0, 3), "snapshot": snapshot}),
+    );
+    let ty = tsp.client.receive_response().result.unwrap();
+    assert_eq!(ty["name"], "Foo");
+    assert_eq!(ty["category"], 3); // Class
+    assert_eq!(ty["flags"], 2); // Instance
+    assert_eq!(ty["moduleName"]["nameParts"], serde_json::json!(["test"]));
+    assert_eq!(ty["decl"]["name"], "Foo");
+
+    // The handle can be used for follow-up queries in the same snapshot.
+    tsp.server.send_request(
+        "typeServer/getRepr",
+        serde_json::json!({"type": ty, "flags": 0, "snapshot": snapshot}),
+    );
+    assert_eq!(
+        tsp.client.receive_response().result,
+        Some(serde_json::json!("Foo"))
+    );
+
+    tsp.shutdown();
+}
+
+#[test]
+fn test_tsp_get_type_outdated_snapshot() {
+    let temp_dir = TempDir::new().unwrap();
+    std::fs::write(temp_dir.path().join("test.py"), "x = 1\n").unwrap();
+    std::fs::write(temp_dir.path().join("pyproject.toml"), "").unwrap();
+
+    let mut tsp = TspInteraction::new();
+    tsp.set_root(temp_dir.path().to_path_buf());
+    tsp.initialize(Default::default());
+    tsp.server.did_open("test.py");
+    tsp.client.expect_any_message();
+
+    let uri = tsp.server.file_uri("test.py");
+    tsp.server.send_request(
+        "typeServer/getType",
+        serde_json::json!({"node": node(&uri, 0, 0, 1), "snapshot": -1}),
+    );
+    let error = tsp.client.receive_response().error.unwrap();
+    assert_eq!(error.code, ErrorCode::ServerCancelled as i32);
+
+    tsp.shutdown();
+}
@@ -9,4 +9,6 @@
 
 pub mod get_snapshot;
 pub mod get_supported_protocol_version;
+pub mod get_type;
 pub mod object_model;
+pub mod resolve_import;
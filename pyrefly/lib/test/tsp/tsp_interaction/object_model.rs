@@ -129,6 +129,23 @@ impl TestTspServer {
         }));
     }
 
+    /// Send a type server request with arbitrary params, returning its id.
+    pub fn send_request(&mut self, method: &str, params: Value) -> RequestId {
+        let id = self.next_request_id();
+        self.send_message(Message::Request(Request {
+            id: id.clone(),
+            method: method.to_owned(),
+            params,
+        }));
+        id
+    }
+
+    pub fn file_uri(&self, file: &str) -> String {
+        Url::from_file_path(self.get_root_or_panic().join(file))
+            .unwrap()
+            .to_string()
+    }
+
     pub fn did_open(&self, file: &'static str) {
         let path = self.get_root_or_panic().join(file);
         self.send_message(Message::Notification(Notification {
@@ -283,6 +300,26 @@ impl TestTspClient {
         });
     }
 
+    /// Wait for the next response, skipping over notifications and requests.
+    pub fn receive_response(&self) -> Response {
+        loop {
+            match self.receiver.recv_timeout(self.timeout) {
+                Ok(msg) => {
+                    eprintln!("client<---server {}", serde_json::to_string(&msg).unwrap());
+                    if let Message::Response(response) = msg {
+                        return response;
+                    }
+                }
+                Err(RecvTimeoutError::Timeout) => {
+                    panic!("Timeout waiting for response");
+                }
+                Err(RecvTimeoutError::Disconnected) => {
+                    panic!("Channel disconnected");
+                }
+            }
+        }
+    }
+
     pub fn expect_any_message(&self) {
         match self.receiver.recv_timeout(self.timeout) {
             Ok(msg) => {
@@ -0,0 +1,58 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Tests for TSP resolveImport request
+
+use tempfile::TempDir;
+
+use crate::test::tsp::tsp_interaction::object_model::TspInteraction;
+
+#[test]
+fn test_tsp_resolve_import() {
+    let temp_dir = TempDir::new().unwrap();
+    std::fs::write(temp_dir.path().join("main.py"), "import lib\n").unwrap();
+    std::fs::write(temp_dir.path().join("lib.py"), "x = 1\n").unwrap();
+    std::fs::write(temp_dir.path().join("pyproject.toml"), "").unwrap();
+
+    let mut tsp = TspInteraction::new();
+    tsp.set_root(temp_dir.path().to_path_buf());
+    tsp.initialize(Default::default());
+    tsp.server.did_open("main.py");
+    tsp.client.expect_any_message();
+
+    tsp.server.get_snapshot();
+    let snapshot = tsp.client.receive_response().result.unwrap();
+
+    tsp.server.send_request(
+        "typeServer/resolveImport",
+        serde_json::json!({
+            "sourceUri": tsp.server.file_uri("main.py"),
+            "moduleDescriptor": {"leadingDots": 0, "nameParts": ["lib"]},
+            "snapshot": snapshot,
+        }),
+    );
+    let resolved = tsp.client.receive_response().result.unwrap();
+    assert!(
+        resolved.as_str().unwrap().ends_with("/lib.py"),
+        "Unexpected resolution: {resolved}"
+    );
+
+    tsp.server.send_request(
+        "typeServer/resolveImport",
+        serde_json::json!({
+            "sourceUri": tsp.server.file_uri("main.py"),
+            "moduleDescriptor": {"leadingDots": 0, "nameParts": ["does_not_exist"]},
+            "snapshot": snapshot,
+        }),
+    );
+    assert_eq!(
+        tsp.client.receive_response().result,
+        Some(serde_json::Value::Null)
+    );
+
+    tsp.shutdown();
+}
@@ -0,0 +1,121 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use crate::test::util::TestEnv;
+use crate::testcase;
+
+testcase!(
+    test_unused_disabled_by_default,
+    r#"
+import os
+
+def _helper() -> None:
+    x = 1
+"#,
+);
+
+testcase!(
+    test_unused_import,
+    TestEnv::new().enable_unused_error(),
+    r#"
+from __future__ import annotations
+import os  # E: Import `os` is unused
+import sys
+from typing import Any, List  # E: Import `Any` is unused
+
+x: List[int] = [len(sys.argv)]
+"#,
+);
+
+testcase!(
+    test_unused_import_reexport,
+    TestEnv::new().enable_unused_error(),
+    r#"
+import os as os
+from typing import Any as Any
+from typing import List
+
+__all__ = ["List"]
+"#,
+);
+
+testcase!(
+    test_unused_variable,
+    TestEnv::new().enable_unused_error(),
+    r#"
+counter = 0
+
+def f(xs: list[int]) -> int:
+    global counter
+    counter = 1
+    unused = 1  # E: Variable `unused` is unused
+    _ignored = 2
+    total = 0
+    for x in xs:
+        total += x
+    return total
+"#,
+);
+
+testcase!(
+    test_unused_parameter,
+    TestEnv::new().enable_unused_error(),
+    r#"
+from typing import overload
+
+def f(used: int, unused: int, _ignored: int, *args: int, **kwargs: int) -> int:  # E: Parameter `unused` is unused
+    return used
+
+class C:
+    def m(self, x: int) -> None:  # E: Parameter `x` is unused
+        print(self)
+
+    def stub(self, x: int) -> None: ...
+
+@overload
+def g(x: int) -> int: ...
+@overload
+def g(x: str) -> str: ...
+def g(x: int | str) -> int | str:
+    return x
+"#,
+);
+
+testcase!(
+    test_unused_private_function,
+    TestEnv::new().enable_unused_error(),
+    r#"
+import functools
+
+def _module_level() -> None:
+    pass
+
+def f() -> None:
+    def _unused() -> None:  # E: Private function `_unused` is unused
+        pass
+
+    def _used_later() -> None:
+        pass
+
+    def public() -> None:
+        _used_later()
+
+    @functools.cache
+    def _decorated() -> None:
+        pass
+
+    public()
+
+class C:
+    def m(self) -> None:
+        def _unused() -> None:  # E: Private function `_unused` is unused
+            pass
+
+    def _private_method(self) -> None:
+        pass
+"#,
+);
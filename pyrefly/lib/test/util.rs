@@ -6,6 +6,8 @@
  */
 
 use std::collections::HashMap;
+use std::fmt;
+use std::fmt::Debug;
 use std::num::NonZeroUsize;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -41,9 +43,11 @@ use ruff_source_file::SourceLocation;
 use ruff_text_size::TextRange;
 use ruff_text_size::TextSize;
 
+use crate::alt::class::plugin::ClassSynthesisPlugin;
 use crate::binding::binding::KeyExport;
 use crate::config::base::UntypedDefBehavior;
 use crate::config::config::ConfigFile;
+use crate::config::config::ImportRule;
 use crate::config::finder::ConfigFinder;
 use crate::error::error::print_errors;
 use crate::module::finder::find_import;
@@ -97,6 +101,26 @@ fn default_path(module: ModuleName) -> PathBuf {
     PathBuf::from(format!("{}.py", module.as_str().replace('.', "/")))
 }
 
+/// Class synthesis plugins to register on the `State`, compared by identity.
+#[derive(Clone, Default)]
+struct TestPlugins(Vec<Arc<dyn ClassSynthesisPlugin>>);
+
+impl Debug for TestPlugins {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.0.iter().map(|x| x.name()))
+            .finish()
+    }
+}
+
+impl PartialEq for TestPlugins {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.len() == other.0.len() && self.0.iter().zip(&other.0).all(|(x, y)| Arc::ptr_eq(x, y))
+    }
+}
+
+impl Eq for TestPlugins {}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct TestEnv {
     modules: Vec<(ModuleName, ModulePath, Option<Arc<FileContents>>)>,
@@ -112,6 +136,12 @@ pub struct TestEnv {
     implicit_abstract_class_error: bool,
     open_unpacking_error: bool,
     missing_override_decorator_error: bool,
+    non_exhaustive_match_error: bool,
+    unreachable_code_error: bool,
+    unknown_fixture_error: bool,
+    unused_error: bool,
+    import_rules: Vec<ImportRule>,
+    class_synthesis_plugins: TestPlugins,
     default_require_level: Require,
 }
 
@@ -133,6 +163,12 @@ impl TestEnv {
             implicit_abstract_class_error: false,
             open_unpacking_error: false,
             missing_override_decorator_error: false,
+            non_exhaustive_match_error: false,
+            unreachable_code_error: false,
+            unknown_fixture_error: false,
+            unused_error: false,
+            import_rules: Vec::new(),
+            class_synthesis_plugins: TestPlugins::default(),
             default_require_level: Require::Exports,
         }
     }
@@ -201,6 +237,38 @@ impl TestEnv {
         self
     }
 
+    pub fn enable_non_exhaustive_match_error(mut self) -> Self {
+        self.non_exhaustive_match_error = true;
+        self
+    }
+
+    pub fn enable_unreachable_code_error(mut self) -> Self {
+        self.unreachable_code_error = true;
+        self
+    }
+
+    pub fn enable_unknown_fixture_error(mut self) -> Self {
+        self.unknown_fixture_error = true;
+        self
+    }
+
+    /// Enable `unused-import`, `unused-parameter`, `unused-variable` and
+    /// `unused-private-function` errors.
+    pub fn enable_unused_error(mut self) -> Self {
+        self.unused_error = true;
+        self
+    }
+
+    pub fn with_class_synthesis_plugin(mut self, plugin: Arc<dyn ClassSynthesisPlugin>) -> Self {
+        self.class_synthesis_plugins.0.push(plugin);
+        self
+    }
+
+    pub fn with_import_rules(mut self, import_rules: Vec<ImportRule>) -> Self {
+        self.import_rules = import_rules;
+        self
+    }
+
     pub fn with_default_require_level(mut self, level: Require) -> Self {
         self.default_require_level = level;
         self
@@ -300,6 +368,22 @@ impl TestEnv {
         if self.missing_override_decorator_error {
             errors.set_error_severity(ErrorKind::MissingOverrideDecorator, Severity::Error);
         }
+        if self.non_exhaustive_match_error {
+            errors.set_error_severity(ErrorKind::NonExhaustiveMatch, Severity::Error);
+        }
+        if self.unreachable_code_error {
+            errors.set_error_severity(ErrorKind::UnreachableCode, Severity::Error);
+        }
+        if self.unknown_fixture_error {
+            errors.set_error_severity(ErrorKind::UnknownFixture, Severity::Error);
+        }
+        if self.unused_error {
+            errors.set_error_severity(ErrorKind::UnusedImport, Severity::Error);
+            errors.set_error_severity(ErrorKind::UnusedParameter, Severity::Error);
+            errors.set_error_severity(ErrorKind::UnusedVariable, Severity::Error);
+            errors.set_error_severity(ErrorKind::UnusedPrivateFunction, Severity::Error);
+        }
+        config.import_rules = self.import_rules.clone();
         let mut sourcedb = MapDatabase::new(config.get_sys_info());
         for (name, path, _) in self.modules.iter() {
             sourcedb.insert(*name, path.dupe());
@@ -325,7 +409,10 @@ impl TestEnv {
             .rev()
             .map(|(x, path, _)| Handle::new(*x, path.dupe(), config.dupe()))
             .collect::<Vec<_>>();
-        let state = State::new(self.config_finder());
+        let mut state = State::new(self.config_finder());
+        for plugin in &self.class_synthesis_plugins.0 {
+            state.register_class_synthesis_plugin(plugin.dupe());
+        }
         let subscriber = TestSubscriber::new();
         let mut transaction = state.new_committable_transaction(
             self.default_require_level,
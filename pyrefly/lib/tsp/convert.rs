@@ -0,0 +1,327 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Conversions between Pyrefly's types and the TSP protocol types
+
+use dupe::Dupe;
+use lsp_server::ResponseError;
+use lsp_types::Url;
+use pyrefly_build::handle::Handle;
+use pyrefly_python::module::Module;
+use pyrefly_python::module_name::ModuleName;
+use pyrefly_python::symbol_kind::SymbolKind;
+use ruff_python_ast::name::Name;
+use ruff_text_size::TextRange;
+use tsp_types as tsp;
+
+use crate::lsp::module_helpers::to_real_path;
+use crate::lsp::non_wasm::server::TspInterface;
+use crate::state::state::Transaction;
+use crate::tsp::handles::DeclarationInfo;
+use crate::tsp::server::TspServer;
+use crate::types::types::BoundMethodType;
+use crate::types::types::Type;
+
+pub fn to_tsp_module_name(module: ModuleName) -> tsp::ModuleName {
+    tsp::ModuleName {
+        leading_dots: 0,
+        name_parts: module
+            .components()
+            .into_iter()
+            .map(|x| x.to_string())
+            .collect(),
+    }
+}
+
+/// Resolve a (possibly relative) TSP module name, as seen from `handle`.
+pub fn from_tsp_module_name(handle: &Handle, module: &tsp::ModuleName) -> Option<ModuleName> {
+    let suffix = if module.name_parts.is_empty() {
+        None
+    } else {
+        Some(Name::new(module.name_parts.join(".")))
+    };
+    handle.module().new_maybe_relative(
+        handle.path().is_init(),
+        u32::try_from(module.leading_dots).ok()?,
+        suffix.as_ref(),
+    )
+}
+
+pub fn module_to_uri(module: &Module) -> Option<String> {
+    Some(
+        Url::from_file_path(to_real_path(module.path())?)
+            .ok()?
+            .to_string(),
+    )
+}
+
+pub fn to_tsp_node(module: &Module, range: TextRange) -> Option<tsp::Node> {
+    Some(tsp::Node {
+        range: tsp::from_lsp_range(module.to_lsp_range(range)),
+        uri: module_to_uri(module)?,
+    })
+}
+
+pub fn declaration_category(kind: Option<SymbolKind>) -> tsp::DeclarationCategory {
+    match kind {
+        Some(SymbolKind::Module) => tsp::DeclarationCategory::Import,
+        Some(SymbolKind::Parameter) => tsp::DeclarationCategory::Param,
+        Some(SymbolKind::TypeParameter) => tsp::DeclarationCategory::TypeParam,
+        Some(SymbolKind::TypeAlias) => tsp::DeclarationCategory::TypeAlias,
+        Some(SymbolKind::Function | SymbolKind::Method) => tsp::DeclarationCategory::Function,
+        Some(SymbolKind::Class) => tsp::DeclarationCategory::Class,
+        Some(SymbolKind::Attribute | SymbolKind::Variable | SymbolKind::Constant) | None => {
+            tsp::DeclarationCategory::Variable
+        }
+    }
+}
+
+fn declaration_flags(kind: Option<SymbolKind>) -> tsp::DeclarationFlags {
+    match kind {
+        Some(SymbolKind::Attribute | SymbolKind::Method) => tsp::DeclarationFlags::CLASS_MEMBER,
+        Some(SymbolKind::Constant) => tsp::DeclarationFlags::CONSTANT,
+        _ => tsp::DeclarationFlags::NONE,
+    }
+}
+
+/// The category, flags and category flags of a type.
+fn type_category(ty: &Type) -> (tsp::TypeCategory, tsp::TypeFlags, i32) {
+    let instance = tsp::TypeFlags::INSTANCE;
+    match ty {
+        Type::Function(_) | Type::Callable(_) => (
+            tsp::TypeCategory::Function,
+            instance | tsp::TypeFlags::CALLABLE,
+            0,
+        ),
+        Type::BoundMethod(m) => match &m.func {
+            BoundMethodType::Overload(_) => (
+                tsp::TypeCategory::Overloaded,
+                instance | tsp::TypeFlags::CALLABLE,
+                0,
+            ),
+            BoundMethodType::Function(_) | BoundMethodType::Forall(_) => (
+                tsp::TypeCategory::Function,
+                instance | tsp::TypeFlags::CALLABLE,
+                0,
+            ),
+        },
+        Type::Overload(_) => (
+            tsp::TypeCategory::Overloaded,
+            instance | tsp::TypeFlags::CALLABLE,
+            0,
+        ),
+        Type::Forall(forall) => {
+            let (category, flags, category_flags) = type_category(&forall.body.clone().as_type());
+            (category, flags | tsp::TypeFlags::GENERIC, category_flags)
+        }
+        Type::ClassDef(_) => (tsp::TypeCategory::Class, tsp::TypeFlags::INSTANTIABLE, 0),
+        Type::ClassType(c) | Type::SelfType(c) => (
+            tsp::TypeCategory::Class,
+            if c.targs().is_empty() {
+                instance
+            } else {
+                instance | tsp::TypeFlags::GENERIC
+            },
+            0,
+        ),
+        Type::TypedDict(_) | Type::PartialTypedDict(_) => (
+            tsp::TypeCategory::Class,
+            instance,
+            tsp::ClassFlags::TYPED_DICT.0,
+        ),
+        Type::Literal(_) => (
+            tsp::TypeCategory::Class,
+            instance | tsp::TypeFlags::LITERAL,
+            0,
+        ),
+        Type::LiteralString | Type::Tuple(_) | Type::None | Type::Ellipsis => {
+            (tsp::TypeCategory::Class, instance, 0)
+        }
+        Type::Module(_) => (tsp::TypeCategory::Module, tsp::TypeFlags::NONE, 0),
+        Type::Union(_) => (tsp::TypeCategory::Union, tsp::TypeFlags::NONE, 0),
+        Type::TypeVar(_) | Type::Quantified(_) | Type::QuantifiedValue(_) => {
+            (tsp::TypeCategory::TypeVar, tsp::TypeFlags::NONE, 0)
+        }
+        Type::ParamSpec(_) => (
+            tsp::TypeCategory::TypeVar,
+            tsp::TypeFlags::NONE,
+            tsp::TypeVarFlags::IS_PARAM_SPEC.0,
+        ),
+        Type::TypeVarTuple(_) => (
+            tsp::TypeCategory::TypeVar,
+            tsp::TypeFlags::NONE,
+            tsp::TypeVarFlags::IS_TYPE_VAR_TUPLE.0,
+        ),
+        Type::Type(inner) => {
+            let (category, flags, category_flags) = type_category(inner);
+            let flags = if flags.contains(instance) {
+                tsp::TypeFlags(flags.0 & !instance.0) | tsp::TypeFlags::INSTANTIABLE
+            } else {
+                flags
+            };
+            (category, flags, category_flags)
+        }
+        Type::TypeAlias(alias) => {
+            let (category, flags, category_flags) = type_category(&alias.as_type());
+            (category, flags | tsp::TypeFlags::FROM_ALIAS, category_flags)
+        }
+        Type::Unpack(inner) => {
+            let (category, flags, category_flags) = type_category(inner);
+            (category, flags | tsp::TypeFlags::UNPACKED, category_flags)
+        }
+        _ => (tsp::TypeCategory::Any, tsp::TypeFlags::NONE, 0),
+    }
+}
+
+/// The simple name of a type, e.g. `MyClass` rather than `my_module.MyClass[int]`.
+fn type_name(ty: &Type) -> String {
+    match ty {
+        Type::Function(f) => f.metadata.kind.function_name().to_string(),
+        Type::Overload(o) => o.metadata.kind.function_name().to_string(),
+        Type::Module(m) => m
+            .parts()
+            .iter()
+            .map(|x| x.as_str())
+            .collect::<Vec<_>>()
+            .join("."),
+        Type::Type(inner) | Type::Unpack(inner) => type_name(inner),
+        Type::TypeAlias(alias) => alias.name.to_string(),
+        _ => match ty.qname() {
+            Some(qname) => qname.id().to_string(),
+            None => ty.to_string(),
+        },
+    }
+}
+
+/// The module a type comes from, if it has one.
+fn type_module(ty: &Type) -> Option<ModuleName> {
+    match ty {
+        Type::Function(f) => Some(f.metadata.kind.module_name()),
+        Type::Overload(o) => Some(o.metadata.kind.module_name()),
+        Type::Type(inner) | Type::Unpack(inner) => type_module(inner),
+        _ => ty.qname().map(|x| x.module_name()),
+    }
+}
+
+impl<T: TspInterface> TspServer<T> {
+    /// Convert a type, registering a handle for it in the current snapshot.
+    ///
+    /// `handle` is the module the type was requested from, which is used to resolve the
+    /// declaration of the type.
+    pub(crate) fn to_tsp_type(&self, handle: &Handle, ty: Type) -> tsp::Type {
+        let (category, flags, category_flags) = type_category(&ty);
+        let name = type_name(&ty);
+        let alias_name = match &ty {
+            Type::TypeAlias(alias) => Some(alias.name.to_string()),
+            _ => None,
+        };
+        let module_name = type_module(&ty).map(to_tsp_module_name);
+        let decl = self.type_declaration(handle, &ty);
+        let type_handle = self
+            .handles()
+            .add_type(self.get_snapshot(), handle.dupe(), ty);
+        tsp::Type {
+            alias_name,
+            category,
+            category_flags,
+            decl,
+            flags,
+            handle: type_handle,
+            module_name,
+            name,
+        }
+    }
+
+    /// Look up a type that was previously handed out to the client, along with the module it
+    /// was requested from.
+    pub(crate) fn from_tsp_type(&self, ty: &tsp::Type) -> Result<(Handle, Type), ResponseError> {
+        self.handles()
+            .get_type(self.get_snapshot(), &ty.handle)
+            .cloned()
+            .ok_or_else(|| tsp::create_internal_error("Unknown type handle"))
+    }
+
+    /// Convert a declaration, registering a handle for it for the rest of the session.
+    pub(crate) fn to_tsp_declaration(
+        &self,
+        name: &str,
+        kind: Option<SymbolKind>,
+        info: DeclarationInfo,
+    ) -> Option<tsp::Declaration> {
+        let uri = module_to_uri(&info.module)?;
+        let node = to_tsp_node(&info.module, info.range);
+        let module_name = to_tsp_module_name(info.module.name());
+        Some(tsp::Declaration {
+            category: declaration_category(kind),
+            flags: declaration_flags(kind),
+            handle: self.handles().add_declaration(info),
+            module_name,
+            name: name.to_owned(),
+            node,
+            uri,
+        })
+    }
+
+    /// Look up a declaration that was previously handed out to the client.
+    pub(crate) fn from_tsp_declaration(
+        &self,
+        decl: &tsp::Declaration,
+    ) -> Result<DeclarationInfo, ResponseError> {
+        self.handles()
+            .get_declaration(&decl.handle)
+            .cloned()
+            .ok_or_else(|| tsp::create_internal_error("Unknown declaration handle"))
+    }
+
+    /// Create a handle for the file at `uri`.
+    pub(crate) fn handle_for_uri(&self, uri: &str) -> Result<Handle, ResponseError> {
+        let uri = Url::parse(uri).map_err(|e| tsp::create_internal_error(&e.to_string()))?;
+        self.inner
+            .make_handle_if_enabled(&uri, None)
+            .ok_or_else(tsp::language_services_disabled_error)
+    }
+
+    /// Resolve a node to the module containing it and its range within that module.
+    pub(crate) fn resolve_node(
+        &self,
+        transaction: &Transaction,
+        node: &tsp::Node,
+    ) -> Result<(Handle, Module, TextRange), ResponseError> {
+        let handle = self.handle_for_uri(&node.uri)?;
+        let module = transaction
+            .get_module_info(&handle)
+            .ok_or_else(|| tsp::create_internal_error("File is not loaded"))?;
+        let range = module.from_lsp_range(tsp::to_lsp_range(&node.range), None);
+        Ok((handle, module, range))
+    }
+
+    fn type_declaration(&self, handle: &Handle, ty: &Type) -> Option<tsp::Declaration> {
+        let (qname, kind) = match ty {
+            Type::ClassDef(cls) => (cls.qname(), SymbolKind::Class),
+            Type::Type(inner) => return self.type_declaration(handle, inner),
+            Type::TypeVar(_) | Type::ParamSpec(_) | Type::TypeVarTuple(_) => {
+                (ty.qname()?, SymbolKind::TypeParameter)
+            }
+            _ => (ty.qname()?, SymbolKind::Class),
+        };
+        let module = qname.module().dupe();
+        self.to_tsp_declaration(
+            qname.id().as_str(),
+            Some(kind),
+            DeclarationInfo {
+                handle: Handle::new(
+                    module.name(),
+                    module.path().dupe(),
+                    handle.sys_info().dupe(),
+                ),
+                module,
+                range: qname.range(),
+                docstring_range: None,
+            },
+        )
+    }
+}
@@ -0,0 +1,166 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Tracking of the type and declaration handles given out to TSP clients.
+//!
+//! Clients refer back to types and declarations by handle, so we remember what each handle
+//! stands for. Type handles are only valid within the snapshot they were created in, and are
+//! dropped as soon as a handle from a newer snapshot is created. Within a snapshot, the same type
+//! requested from the same module always gets the same handle, so repeated queries don't grow
+//! the table. Declaration handles are valid for the whole session, and the same declaration
+//! always gets the same handle.
+
+use std::collections::HashMap;
+
+use dupe::Dupe;
+use pyrefly_build::handle::Handle;
+use pyrefly_python::module::Module;
+use pyrefly_python::module_path::ModulePath;
+use ruff_text_size::TextRange;
+use tsp_types as tsp;
+
+use crate::types::types::Type;
+
+/// Everything we know about a declaration that has been handed out to a client.
+#[derive(Debug, Clone)]
+pub struct DeclarationInfo {
+    /// A handle for the module containing the declaration, used to answer follow-up queries.
+    pub handle: Handle,
+    pub module: Module,
+    pub range: TextRange,
+    pub docstring_range: Option<TextRange>,
+}
+
+#[derive(Debug, Default)]
+pub struct TspHandles {
+    /// The snapshot that `types` were created in.
+    snapshot: i32,
+    /// Each type, along with the module it was requested from.
+    types: Vec<(Handle, Type)>,
+    type_ids: HashMap<(Handle, Type), usize>,
+    declarations: Vec<DeclarationInfo>,
+    declaration_ids: HashMap<(ModulePath, TextRange), usize>,
+}
+
+impl TspHandles {
+    pub fn add_type(&mut self, snapshot: i32, handle: Handle, ty: Type) -> tsp::TypeHandle {
+        if snapshot != self.snapshot {
+            self.snapshot = snapshot;
+            self.types.clear();
+            self.type_ids.clear();
+        }
+        let key = (handle, ty);
+        let id = match self.type_ids.get(&key) {
+            Some(id) => *id,
+            None => {
+                self.types.push(key.clone());
+                self.type_ids.insert(key, self.types.len() - 1);
+                self.types.len() - 1
+            }
+        };
+        tsp::TypeHandle::Int(id as i32)
+    }
+
+    /// Look up a type handle, returning `None` if it is unknown or from an older snapshot.
+    pub fn get_type(&self, snapshot: i32, handle: &tsp::TypeHandle) -> Option<&(Handle, Type)> {
+        if snapshot != self.snapshot {
+            return None;
+        }
+        match handle {
+            tsp::TypeHandle::Int(i) => self.types.get(usize::try_from(*i).ok()?),
+            tsp::TypeHandle::String(_) => None,
+        }
+    }
+
+    pub fn add_declaration(&mut self, info: DeclarationInfo) -> tsp::DeclarationHandle {
+        let key = (info.module.path().dupe(), info.range);
+        let id = match self.declaration_ids.get(&key) {
+            Some(id) => {
+                // Keep the most recent view of the declaration.
+                self.declarations[*id] = info;
+                *id
+            }
+            None => {
+                self.declarations.push(info);
+                self.declaration_ids
+                    .insert(key, self.declarations.len() - 1);
+                self.declarations.len() - 1
+            }
+        };
+        tsp::DeclarationHandle::Int(id as i32)
+    }
+
+    pub fn get_declaration(&self, handle: &tsp::DeclarationHandle) -> Option<&DeclarationInfo> {
+        match handle {
+            tsp::DeclarationHandle::Int(i) => self.declarations.get(usize::try_from(*i).ok()?),
+            tsp::DeclarationHandle::String(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use pyrefly_python::module_name::ModuleName;
+    use ruff_text_size::TextSize;
+
+    use super::*;
+
+    fn test_module() -> Module {
+        Module::new(
+            ModuleName::from_str("foo"),
+            ModulePath::memory("foo.py".into()),
+            Arc::new("x = 1\n".to_owned()),
+        )
+    }
+
+    fn test_handle(module: &Module) -> Handle {
+        Handle::new(module.name(), module.path().dupe(), Default::default())
+    }
+
+    #[test]
+    fn test_type_handles_expire_with_snapshot() {
+        let mut handles = TspHandles::default();
+        let handle = test_handle(&test_module());
+        let h = handles.add_type(1, handle.dupe(), Type::None);
+        assert_eq!(handles.get_type(1, &h).map(|(_, ty)| ty), Some(&Type::None));
+        assert!(handles.get_type(2, &h).is_none());
+        handles.add_type(2, handle, Type::any_implicit());
+        assert!(handles.get_type(1, &h).is_none());
+    }
+
+    #[test]
+    fn test_type_handles_are_interned() {
+        let mut handles = TspHandles::default();
+        let handle = test_handle(&test_module());
+        let a = handles.add_type(1, handle.dupe(), Type::None);
+        let b = handles.add_type(1, handle.dupe(), Type::any_implicit());
+        assert_ne!(a, b);
+        assert_eq!(handles.add_type(1, handle, Type::None), a);
+        assert_eq!(handles.types.len(), 2);
+    }
+
+    #[test]
+    fn test_declaration_handles_are_stable() {
+        let mut handles = TspHandles::default();
+        let module = test_module();
+        let info = |range| DeclarationInfo {
+            handle: test_handle(&module),
+            module: module.dupe(),
+            range,
+            docstring_range: None,
+        };
+        let x = TextRange::new(TextSize::new(0), TextSize::new(1));
+        let one = TextRange::new(TextSize::new(4), TextSize::new(5));
+        let a = handles.add_declaration(info(x));
+        let b = handles.add_declaration(info(one));
+        assert_ne!(a, b);
+        assert_eq!(handles.add_declaration(info(x)), a);
+        assert_eq!(handles.get_declaration(&b).map(|d| d.range), Some(one));
+    }
+}
@@ -5,5 +5,7 @@
  * LICENSE file in the root directory of this source tree.
  */
 
+pub mod convert;
+pub mod handles;
 pub mod requests;
 pub mod server;
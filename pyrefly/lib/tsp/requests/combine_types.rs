@@ -0,0 +1,33 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Implementation of the combineTypes TSP request
+
+use lsp_server::ResponseError;
+use tsp_types as tsp;
+use tsp_types::CombineTypesParams;
+
+use crate::lsp::non_wasm::server::TspInterface;
+use crate::tsp::server::TspServer;
+use crate::types::simplify::unions;
+
+impl<T: TspInterface> TspServer<T> {
+    /// Combine types into their union.
+    pub fn combine_types(
+        &self,
+        params: CombineTypesParams,
+    ) -> Result<Option<tsp::Type>, ResponseError> {
+        let mut handle = None;
+        let mut types = Vec::with_capacity(params.types.len());
+        for ty in &params.types {
+            let (h, ty) = self.from_tsp_type(ty)?;
+            handle.get_or_insert(h);
+            types.push(ty);
+        }
+        Ok(handle.map(|handle| self.to_tsp_type(&handle, unions(types))))
+    }
+}
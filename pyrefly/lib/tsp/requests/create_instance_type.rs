@@ -0,0 +1,35 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Implementation of the createInstanceType TSP request
+
+use lsp_server::ResponseError;
+use tsp_types as tsp;
+use tsp_types::CreateInstanceTypeParams;
+
+use crate::lsp::non_wasm::server::TspInterface;
+use crate::state::state::Transaction;
+use crate::tsp::server::TspServer;
+use crate::types::types::Type;
+
+impl<T: TspInterface> TspServer<T> {
+    pub fn create_instance_type(
+        &self,
+        transaction: &Transaction<'_>,
+        params: CreateInstanceTypeParams,
+    ) -> Result<tsp::Type, ResponseError> {
+        let (handle, ty) = self.from_tsp_type(&params.type_)?;
+        let instance = match ty {
+            Type::ClassDef(cls) => transaction
+                .ad_hoc_solve(&handle, |solver| solver.instantiate(&cls))
+                .unwrap_or_else(|| Type::ClassDef(cls)),
+            Type::Type(inner) => *inner,
+            ty => ty,
+        };
+        Ok(self.to_tsp_type(&handle, instance))
+    }
+}
@@ -0,0 +1,44 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Implementation of the getBuiltinType TSP request
+
+use lsp_server::ResponseError;
+use pyrefly_python::module_name::ModuleName;
+use ruff_python_ast::name::Name;
+use tsp_types as tsp;
+use tsp_types::GetBuiltinTypeParams;
+
+use crate::lsp::non_wasm::server::TspInterface;
+use crate::state::state::Transaction;
+use crate::tsp::server::TspServer;
+use crate::types::module::ModuleType;
+use crate::types::types::Type;
+
+impl<T: TspInterface> TspServer<T> {
+    pub fn get_builtin_type(
+        &self,
+        transaction: &Transaction<'_>,
+        params: GetBuiltinTypeParams,
+    ) -> Result<Option<tsp::Type>, ResponseError> {
+        let (handle, _, _) = self.resolve_node(transaction, &params.scoping_node)?;
+        let name = Name::new(&params.name);
+        let ty = transaction
+            .ad_hoc_solve(&handle, |solver| {
+                solver.completions(
+                    Type::Module(ModuleType::new_as(ModuleName::builtins())),
+                    Some(&name),
+                    true,
+                )
+            })
+            .unwrap_or_default()
+            .into_iter()
+            .find(|attr| attr.name == name)
+            .and_then(|attr| attr.ty);
+        Ok(ty.map(|ty| self.to_tsp_type(&handle, ty)))
+    }
+}
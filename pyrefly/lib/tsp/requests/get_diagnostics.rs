@@ -0,0 +1,44 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Implementation of the getDiagnostics TSP request
+
+use lsp_server::ResponseError;
+use tsp_types as tsp;
+use tsp_types::GetDiagnosticsParams;
+
+use crate::config::error_kind::Severity;
+use crate::lsp::non_wasm::server::TspInterface;
+use crate::state::state::Transaction;
+use crate::tsp::server::TspServer;
+
+impl<T: TspInterface> TspServer<T> {
+    pub fn get_diagnostics(
+        &self,
+        transaction: &Transaction<'_>,
+        params: GetDiagnosticsParams,
+    ) -> Result<Vec<tsp::Diagnostic>, ResponseError> {
+        let handle = self.handle_for_uri(&params.uri)?;
+        Ok(transaction
+            .get_errors([&handle])
+            .collect_errors()
+            .shown
+            .iter()
+            .map(|e| tsp::Diagnostic {
+                code: Some(tsp::OR2::U(e.error_kind().to_name().to_owned())),
+                message: e.msg(),
+                range: tsp::from_lsp_range(e.module().to_lsp_range(e.range())),
+                severity: Some(match e.severity() {
+                    Severity::Error => 1,
+                    Severity::Warn => 2,
+                    Severity::Info | Severity::Ignore => 3,
+                }),
+                source: Some("Pyrefly".to_owned()),
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,21 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Implementation of the getDiagnosticsVersion TSP request
+
+use tsp_types::GetDiagnosticsVersionParams;
+
+use crate::lsp::non_wasm::server::TspInterface;
+use crate::tsp::server::TspServer;
+
+impl<T: TspInterface> TspServer<T> {
+    /// Diagnostics can only change when the snapshot does, so the snapshot doubles as the
+    /// version of the diagnostics for every file.
+    pub fn get_diagnostics_version(&self, _params: GetDiagnosticsVersionParams) -> i32 {
+        self.get_snapshot()
+    }
+}
@@ -0,0 +1,34 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Implementation of the getDocstring TSP request
+
+use lsp_server::ResponseError;
+use pyrefly_python::docstring::Docstring;
+use tsp_types::GetDocstringParams;
+
+use crate::lsp::non_wasm::server::TspInterface;
+use crate::state::lsp::FindPreference;
+use crate::state::state::Transaction;
+use crate::tsp::server::TspServer;
+
+impl<T: TspInterface> TspServer<T> {
+    pub fn get_docstring(
+        &self,
+        transaction: &Transaction<'_>,
+        params: GetDocstringParams,
+    ) -> Result<Option<String>, ResponseError> {
+        let info = self.from_tsp_declaration(&params.decl)?;
+        if let Some(range) = info.docstring_range {
+            return Ok(Some(Docstring(range, info.module).resolve()));
+        }
+        Ok(transaction
+            .find_definition(&info.handle, info.range.start(), FindPreference::default())
+            .into_iter()
+            .find_map(|def| Some(Docstring(def.docstring_range?, def.module).resolve())))
+    }
+}
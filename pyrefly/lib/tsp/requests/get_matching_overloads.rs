@@ -0,0 +1,59 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Implementation of the getMatchingOverloads TSP request
+
+use lsp_server::ResponseError;
+use pyrefly_python::ast::Ast;
+use ruff_python_ast::AnyNodeRef;
+use ruff_text_size::Ranged;
+use tsp_types as tsp;
+use tsp_types::GetMatchingOverloadsParams;
+
+use crate::lsp::non_wasm::server::TspInterface;
+use crate::state::state::Transaction;
+use crate::tsp::requests::get_overloads::overloads;
+use crate::tsp::server::TspServer;
+
+impl<T: TspInterface> TspServer<T> {
+    pub fn get_matching_overloads(
+        &self,
+        transaction: &Transaction<'_>,
+        params: GetMatchingOverloadsParams,
+    ) -> Result<Vec<tsp::Type>, ResponseError> {
+        let (handle, _, range) = self.resolve_node(transaction, &params.call_node)?;
+        let Some(ast) = transaction.get_ast(&handle) else {
+            return Ok(Vec::new());
+        };
+        let calls = Ast::locate_node(&ast, range.start())
+            .into_iter()
+            .filter_map(|node| match node {
+                AnyNodeRef::ExprCall(call) => Some(call),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let Some(call) = calls
+            .iter()
+            .find(|call| call.range() == range)
+            .or(calls.first())
+        else {
+            return Ok(Vec::new());
+        };
+        let matching = match transaction.get_chosen_overload_trace(&handle, call.arguments.range) {
+            Some(chosen) => vec![chosen],
+            // If no single overload was chosen, every overload is a candidate.
+            None => transaction
+                .get_type_trace(&handle, call.func.range())
+                .map(|callee| overloads(&callee))
+                .unwrap_or_default(),
+        };
+        Ok(matching
+            .into_iter()
+            .map(|ty| self.to_tsp_type(&handle, ty))
+            .collect())
+    }
+}
@@ -0,0 +1,49 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Implementation of the getMetaclass TSP request
+
+use lsp_server::ResponseError;
+use tsp_types as tsp;
+use tsp_types::GetMetaclassParams;
+
+use crate::lsp::non_wasm::server::TspInterface;
+use crate::state::state::Transaction;
+use crate::tsp::server::TspServer;
+use crate::types::types::Type;
+
+impl<T: TspInterface> TspServer<T> {
+    pub fn get_metaclass(
+        &self,
+        transaction: &Transaction<'_>,
+        params: GetMetaclassParams,
+    ) -> Result<Option<tsp::Type>, ResponseError> {
+        let (handle, ty) = self.from_tsp_type(&params.type_)?;
+        let cls = match &ty {
+            Type::ClassDef(cls) => cls.clone(),
+            Type::ClassType(c) | Type::SelfType(c) => c.class_object().clone(),
+            Type::Type(inner) if let Type::ClassType(c) = &**inner => c.class_object().clone(),
+            _ => return Ok(None),
+        };
+        let metaclass = transaction
+            .ad_hoc_solve(&handle, |solver| {
+                solver
+                    .get_metadata_for_class(&cls)
+                    .custom_metaclass()
+                    .map(|m| m.class_object().clone())
+            })
+            .flatten()
+            .unwrap_or_else(|| {
+                transaction
+                    .get_stdlib(&handle)
+                    .builtins_type()
+                    .class_object()
+                    .clone()
+            });
+        Ok(Some(self.to_tsp_type(&handle, Type::ClassDef(metaclass))))
+    }
+}
@@ -0,0 +1,44 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Implementation of the getOverloads TSP request
+
+use lsp_server::ResponseError;
+use tsp_types as tsp;
+use tsp_types::GetOverloadsParams;
+
+use crate::lsp::non_wasm::server::TspInterface;
+use crate::tsp::server::TspServer;
+use crate::types::types::BoundMethodType;
+use crate::types::types::Type;
+
+/// The signatures of an overloaded function, not including the implementation.
+pub(crate) fn overloads(ty: &Type) -> Vec<Type> {
+    match ty {
+        Type::Overload(overload) => overload.signatures.iter().map(|x| x.as_type()).collect(),
+        Type::BoundMethod(method) => match &method.func {
+            BoundMethodType::Overload(overload) => {
+                overload.signatures.iter().map(|x| x.as_type()).collect()
+            }
+            BoundMethodType::Function(_) | BoundMethodType::Forall(_) => Vec::new(),
+        },
+        _ => Vec::new(),
+    }
+}
+
+impl<T: TspInterface> TspServer<T> {
+    pub fn get_overloads(
+        &self,
+        params: GetOverloadsParams,
+    ) -> Result<Vec<tsp::Type>, ResponseError> {
+        let (handle, ty) = self.from_tsp_type(&params.type_)?;
+        Ok(overloads(&ty)
+            .into_iter()
+            .map(|ty| self.to_tsp_type(&handle, ty))
+            .collect())
+    }
+}
@@ -0,0 +1,36 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Implementation of the getPythonSearchPaths TSP request
+
+use lsp_server::ResponseError;
+use tsp_types::GetPythonSearchPathsParams;
+
+use crate::lsp::non_wasm::server::TspInterface;
+use crate::state::state::Transaction;
+use crate::tsp::server::TspServer;
+
+impl<T: TspInterface> TspServer<T> {
+    /// The directories imports are resolved from for the given file, in priority order.
+    pub fn get_python_search_paths(
+        &self,
+        transaction: &Transaction<'_>,
+        params: GetPythonSearchPathsParams,
+    ) -> Result<Vec<String>, ResponseError> {
+        let handle = self.handle_for_uri(&params.from_uri)?;
+        let config = transaction.get_config(&handle).unwrap_or_else(|| {
+            transaction
+                .config_finder()
+                .python_file(handle.module(), handle.path())
+        });
+        Ok(config
+            .search_path()
+            .chain(config.site_package_path())
+            .map(|path| path.to_string_lossy().into_owned())
+            .collect())
+    }
+}
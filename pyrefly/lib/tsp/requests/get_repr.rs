@@ -0,0 +1,44 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Implementation of the getRepr TSP request
+
+use lsp_server::ResponseError;
+use tsp_types::GetReprParams;
+
+use crate::lsp::non_wasm::server::TspInterface;
+use crate::state::state::Transaction;
+use crate::tsp::server::TspServer;
+use crate::types::types::Type;
+
+impl<T: TspInterface> TspServer<T> {
+    pub fn get_repr(
+        &self,
+        transaction: &Transaction<'_>,
+        params: GetReprParams,
+    ) -> Result<String, ResponseError> {
+        let (handle, mut ty) = self.from_tsp_type(&params.type_)?;
+        if params.flags.has_expand_type_aliases()
+            && let Type::TypeAlias(alias) = &ty
+        {
+            ty = match alias.as_type() {
+                Type::Type(inner) => *inner,
+                ty => ty,
+            };
+        }
+        if params.flags.has_convert_to_instance_type() {
+            ty = match ty {
+                Type::ClassDef(cls) => transaction
+                    .ad_hoc_solve(&handle, |solver| solver.instantiate(&cls))
+                    .unwrap_or_else(|| Type::ClassDef(cls)),
+                Type::Type(inner) => *inner,
+                ty => ty,
+            };
+        }
+        Ok(ty.to_string())
+    }
+}
@@ -0,0 +1,68 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Implementation of the getSymbolsForNode TSP request
+
+use dupe::Dupe;
+use lsp_server::ResponseError;
+use tsp_types as tsp;
+use tsp_types::GetSymbolsForNodeParams;
+
+use crate::export::exports::ExportLocation;
+use crate::lsp::non_wasm::server::TspInterface;
+use crate::state::state::Transaction;
+use crate::tsp::handles::DeclarationInfo;
+use crate::tsp::server::TspServer;
+use crate::types::types::Type;
+
+impl<T: TspInterface> TspServer<T> {
+    /// Get the symbols visible at the top level of the module containing the node.
+    pub fn get_symbols_for_node(
+        &self,
+        transaction: &Transaction<'_>,
+        params: GetSymbolsForNodeParams,
+    ) -> Result<Vec<tsp::Symbol>, ResponseError> {
+        let (handle, module, _) = self.resolve_node(transaction, &params.node)?;
+        let exports = transaction.get_exports(&handle);
+        let mut symbols = Vec::new();
+        for (name, location) in exports.iter() {
+            if let Some(wanted) = &params.name
+                && name.as_str() != wanted
+            {
+                continue;
+            }
+            // Re-exports are reported by the module that defines them.
+            let ExportLocation::ThisModule(export) = location else {
+                continue;
+            };
+            let ty = transaction
+                .get_type_at(&handle, export.location.start())
+                .unwrap_or_else(Type::any_implicit);
+            let decls = self
+                .to_tsp_declaration(
+                    name.as_str(),
+                    export.symbol_kind,
+                    DeclarationInfo {
+                        handle: handle.dupe(),
+                        module: module.dupe(),
+                        range: export.location,
+                        docstring_range: export.docstring_range,
+                    },
+                )
+                .into_iter()
+                .collect();
+            symbols.push(tsp::Symbol {
+                decls,
+                flags: tsp::SymbolFlags::NONE,
+                name: name.to_string(),
+                parent: None,
+                type_: self.to_tsp_type(&handle, ty),
+            });
+        }
+        Ok(symbols)
+    }
+}
@@ -0,0 +1,89 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Implementation of the getSymbolsForType TSP request
+
+use dupe::Dupe;
+use lsp_server::ResponseError;
+use pyrefly_build::handle::Handle;
+use pyrefly_python::module::TextRangeWithModule;
+use pyrefly_python::symbol_kind::SymbolKind;
+use ruff_python_ast::name::Name;
+use tsp_types as tsp;
+use tsp_types::GetSymbolsForTypeParams;
+
+use crate::alt::attr::AttrDefinition;
+use crate::alt::attr::AttrInfo;
+use crate::lsp::non_wasm::server::TspInterface;
+use crate::state::state::Transaction;
+use crate::tsp::handles::DeclarationInfo;
+use crate::tsp::server::TspServer;
+use crate::types::types::Type;
+
+impl<T: TspInterface> TspServer<T> {
+    pub fn get_symbols_for_type(
+        &self,
+        transaction: &Transaction<'_>,
+        params: GetSymbolsForTypeParams,
+    ) -> Result<Vec<tsp::Symbol>, ResponseError> {
+        let (handle, ty) = self.from_tsp_type(&params.type_)?;
+        let name = params.name.as_deref().map(Name::new);
+        let attrs = transaction
+            .ad_hoc_solve(&handle, |solver| {
+                solver.completions(ty, name.as_ref(), true)
+            })
+            .unwrap_or_default();
+        Ok(attrs
+            .into_iter()
+            .filter(|attr| name.as_ref().is_none_or(|name| &attr.name == name))
+            .map(|attr| self.attr_to_symbol(&handle, attr, Some(params.type_.clone())))
+            .collect())
+    }
+
+    pub(crate) fn attr_to_symbol(
+        &self,
+        handle: &Handle,
+        attr: AttrInfo,
+        parent: Option<tsp::Type>,
+    ) -> tsp::Symbol {
+        let kind = match &attr.ty {
+            Some(Type::ClassDef(_)) => SymbolKind::Class,
+            Some(Type::Function(_) | Type::Overload(_) | Type::BoundMethod(_)) => {
+                SymbolKind::Method
+            }
+            Some(Type::Module(_)) => SymbolKind::Module,
+            _ => SymbolKind::Attribute,
+        };
+        let decls = match attr.definition {
+            Some(AttrDefinition::FullyResolved(TextRangeWithModule { module, range })) => self
+                .to_tsp_declaration(
+                    attr.name.as_str(),
+                    Some(kind),
+                    DeclarationInfo {
+                        handle: Handle::new(
+                            module.name(),
+                            module.path().dupe(),
+                            handle.sys_info().dupe(),
+                        ),
+                        module,
+                        range,
+                        docstring_range: attr.docstring_range,
+                    },
+                )
+                .into_iter()
+                .collect(),
+            _ => Vec::new(),
+        };
+        tsp::Symbol {
+            decls,
+            flags: tsp::SymbolFlags::NONE,
+            name: attr.name.to_string(),
+            parent,
+            type_: self.to_tsp_type(handle, attr.ty.unwrap_or_else(Type::any_implicit)),
+        }
+    }
+}
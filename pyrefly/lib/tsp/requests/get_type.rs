@@ -0,0 +1,32 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Implementation of the getType TSP request
+
+use lsp_server::ResponseError;
+use tsp_types as tsp;
+use tsp_types::GetTypeParams;
+
+use crate::lsp::non_wasm::server::TspInterface;
+use crate::state::state::Transaction;
+use crate::tsp::server::TspServer;
+
+impl<T: TspInterface> TspServer<T> {
+    pub fn get_type(
+        &self,
+        transaction: &Transaction<'_>,
+        params: GetTypeParams,
+    ) -> Result<Option<tsp::Type>, ResponseError> {
+        let (handle, _, range) = self.resolve_node(transaction, &params.node)?;
+        // Prefer the type of the expression spanning exactly this node, and fall back to the
+        // type of whatever identifier the node starts at.
+        let ty = transaction
+            .get_type_trace(&handle, range)
+            .or_else(|| transaction.get_type_at(&handle, range.start()));
+        Ok(ty.map(|ty| self.to_tsp_type(&handle, ty)))
+    }
+}
@@ -0,0 +1,35 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Implementation of the getTypeAliasInfo TSP request
+
+use lsp_server::ResponseError;
+use tsp_types as tsp;
+use tsp_types::GetTypeAliasInfoParams;
+
+use crate::lsp::non_wasm::server::TspInterface;
+use crate::tsp::server::TspServer;
+use crate::types::types::Forallable;
+use crate::types::types::Type;
+
+impl<T: TspInterface> TspServer<T> {
+    pub fn get_type_alias_info(
+        &self,
+        params: GetTypeAliasInfoParams,
+    ) -> Result<Option<tsp::TypeAliasInfo>, ResponseError> {
+        let (_, ty) = self.from_tsp_type(&params.type_)?;
+        let alias = match &ty {
+            Type::TypeAlias(alias) => alias,
+            Type::Forall(forall) if let Forallable::TypeAlias(alias) = &forall.body => alias,
+            _ => return Ok(None),
+        };
+        Ok(Some(tsp::TypeAliasInfo {
+            name: alias.name.to_string(),
+            type_args: None,
+        }))
+    }
+}
@@ -0,0 +1,52 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Implementation of the getTypeArgs TSP request
+
+use lsp_server::ResponseError;
+use tsp_types as tsp;
+use tsp_types::GetTypeArgsParams;
+
+use crate::lsp::non_wasm::server::TspInterface;
+use crate::tsp::server::TspServer;
+use crate::types::tuple::Tuple;
+use crate::types::types::Type;
+
+/// The members of a union, or the type arguments of a generic type.
+fn type_args(ty: &Type) -> Vec<Type> {
+    match ty {
+        Type::Union(u) => u.members.clone(),
+        Type::ClassType(c) | Type::SelfType(c) => c.targs().as_slice().to_vec(),
+        Type::Tuple(Tuple::Concrete(elts)) => elts.clone(),
+        Type::Tuple(Tuple::Unbounded(elt)) => vec![(**elt).clone()],
+        Type::Tuple(Tuple::Unpacked(unpacked)) => {
+            let (prefix, middle, suffix) = &**unpacked;
+            prefix
+                .iter()
+                .chain([middle])
+                .chain(suffix)
+                .cloned()
+                .collect()
+        }
+        Type::Type(inner) => type_args(inner),
+        Type::TypeAlias(alias) => type_args(&alias.as_type()),
+        _ => Vec::new(),
+    }
+}
+
+impl<T: TspInterface> TspServer<T> {
+    pub fn get_type_args(
+        &self,
+        params: GetTypeArgsParams,
+    ) -> Result<Vec<tsp::Type>, ResponseError> {
+        let (handle, ty) = self.from_tsp_type(&params.type_)?;
+        Ok(type_args(&ty)
+            .into_iter()
+            .map(|ty| self.to_tsp_type(&handle, ty))
+            .collect())
+    }
+}
@@ -0,0 +1,33 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Implementation of the getTypeOfDeclaration TSP request
+
+use lsp_server::ResponseError;
+use tsp_types as tsp;
+use tsp_types::GetTypeOfDeclarationParams;
+
+use crate::lsp::non_wasm::server::TspInterface;
+use crate::state::state::Transaction;
+use crate::tsp::server::TspServer;
+
+impl<T: TspInterface> TspServer<T> {
+    pub fn get_type_of_declaration(
+        &self,
+        transaction: &Transaction<'_>,
+        params: GetTypeOfDeclarationParams,
+    ) -> Result<tsp::Type, ResponseError> {
+        let info = self.from_tsp_declaration(&params.decl)?;
+        Ok(
+            match transaction.get_type_at(&info.handle, info.range.start()) {
+                Some(ty) => self.to_tsp_type(&info.handle, ty),
+                // The declaring module may not have been checked in enough detail to know.
+                None => tsp::create_default_type_for_declaration(&params.decl),
+            },
+        )
+    }
+}
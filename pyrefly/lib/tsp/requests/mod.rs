@@ -7,5 +7,24 @@
 
 //! TSP request implementations
 
+pub mod combine_types;
+pub mod create_instance_type;
+pub mod get_builtin_type;
+pub mod get_diagnostics;
+pub mod get_diagnostics_version;
+pub mod get_docstring;
+pub mod get_matching_overloads;
+pub mod get_metaclass;
+pub mod get_overloads;
+pub mod get_python_search_paths;
+pub mod get_repr;
 pub mod get_snapshot;
 pub mod get_supported_protocol_version;
+pub mod get_symbols_for_node;
+pub mod get_symbols_for_type;
+pub mod get_type;
+pub mod get_type_alias_info;
+pub mod get_type_args;
+pub mod get_type_of_declaration;
+pub mod resolve_import;
+pub mod resolve_import_declaration;
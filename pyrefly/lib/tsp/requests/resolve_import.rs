@@ -0,0 +1,36 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Implementation of the resolveImport TSP request
+
+use lsp_server::ResponseError;
+use tsp_types::ResolveImportParams;
+
+use crate::lsp::non_wasm::server::TspInterface;
+use crate::state::state::Transaction;
+use crate::tsp::convert::from_tsp_module_name;
+use crate::tsp::convert::module_to_uri;
+use crate::tsp::server::TspServer;
+
+impl<T: TspInterface> TspServer<T> {
+    /// Resolve an import to the URI of the file it refers to.
+    pub fn resolve_import(
+        &self,
+        transaction: &Transaction<'_>,
+        params: ResolveImportParams,
+    ) -> Result<Option<String>, ResponseError> {
+        let handle = self.handle_for_uri(&params.source_uri)?;
+        let Some(module) = from_tsp_module_name(&handle, &params.module_descriptor) else {
+            return Ok(None);
+        };
+        Ok(transaction
+            .import_handle(&handle, module, None)
+            .finding()
+            .and_then(|imported| transaction.get_module_info(&imported))
+            .and_then(|info| module_to_uri(&info)))
+    }
+}
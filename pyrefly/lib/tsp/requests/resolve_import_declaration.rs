@@ -0,0 +1,55 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Implementation of the resolveImportDeclaration TSP request
+
+use dupe::Dupe;
+use lsp_server::ResponseError;
+use pyrefly_build::handle::Handle;
+use tsp_types as tsp;
+use tsp_types::ResolveImportDeclarationParams;
+
+use crate::lsp::non_wasm::server::TspInterface;
+use crate::state::lsp::FindPreference;
+use crate::state::state::Transaction;
+use crate::tsp::handles::DeclarationInfo;
+use crate::tsp::server::TspServer;
+
+impl<T: TspInterface> TspServer<T> {
+    /// Follow an import declaration to the declaration it ultimately refers to.
+    pub fn resolve_import_declaration(
+        &self,
+        transaction: &Transaction<'_>,
+        params: ResolveImportDeclarationParams,
+    ) -> Result<Option<tsp::Declaration>, ResponseError> {
+        if params.decl.category != tsp::DeclarationCategory::Import {
+            return Ok(Some(params.decl));
+        }
+        let info = self.from_tsp_declaration(&params.decl)?;
+        let Some(def) = transaction
+            .find_definition(&info.handle, info.range.start(), FindPreference::default())
+            .into_iter()
+            .next()
+        else {
+            return Ok(None);
+        };
+        Ok(self.to_tsp_declaration(
+            &params.decl.name,
+            def.metadata.symbol_kind(),
+            DeclarationInfo {
+                handle: Handle::new(
+                    def.module.name(),
+                    def.module.path().dupe(),
+                    info.handle.sys_info().dupe(),
+                ),
+                module: def.module,
+                range: def.definition_range,
+                docstring_range: def.docstring_range,
+            },
+        ))
+    }
+}
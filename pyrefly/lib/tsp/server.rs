@@ -8,16 +8,22 @@
 use std::collections::HashSet;
 use std::sync::Arc;
 use std::sync::Mutex;
+use std::sync::MutexGuard;
 
 use lsp_server::Request;
 use lsp_server::RequestId;
+use lsp_server::Response;
+use lsp_server::ResponseError;
 use lsp_types::InitializeParams;
 use lsp_types::ServerCapabilities;
 use pyrefly_util::telemetry::Telemetry;
 use pyrefly_util::telemetry::TelemetryEvent;
 use pyrefly_util::telemetry::TelemetryEventKind;
+use serde::Serialize;
 use tracing::info;
 use tsp_types::TSPRequests;
+use tsp_types::create_internal_error;
+use tsp_types::snapshot_outdated_error;
 
 use crate::commands::lsp::IndexingMode;
 use crate::lsp::non_wasm::lsp::new_response;
@@ -27,12 +33,16 @@ use crate::lsp::non_wasm::server::TspInterface;
 use crate::lsp::non_wasm::server::capabilities;
 use crate::lsp::non_wasm::server::dispatch_lsp_events;
 use crate::lsp::non_wasm::transaction_manager::TransactionManager;
+use crate::state::state::Transaction;
+use crate::tsp::handles::TspHandles;
 
 /// TSP server that delegates to LSP server infrastructure while handling only TSP requests
 pub struct TspServer<T: TspInterface> {
     pub inner: T,
     /// Current snapshot version, updated on RecheckFinished events
     pub(crate) current_snapshot: Arc<Mutex<i32>>,
+    /// The type and declaration handles given out to the client
+    handles: Mutex<TspHandles>,
 }
 
 impl<T: TspInterface> TspServer<T> {
@@ -40,9 +50,16 @@ impl<T: TspInterface> TspServer<T> {
         Self {
             inner: lsp_server,
             current_snapshot: Arc::new(Mutex::new(0)), // Start at 0, increments on RecheckFinished
+            handles: Mutex::new(TspHandles::default()),
         }
     }
 
+    pub(crate) fn handles(&self) -> MutexGuard<'_, TspHandles> {
+        self.handles
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
     pub fn process_event<'a>(
         &'a self,
         ide_transaction_manager: &mut TransactionManager<'a>,
@@ -65,7 +82,7 @@ impl<T: TspInterface> TspServer<T> {
 
         // For TSP requests, handle them specially
         if let LspEvent::LspRequest(ref request) = event {
-            if self.handle_tsp_request(ide_transaction_manager, request)? {
+            if self.handle_tsp_request(ide_transaction_manager, telemetry, request)? {
                 return Ok(ProcessEvent::Continue);
             }
             // If it's not a TSP request, let the LSP server reject it since TSP server shouldn't handle LSP requests
@@ -96,7 +113,8 @@ impl<T: TspInterface> TspServer<T> {
 
     fn handle_tsp_request<'a>(
         &'a self,
-        _ide_transaction_manager: &mut TransactionManager<'a>,
+        ide_transaction_manager: &mut TransactionManager<'a>,
+        telemetry: &mut TelemetryEvent,
         request: &Request,
     ) -> anyhow::Result<bool> {
         // Convert the request into a TSPRequests enum
@@ -117,20 +135,142 @@ impl<T: TspInterface> TspServer<T> {
                     request.id.clone(),
                     Ok(self.get_supported_protocol_version()),
                 ));
-                Ok(true)
             }
             TSPRequests::GetSnapshotRequest { .. } => {
                 // Get snapshot doesn't need a transaction since it just returns the cached value
                 self.inner
                     .send_response(new_response(request.id.clone(), Ok(self.get_snapshot())));
-                Ok(true)
             }
-            _ => {
-                // Other TSP requests not yet implemented
-                Ok(false)
+            msg => {
+                let mut transaction =
+                    ide_transaction_manager.non_committable_transaction(self.inner.state());
+                // Make sure queries see the latest contents of open files, as the LSP server does.
+                self.inner
+                    .validate_in_memory_for_transaction(&mut transaction, telemetry);
+                let result = self.handle_snapshot_request(&transaction, msg);
+                ide_transaction_manager.save(transaction, telemetry);
+                self.inner.send_response(match result {
+                    Ok(value) => Response {
+                        id: request.id.clone(),
+                        result: Some(value),
+                        error: None,
+                    },
+                    Err(error) => Response {
+                        id: request.id.clone(),
+                        result: None,
+                        error: Some(error),
+                    },
+                });
             }
         }
+        Ok(true)
     }
+
+    /// Answer a request that queries the state at a particular snapshot.
+    fn handle_snapshot_request(
+        &self,
+        transaction: &Transaction<'_>,
+        msg: TSPRequests,
+    ) -> Result<serde_json::Value, ResponseError> {
+        match msg {
+            TSPRequests::GetDiagnosticsRequest { params, .. } => {
+                self.check_snapshot(params.snapshot)?;
+                to_json(self.get_diagnostics(transaction, params)?)
+            }
+            TSPRequests::GetDiagnosticsVersionRequest { params, .. } => {
+                self.check_snapshot(params.snapshot)?;
+                to_json(self.get_diagnostics_version(params))
+            }
+            TSPRequests::GetTypeRequest { params, .. } => {
+                self.check_snapshot(params.snapshot)?;
+                to_json(self.get_type(transaction, params)?)
+            }
+            TSPRequests::GetBuiltinTypeRequest { params, .. } => {
+                self.check_snapshot(params.snapshot)?;
+                to_json(self.get_builtin_type(transaction, params)?)
+            }
+            TSPRequests::GetTypeArgsRequest { params, .. } => {
+                self.check_snapshot(params.snapshot)?;
+                to_json(self.get_type_args(params)?)
+            }
+            TSPRequests::GetSymbolsForTypeRequest { params, .. } => {
+                self.check_snapshot(params.snapshot)?;
+                to_json(self.get_symbols_for_type(transaction, params)?)
+            }
+            TSPRequests::GetSymbolsForNodeRequest { params, .. } => {
+                self.check_snapshot(params.snapshot)?;
+                to_json(self.get_symbols_for_node(transaction, params)?)
+            }
+            TSPRequests::GetOverloadsRequest { params, .. } => {
+                self.check_snapshot(params.snapshot)?;
+                to_json(self.get_overloads(params)?)
+            }
+            TSPRequests::GetMatchingOverloadsRequest { params, .. } => {
+                self.check_snapshot(params.snapshot)?;
+                to_json(self.get_matching_overloads(transaction, params)?)
+            }
+            TSPRequests::GetMetaclassRequest { params, .. } => {
+                self.check_snapshot(params.snapshot)?;
+                to_json(self.get_metaclass(transaction, params)?)
+            }
+            TSPRequests::GetTypeOfDeclarationRequest { params, .. } => {
+                self.check_snapshot(params.snapshot)?;
+                to_json(self.get_type_of_declaration(transaction, params)?)
+            }
+            TSPRequests::GetReprRequest { params, .. } => {
+                self.check_snapshot(params.snapshot)?;
+                to_json(self.get_repr(transaction, params)?)
+            }
+            TSPRequests::GetDocstringRequest { params, .. } => {
+                self.check_snapshot(params.snapshot)?;
+                to_json(self.get_docstring(transaction, params)?)
+            }
+            TSPRequests::ResolveImportDeclarationRequest { params, .. } => {
+                self.check_snapshot(params.snapshot)?;
+                to_json(self.resolve_import_declaration(transaction, params)?)
+            }
+            TSPRequests::ResolveImportRequest { params, .. } => {
+                self.check_snapshot(params.snapshot)?;
+                to_json(self.resolve_import(transaction, params)?)
+            }
+            TSPRequests::GetTypeAliasInfoRequest { params, .. } => {
+                self.check_snapshot(params.snapshot)?;
+                to_json(self.get_type_alias_info(params)?)
+            }
+            TSPRequests::CombineTypesRequest { params, .. } => {
+                self.check_snapshot(params.snapshot)?;
+                to_json(self.combine_types(params)?)
+            }
+            TSPRequests::CreateInstanceTypeRequest { params, .. } => {
+                self.check_snapshot(params.snapshot)?;
+                to_json(self.create_instance_type(transaction, params)?)
+            }
+            TSPRequests::GetPythonSearchPathsRequest { params, .. } => {
+                self.check_snapshot(params.snapshot)?;
+                to_json(self.get_python_search_paths(transaction, params)?)
+            }
+            TSPRequests::GetSnapshotRequest { .. }
+            | TSPRequests::GetSupportedProtocolVersionRequest { .. } => Err(ResponseError {
+                code: lsp_server::ErrorCode::InvalidRequest as i32,
+                message: "Request does not query a snapshot".to_owned(),
+                data: None,
+            }),
+        }
+    }
+
+    /// Requests made against an older snapshot can't be answered, since the handles they
+    /// contain may no longer be valid.
+    fn check_snapshot(&self, snapshot: i32) -> Result<(), ResponseError> {
+        if snapshot == self.get_snapshot() {
+            Ok(())
+        } else {
+            Err(snapshot_outdated_error())
+        }
+    }
+}
+
+fn to_json(value: impl Serialize) -> Result<serde_json::Value, ResponseError> {
+    serde_json::to_value(value).map_err(|e| create_internal_error(&e.to_string()))
 }
 
 pub fn tsp_loop(
@@ -74,6 +74,11 @@ let docsSidebar = [
                 id: 'django',
                 label: 'Django Support',
             },
+            {
+                type: 'doc' as const,
+                id: 'sqlalchemy',
+                label: 'SQLAlchemy Support',
+            },
         ],
     },
     {